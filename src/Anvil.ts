@@ -1,14 +1,14 @@
 import { RGBA } from '@sledge-pdm/core';
 import { DiffsController } from './buffer/DiffsController';
 import { TilesController } from './buffer/TilesController';
-import { packedU32ToRgba, rgbaToPackedU32 } from './ops/Packing';
+import { linearToTileIndex, packedU32ToRgba, rgbaToPackedU32 } from './ops/Packing';
 import type { PackedDiffs } from './types/patch/Patch';
 import type { PixelPatchData } from './types/patch/pixel';
 import type { PackedWholePatchData } from './types/patch/whole';
 import type { RawPixelData } from './types/rawBuffer';
 import { toUint8Array, toUint8ClampedArray } from './types/rawBuffer';
 import type { Point, Size, TileIndex } from './types/types';
-import { AntialiasMode, RgbaBuffer } from './wasm/pkg/anvil_wasm';
+import { AntialiasMode, Connectivity, CoverageMode, FillResult, RgbaBuffer } from './wasm/pkg/anvil_wasm';
 
 /**
  * Anvil - Main facade for pixel-based drawing operations
@@ -227,24 +227,50 @@ export class Anvil {
     this.diffsController.discard();
   }
 
-  fillWithMaskArea(args: { mask: Uint8Array; color: RGBA }): boolean {
-    return this.buffer.fillMaskArea(args.mask, ...args.color);
+  /**
+   * Fill every pixel whose mask value is non-zero.
+   * @param args.mode How mask values are applied (`CoverageMode.Binary` by default, which fully fills any non-zero value)
+   * @param args.coverage Also return the coverage of the changed pixels (off by default)
+   * @returns What changed. Always an object, even if no pixel changed; check `changedPixels()`.
+   * Call `free()` on it when done.
+   */
+  fillWithMaskArea(args: { mask: Uint8Array; color: RGBA; mode?: CoverageMode; coverage?: boolean }): FillResult {
+    this.buffer.setFillCoverage(args.coverage ?? false);
+    return this.buffer.fillMaskAreaWithCoverage(args.mask, ...args.color, args.mode ?? CoverageMode.Binary);
   }
 
+  /**
+   * Flood fill from a start point.
+   * @param args.coverage Also return the coverage of the changed pixels (off by default)
+   * @returns What changed, or undefined if the start point is out of bounds or not allowed by the mask.
+   * A result is returned even if no pixel changed; check `changedPixels()`. Call `free()` on it when done.
+   */
   floodFill(args: {
     startX: number;
     startY: number;
     color: RGBA;
     threshold?: number;
+    connectivity?: Connectivity;
+    coverage?: boolean;
     mask?: {
       buffer: Uint8Array;
       mode: 'inside' | 'outside' | 'none';
     };
-  }): boolean {
+  }): FillResult | undefined {
+    const connectivity = args.connectivity ?? Connectivity.Four;
+    this.buffer.setFillCoverage(args.coverage ?? false);
     if (args.mask) {
-      return this.buffer.floodFillWithMask(args.startX, args.startY, ...args.color, args.threshold ?? 0, args.mask.buffer, args.mask.mode);
+      return this.buffer.floodFillWithMask(
+        args.startX,
+        args.startY,
+        ...args.color,
+        args.threshold ?? 0,
+        args.mask.buffer,
+        args.mask.mode,
+        connectivity
+      );
     } else {
-      return this.buffer.floodFill(args.startX, args.startY, ...args.color, args.threshold ?? 0);
+      return this.buffer.floodFill(args.startX, args.startY, ...args.color, args.threshold ?? 0, connectivity);
     }
   }
 
@@ -278,6 +304,17 @@ export class Anvil {
     this.tilesController.setAllDirty();
   }
 
+  // Capture the current contents of the given tiles (row-major linear indices) before changing them.
+  // A transaction holds one tile patch, so capture every tile it will touch in a single call.
+  addCurrentTilesDiff(tileIndices: number[]): void {
+    const swapBufferTiles = this.buffer.captureTiles(this.tileSize, new Uint32Array(tileIndices));
+    this.diffsController.addTilesPacked({ swapBufferTiles });
+    const cols = Math.ceil(this.getWidth() / this.tileSize);
+    for (const linear of tileIndices) {
+      this.tilesController.setDirty(linearToTileIndex(linear, cols), true);
+    }
+  }
+
   addPartialDiff(boundBox: { x: number; y: number; width: number; height: number }, swapBuffer: RawPixelData, setDirty?: boolean): void {
     const clampedSwap = toUint8ClampedArray(swapBuffer);
     this.diffsController.addPartial({ boundBox, swapBuffer: clampedSwap });
@@ -357,6 +394,16 @@ export class Anvil {
       patch.partial.swapBufferWebp = decoded;
     }
 
+    // Tiles (swap method - the returned patch restores the tiles for the opposite direction)
+    if (patch.tiles) {
+      const swapped = this.buffer.swapTiles(patch.tiles.swapBufferTiles);
+      if (swapped.length > 0) {
+        patch.tiles.swapBufferTiles = swapped;
+      } else {
+        console.warn('applyPatch: tile patch does not match this buffer');
+      }
+    }
+
     // Pixels
     patch.pixels = patch.pixels?.map((p) => {
       const colorUnpacked = packedU32ToRgba(p.color);
@@ -2,11 +2,13 @@ import { packPartial, packPending, packWhole } from '../ops/Packing.js';
 import type { PartialPatchData } from '../types/patch/partial.js';
 import type { PackedDiffs, PendingDiffs } from '../types/patch/Patch.js';
 import type { PixelPatchData } from '../types/patch/pixel.js';
+import type { PackedTilePatchData } from '../types/patch/tile.js';
 import type { PackedWholePatchData, WholePatchData } from '../types/patch/whole.js';
 
 export class DiffsController {
   diffs: PendingDiffs = {
     pixels: [],
+    tiles: undefined,
     partial: undefined,
     whole: undefined,
   };
@@ -27,14 +29,20 @@ export class DiffsController {
     this.diffs.pixels = [];
   }
 
+  addTilesPacked(packed: PackedTilePatchData) {
+    this.diffs.tiles = packed;
+  }
+
   addWhole(unpacked: WholePatchData) {
     this.diffs.whole = packWhole(unpacked);
+    this.diffs.tiles = undefined;
     this.diffs.partial = undefined;
     this.diffs.pixels = [];
   }
 
   addWholePacked(packed: PackedWholePatchData) {
     this.diffs.whole = packed;
+    this.diffs.tiles = undefined;
     this.diffs.partial = undefined;
     this.diffs.pixels = [];
   }
@@ -43,7 +51,9 @@ export class DiffsController {
    * Check if there are pending changes
    */
   hasPendingChanges(): boolean {
-    return this.diffs.pixels.length > 0 || this.diffs.partial !== undefined || this.diffs.whole !== undefined;
+    return (
+      this.diffs.pixels.length > 0 || this.diffs.tiles !== undefined || this.diffs.partial !== undefined || this.diffs.whole !== undefined
+    );
   }
 
   /**
@@ -76,6 +86,7 @@ export class DiffsController {
    */
   discard(): void {
     this.diffs.pixels = [];
+    this.diffs.tiles = undefined;
     this.diffs.partial = undefined;
     this.diffs.whole = undefined;
   }
@@ -6,7 +6,7 @@ import type { PackedWholePatchData, WholePatchData } from '../types/patch/whole'
 import type { RawPixelData } from '../types/rawBuffer';
 import { toUint8Array } from '../types/rawBuffer';
 import type { TileIndex } from '../types/types';
-import { png_to_raw, raw_to_png, raw_to_webp, webp_to_raw } from '../wasm/pkg/anvil_wasm';
+import { BufferDiff, DiffKind, png_to_raw, raw_to_png, raw_to_webp, webp_to_raw } from '../wasm/pkg/anvil_wasm';
 
 export function rawToWebp(buffer: RawPixelData, width: number, height: number): Uint8Array {
   const start = performance.now();
@@ -120,6 +120,9 @@ export function packPending(pendingDiffs: PendingDiffs): PackedDiffs {
   if (pendingDiffs.partial) {
     packed.partial = pendingDiffs.partial;
   }
+  if (pendingDiffs.tiles) {
+    packed.tiles = pendingDiffs.tiles;
+  }
 
   // Pixel changes - convert to the Patch format
   if (pendingDiffs.pixels.length > 0) {
@@ -128,3 +131,28 @@ export function packPending(pendingDiffs: PendingDiffs): PackedDiffs {
 
   return packed;
 }
+
+// Convert the result of the wasm `diff` into patches for Anvil.applyPatch (undefined if nothing changed)
+export function packBufferDiff(diff: BufferDiff, width: number, height: number): PackedDiffs | undefined {
+  switch (diff.kind()) {
+    case DiffKind.Pixel: {
+      const triples = diff.pixels();
+      const pixels: PackedPixelPatchData[] = [];
+      for (let i = 0; i < triples.length; i += 3) {
+        // `| 0` matches the signed value rgbaToPackedU32 produces
+        pixels.push({ x: triples[i], y: triples[i + 1], color: triples[i + 2] | 0 });
+      }
+      return { pixels };
+    }
+    case DiffKind.Tile:
+      return { tiles: { swapBufferTiles: diff.swapBuffer() } };
+    case DiffKind.Partial: {
+      const [x, y, boxWidth, boxHeight] = diff.boundBox();
+      return { partial: { boundBox: { x, y, width: boxWidth, height: boxHeight }, swapBufferWebp: diff.swapBuffer() } };
+    }
+    case DiffKind.Whole:
+      return { whole: { width, height, swapBufferWebp: diff.swapBuffer() } };
+    default:
+      return undefined;
+  }
+}
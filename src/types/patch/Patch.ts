@@ -1,17 +1,20 @@
 import type { PackedPartialPatchData } from './partial.js';
 import type { PackedPixelPatchData, PixelPatchData } from './pixel.js';
+import type { PackedTilePatchData } from './tile.js';
 import type { PackedWholePatchData } from './whole.js';
 
 export type PatchType = 'pixel' | 'tile' | 'partial' | 'whole';
 
 export interface PendingDiffs {
   pixels: PixelPatchData[];
+  tiles?: PackedTilePatchData;
   partial?: PackedPartialPatchData;
   whole?: PackedWholePatchData;
 }
 
 export interface PackedDiffs {
   pixels?: PackedPixelPatchData[];
+  tiles?: PackedTilePatchData;
   partial?: PackedPartialPatchData;
   whole?: PackedWholePatchData;
 }
@@ -0,0 +1,8 @@
+/**
+ * Tile Diff
+ * packed: tile patch from RgbaBuffer.captureTiles (tile size, linear tile indices and their RGBA contents)
+ */
+
+export interface PackedTilePatchData {
+  swapBufferTiles: Uint8Array;
+}
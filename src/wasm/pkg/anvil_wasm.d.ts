@@ -1,31 +1,38 @@
 /* tslint:disable */
 /* eslint-disable */
-export function fill_mask_area(buffer: Uint8Array, mask: Uint8Array, fill_color_r: number, fill_color_g: number, fill_color_b: number, fill_color_a: number): boolean;
-export function invert(pixels: Uint8Array, width: number, height: number): void;
 /**
- * Apply ordered dithering with simple parameters
+ * Paint a gradient over the buffer.
+ *
+ * `stop_positions` (0-1) and `stop_colors` (flattened RGBA, four per stop) describe the
+ * color stops; stops need not be sorted. A non-empty `selection_mask` (width * height,
+ * 0-255 coverage) limits the fill and fades it at partial coverage.
+ * Returns false if there are no stops or the sizes do not match.
  */
-export function dithering_ordered(pixels: Uint8Array, width: number, height: number, levels: number): void;
+export function gradient_fill(buffer: Uint8Array, width: number, height: number, stop_positions: Float32Array, stop_colors: Uint8Array, selection_mask: Uint8Array, options: GradientFillOption): boolean;
 /**
- * Apply dithering effect to the image
+ * Remove small isolated pixel groups with default settings
  */
-export function dithering(pixels: Uint8Array, width: number, height: number, options: DitheringOption): void;
+export function dust_removal_simple(pixels: Uint8Array, width: number, height: number, max_size: number): void;
+/**
+ * Remove small isolated pixel groups (dust removal)
+ */
+export function dust_removal(pixels: Uint8Array, width: number, height: number, options: DustRemovalOption): void;
 /**
  * Apply random dithering with simple parameters
  */
 export function dithering_random(pixels: Uint8Array, width: number, height: number, levels: number): void;
 /**
- * Apply error diffusion dithering with simple parameters
+ * Apply dithering effect to the image
  */
-export function dithering_error_diffusion(pixels: Uint8Array, width: number, height: number, levels: number): void;
+export function dithering(pixels: Uint8Array, width: number, height: number, options: DitheringOption): void;
 /**
- * Remove small isolated pixel groups (dust removal)
+ * Apply error diffusion dithering with simple parameters
  */
-export function dust_removal(pixels: Uint8Array, width: number, height: number, options: DustRemovalOption): void;
+export function dithering_error_diffusion(pixels: Uint8Array, width: number, height: number, levels: number): void;
 /**
- * Remove small isolated pixel groups with default settings
+ * Apply ordered dithering with simple parameters
  */
-export function dust_removal_simple(pixels: Uint8Array, width: number, height: number, max_size: number): void;
+export function dithering_ordered(pixels: Uint8Array, width: number, height: number, levels: number): void;
 /**
  * Apply posterize effect to reduce the number of color levels
  */
@@ -35,22 +42,26 @@ export function posterize(pixels: Uint8Array, width: number, height: number, opt
  */
 export function posterize_simple(pixels: Uint8Array, width: number, height: number, levels: number): void;
 /**
- * Apply brightness and contrast adjustments to the image
- */
-export function brightness_contrast(pixels: Uint8Array, width: number, height: number, options: BrightnessContrastOption): void;
-/**
- * Apply only contrast adjustment to the image
+ * 参照バッファで領域を求め、別のバッファに塗る FloodFill
+ *
+ * 線画レイヤー（または合成結果）を `reference` に渡し、塗りを `target` に書き込みます。
+ * 両者は同じ width * height である必要があります。
+ * `selection_mask` と `limit_mode` は `scanline_flood_fill_with_options` と同じです。
  */
-export function contrast(pixels: Uint8Array, width: number, height: number, contrast: number): void;
+export function scanline_flood_fill_from_reference(target: Uint8Array, reference: Uint8Array, width: number, height: number, start_x: number, start_y: number, fill_color_r: number, fill_color_g: number, fill_color_b: number, fill_color_a: number, selection_mask: Uint8Array, limit_mode: string, options: FloodFillOption): boolean;
 /**
- * Apply only brightness adjustment to the image
+ * 色距離の指定付きスキャンライン FloodFill
+ *
+ * `selection_mask` と `limit_mode` は `scanline_flood_fill_with_mask` と同じです。
+ * 選択範囲で制限しない場合は空の配列と "none" を渡します。
  */
-export function brightness(pixels: Uint8Array, width: number, height: number, brightness: number): void;
-export function gaussian_blur(pixels: Uint8Array, width: number, height: number, options: GaussianBlurOption): void;
+export function scanline_flood_fill_with_options(buffer: Uint8Array, width: number, height: number, start_x: number, start_y: number, fill_color_r: number, fill_color_g: number, fill_color_b: number, fill_color_a: number, selection_mask: Uint8Array, limit_mode: string, options: FloodFillOption): boolean;
 /**
  * 選択範囲制限付きスキャンライン FloodFill
+ *
+ * `connectivity` を省略すると4近傍で連結します。
  */
-export function scanline_flood_fill_with_mask(buffer: Uint8Array, width: number, height: number, start_x: number, start_y: number, fill_color_r: number, fill_color_g: number, fill_color_b: number, fill_color_a: number, threshold: number, selection_mask: Uint8Array, limit_mode: string): boolean;
+export function scanline_flood_fill_with_mask(buffer: Uint8Array, width: number, height: number, start_x: number, start_y: number, fill_color_r: number, fill_color_g: number, fill_color_b: number, fill_color_a: number, threshold: number, selection_mask: Uint8Array, limit_mode: string, connectivity?: Connectivity | null): boolean;
 /**
  * スキャンライン方式のFloodFill実装
  *
@@ -59,15 +70,101 @@ export function scanline_flood_fill_with_mask(buffer: Uint8Array, width: number,
  * - スタックオーバーフロー回避
  * - 高速な隣接色判定
  * - 選択範囲制限サポート
+ *
+ * `connectivity` を省略すると4近傍で連結します。
  */
-export function scanline_flood_fill(buffer: Uint8Array, width: number, height: number, start_x: number, start_y: number, fill_color_r: number, fill_color_g: number, fill_color_b: number, fill_color_a: number, threshold: number): boolean;
-export function grayscale(pixels: Uint8Array, width: number, height: number): void;
-export function patch_buffer_rgba(target: Uint8Array, target_width: number, target_height: number, patch: Uint8Array, patch_width: number, patch_height: number, offset_x: number, offset_y: number, options: PatchBufferRgbaOption): Uint8Array;
-export function patch_buffer_rgba_instant(target: Uint8Array, target_width: number, target_height: number, patch: Uint8Array, patch_width: number, patch_height: number, offset_x: number, offset_y: number, scale_x: number, scale_y: number, rotate_deg: number, options: PatchBufferRgbaOption): void;
-export function webp_to_raw(webp_buffer: Uint8Array, width: number, height: number): Uint8Array;
+export function scanline_flood_fill(buffer: Uint8Array, width: number, height: number, start_x: number, start_y: number, fill_color_r: number, fill_color_g: number, fill_color_b: number, fill_color_a: number, threshold: number, connectivity?: Connectivity | null): boolean;
+/**
+ * `magic_wand` with a configurable color metric, connectivity and gap closing.
+ * `options.threshold` is the tolerance.
+ */
+export function magic_wand_with_options(buffer: Uint8Array, width: number, height: number, start_x: number, start_y: number, contiguous: boolean, antialias: boolean, options: FloodFillOption): Uint8Array;
+/**
+ * Select pixels similar to the color at (start_x, start_y) and return their coverage mask.
+ *
+ * Contiguous mode walks the region connected through `connectivity` (default `Four`) like
+ * `scanline_flood_fill`; otherwise every matching pixel in the buffer is selected. With
+ * `antialias`, pixels just outside the selection get partial coverage by how close their
+ * color is to the start color. Returns an empty array if the start point is out of bounds.
+ */
+export function magic_wand(buffer: Uint8Array, width: number, height: number, start_x: number, start_y: number, tolerance: number, contiguous: boolean, antialias: boolean, connectivity?: Connectivity | null): Uint8Array;
 export function png_to_raw(png_buffer: Uint8Array, _width: number, _height: number): Uint8Array;
+export function webp_to_raw(webp_buffer: Uint8Array, width: number, height: number): Uint8Array;
 export function raw_to_webp(buffer: Uint8Array, width: number, height: number): Uint8Array;
 export function raw_to_png(buffer: Uint8Array, width: number, height: number): Uint8Array;
+/**
+ * Rasterize a lasso outline into a width * height coverage mask.
+ *
+ * `points` is [x0, y0, x1, y1, ...] in pixel coordinates and is closed back to the
+ * first point; parts outside the bounds are clipped. With `antialias` the edge gets
+ * partial coverage, otherwise a pixel is selected when its center is inside.
+ * The result can be passed as-is to `sliceWithMask`, `cropWithMask` and
+ * `floodFillWithMask`.
+ */
+export function lasso_mask(width: number, height: number, points: Float32Array, rule: FillRule, antialias: boolean): Uint8Array;
+/**
+ * Fill the buffer with a repeating tile.
+ *
+ * A non-empty `selection_mask` (width * height, 0-255 coverage) limits the fill and fades
+ * it at partial coverage. Returns false if the sizes do not match or the tile is empty.
+ */
+export function pattern_fill(buffer: Uint8Array, width: number, height: number, tile: Uint8Array, tile_width: number, tile_height: number, selection_mask: Uint8Array, options: PatternFillOption): boolean;
+/**
+ * `scanline_flood_fill_with_options` that paints the tile instead of a solid color.
+ */
+export function scanline_pattern_flood_fill(buffer: Uint8Array, width: number, height: number, start_x: number, start_y: number, tile: Uint8Array, tile_width: number, tile_height: number, selection_mask: Uint8Array, limit_mode: string, fill_options: FloodFillOption, options: PatternFillOption): boolean;
+/**
+ * `fill_mask_area` where mask values are applied according to `mode`,
+ * so feathered selections fade out instead of being filled at full strength.
+ */
+export function fill_mask_area_with_coverage(buffer: Uint8Array, mask: Uint8Array, fill_color_r: number, fill_color_g: number, fill_color_b: number, fill_color_a: number, mode: CoverageMode): boolean;
+export function fill_mask_area(buffer: Uint8Array, mask: Uint8Array, fill_color_r: number, fill_color_g: number, fill_color_b: number, fill_color_a: number): boolean;
+export function patch_buffer_rgba_instant(target: Uint8Array, target_width: number, target_height: number, patch: Uint8Array, patch_width: number, patch_height: number, offset_x: number, offset_y: number, scale_x: number, scale_y: number, rotate_deg: number, options: PatchBufferRgbaOption): void;
+export function patch_buffer_rgba(target: Uint8Array, target_width: number, target_height: number, patch: Uint8Array, patch_width: number, patch_height: number, offset_x: number, offset_y: number, options: PatchBufferRgbaOption): Uint8Array;
+export function gaussian_blur(pixels: Uint8Array, width: number, height: number, options: GaussianBlurOption): void;
+/**
+ * Apply brightness and contrast adjustments to the image
+ */
+export function brightness_contrast(pixels: Uint8Array, width: number, height: number, options: BrightnessContrastOption): void;
+/**
+ * Apply only brightness adjustment to the image
+ */
+export function brightness(pixels: Uint8Array, width: number, height: number, brightness: number): void;
+/**
+ * Apply only contrast adjustment to the image
+ */
+export function contrast(pixels: Uint8Array, width: number, height: number, contrast: number): void;
+export function invert(pixels: Uint8Array, width: number, height: number): void;
+export function grayscale(pixels: Uint8Array, width: number, height: number): void;
+/**
+ * Soften the selection edge with a Gaussian falloff. `radius` spans two standard deviations.
+ */
+export function feather_mask(mask: Uint8Array, width: number, height: number, radius: number): void;
+/**
+ * Replace the selection with a ring of `size` pixels centered on its edge.
+ */
+export function border_mask(mask: Uint8Array, width: number, height: number, size: number, shape: MaskShape): void;
+/**
+ * Expand the selection by `radius` pixels.
+ */
+export function grow_mask(mask: Uint8Array, width: number, height: number, radius: number, shape: MaskShape): void;
+/**
+ * Contract the selection by `radius` pixels. The canvas edge does not erode the selection.
+ */
+export function shrink_mask(mask: Uint8Array, width: number, height: number, radius: number, shape: MaskShape): void;
+/**
+ * Round off jagged edges: each pixel is selected when most of its (2r+1)^2 neighborhood is.
+ */
+export function smooth_mask(mask: Uint8Array, width: number, height: number, radius: number): void;
+/**
+ * Compare two buffers of the same size and pick the smallest patch that restores `before`.
+ * `packBufferDiff` turns the result into `PackedDiffs` for `Anvil.applyPatch`.
+ */
+export function diff(before: RgbaBuffer, after: RgbaBuffer, tile_size: number): BufferDiff;
+/**
+ * Header that starts a history log. Append `PatchRecord.encode()` output after it.
+ */
+export function history_log_header(): Uint8Array;
 export enum AlphaBlurMode {
   /**
    * Skip alpha channel (preserve original alpha values)
@@ -83,6 +180,126 @@ export enum AntialiasMode {
   Bilinear = 1,
   Bicubic = 2,
 }
+export enum BlendMode {
+  /**
+   * Plain source-over
+   */
+  Normal = 0,
+  Multiply = 1,
+  Screen = 2,
+  Overlay = 3,
+  Darken = 4,
+  Lighten = 5,
+  ColorDodge = 6,
+  ColorBurn = 7,
+  HardLight = 8,
+  SoftLight = 9,
+  Difference = 10,
+  Exclusion = 11,
+  /**
+   * Hue of the source with saturation and luminosity of the backdrop
+   */
+  Hue = 12,
+  /**
+   * Saturation of the source with hue and luminosity of the backdrop
+   */
+  Saturation = 13,
+  /**
+   * Hue and saturation of the source with luminosity of the backdrop
+   */
+  Color = 14,
+  /**
+   * Luminosity of the source with hue and saturation of the backdrop
+   */
+  Luminosity = 15,
+  /**
+   * Linear dodge (backdrop + source, clamped)
+   */
+  Add = 16,
+  /**
+   * Backdrop - source, clamped
+   */
+  Subtract = 17,
+}
+/**
+ * How the distance between two colors is measured when matching against a threshold.
+ * Every metric is scaled so that 255 roughly spans black to white.
+ */
+export enum ColorMetric {
+  /**
+   * Largest per-channel difference (the legacy behavior)
+   */
+  ChannelMax = 0,
+  /**
+   * Euclidean distance over straight RGBA
+   */
+  Euclidean = 1,
+  /**
+   * Euclidean distance over premultiplied RGBA, so all fully transparent pixels match
+   */
+  Premultiplied = 2,
+  /**
+   * Perceptual distance in OKLab
+   */
+  OkLab = 3,
+  /**
+   * Perceptual CIEDE2000 distance in CIELAB (D65)
+   */
+  Ciede2000 = 4,
+}
+/**
+ * Which neighbors count as connected when growing a region.
+ */
+export enum Connectivity {
+  /**
+   * Edge neighbors only (left, right, up, down)
+   */
+  Four = 4,
+  /**
+   * Edge and corner neighbors, so regions cross one-pixel diagonal lines
+   */
+  Eight = 8,
+}
+/**
+ * How mask values are applied when filling.
+ */
+export enum CoverageMode {
+  /**
+   * Any non-zero value is fully selected (the legacy behavior)
+   */
+  Binary = 0,
+  /**
+   * Values are 0-255 coverage; the color is interpolated in straight RGBA
+   */
+  Straight = 1,
+  /**
+   * Values are 0-255 coverage; the color is interpolated in premultiplied RGBA,
+   * so transparent pixels do not darken the edge
+   */
+  Premultiplied = 2,
+}
+export enum DiffKind {
+  /**
+   * Buffers are identical
+   */
+  Unchanged = 0,
+  /**
+   * Position + previous packed RGBA32 per changed pixel
+   */
+  Pixel = 1,
+  /**
+   * Tile patch (see `RgbaBuffer.swapTiles`)
+   */
+  Tile = 2,
+  /**
+   * Bounding box + previous contents as WebP
+   */
+  Partial = 3,
+  /**
+   * Whole previous buffer as WebP
+   */
+  Whole = 4,
+}
 export enum DitheringMode {
   /**
    * Random dithering (white noise)
@@ -97,6 +314,154 @@ export enum DitheringMode {
    */
   Ordered = 2,
 }
+/**
+ * Which pixels a fill may expand over.
+ */
+export enum ExpandLimit {
+  /**
+   * Expand over every pixel in reach
+   */
+  None = 0,
+  /**
+   * Only under pixels darker than the threshold (composited over white, so
+   * transparent pixels count as light)
+   */
+  Darker = 1,
+  /**
+   * Only under pixels whose alpha is above the threshold
+   */
+  Opaque = 2,
+}
+/**
+ * Which areas of a self-intersecting or nested outline count as inside.
+ */
+export enum FillRule {
+  /**
+   * Inside wherever the outline winds around the point at least once
+   */
+  NonZero = 0,
+  /**
+   * Inside wherever the outline crosses an odd number of times
+   */
+  EvenOdd = 1,
+}
+export enum GradientDither {
+  /**
+   * Round to the nearest value
+   */
+  None = 0,
+  /**
+   * 4x4 Bayer matrix
+   */
+  Ordered = 1,
+  /**
+   * 32x32 blue noise threshold map
+   */
+  BlueNoise = 2,
+}
+/**
+ * Color space the stops are interpolated in. Alpha is always interpolated premultiplied.
+ */
+export enum GradientInterpolation {
+  Srgb = 0,
+  LinearRgb = 1,
+  OkLab = 2,
+}
+/**
+ * What happens past the last stop.
+ */
+export enum GradientRepeat {
+  /**
+   * The end colors extend outward
+   */
+  None = 0,
+  /**
+   * The gradient starts over
+   */
+  Repeat = 1,
+  /**
+   * The gradient runs back and forth
+   */
+  Reflect = 2,
+}
+export enum GradientShape {
+  /**
+   * Along the line from the start point to the end point
+   */
+  Linear = 0,
+  /**
+   * Circles around the start point, reaching the last stop at the end point
+   */
+  Radial = 1,
+  /**
+   * Sweeps clockwise around the start point, beginning in the direction of the end point
+   */
+  Angular = 2,
+  /**
+   * Squares around the start point, with a corner at the end point
+   */
+  Diamond = 3,
+  /**
+   * Linear, mirrored around the start point
+   */
+  Reflected = 4,
+}
+/**
+ * Shape of open stroke ends.
+ */
+export enum LineCap {
+  /**
+   * Ends flush with the endpoint
+   */
+  Butt = 0,
+  Round = 1,
+  /**
+   * Extends half the stroke width past the endpoint
+   */
+  Square = 2,
+}
+/**
+ * Shape of stroke corners.
+ */
+export enum LineJoin {
+  /**
+   * Sharp corner, beveled past `miter_limit`
+   */
+  Miter = 0,
+  Round = 1,
+  Bevel = 2,
+}
+export enum MaskShape {
+  /**
+   * (2r+1) x (2r+1) square, works directly on soft coverage
+   */
+  Square = 0,
+  /**
+   * Euclidean disc of radius r, measured from pixels with coverage >= 128
+   */
+  Round = 1,
+}
+/**
+ * How a new selection is combined with an existing one.
+ */
+export enum SelectionOp {
+  /**
+   * Discard the existing selection
+   */
+  Replace = 0,
+  /**
+   * Union
+   */
+  Add = 1,
+  /**
+   * Remove the new selection from the existing one
+   */
+  Subtract = 2,
+  /**
+   * Keep only the overlap
+   */
+  Intersect = 3,
+}
 export class BrightnessContrastOption {
   free(): void;
   [Symbol.dispose](): void;
@@ -110,6 +475,51 @@ export class BrightnessContrastOption {
    */
   contrast: number;
 }
+/**
+ * Brush tip: a coverage mask placed relative to the dab center, like `ShapeMask` on
+ * the TS side (top-left corner at center + offset).
+ */
+export class BrushStamp {
+  free(): void;
+  [Symbol.dispose](): void;
+  /**
+   * Create a stamp from a `ShapeMask`-style flag mask where any non-zero value is fully covered.
+   */
+  static fromFlags(mask: Uint8Array, width: number, height: number, offset_x: number, offset_y: number): BrushStamp;
+  /**
+   * Create a stamp from coverage bytes (0-255).
+   */
+  constructor(mask: Uint8Array, width: number, height: number, offset_x: number, offset_y: number);
+  width(): number;
+  height(): number;
+}
+/**
+ * Result of `diff`: the cheapest undo representation of a change.
+ */
+export class BufferDiff {
+  private constructor();
+  free(): void;
+  [Symbol.dispose](): void;
+  /**
+   * Encoded previous contents: WebP for `Partial`/`Whole`, a tile patch for `Tile`.
+   */
+  swapBuffer(): Uint8Array;
+  /**
+   * Row-major linear indices of the changed tiles.
+   */
+  changedTiles(): Uint32Array;
+  changedPixels(): number;
+  kind(): DiffKind;
+  /**
+   * Flattened [x, y, color] triples for `Pixel` diffs, with the previous color
+   * packed as (a << 24) | (r << 16) | (g << 8) | b like `rgbaToPackedU32`.
+   */
+  pixels(): Uint32Array;
+  /**
+   * Bounding box of the changed pixels as [x, y, width, height].
+   */
+  boundBox(): Uint32Array;
+}
 export class DitheringOption {
   free(): void;
   [Symbol.dispose](): void;
@@ -130,6 +540,9 @@ export class DitheringOption {
 export class DustRemovalOption {
   free(): void;
   [Symbol.dispose](): void;
+  /**
+   * `connectivity` starts as `Eight` and is set as a property.
+   */
   constructor(max_size: number, alpha_threshold: number);
   /**
    * Maximum size of pixel groups to remove (1-100, groups with this many pixels or fewer will be removed)
@@ -139,27 +552,342 @@ export class DustRemovalOption {
    * Minimum alpha threshold to consider a pixel as non-transparent (0-255)
    */
   alpha_threshold: number;
+  /**
+   * Which neighbors belong to the same pixel group
+   */
+  connectivity: Connectivity;
 }
-export class GaussianBlurOption {
+/**
+ * What a fill changed, so the caller can record a partial or tile diff instead of
+ * snapshotting the whole buffer.
+ */
+export class FillResult {
+  private constructor();
   free(): void;
   [Symbol.dispose](): void;
-  constructor(radius: number, alpha_mode: AlphaBlurMode);
+  changedPixels(): number;
   /**
-   * Blur radius (higher values create stronger blur effect)
+   * Coverage the fill was applied with, cropped to `boundBox` (row-major).
+   * Pixels that kept their color are 0. Empty unless the buffer was asked to record it
+   * with `setFillCoverage(true)`.
    */
-  radius: number;
+  coverage(): Uint8Array;
   /**
-   * How to handle the alpha channel
+   * Bounding box of the changed pixels as [x, y, width, height] (all zero if nothing changed).
    */
-  alpha_mode: AlphaBlurMode;
+  boundBox(): Uint32Array;
 }
-export class PatchBufferRgbaOption {
+export class FloodFillOption {
   free(): void;
   [Symbol.dispose](): void;
+  /**
+   * `connectivity` starts as `Four` and `gap_size`, `expand`, `expand_limit`,
+   * `expand_threshold`, `antialias` and `mask_mode` start disabled (`mask_mode` is
+   * `Binary`); all are set as properties.
+   */
+  constructor(threshold: number, metric: ColorMetric, include_alpha: boolean);
+  /**
+   * Largest color distance (0-255) still treated as the target color
+   */
+  threshold: number;
+  /**
+   * How color distance is measured
+   */
+  metric: ColorMetric;
+  /**
+   * Whether alpha takes part in the comparison
+   */
+  include_alpha: boolean;
+  /**
+   * Neighborhood used to grow the region
+   */
+  connectivity: Connectivity;
+  /**
+   * Openings in the boundary up to this many pixels wide are treated as closed (0 = off)
+   */
+  gap_size: number;
+  /**
+   * Grow the filled region by this many pixels after it is found (0 = off)
+   */
+  expand: number;
+  /**
+   * Which pixels the expansion may cover
+   */
+  expand_limit: ExpandLimit;
+  /**
+   * Lightness or alpha threshold for `expand_limit`
+   */
+  expand_threshold: number;
+  /**
+   * Give pixels just outside the fill partial coverage by how close their color is to
+   * the target, so antialiased boundaries blend in instead of leaving a hard edge
+   */
+  antialias: boolean;
+  /**
+   * How `selection_mask` values limit the fill; soft modes fade the fill at feathered
+   * selection edges
+   */
+  mask_mode: CoverageMode;
+}
+export class GaussianBlurOption {
+  free(): void;
+  [Symbol.dispose](): void;
+  constructor(radius: number, alpha_mode: AlphaBlurMode);
+  /**
+   * Blur radius (higher values create stronger blur effect)
+   */
+  radius: number;
+  /**
+   * How to handle the alpha channel
+   */
+  alpha_mode: AlphaBlurMode;
+}
+export class GradientFillOption {
+  free(): void;
+  [Symbol.dispose](): void;
+  /**
+   * `interpolation`, `repeat` and `dither` default to sRGB, none and none and are set
+   * as properties.
+   */
+  constructor(shape: GradientShape, start_x: number, start_y: number, end_x: number, end_y: number);
+  shape: GradientShape;
+  /**
+   * Start point in pixels (pixel centers are at +0.5)
+   */
+  start_x: number;
+  start_y: number;
+  /**
+   * End point in pixels
+   */
+  end_x: number;
+  end_y: number;
+  interpolation: GradientInterpolation;
+  repeat: GradientRepeat;
+  dither: GradientDither;
+}
+export class GroupOption {
+  free(): void;
+  [Symbol.dispose](): void;
+  constructor(opacity: number, visible: boolean, blend_mode: BlendMode, clipping: boolean, pass_through: boolean);
+  /**
+   * Group opacity (0.0-1.0)
+   */
+  opacity: number;
+  /**
+   * Hidden groups skip every layer pushed inside them
+   */
+  visible: boolean;
+  /**
+   * Blend mode used when an isolated group is composited onto its parent
+   */
+  blend_mode: BlendMode;
+  /**
+   * Clip to the alpha of the nearest non-clipping layer below
+   */
+  clipping: boolean;
+  /**
+   * Composite children directly onto the backdrop instead of into an isolated buffer
+   */
+  pass_through: boolean;
+}
+/**
+ * Sequential reader over a history log. Every record is validated as it is read.
+ */
+export class HistoryReader {
+  free(): void;
+  [Symbol.dispose](): void;
+  constructor(bytes: Uint8Array);
+  /**
+   * Byte offset of the next unread record.
+   */
+  offset(): number;
+  /**
+   * Read the next record, or `undefined` at the end of the log.
+   * A corrupt or truncated record is an error; `offset()` then still points at it,
+   * so callers can truncate the log to the last valid record.
+   */
+  next(): PatchRecord | undefined;
+}
+export class LayerOption {
+  free(): void;
+  [Symbol.dispose](): void;
+  constructor(opacity: number, visible: boolean, blend_mode: BlendMode, clipping: boolean);
+  /**
+   * Layer opacity (0.0-1.0)
+   */
+  opacity: number;
+  /**
+   * Hidden layers are skipped (and hide the layers clipped to them)
+   */
+  visible: boolean;
+  blend_mode: BlendMode;
+  /**
+   * Clip to the alpha of the nearest non-clipping layer below
+   */
+  clipping: boolean;
+}
+/**
+ * Multi-layer compositor.
+ *
+ * Layers are pushed bottom to top between a `begin*` call and `compositeInto`,
+ * so each `RgbaBuffer` is only borrowed for the duration of its `pushLayer` call.
+ * Groups are opened with `beginGroup` and closed with `endGroup`, following the
+ * layer tree depth-first. Only the region chosen by `begin*` is recomposited.
+ */
+export class LayerStack {
+  free(): void;
+  [Symbol.dispose](): void;
+  /**
+   * Start compositing only the given rectangle (clipped to the canvas).
+   */
+  beginRect(x: number, y: number, width: number, height: number): void;
+  /**
+   * Composite a layer over everything pushed so far.
+   */
+  pushLayer(buffer: RgbaBuffer, options: LayerOption): boolean;
+  /**
+   * Open a group. Layers pushed until the matching `endGroup` belong to it.
+   */
+  beginGroup(options: GroupOption): void;
+  /**
+   * Start compositing only the given tiles (row-major linear indices).
+   */
+  beginTiles(tile_size: number, tile_indices: Uint32Array): void;
+  /**
+   * Copy the composited region into the output buffer.
+   */
+  compositeInto(output: RgbaBuffer): boolean;
+  /**
+   * Composite a layer through an 8-bit mask (one byte per pixel, 255 = fully shown).
+   */
+  pushLayerWithMask(buffer: RgbaBuffer, options: LayerOption, mask: Uint8Array): boolean;
+  /**
+   * Open a group whose result is shown through an 8-bit mask.
+   */
+  beginGroupWithMask(options: GroupOption, mask: Uint8Array): boolean;
+  constructor(width: number, height: number);
+  /**
+   * Start compositing the whole canvas.
+   */
+  begin(): void;
+  width(): number;
+  height(): number;
+  /**
+   * Close the innermost group and composite it onto its parent.
+   */
+  endGroup(): boolean;
+}
+export class PatchBufferRgbaOption {
+  free(): void;
+  [Symbol.dispose](): void;
+  /**
+   * `blend_mode` and `opacity` start as `Normal` and 1.0 and are set as properties.
+   */
   constructor(antialias_mode: AntialiasMode, flip_x: boolean, flip_y: boolean);
   antialias_mode: AntialiasMode;
   flip_x: boolean;
   flip_y: boolean;
+  blend_mode: BlendMode;
+  /**
+   * Global opacity applied on top of the source alpha (0.0-1.0)
+   */
+  opacity: number;
+}
+/**
+ * A serializable patch (PackedDiffs + PatchMetadata).
+ */
+export class PatchRecord {
+  free(): void;
+  [Symbol.dispose](): void;
+  /**
+   * Set pixel diffs as flattened [x, y, packed color] triples.
+   */
+  setPixels(pixels: Uint32Array): boolean;
+  /**
+   * Buffer size of the tile patch as [width, height] (empty if absent).
+   */
+  tilesSize(): Uint32Array;
+  /**
+   * Whole buffer size as [width, height] (empty if absent).
+   */
+  wholeSize(): Uint32Array;
+  wholeWebp(): Uint8Array;
+  hasPartial(): boolean;
+  pixelCount(): number | undefined;
+  setPartial(x: number, y: number, width: number, height: number, webp: Uint8Array): void;
+  partialWebp(): Uint8Array;
+  setLayerId(layer_id: string): void;
+  setTimestamp(timestamp: number): void;
+  setPixelCount(pixel_count: number): void;
+  /**
+   * Partial bound box as [x, y, width, height] (empty if absent).
+   */
+  partialBoundBox(): Int32Array;
+  constructor();
+  tool(): string | undefined;
+  tiles(): Uint8Array;
+  /**
+   * Decode a single framed record produced by `encode`.
+   */
+  static decode(record: Uint8Array): PatchRecord;
+  /**
+   * Encode this patch as a single framed record, ready to append to a history log.
+   */
+  encode(): Uint8Array;
+  pixels(): Uint32Array;
+  layerId(): string | undefined;
+  setTool(tool: string): void;
+  hasTiles(): boolean;
+  hasWhole(): boolean;
+  /**
+   * Set a tile patch (see `RgbaBuffer.swapTiles`) for a buffer of the given size.
+   * Returns false if the patch does not decode for that size.
+   */
+  setTiles(width: number, height: number, tiles: Uint8Array): boolean;
+  setWhole(width: number, height: number, webp: Uint8Array): void;
+  timestamp(): number | undefined;
+}
+/**
+ * Outline built from move/line/quadratic/cubic segments, in canvas pixels.
+ * Curves are flattened as they are added.
+ */
+export class Path {
+  free(): void;
+  [Symbol.dispose](): void;
+  constructor();
+  clear(): void;
+  /**
+   * Close the current subpath back to its first point. The next segment starts from there.
+   */
+  close(): void;
+  lineTo(x: number, y: number): void;
+  /**
+   * Start a new subpath at (x, y).
+   */
+  moveTo(x: number, y: number): void;
+  /**
+   * Quadratic Bézier through control point (cx, cy) to (x, y).
+   */
+  quadTo(cx: number, cy: number, x: number, y: number): void;
+  /**
+   * Cubic Bézier through control points (c1x, c1y) and (c2x, c2y) to (x, y).
+   */
+  cubicTo(c1x: number, c1y: number, c2x: number, c2y: number, x: number, y: number): void;
+  isEmpty(): boolean;
+}
+export class PatternFillOption {
+  free(): void;
+  [Symbol.dispose](): void;
+  constructor(offset_x: number, offset_y: number, scale: number);
+  /**
+   * Canvas position of the tile's top-left corner (the pattern repeats in every direction)
+   */
+  offset_x: number;
+  offset_y: number;
+  /**
+   * Integer magnification of the tile (nearest neighbor, at least 1)
+   */
+  scale: number;
 }
 export class PosterizeOption {
   free(): void;
@@ -173,9 +901,49 @@ export class PosterizeOption {
 export class RgbaBuffer {
   free(): void;
   [Symbol.dispose](): void;
+  overwriteWith(raw: Uint8Array, width: number, height: number): boolean;
+  get(x: number, y: number): Array<any>;
+  len(): number;
+  constructor(width: number, height: number);
+  ptr(): number;
+  set(x: number, y: number, r: number, g: number, b: number, a: number): boolean;
+  clone(): RgbaBuffer;
+  width(): number;
+  height(): number;
+  static fromRaw(width: number, height: number, buf: Uint8Array): RgbaBuffer;
+  is_empty(): boolean;
+  data(): Uint8ClampedArray;
+  static fromWebp(width: number, height: number, webp_buf: Uint8Array): RgbaBuffer;
+  isInBounds(x: number, y: number): boolean;
+  indexGet(idx: number): Array<any>;
+  indexSet(idx: number, r: number, g: number, b: number, a: number): boolean;
+  writeRect(rect_x: number, rect_y: number, rect_width: number, rect_height: number, data: Uint8Array): boolean;
+  writePixels(coords: Uint32Array, colors: Uint8Array): boolean;
+  readRect(rect_x: number, rect_y: number, rect_width: number, rect_height: number): Uint8Array;
+  /**
+   * Swap the tiles stored in a tile patch with the current contents.
+   * Returns the tile patch to apply for the opposite direction (undo <-> redo),
+   * or an empty array if the patch is invalid for this buffer.
+   */
+  swapTiles(encoded: Uint8Array): Uint8Array;
+  /**
+   * Capture the current contents of the given tiles (row-major linear indices) as a tile patch.
+   */
+  captureTiles(tile_size: number, tile_indices: Uint32Array): Uint8Array;
+  importPng(png_buffer: Uint8Array, width: number, height: number): boolean;
+  importRaw(raw: Uint8Array, width: number, height: number): boolean;
+  importWebp(webp_buffer: Uint8Array, width: number, height: number): boolean;
   dustRemoval(max_size: number, alpha_threshold: number): void;
   gaussianBlur(radius: number, alpha_mode: AlphaBlurMode): void;
   brightnessAndContrast(brightness: number, contrast: number): void;
+  invertInSelection(selection: SelectionMask): boolean;
+  ditheringInSelection(selection: SelectionMask, mode: DitheringMode, levels: number, strength: number): boolean;
+  grayscaleInSelection(selection: SelectionMask): boolean;
+  posterizeInSelection(selection: SelectionMask, levels: number): boolean;
+  dustRemovalInSelection(selection: SelectionMask, max_size: number, alpha_threshold: number): boolean;
+  dustRemovalWithOptions(options: DustRemovalOption): void;
+  gaussianBlurInSelection(selection: SelectionMask, radius: number, alpha_mode: AlphaBlurMode): boolean;
+  brightnessAndContrastInSelection(selection: SelectionMask, brightness: number, contrast: number): boolean;
   invert(): void;
   dithering(mode: DitheringMode, levels: number, strength: number): void;
   grayscale(): void;
@@ -184,35 +952,424 @@ export class RgbaBuffer {
   cropWithMask(mask: Uint8Array, mask_width: number, mask_height: number, mask_offset_x: number, mask_offset_y: number): Uint8Array;
   sliceWithMask(mask: Uint8Array, mask_width: number, mask_height: number, mask_offset_x: number, mask_offset_y: number): Uint8Array;
   blitFromBuffer(source: RgbaBuffer, offset_x: number, offset_y: number, scale_x: number, scale_y: number, rotate_deg: number, antialias_mode: AntialiasMode, flip_x: boolean, flip_y: boolean): void;
-  writeRect(rect_x: number, rect_y: number, rect_width: number, rect_height: number, data: Uint8Array): boolean;
-  writePixels(coords: Uint32Array, colors: Uint8Array): boolean;
-  readRect(rect_x: number, rect_y: number, rect_width: number, rect_height: number): Uint8Array;
+  cropWithSelection(selection: SelectionMask, offset_x: number, offset_y: number): Uint8Array;
+  sliceWithSelection(selection: SelectionMask, offset_x: number, offset_y: number): Uint8Array;
+  blitFromRawWithOptions(source: Uint8Array, source_width: number, source_height: number, offset_x: number, offset_y: number, scale_x: number, scale_y: number, rotate_deg: number, options: PatchBufferRgbaOption): void;
+  blitFromBufferWithOptions(source: RgbaBuffer, offset_x: number, offset_y: number, scale_x: number, scale_y: number, rotate_deg: number, options: PatchBufferRgbaOption): void;
+  /**
+   * Flood fill and report what changed, or undefined if the start point is out of bounds.
+   * `connectivity` defaults to `Four`.
+   */
+  floodFill(start_x: number, start_y: number, fill_color_r: number, fill_color_g: number, fill_color_b: number, fill_color_a: number, threshold: number, connectivity?: Connectivity | null): FillResult | undefined;
+  /**
+   * Fill the whole buffer with `tile` repeated.
+   */
+  patternFill(tile: RgbaBuffer, options: PatternFillOption): FillResult | undefined;
+  /**
+   * Paint a gradient over the whole buffer.
+   * `stop_colors` holds four RGBA bytes per entry of `stop_positions` (0-1).
+   * Undefined if there are no stops.
+   */
+  gradientFill(stop_positions: Float32Array, stop_colors: Uint8Array, options: GradientFillOption): FillResult | undefined;
+  fillAllCodes(fill_code: number): boolean;
+  /**
+   * Fill every pixel whose mask flag is non-zero and report what changed.
+   */
+  fillMaskArea(mask: Uint8Array, fill_color_r: number, fill_color_g: number, fill_color_b: number, fill_color_a: number): FillResult;
+  /**
+   * Fill the selection, fading out where its coverage is partial (premultiplied).
+   * The selection must match the buffer size.
+   */
+  fillSelection(selection: SelectionMask, fill_color_r: number, fill_color_g: number, fill_color_b: number, fill_color_a: number): FillResult | undefined;
+  fillAllPixels(fill_color_r: number, fill_color_g: number, fill_color_b: number, fill_color_a: number): boolean;
+  /**
+   * Make fills copy the coverage of the pixels they changed into `FillResult.coverage`.
+   * Off by default, since the copy is as large as the changed area.
+   */
+  setFillCoverage(enabled: boolean): void;
+  /**
+   * `floodFill` limited by a selection mask. Undefined if the start point is not allowed.
+   * `connectivity` defaults to `Four`.
+   */
+  floodFillWithMask(start_x: number, start_y: number, fill_color_r: number, fill_color_g: number, fill_color_b: number, fill_color_a: number, threshold: number, selection_mask: Uint8Array, limit_mode: string, connectivity?: Connectivity | null): FillResult | undefined;
+  /**
+   * Flood fill with a configurable color metric and alpha handling.
+   */
+  floodFillWithOptions(start_x: number, start_y: number, fill_color_r: number, fill_color_g: number, fill_color_b: number, fill_color_a: number, options: FloodFillOption): FillResult | undefined;
+  /**
+   * Bucket fill with `tile` instead of a solid color. The region is found like
+   * `floodFillWithOptions`.
+   */
+  floodFillWithPattern(start_x: number, start_y: number, tile: RgbaBuffer, fill_options: FloodFillOption, options: PatternFillOption): FillResult | undefined;
+  /**
+   * Flood fill whose region is computed from `reference` (e.g. the line art layer or a
+   * merged composite) while the fill color is written into this buffer.
+   * Both buffers must have the same size.
+   */
+  floodFillFromReference(reference: RgbaBuffer, start_x: number, start_y: number, fill_color_r: number, fill_color_g: number, fill_color_b: number, fill_color_a: number, options: FloodFillOption): FillResult | undefined;
+  /**
+   * `floodFillWithMask` limited by a `SelectionMask` of the buffer size.
+   */
+  floodFillWithSelection(start_x: number, start_y: number, fill_color_r: number, fill_color_g: number, fill_color_b: number, fill_color_a: number, threshold: number, selection: SelectionMask, limit_mode: string, connectivity?: Connectivity | null): FillResult | undefined;
+  /**
+   * `patternFill` limited to a `SelectionMask` of the buffer size, fading at partial
+   * coverage.
+   */
+  patternFillWithSelection(tile: RgbaBuffer, selection: SelectionMask, options: PatternFillOption): FillResult | undefined;
+  /**
+   * `fillMaskArea` where mask values are applied according to `mode`.
+   */
+  fillMaskAreaWithCoverage(mask: Uint8Array, fill_color_r: number, fill_color_g: number, fill_color_b: number, fill_color_a: number, mode: CoverageMode): FillResult;
+  /**
+   * `gradientFill` limited to a `SelectionMask` of the buffer size, fading at partial
+   * coverage.
+   */
+  gradientFillWithSelection(stop_positions: Float32Array, stop_colors: Uint8Array, selection: SelectionMask, options: GradientFillOption): FillResult | undefined;
+  /**
+   * `floodFillWithPattern` limited by a `SelectionMask` of the buffer size.
+   */
+  floodFillWithPatternAndSelection(start_x: number, start_y: number, tile: RgbaBuffer, selection: SelectionMask, limit_mode: string, fill_options: FloodFillOption, options: PatternFillOption): FillResult | undefined;
+  /**
+   * `floodFillWithOptions` limited by a `SelectionMask` of the buffer size.
+   */
+  floodFillWithSelectionAndOptions(start_x: number, start_y: number, fill_color_r: number, fill_color_g: number, fill_color_b: number, fill_color_a: number, selection: SelectionMask, limit_mode: string, options: FloodFillOption): FillResult | undefined;
+  /**
+   * `floodFillFromReference` limited by a `SelectionMask` of the buffer size.
+   */
+  floodFillFromReferenceWithSelection(reference: RgbaBuffer, start_x: number, start_y: number, fill_color_r: number, fill_color_g: number, fill_color_b: number, fill_color_a: number, selection: SelectionMask, limit_mode: string, options: FloodFillOption): FillResult | undefined;
+  /**
+   * Select pixels similar to the one at (x, y). The buffer is left untouched.
+   * An out-of-bounds start point yields an empty selection. `connectivity` defaults to `Four`.
+   */
+  magicWand(x: number, y: number, tolerance: number, contiguous: boolean, antialias: boolean, connectivity?: Connectivity | null): SelectionMask;
+  /**
+   * Run the magic wand and combine its result into an existing selection of the buffer size.
+   * `connectivity` defaults to `Four`.
+   */
+  magicWandInto(selection: SelectionMask, x: number, y: number, tolerance: number, contiguous: boolean, antialias: boolean, op: SelectionOp, connectivity?: Connectivity | null): boolean;
+  /**
+   * `magicWand` with a configurable color metric; `options.threshold` is the tolerance.
+   */
+  magicWandWithOptions(x: number, y: number, contiguous: boolean, antialias: boolean, options: FloodFillOption): SelectionMask;
+  magicWandIntoWithOptions(selection: SelectionMask, x: number, y: number, contiguous: boolean, antialias: boolean, options: FloodFillOption, op: SelectionOp): boolean;
+  /**
+   * Stop journaling and drop the transaction in progress.
+   */
+  endJournal(): void;
+  /**
+   * Start journaling writes into tile snapshots.
+   * Any transaction in progress is discarded.
+   */
+  beginJournal(tile_size: number, budget_bytes: number): boolean;
+  isJournaling(): boolean;
+  /**
+   * Bytes currently held by tile snapshots, raw or encoded. Never above the budget.
+   */
+  journalBytes(): number;
+  /**
+   * Close the current transaction and return its undo record, then start a new one.
+   * The record is a tile patch: apply it with `swapTiles`, which returns the redo record.
+   * Returns an empty array if nothing changed or the transaction overflowed.
+   */
+  commitJournal(): Uint8Array;
+  /**
+   * Whether the transaction in progress outgrew the budget. Its undo record is lost,
+   * so `commitJournal` returns an empty array and the caller has to record the change
+   * another way.
+   */
+  journalOverflowed(): boolean;
+  /**
+   * Circle around (center_x, center_y). Aliased circles with an odd diameter need a
+   * center on a pixel center (e.g. 10.5).
+   */
+  drawCircle(center_x: number, center_y: number, radius: number, color_r: number, color_g: number, color_b: number, color_a: number, options: ShapeOption): FillResult | undefined;
+  /**
+   * Ellipse inscribed in the box (x, y, width, height).
+   */
+  drawEllipse(x: number, y: number, width: number, height: number, color_r: number, color_g: number, color_b: number, color_a: number, options: ShapeOption): FillResult | undefined;
+  /**
+   * Closed polygon through `points` ([x0, y0, x1, y1, ...]).
+   */
+  drawPolygon(points: Float32Array, color_r: number, color_g: number, color_b: number, color_a: number, options: ShapeOption): FillResult | undefined;
+  drawRoundedRect(x: number, y: number, width: number, height: number, radius: number, color_r: number, color_g: number, color_b: number, color_a: number, options: ShapeOption): FillResult | undefined;
+  drawLine(x0: number, y0: number, x1: number, y1: number, color_r: number, color_g: number, color_b: number, color_a: number, options: ShapeOption): FillResult | undefined;
+  /**
+   * Fill and/or stroke `path` as set in `options`.
+   */
+  drawPath(path: Path, color_r: number, color_g: number, color_b: number, color_a: number, options: ShapeOption): FillResult | undefined;
+  drawRect(x: number, y: number, width: number, height: number, color_r: number, color_g: number, color_b: number, color_a: number, options: ShapeOption): FillResult | undefined;
   exportPng(): Uint8Array;
   exportWebp(): Uint8Array;
   resizeWithOrigins(new_width: number, new_height: number, src_origin_x: number, src_origin_y: number, dest_origin_x: number, dest_origin_y: number): void;
   resize(new_width: number, new_height: number): void;
-  overwriteWith(raw: Uint8Array, width: number, height: number): boolean;
-  get(x: number, y: number): Array<any>;
-  len(): number;
+}
+/**
+ * Selection with 8-bit coverage per pixel (0 = unselected, 255 = fully selected).
+ */
+export class SelectionMask {
+  free(): void;
+  [Symbol.dispose](): void;
+  /**
+   * Selection from a free-hand or polygon lasso, see `lasso_mask`.
+   */
+  static fromLasso(width: number, height: number, points: Float32Array, rule: FillRule, antialias: boolean): SelectionMask;
+  xor(other: SelectionMask): boolean;
+  /**
+   * Combine `other` into this selection according to `op`.
+   */
+  apply(other: SelectionMask, op: SelectionOp): boolean;
+  union(other: SelectionMask): boolean;
+  invert(): void;
+  subtract(other: SelectionMask): boolean;
+  intersect(other: SelectionMask): boolean;
+  grow(radius: number, shape: MaskShape): void;
+  border(size: number, shape: MaskShape): void;
+  shrink(radius: number, shape: MaskShape): void;
+  smooth(radius: number): void;
+  feather(radius: number): void;
+  /**
+   * Import a flag mask where any non-zero value means fully selected.
+   */
+  static fromFlags(width: number, height: number, buf: Uint8Array): SelectionMask;
+  selectAll(): void;
+  /**
+   * Coverage at a pixel, 0 outside the mask.
+   */
+  get(x: number, y: number): number;
+  /**
+   * Create an empty selection.
+   */
   constructor(width: number, height: number);
-  ptr(): number;
-  set(x: number, y: number, r: number, g: number, b: number, a: number): boolean;
-  clone(): RgbaBuffer;
+  set(x: number, y: number, coverage: number): boolean;
+  clear(): void;
+  clone(): SelectionMask;
   width(): number;
   height(): number;
-  static fromRaw(width: number, height: number, buf: Uint8Array): RgbaBuffer;
-  is_empty(): boolean;
-  data(): Uint8ClampedArray;
-  static fromWebp(width: number, height: number, webp_buf: Uint8Array): RgbaBuffer;
+  /**
+   * Copy of the coverage bytes.
+   */
+  toRaw(): Uint8Array;
+  /**
+   * Import coverage bytes (0-255) as they are.
+   */
+  static fromRaw(width: number, height: number, buf: Uint8Array): SelectionMask;
+  /**
+   * True when no pixel has any coverage.
+   */
+  isEmpty(): boolean;
+  /**
+   * Copy as a flag mask (1 = selected, 0 = not) for the slice-based APIs.
+   */
+  toFlags(): Uint8Array;
+  data(): Uint8Array;
   isInBounds(x: number, y: number): boolean;
-  indexGet(idx: number): Array<any>;
-  indexSet(idx: number, r: number, g: number, b: number, a: number): boolean;
-  floodFill(start_x: number, start_y: number, fill_color_r: number, fill_color_g: number, fill_color_b: number, fill_color_a: number, threshold: number): boolean;
-  fillAllCodes(fill_code: number): boolean;
-  fillMaskArea(mask: Uint8Array, fill_color_r: number, fill_color_g: number, fill_color_b: number, fill_color_a: number): boolean;
+  /**
+   * Combine the fill and/or stroke of `path` into this selection according to `op`.
+   * A selection set on `options` limits the path coverage first.
+   * Returns false if that selection does not match this mask's size.
+   */
+  drawPath(path: Path, options: ShapeOption, op: SelectionOp): boolean;
+  /**
+   * Bounding box of the covered pixels as [x, y, width, height].
+   * Returns an empty array if nothing is selected.
+   */
+  boundBox(): Uint32Array;
+  /**
+   * Move the selection by whole pixels. Coverage shifted past the edges is dropped.
+   */
+  translate(dx: number, dy: number): void;
+}
+/**
+ * How a shape is drawn. `selection` is set through `setSelection` and limits the shape
+ * like in the fill functions.
+ */
+export class ShapeOption {
+  free(): void;
+  [Symbol.dispose](): void;
+  /**
+   * Limit drawing to `selection` (copied; it must match the target buffer size).
+   */
+  setSelection(selection: SelectionMask): void;
+  clearSelection(): void;
+  /**
+   * `fill_rule`, `line_join`, `line_cap` and `miter_limit` start as `NonZero`, `Miter`,
+   * `Butt` and 4 and are set as properties.
+   */
+  constructor(fill: boolean, stroke_width: number, antialias: boolean);
+  /**
+   * Paint the interior
+   */
+  fill: boolean;
+  /**
+   * Outline width in pixels (0 = no outline). Rectangles and ellipses draw it inside
+   * their bounds, polygons and paths center it on the outline, and lines always use
+   * at least 1.
+   */
+  stroke_width: number;
+  /**
+   * Antialiased coverage instead of aliased pixel-art output
+   */
+  antialias: boolean;
+  /**
+   * Inside test for polygon and path fills
+   */
+  fill_rule: FillRule;
+  /**
+   * Corner shape of antialiased polygon and path outlines
+   */
+  line_join: LineJoin;
+  /**
+   * End shape of open path outlines
+   */
+  line_cap: LineCap;
+  /**
+   * Longest miter, in stroke widths, before a `Miter` join is beveled
+   */
+  miter_limit: number;
+}
+/**
+ * One brush stroke drawn into an `RgbaBuffer` point by point.
+ *
+ * Dabs are placed along the pixel line between consecutive points with pressure, size
+ * and opacity interpolated. Within a stroke each pixel keeps the highest coverage it
+ * received, so overlapping dabs do not build up opacity. The stroke remembers the color
+ * every touched pixel had before it started, which is the undo diff of the stroke.
+ */
+export class Stroke {
+  free(): void;
+  [Symbol.dispose](): void;
+  /**
+   * Number of pixels the stroke has touched.
+   */
+  changedPixels(): number;
+  constructor(stamp: BrushStamp, color_r: number, color_g: number, color_b: number, color_a: number, options: StrokeOption);
+  /**
+   * Flattened [x, y, color] triples of every touched pixel with the color it had before
+   * the stroke, packed as (a << 24) | (r << 16) | (g << 8) | b like `rgbaToPackedU32`.
+   */
+  pixels(): Uint32Array;
+  /**
+   * Draw up to the next input point.
+   *
+   * `size` scales the stamp (1 = as given) and `opacity` (0-1) scales its coverage;
+   * `pressure` (0-1) scales either according to the options.
+   * Returns the bounds written by this call as [x, y, width, height], or an empty
+   * array if nothing changed.
+   */
+  addPoint(buffer: RgbaBuffer, x: number, y: number, pressure: number, size: number, opacity: number): Uint32Array;
+  /**
+   * Bounding box of the touched pixels as [x, y, width, height] (all zero if none).
+   */
+  boundBox(): Uint32Array;
+}
+export class StrokeOption {
+  free(): void;
+  [Symbol.dispose](): void;
+  /**
+   * `pixel_perfect` starts disabled and is set as a property.
+   */
+  constructor(spacing: number, pressure_size: boolean, pressure_opacity: boolean);
+  /**
+   * Distance between dabs as a fraction of the dab size (0 = a dab on every pixel)
+   */
+  spacing: number;
+  /**
+   * Pressure scales the dab size
+   */
+  pressure_size: boolean;
+  /**
+   * Pressure scales the dab opacity
+   */
+  pressure_opacity: boolean;
+  /**
+   * Drop the corner pixel of every L-shaped step so 1px lines stay one pixel thin.
+   * Only affects 1x1 dabs.
+   */
+  pixel_perfect: boolean;
+}
+/**
+ * Sparse RGBA storage for very large canvases.
+ *
+ * The canvas is split into square tiles. Uniform tiles keep a single color, and only
+ * tiles that actually hold differing pixels allocate memory.
+ */
+export class TiledRgbaBuffer {
+  free(): void;
+  [Symbol.dispose](): void;
+  blitFromRaw(source: Uint8Array, source_width: number, source_height: number, offset_x: number, offset_y: number, scale_x: number, scale_y: number, rotate_deg: number, antialias_mode: AntialiasMode, flip_x: boolean, flip_y: boolean): void;
+  blitFromBuffer(source: RgbaBuffer, offset_x: number, offset_y: number, scale_x: number, scale_y: number, rotate_deg: number, antialias_mode: AntialiasMode, flip_x: boolean, flip_y: boolean): void;
+  /**
+   * Blit only touches the tiles under the transformed source bounds.
+   */
+  blitFromRawWithOptions(source: Uint8Array, source_width: number, source_height: number, offset_x: number, offset_y: number, scale_x: number, scale_y: number, rotate_deg: number, options: PatchBufferRgbaOption): void;
+  blitFromBufferWithOptions(source: RgbaBuffer, offset_x: number, offset_y: number, scale_x: number, scale_y: number, rotate_deg: number, options: PatchBufferRgbaOption): void;
+  /**
+   * Flood fill from a seed point and report what changed, or undefined if the start
+   * point is out of bounds (see `RgbaBuffer.floodFill`). `connectivity` defaults to `Four`.
+   *
+   * The fill reads pixels tile by tile and keeps its walk state only for the tiles it
+   * reaches, so it never materializes the canvas. Only changed tiles are written back.
+   */
+  floodFill(start_x: number, start_y: number, fill_color_r: number, fill_color_g: number, fill_color_b: number, fill_color_a: number, threshold: number, connectivity?: Connectivity | null): FillResult | undefined;
+  /**
+   * Fill pixels whose canvas-sized mask value is non-zero and report what changed.
+   * Fully covered tiles become uniform and untouched tiles stay unallocated.
+   */
+  fillMaskArea(mask: Uint8Array, fill_color_r: number, fill_color_g: number, fill_color_b: number, fill_color_a: number): FillResult;
+  /**
+   * Fill the whole canvas. Every tile collapses to a single color.
+   */
   fillAllPixels(fill_color_r: number, fill_color_g: number, fill_color_b: number, fill_color_a: number): boolean;
-  floodFillWithMask(start_x: number, start_y: number, fill_color_r: number, fill_color_g: number, fill_color_b: number, fill_color_a: number, threshold: number, selection_mask: Uint8Array, limit_mode: string): boolean;
-  importPng(png_buffer: Uint8Array, width: number, height: number): boolean;
-  importRaw(raw: Uint8Array, width: number, height: number): boolean;
-  importWebp(webp_buffer: Uint8Array, width: number, height: number): boolean;
+  /**
+   * Make fills copy the coverage of the pixels they changed into `FillResult.coverage`
+   * (see `RgbaBuffer.setFillCoverage`).
+   */
+  setFillCoverage(enabled: boolean): void;
+  /**
+   * `floodFill` limited by a selection mask (see `RgbaBuffer.floodFillWithMask`).
+   */
+  floodFillWithMask(start_x: number, start_y: number, fill_color_r: number, fill_color_g: number, fill_color_b: number, fill_color_a: number, threshold: number, selection_mask: Uint8Array, limit_mode: string, connectivity?: Connectivity | null): FillResult | undefined;
+  /**
+   * `fillMaskArea` where mask values are applied according to `mode`
+   * (see `RgbaBuffer.fillMaskAreaWithCoverage`).
+   */
+  fillMaskAreaWithCoverage(mask: Uint8Array, fill_color_r: number, fill_color_g: number, fill_color_b: number, fill_color_a: number, mode: CoverageMode): FillResult;
+  /**
+   * Write a rectangle of pixels. Tiles that end up uniform are stored as a single color.
+   */
+  writeRect(rect_x: number, rect_y: number, rect_width: number, rect_height: number, data: Uint8Array): boolean;
+  writePixels(coords: Uint32Array, colors: Uint8Array): boolean;
+  readRect(rect_x: number, rect_y: number, rect_width: number, rect_height: number): Uint8Array;
+  /**
+   * Build sparse storage from a dense buffer.
+   */
+  static fromBuffer(buffer: RgbaBuffer, tile_size: number): TiledRgbaBuffer;
+  /**
+   * Bytes used by dense tile storage.
+   */
+  allocatedBytes(): number;
+  /**
+   * Number of tiles that hold their own pixel storage.
+   */
+  allocatedTiles(): number;
+  get(x: number, y: number): Array<any>;
+  constructor(width: number, height: number, tile_size: number);
+  set(x: number, y: number, r: number, g: number, b: number, a: number): boolean;
+  width(): number;
+  height(): number;
+  /**
+   * Collapse dense tiles whose pixels all share one color.
+   */
+  compact(): void;
+  isInBounds(x: number, y: number): boolean;
+  tileSize(): number;
+  /**
+   * Materialize the whole canvas as a dense buffer (width * height * 4 bytes).
+   */
+  toBuffer(): RgbaBuffer;
+  /**
+   * Encode the canvas as PNG one tile row at a time, without materializing the whole image.
+   * Returns an empty array on failure.
+   */
+  exportPng(): Uint8Array;
 }
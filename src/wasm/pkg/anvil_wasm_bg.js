@@ -39,6 +39,20 @@ function getStringFromWasm0(ptr, len) {
     return decodeText(ptr, len);
 }
 
+let cachedUint32ArrayMemory0 = null;
+
+function getUint32ArrayMemory0() {
+    if (cachedUint32ArrayMemory0 === null || cachedUint32ArrayMemory0.byteLength === 0) {
+        cachedUint32ArrayMemory0 = new Uint32Array(wasm.memory.buffer);
+    }
+    return cachedUint32ArrayMemory0;
+}
+
+function getArrayU32FromWasm0(ptr, len) {
+    ptr = ptr >>> 0;
+    return getUint32ArrayMemory0().subarray(ptr / 4, ptr / 4 + len);
+}
+
 let WASM_VECTOR_LEN = 0;
 
 function passArray8ToWasm0(arg, malloc) {
@@ -47,22 +61,21 @@ function passArray8ToWasm0(arg, malloc) {
     WASM_VECTOR_LEN = arg.length;
     return ptr;
 }
-/**
- * @param {Uint8Array} buffer
- * @param {Uint8Array} mask
- * @param {number} fill_color_r
- * @param {number} fill_color_g
- * @param {number} fill_color_b
- * @param {number} fill_color_a
- * @returns {boolean}
- */
-export function fill_mask_area(buffer, mask, fill_color_r, fill_color_g, fill_color_b, fill_color_a) {
-    var ptr0 = passArray8ToWasm0(buffer, wasm.__wbindgen_malloc);
-    var len0 = WASM_VECTOR_LEN;
-    const ptr1 = passArray8ToWasm0(mask, wasm.__wbindgen_malloc);
-    const len1 = WASM_VECTOR_LEN;
-    const ret = wasm.fill_mask_area(ptr0, len0, buffer, ptr1, len1, fill_color_r, fill_color_g, fill_color_b, fill_color_a);
-    return ret !== 0;
+
+let cachedFloat32ArrayMemory0 = null;
+
+function getFloat32ArrayMemory0() {
+    if (cachedFloat32ArrayMemory0 === null || cachedFloat32ArrayMemory0.byteLength === 0) {
+        cachedFloat32ArrayMemory0 = new Float32Array(wasm.memory.buffer);
+    }
+    return cachedFloat32ArrayMemory0;
+}
+
+function passArrayF32ToWasm0(arg, malloc) {
+    const ptr = malloc(arg.length * 4, 4) >>> 0;
+    getFloat32ArrayMemory0().set(arg, ptr / 4);
+    WASM_VECTOR_LEN = arg.length;
+    return ptr;
 }
 
 function _assertClass(instance, klass) {
@@ -71,41 +84,60 @@ function _assertClass(instance, klass) {
     }
 }
 /**
- * @param {Uint8Array} pixels
+ * Paint a gradient over the buffer.
+ *
+ * `stop_positions` (0-1) and `stop_colors` (flattened RGBA, four per stop) describe the
+ * color stops; stops need not be sorted. A non-empty `selection_mask` (width * height,
+ * 0-255 coverage) limits the fill and fades it at partial coverage.
+ * Returns false if there are no stops or the sizes do not match.
+ * @param {Uint8Array} buffer
  * @param {number} width
  * @param {number} height
+ * @param {Float32Array} stop_positions
+ * @param {Uint8Array} stop_colors
+ * @param {Uint8Array} selection_mask
+ * @param {GradientFillOption} options
+ * @returns {boolean}
  */
-export function invert(pixels, width, height) {
-    var ptr0 = passArray8ToWasm0(pixels, wasm.__wbindgen_malloc);
+export function gradient_fill(buffer, width, height, stop_positions, stop_colors, selection_mask, options) {
+    var ptr0 = passArray8ToWasm0(buffer, wasm.__wbindgen_malloc);
     var len0 = WASM_VECTOR_LEN;
-    wasm.invert(ptr0, len0, pixels, width, height);
+    const ptr1 = passArrayF32ToWasm0(stop_positions, wasm.__wbindgen_malloc);
+    const len1 = WASM_VECTOR_LEN;
+    const ptr2 = passArray8ToWasm0(stop_colors, wasm.__wbindgen_malloc);
+    const len2 = WASM_VECTOR_LEN;
+    const ptr3 = passArray8ToWasm0(selection_mask, wasm.__wbindgen_malloc);
+    const len3 = WASM_VECTOR_LEN;
+    _assertClass(options, GradientFillOption);
+    const ret = wasm.gradient_fill(ptr0, len0, buffer, width, height, ptr1, len1, ptr2, len2, ptr3, len3, options.__wbg_ptr);
+    return ret !== 0;
 }
 
 /**
- * Apply ordered dithering with simple parameters
+ * Remove small isolated pixel groups with default settings
  * @param {Uint8Array} pixels
  * @param {number} width
  * @param {number} height
- * @param {number} levels
+ * @param {number} max_size
  */
-export function dithering_ordered(pixels, width, height, levels) {
+export function dust_removal_simple(pixels, width, height, max_size) {
     var ptr0 = passArray8ToWasm0(pixels, wasm.__wbindgen_malloc);
     var len0 = WASM_VECTOR_LEN;
-    wasm.dithering_ordered(ptr0, len0, pixels, width, height, levels);
+    wasm.dust_removal_simple(ptr0, len0, pixels, width, height, max_size);
 }
 
 /**
- * Apply dithering effect to the image
+ * Remove small isolated pixel groups (dust removal)
  * @param {Uint8Array} pixels
  * @param {number} width
  * @param {number} height
- * @param {DitheringOption} options
+ * @param {DustRemovalOption} options
  */
-export function dithering(pixels, width, height, options) {
+export function dust_removal(pixels, width, height, options) {
     var ptr0 = passArray8ToWasm0(pixels, wasm.__wbindgen_malloc);
     var len0 = WASM_VECTOR_LEN;
-    _assertClass(options, DitheringOption);
-    wasm.dithering(ptr0, len0, pixels, width, height, options.__wbg_ptr);
+    _assertClass(options, DustRemovalOption);
+    wasm.dust_removal(ptr0, len0, pixels, width, height, options.__wbg_ptr);
 }
 
 /**
@@ -122,58 +154,43 @@ export function dithering_random(pixels, width, height, levels) {
 }
 
 /**
- * Apply error diffusion dithering with simple parameters
+ * Apply dithering effect to the image
  * @param {Uint8Array} pixels
  * @param {number} width
  * @param {number} height
- * @param {number} levels
+ * @param {DitheringOption} options
  */
-export function dithering_error_diffusion(pixels, width, height, levels) {
+export function dithering(pixels, width, height, options) {
     var ptr0 = passArray8ToWasm0(pixels, wasm.__wbindgen_malloc);
     var len0 = WASM_VECTOR_LEN;
-    wasm.dithering_error_diffusion(ptr0, len0, pixels, width, height, levels);
-}
-
-let cachedUint32ArrayMemory0 = null;
-
-function getUint32ArrayMemory0() {
-    if (cachedUint32ArrayMemory0 === null || cachedUint32ArrayMemory0.byteLength === 0) {
-        cachedUint32ArrayMemory0 = new Uint32Array(wasm.memory.buffer);
-    }
-    return cachedUint32ArrayMemory0;
+    _assertClass(options, DitheringOption);
+    wasm.dithering(ptr0, len0, pixels, width, height, options.__wbg_ptr);
 }
 
-function passArray32ToWasm0(arg, malloc) {
-    const ptr = malloc(arg.length * 4, 4) >>> 0;
-    getUint32ArrayMemory0().set(arg, ptr / 4);
-    WASM_VECTOR_LEN = arg.length;
-    return ptr;
-}
 /**
- * Remove small isolated pixel groups (dust removal)
+ * Apply error diffusion dithering with simple parameters
  * @param {Uint8Array} pixels
  * @param {number} width
  * @param {number} height
- * @param {DustRemovalOption} options
+ * @param {number} levels
  */
-export function dust_removal(pixels, width, height, options) {
+export function dithering_error_diffusion(pixels, width, height, levels) {
     var ptr0 = passArray8ToWasm0(pixels, wasm.__wbindgen_malloc);
     var len0 = WASM_VECTOR_LEN;
-    _assertClass(options, DustRemovalOption);
-    wasm.dust_removal(ptr0, len0, pixels, width, height, options.__wbg_ptr);
+    wasm.dithering_error_diffusion(ptr0, len0, pixels, width, height, levels);
 }
 
 /**
- * Remove small isolated pixel groups with default settings
+ * Apply ordered dithering with simple parameters
  * @param {Uint8Array} pixels
  * @param {number} width
  * @param {number} height
- * @param {number} max_size
+ * @param {number} levels
  */
-export function dust_removal_simple(pixels, width, height, max_size) {
+export function dithering_ordered(pixels, width, height, levels) {
     var ptr0 = passArray8ToWasm0(pixels, wasm.__wbindgen_malloc);
     var len0 = WASM_VECTOR_LEN;
-    wasm.dust_removal_simple(ptr0, len0, pixels, width, height, max_size);
+    wasm.dithering_ordered(ptr0, len0, pixels, width, height, levels);
 }
 
 /**
@@ -203,59 +220,6 @@ export function posterize_simple(pixels, width, height, levels) {
     wasm.posterize_simple(ptr0, len0, pixels, width, height, levels);
 }
 
-/**
- * Apply brightness and contrast adjustments to the image
- * @param {Uint8Array} pixels
- * @param {number} width
- * @param {number} height
- * @param {BrightnessContrastOption} options
- */
-export function brightness_contrast(pixels, width, height, options) {
-    var ptr0 = passArray8ToWasm0(pixels, wasm.__wbindgen_malloc);
-    var len0 = WASM_VECTOR_LEN;
-    _assertClass(options, BrightnessContrastOption);
-    wasm.brightness_contrast(ptr0, len0, pixels, width, height, options.__wbg_ptr);
-}
-
-/**
- * Apply only contrast adjustment to the image
- * @param {Uint8Array} pixels
- * @param {number} width
- * @param {number} height
- * @param {number} contrast
- */
-export function contrast(pixels, width, height, contrast) {
-    var ptr0 = passArray8ToWasm0(pixels, wasm.__wbindgen_malloc);
-    var len0 = WASM_VECTOR_LEN;
-    wasm.contrast(ptr0, len0, pixels, width, height, contrast);
-}
-
-/**
- * Apply only brightness adjustment to the image
- * @param {Uint8Array} pixels
- * @param {number} width
- * @param {number} height
- * @param {number} brightness
- */
-export function brightness(pixels, width, height, brightness) {
-    var ptr0 = passArray8ToWasm0(pixels, wasm.__wbindgen_malloc);
-    var len0 = WASM_VECTOR_LEN;
-    wasm.brightness(ptr0, len0, pixels, width, height, brightness);
-}
-
-/**
- * @param {Uint8Array} pixels
- * @param {number} width
- * @param {number} height
- * @param {GaussianBlurOption} options
- */
-export function gaussian_blur(pixels, width, height, options) {
-    var ptr0 = passArray8ToWasm0(pixels, wasm.__wbindgen_malloc);
-    var len0 = WASM_VECTOR_LEN;
-    _assertClass(options, GaussianBlurOption);
-    wasm.gaussian_blur(ptr0, len0, pixels, width, height, options.__wbg_ptr);
-}
-
 const cachedTextEncoder = new TextEncoder();
 
 if (!('encodeInto' in cachedTextEncoder)) {
@@ -307,8 +271,79 @@ function passStringToWasm0(arg, malloc, realloc) {
     WASM_VECTOR_LEN = offset;
     return ptr;
 }
+/**
+ * 参照バッファで領域を求め、別のバッファに塗る FloodFill
+ *
+ * 線画レイヤー（または合成結果）を `reference` に渡し、塗りを `target` に書き込みます。
+ * 両者は同じ width * height である必要があります。
+ * `selection_mask` と `limit_mode` は `scanline_flood_fill_with_options` と同じです。
+ * @param {Uint8Array} target
+ * @param {Uint8Array} reference
+ * @param {number} width
+ * @param {number} height
+ * @param {number} start_x
+ * @param {number} start_y
+ * @param {number} fill_color_r
+ * @param {number} fill_color_g
+ * @param {number} fill_color_b
+ * @param {number} fill_color_a
+ * @param {Uint8Array} selection_mask
+ * @param {string} limit_mode
+ * @param {FloodFillOption} options
+ * @returns {boolean}
+ */
+export function scanline_flood_fill_from_reference(target, reference, width, height, start_x, start_y, fill_color_r, fill_color_g, fill_color_b, fill_color_a, selection_mask, limit_mode, options) {
+    var ptr0 = passArray8ToWasm0(target, wasm.__wbindgen_malloc);
+    var len0 = WASM_VECTOR_LEN;
+    const ptr1 = passArray8ToWasm0(reference, wasm.__wbindgen_malloc);
+    const len1 = WASM_VECTOR_LEN;
+    const ptr2 = passArray8ToWasm0(selection_mask, wasm.__wbindgen_malloc);
+    const len2 = WASM_VECTOR_LEN;
+    const ptr3 = passStringToWasm0(limit_mode, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len3 = WASM_VECTOR_LEN;
+    _assertClass(options, FloodFillOption);
+    const ret = wasm.scanline_flood_fill_from_reference(ptr0, len0, target, ptr1, len1, width, height, start_x, start_y, fill_color_r, fill_color_g, fill_color_b, fill_color_a, ptr2, len2, ptr3, len3, options.__wbg_ptr);
+    return ret !== 0;
+}
+
+/**
+ * 色距離の指定付きスキャンライン FloodFill
+ *
+ * `selection_mask` と `limit_mode` は `scanline_flood_fill_with_mask` と同じです。
+ * 選択範囲で制限しない場合は空の配列と "none" を渡します。
+ * @param {Uint8Array} buffer
+ * @param {number} width
+ * @param {number} height
+ * @param {number} start_x
+ * @param {number} start_y
+ * @param {number} fill_color_r
+ * @param {number} fill_color_g
+ * @param {number} fill_color_b
+ * @param {number} fill_color_a
+ * @param {Uint8Array} selection_mask
+ * @param {string} limit_mode
+ * @param {FloodFillOption} options
+ * @returns {boolean}
+ */
+export function scanline_flood_fill_with_options(buffer, width, height, start_x, start_y, fill_color_r, fill_color_g, fill_color_b, fill_color_a, selection_mask, limit_mode, options) {
+    var ptr0 = passArray8ToWasm0(buffer, wasm.__wbindgen_malloc);
+    var len0 = WASM_VECTOR_LEN;
+    const ptr1 = passArray8ToWasm0(selection_mask, wasm.__wbindgen_malloc);
+    const len1 = WASM_VECTOR_LEN;
+    const ptr2 = passStringToWasm0(limit_mode, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len2 = WASM_VECTOR_LEN;
+    _assertClass(options, FloodFillOption);
+    const ret = wasm.scanline_flood_fill_with_options(ptr0, len0, buffer, width, height, start_x, start_y, fill_color_r, fill_color_g, fill_color_b, fill_color_a, ptr1, len1, ptr2, len2, options.__wbg_ptr);
+    return ret !== 0;
+}
+
+function isLikeNone(x) {
+    return x === undefined || x === null;
+}
 /**
  * 選択範囲制限付きスキャンライン FloodFill
+ *
+ * `connectivity` を省略すると4近傍で連結します。
  * @param {Uint8Array} buffer
  * @param {number} width
  * @param {number} height
@@ -321,16 +356,17 @@ function passStringToWasm0(arg, malloc, realloc) {
  * @param {number} threshold
  * @param {Uint8Array} selection_mask
  * @param {string} limit_mode
+ * @param {Connectivity | null} [connectivity]
  * @returns {boolean}
  */
-export function scanline_flood_fill_with_mask(buffer, width, height, start_x, start_y, fill_color_r, fill_color_g, fill_color_b, fill_color_a, threshold, selection_mask, limit_mode) {
+export function scanline_flood_fill_with_mask(buffer, width, height, start_x, start_y, fill_color_r, fill_color_g, fill_color_b, fill_color_a, threshold, selection_mask, limit_mode, connectivity) {
     var ptr0 = passArray8ToWasm0(buffer, wasm.__wbindgen_malloc);
     var len0 = WASM_VECTOR_LEN;
     const ptr1 = passArray8ToWasm0(selection_mask, wasm.__wbindgen_malloc);
     const len1 = WASM_VECTOR_LEN;
     const ptr2 = passStringToWasm0(limit_mode, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
     const len2 = WASM_VECTOR_LEN;
-    const ret = wasm.scanline_flood_fill_with_mask(ptr0, len0, buffer, width, height, start_x, start_y, fill_color_r, fill_color_g, fill_color_b, fill_color_a, threshold, ptr1, len1, ptr2, len2);
+    const ret = wasm.scanline_flood_fill_with_mask(ptr0, len0, buffer, width, height, start_x, start_y, fill_color_r, fill_color_g, fill_color_b, fill_color_a, threshold, ptr1, len1, ptr2, len2, isLikeNone(connectivity) ? 0 : connectivity);
     return ret !== 0;
 }
 
@@ -342,6 +378,8 @@ export function scanline_flood_fill_with_mask(buffer, width, height, start_x, st
  * - スタックオーバーフロー回避
  * - 高速な隣接色判定
  * - 選択範囲制限サポート
+ *
+ * `connectivity` を省略すると4近傍で連結します。
  * @param {Uint8Array} buffer
  * @param {number} width
  * @param {number} height
@@ -352,76 +390,91 @@ export function scanline_flood_fill_with_mask(buffer, width, height, start_x, st
  * @param {number} fill_color_b
  * @param {number} fill_color_a
  * @param {number} threshold
+ * @param {Connectivity | null} [connectivity]
  * @returns {boolean}
  */
-export function scanline_flood_fill(buffer, width, height, start_x, start_y, fill_color_r, fill_color_g, fill_color_b, fill_color_a, threshold) {
+export function scanline_flood_fill(buffer, width, height, start_x, start_y, fill_color_r, fill_color_g, fill_color_b, fill_color_a, threshold, connectivity) {
     var ptr0 = passArray8ToWasm0(buffer, wasm.__wbindgen_malloc);
     var len0 = WASM_VECTOR_LEN;
-    const ret = wasm.scanline_flood_fill(ptr0, len0, buffer, width, height, start_x, start_y, fill_color_r, fill_color_g, fill_color_b, fill_color_a, threshold);
+    const ret = wasm.scanline_flood_fill(ptr0, len0, buffer, width, height, start_x, start_y, fill_color_r, fill_color_g, fill_color_b, fill_color_a, threshold, isLikeNone(connectivity) ? 0 : connectivity);
     return ret !== 0;
 }
 
-function takeFromExternrefTable0(idx) {
-    const value = wasm.__wbindgen_externrefs.get(idx);
-    wasm.__externref_table_dealloc(idx);
-    return value;
-}
 /**
- * @param {Uint8Array} pixels
+ * `magic_wand` with a configurable color metric, connectivity and gap closing.
+ * `options.threshold` is the tolerance.
+ * @param {Uint8Array} buffer
  * @param {number} width
  * @param {number} height
+ * @param {number} start_x
+ * @param {number} start_y
+ * @param {boolean} contiguous
+ * @param {boolean} antialias
+ * @param {FloodFillOption} options
+ * @returns {Uint8Array}
  */
-export function grayscale(pixels, width, height) {
-    var ptr0 = passArray8ToWasm0(pixels, wasm.__wbindgen_malloc);
-    var len0 = WASM_VECTOR_LEN;
-    wasm.grayscale(ptr0, len0, pixels, width, height);
+export function magic_wand_with_options(buffer, width, height, start_x, start_y, contiguous, antialias, options) {
+    const ptr0 = passArray8ToWasm0(buffer, wasm.__wbindgen_malloc);
+    const len0 = WASM_VECTOR_LEN;
+    _assertClass(options, FloodFillOption);
+    const ret = wasm.magic_wand_with_options(ptr0, len0, width, height, start_x, start_y, contiguous, antialias, options.__wbg_ptr);
+    var v2 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
+    wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
+    return v2;
 }
 
 /**
- * @param {Uint8Array} target
- * @param {number} target_width
- * @param {number} target_height
- * @param {Uint8Array} patch
- * @param {number} patch_width
- * @param {number} patch_height
- * @param {number} offset_x
- * @param {number} offset_y
- * @param {PatchBufferRgbaOption} options
+ * Select pixels similar to the color at (start_x, start_y) and return their coverage mask.
+ *
+ * Contiguous mode walks the region connected through `connectivity` (default `Four`) like
+ * `scanline_flood_fill`; otherwise every matching pixel in the buffer is selected. With
+ * `antialias`, pixels just outside the selection get partial coverage by how close their
+ * color is to the start color. Returns an empty array if the start point is out of bounds.
+ * @param {Uint8Array} buffer
+ * @param {number} width
+ * @param {number} height
+ * @param {number} start_x
+ * @param {number} start_y
+ * @param {number} tolerance
+ * @param {boolean} contiguous
+ * @param {boolean} antialias
+ * @param {Connectivity | null} [connectivity]
  * @returns {Uint8Array}
  */
-export function patch_buffer_rgba(target, target_width, target_height, patch, patch_width, patch_height, offset_x, offset_y, options) {
-    const ptr0 = passArray8ToWasm0(target, wasm.__wbindgen_malloc);
+export function magic_wand(buffer, width, height, start_x, start_y, tolerance, contiguous, antialias, connectivity) {
+    const ptr0 = passArray8ToWasm0(buffer, wasm.__wbindgen_malloc);
     const len0 = WASM_VECTOR_LEN;
-    const ptr1 = passArray8ToWasm0(patch, wasm.__wbindgen_malloc);
-    const len1 = WASM_VECTOR_LEN;
-    _assertClass(options, PatchBufferRgbaOption);
-    const ret = wasm.patch_buffer_rgba(ptr0, len0, target_width, target_height, ptr1, len1, patch_width, patch_height, offset_x, offset_y, options.__wbg_ptr);
-    var v3 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
+    const ret = wasm.magic_wand(ptr0, len0, width, height, start_x, start_y, tolerance, contiguous, antialias, isLikeNone(connectivity) ? 0 : connectivity);
+    var v2 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
     wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
-    return v3;
+    return v2;
 }
 
-/**
- * @param {Uint8Array} target
- * @param {number} target_width
- * @param {number} target_height
- * @param {Uint8Array} patch
- * @param {number} patch_width
- * @param {number} patch_height
- * @param {number} offset_x
- * @param {number} offset_y
- * @param {number} scale_x
- * @param {number} scale_y
- * @param {number} rotate_deg
- * @param {PatchBufferRgbaOption} options
+function takeFromExternrefTable0(idx) {
+    const value = wasm.__wbindgen_externrefs.get(idx);
+    wasm.__externref_table_dealloc(idx);
+    return value;
+}
+
+function passArray32ToWasm0(arg, malloc) {
+    const ptr = malloc(arg.length * 4, 4) >>> 0;
+    getUint32ArrayMemory0().set(arg, ptr / 4);
+    WASM_VECTOR_LEN = arg.length;
+    return ptr;
+}
+/**
+ * @param {Uint8Array} png_buffer
+ * @param {number} _width
+ * @param {number} _height
+ * @returns {Uint8Array}
  */
-export function patch_buffer_rgba_instant(target, target_width, target_height, patch, patch_width, patch_height, offset_x, offset_y, scale_x, scale_y, rotate_deg, options) {
-    var ptr0 = passArray8ToWasm0(target, wasm.__wbindgen_malloc);
-    var len0 = WASM_VECTOR_LEN;
-    const ptr1 = passArray8ToWasm0(patch, wasm.__wbindgen_malloc);
-    const len1 = WASM_VECTOR_LEN;
-    _assertClass(options, PatchBufferRgbaOption);
-    wasm.patch_buffer_rgba_instant(ptr0, len0, target, target_width, target_height, ptr1, len1, patch_width, patch_height, offset_x, offset_y, scale_x, scale_y, rotate_deg, options.__wbg_ptr);
+export function png_to_raw(png_buffer, _width, _height) {
+    const ptr0 = passArray8ToWasm0(png_buffer, wasm.__wbindgen_malloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.png_to_raw(ptr0, len0, _width, _height);
+    var v2 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
+    wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
+    return v2;
 }
 
 /**
@@ -440,15 +493,15 @@ export function webp_to_raw(webp_buffer, width, height) {
 }
 
 /**
- * @param {Uint8Array} png_buffer
- * @param {number} _width
- * @param {number} _height
+ * @param {Uint8Array} buffer
+ * @param {number} width
+ * @param {number} height
  * @returns {Uint8Array}
  */
-export function png_to_raw(png_buffer, _width, _height) {
-    const ptr0 = passArray8ToWasm0(png_buffer, wasm.__wbindgen_malloc);
+export function raw_to_webp(buffer, width, height) {
+    const ptr0 = passArray8ToWasm0(buffer, wasm.__wbindgen_malloc);
     const len0 = WASM_VECTOR_LEN;
-    const ret = wasm.png_to_raw(ptr0, len0, _width, _height);
+    const ret = wasm.raw_to_webp(ptr0, len0, width, height);
     var v2 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
     wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
     return v2;
@@ -460,504 +513,4274 @@ export function png_to_raw(png_buffer, _width, _height) {
  * @param {number} height
  * @returns {Uint8Array}
  */
-export function raw_to_webp(buffer, width, height) {
+export function raw_to_png(buffer, width, height) {
     const ptr0 = passArray8ToWasm0(buffer, wasm.__wbindgen_malloc);
     const len0 = WASM_VECTOR_LEN;
-    const ret = wasm.raw_to_webp(ptr0, len0, width, height);
+    const ret = wasm.raw_to_png(ptr0, len0, width, height);
     var v2 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
     wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
     return v2;
 }
 
 /**
- * @param {Uint8Array} buffer
+ * Rasterize a lasso outline into a width * height coverage mask.
+ *
+ * `points` is [x0, y0, x1, y1, ...] in pixel coordinates and is closed back to the
+ * first point; parts outside the bounds are clipped. With `antialias` the edge gets
+ * partial coverage, otherwise a pixel is selected when its center is inside.
+ * The result can be passed as-is to `sliceWithMask`, `cropWithMask` and
+ * `floodFillWithMask`.
  * @param {number} width
  * @param {number} height
+ * @param {Float32Array} points
+ * @param {FillRule} rule
+ * @param {boolean} antialias
  * @returns {Uint8Array}
  */
-export function raw_to_png(buffer, width, height) {
-    const ptr0 = passArray8ToWasm0(buffer, wasm.__wbindgen_malloc);
+export function lasso_mask(width, height, points, rule, antialias) {
+    const ptr0 = passArrayF32ToWasm0(points, wasm.__wbindgen_malloc);
     const len0 = WASM_VECTOR_LEN;
-    const ret = wasm.raw_to_png(ptr0, len0, width, height);
+    const ret = wasm.lasso_mask(width, height, ptr0, len0, rule, antialias);
     var v2 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
     wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
     return v2;
 }
 
 /**
- * @enum {0 | 1}
+ * Fill the buffer with a repeating tile.
+ *
+ * A non-empty `selection_mask` (width * height, 0-255 coverage) limits the fill and fades
+ * it at partial coverage. Returns false if the sizes do not match or the tile is empty.
+ * @param {Uint8Array} buffer
+ * @param {number} width
+ * @param {number} height
+ * @param {Uint8Array} tile
+ * @param {number} tile_width
+ * @param {number} tile_height
+ * @param {Uint8Array} selection_mask
+ * @param {PatternFillOption} options
+ * @returns {boolean}
  */
-export const AlphaBlurMode = Object.freeze({
-    /**
-     * Skip alpha channel (preserve original alpha values)
-     */
-    Skip: 0, "0": "Skip",
-    /**
-     * Apply blur to alpha channel as well
-     */
-    Blur: 1, "1": "Blur",
-});
+export function pattern_fill(buffer, width, height, tile, tile_width, tile_height, selection_mask, options) {
+    var ptr0 = passArray8ToWasm0(buffer, wasm.__wbindgen_malloc);
+    var len0 = WASM_VECTOR_LEN;
+    const ptr1 = passArray8ToWasm0(tile, wasm.__wbindgen_malloc);
+    const len1 = WASM_VECTOR_LEN;
+    const ptr2 = passArray8ToWasm0(selection_mask, wasm.__wbindgen_malloc);
+    const len2 = WASM_VECTOR_LEN;
+    _assertClass(options, PatternFillOption);
+    const ret = wasm.pattern_fill(ptr0, len0, buffer, width, height, ptr1, len1, tile_width, tile_height, ptr2, len2, options.__wbg_ptr);
+    return ret !== 0;
+}
+
 /**
- * @enum {0 | 1 | 2}
+ * `scanline_flood_fill_with_options` that paints the tile instead of a solid color.
+ * @param {Uint8Array} buffer
+ * @param {number} width
+ * @param {number} height
+ * @param {number} start_x
+ * @param {number} start_y
+ * @param {Uint8Array} tile
+ * @param {number} tile_width
+ * @param {number} tile_height
+ * @param {Uint8Array} selection_mask
+ * @param {string} limit_mode
+ * @param {FloodFillOption} fill_options
+ * @param {PatternFillOption} options
+ * @returns {boolean}
  */
-export const AntialiasMode = Object.freeze({
-    Nearest: 0, "0": "Nearest",
-    Bilinear: 1, "1": "Bilinear",
-    Bicubic: 2, "2": "Bicubic",
-});
+export function scanline_pattern_flood_fill(buffer, width, height, start_x, start_y, tile, tile_width, tile_height, selection_mask, limit_mode, fill_options, options) {
+    var ptr0 = passArray8ToWasm0(buffer, wasm.__wbindgen_malloc);
+    var len0 = WASM_VECTOR_LEN;
+    const ptr1 = passArray8ToWasm0(tile, wasm.__wbindgen_malloc);
+    const len1 = WASM_VECTOR_LEN;
+    const ptr2 = passArray8ToWasm0(selection_mask, wasm.__wbindgen_malloc);
+    const len2 = WASM_VECTOR_LEN;
+    const ptr3 = passStringToWasm0(limit_mode, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+    const len3 = WASM_VECTOR_LEN;
+    _assertClass(fill_options, FloodFillOption);
+    _assertClass(options, PatternFillOption);
+    const ret = wasm.scanline_pattern_flood_fill(ptr0, len0, buffer, width, height, start_x, start_y, ptr1, len1, tile_width, tile_height, ptr2, len2, ptr3, len3, fill_options.__wbg_ptr, options.__wbg_ptr);
+    return ret !== 0;
+}
+
 /**
- * @enum {0 | 1 | 2}
+ * `fill_mask_area` where mask values are applied according to `mode`,
+ * so feathered selections fade out instead of being filled at full strength.
+ * @param {Uint8Array} buffer
+ * @param {Uint8Array} mask
+ * @param {number} fill_color_r
+ * @param {number} fill_color_g
+ * @param {number} fill_color_b
+ * @param {number} fill_color_a
+ * @param {CoverageMode} mode
+ * @returns {boolean}
  */
-export const DitheringMode = Object.freeze({
+export function fill_mask_area_with_coverage(buffer, mask, fill_color_r, fill_color_g, fill_color_b, fill_color_a, mode) {
+    var ptr0 = passArray8ToWasm0(buffer, wasm.__wbindgen_malloc);
+    var len0 = WASM_VECTOR_LEN;
+    const ptr1 = passArray8ToWasm0(mask, wasm.__wbindgen_malloc);
+    const len1 = WASM_VECTOR_LEN;
+    const ret = wasm.fill_mask_area_with_coverage(ptr0, len0, buffer, ptr1, len1, fill_color_r, fill_color_g, fill_color_b, fill_color_a, mode);
+    return ret !== 0;
+}
+
+/**
+ * @param {Uint8Array} buffer
+ * @param {Uint8Array} mask
+ * @param {number} fill_color_r
+ * @param {number} fill_color_g
+ * @param {number} fill_color_b
+ * @param {number} fill_color_a
+ * @returns {boolean}
+ */
+export function fill_mask_area(buffer, mask, fill_color_r, fill_color_g, fill_color_b, fill_color_a) {
+    var ptr0 = passArray8ToWasm0(buffer, wasm.__wbindgen_malloc);
+    var len0 = WASM_VECTOR_LEN;
+    const ptr1 = passArray8ToWasm0(mask, wasm.__wbindgen_malloc);
+    const len1 = WASM_VECTOR_LEN;
+    const ret = wasm.fill_mask_area(ptr0, len0, buffer, ptr1, len1, fill_color_r, fill_color_g, fill_color_b, fill_color_a);
+    return ret !== 0;
+}
+
+/**
+ * @param {Uint8Array} target
+ * @param {number} target_width
+ * @param {number} target_height
+ * @param {Uint8Array} patch
+ * @param {number} patch_width
+ * @param {number} patch_height
+ * @param {number} offset_x
+ * @param {number} offset_y
+ * @param {number} scale_x
+ * @param {number} scale_y
+ * @param {number} rotate_deg
+ * @param {PatchBufferRgbaOption} options
+ */
+export function patch_buffer_rgba_instant(target, target_width, target_height, patch, patch_width, patch_height, offset_x, offset_y, scale_x, scale_y, rotate_deg, options) {
+    var ptr0 = passArray8ToWasm0(target, wasm.__wbindgen_malloc);
+    var len0 = WASM_VECTOR_LEN;
+    const ptr1 = passArray8ToWasm0(patch, wasm.__wbindgen_malloc);
+    const len1 = WASM_VECTOR_LEN;
+    _assertClass(options, PatchBufferRgbaOption);
+    wasm.patch_buffer_rgba_instant(ptr0, len0, target, target_width, target_height, ptr1, len1, patch_width, patch_height, offset_x, offset_y, scale_x, scale_y, rotate_deg, options.__wbg_ptr);
+}
+
+/**
+ * @param {Uint8Array} target
+ * @param {number} target_width
+ * @param {number} target_height
+ * @param {Uint8Array} patch
+ * @param {number} patch_width
+ * @param {number} patch_height
+ * @param {number} offset_x
+ * @param {number} offset_y
+ * @param {PatchBufferRgbaOption} options
+ * @returns {Uint8Array}
+ */
+export function patch_buffer_rgba(target, target_width, target_height, patch, patch_width, patch_height, offset_x, offset_y, options) {
+    const ptr0 = passArray8ToWasm0(target, wasm.__wbindgen_malloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ptr1 = passArray8ToWasm0(patch, wasm.__wbindgen_malloc);
+    const len1 = WASM_VECTOR_LEN;
+    _assertClass(options, PatchBufferRgbaOption);
+    const ret = wasm.patch_buffer_rgba(ptr0, len0, target_width, target_height, ptr1, len1, patch_width, patch_height, offset_x, offset_y, options.__wbg_ptr);
+    var v3 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
+    wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
+    return v3;
+}
+
+/**
+ * @param {Uint8Array} pixels
+ * @param {number} width
+ * @param {number} height
+ * @param {GaussianBlurOption} options
+ */
+export function gaussian_blur(pixels, width, height, options) {
+    var ptr0 = passArray8ToWasm0(pixels, wasm.__wbindgen_malloc);
+    var len0 = WASM_VECTOR_LEN;
+    _assertClass(options, GaussianBlurOption);
+    wasm.gaussian_blur(ptr0, len0, pixels, width, height, options.__wbg_ptr);
+}
+
+/**
+ * Apply brightness and contrast adjustments to the image
+ * @param {Uint8Array} pixels
+ * @param {number} width
+ * @param {number} height
+ * @param {BrightnessContrastOption} options
+ */
+export function brightness_contrast(pixels, width, height, options) {
+    var ptr0 = passArray8ToWasm0(pixels, wasm.__wbindgen_malloc);
+    var len0 = WASM_VECTOR_LEN;
+    _assertClass(options, BrightnessContrastOption);
+    wasm.brightness_contrast(ptr0, len0, pixels, width, height, options.__wbg_ptr);
+}
+
+/**
+ * Apply only brightness adjustment to the image
+ * @param {Uint8Array} pixels
+ * @param {number} width
+ * @param {number} height
+ * @param {number} brightness
+ */
+export function brightness(pixels, width, height, brightness) {
+    var ptr0 = passArray8ToWasm0(pixels, wasm.__wbindgen_malloc);
+    var len0 = WASM_VECTOR_LEN;
+    wasm.brightness(ptr0, len0, pixels, width, height, brightness);
+}
+
+/**
+ * Apply only contrast adjustment to the image
+ * @param {Uint8Array} pixels
+ * @param {number} width
+ * @param {number} height
+ * @param {number} contrast
+ */
+export function contrast(pixels, width, height, contrast) {
+    var ptr0 = passArray8ToWasm0(pixels, wasm.__wbindgen_malloc);
+    var len0 = WASM_VECTOR_LEN;
+    wasm.contrast(ptr0, len0, pixels, width, height, contrast);
+}
+
+/**
+ * @param {Uint8Array} pixels
+ * @param {number} width
+ * @param {number} height
+ */
+export function invert(pixels, width, height) {
+    var ptr0 = passArray8ToWasm0(pixels, wasm.__wbindgen_malloc);
+    var len0 = WASM_VECTOR_LEN;
+    wasm.invert(ptr0, len0, pixels, width, height);
+}
+
+/**
+ * @param {Uint8Array} pixels
+ * @param {number} width
+ * @param {number} height
+ */
+export function grayscale(pixels, width, height) {
+    var ptr0 = passArray8ToWasm0(pixels, wasm.__wbindgen_malloc);
+    var len0 = WASM_VECTOR_LEN;
+    wasm.grayscale(ptr0, len0, pixels, width, height);
+}
+
+/**
+ * Soften the selection edge with a Gaussian falloff. `radius` spans two standard deviations.
+ * @param {Uint8Array} mask
+ * @param {number} width
+ * @param {number} height
+ * @param {number} radius
+ */
+export function feather_mask(mask, width, height, radius) {
+    var ptr0 = passArray8ToWasm0(mask, wasm.__wbindgen_malloc);
+    var len0 = WASM_VECTOR_LEN;
+    wasm.feather_mask(ptr0, len0, mask, width, height, radius);
+}
+
+/**
+ * Replace the selection with a ring of `size` pixels centered on its edge.
+ * @param {Uint8Array} mask
+ * @param {number} width
+ * @param {number} height
+ * @param {number} size
+ * @param {MaskShape} shape
+ */
+export function border_mask(mask, width, height, size, shape) {
+    var ptr0 = passArray8ToWasm0(mask, wasm.__wbindgen_malloc);
+    var len0 = WASM_VECTOR_LEN;
+    wasm.border_mask(ptr0, len0, mask, width, height, size, shape);
+}
+
+/**
+ * Expand the selection by `radius` pixels.
+ * @param {Uint8Array} mask
+ * @param {number} width
+ * @param {number} height
+ * @param {number} radius
+ * @param {MaskShape} shape
+ */
+export function grow_mask(mask, width, height, radius, shape) {
+    var ptr0 = passArray8ToWasm0(mask, wasm.__wbindgen_malloc);
+    var len0 = WASM_VECTOR_LEN;
+    wasm.grow_mask(ptr0, len0, mask, width, height, radius, shape);
+}
+
+/**
+ * Contract the selection by `radius` pixels. The canvas edge does not erode the selection.
+ * @param {Uint8Array} mask
+ * @param {number} width
+ * @param {number} height
+ * @param {number} radius
+ * @param {MaskShape} shape
+ */
+export function shrink_mask(mask, width, height, radius, shape) {
+    var ptr0 = passArray8ToWasm0(mask, wasm.__wbindgen_malloc);
+    var len0 = WASM_VECTOR_LEN;
+    wasm.shrink_mask(ptr0, len0, mask, width, height, radius, shape);
+}
+
+/**
+ * Round off jagged edges: each pixel is selected when most of its (2r+1)^2 neighborhood is.
+ * @param {Uint8Array} mask
+ * @param {number} width
+ * @param {number} height
+ * @param {number} radius
+ */
+export function smooth_mask(mask, width, height, radius) {
+    var ptr0 = passArray8ToWasm0(mask, wasm.__wbindgen_malloc);
+    var len0 = WASM_VECTOR_LEN;
+    wasm.smooth_mask(ptr0, len0, mask, width, height, radius);
+}
+
+/**
+ * Compare two buffers of the same size and pick the smallest patch that restores `before`.
+ * `packBufferDiff` turns the result into `PackedDiffs` for `Anvil.applyPatch`.
+ * @param {RgbaBuffer} before
+ * @param {RgbaBuffer} after
+ * @param {number} tile_size
+ * @returns {BufferDiff}
+ */
+export function diff(before, after, tile_size) {
+    _assertClass(before, RgbaBuffer);
+    _assertClass(after, RgbaBuffer);
+    const ret = wasm.diff(before.__wbg_ptr, after.__wbg_ptr, tile_size);
+    if (ret[2]) {
+        throw takeFromExternrefTable0(ret[1]);
+    }
+    return BufferDiff.__wrap(ret[0]);
+}
+
+let cachedInt32ArrayMemory0 = null;
+
+function getInt32ArrayMemory0() {
+    if (cachedInt32ArrayMemory0 === null || cachedInt32ArrayMemory0.byteLength === 0) {
+        cachedInt32ArrayMemory0 = new Int32Array(wasm.memory.buffer);
+    }
+    return cachedInt32ArrayMemory0;
+}
+
+function getArrayI32FromWasm0(ptr, len) {
+    ptr = ptr >>> 0;
+    return getInt32ArrayMemory0().subarray(ptr / 4, ptr / 4 + len);
+}
+/**
+ * Header that starts a history log. Append `PatchRecord.encode()` output after it.
+ * @returns {Uint8Array}
+ */
+export function history_log_header() {
+    const ret = wasm.history_log_header();
+    var v1 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
+    wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
+    return v1;
+}
+
+/**
+ * @enum {0 | 1}
+ */
+export const AlphaBlurMode = Object.freeze({
+    /**
+     * Skip alpha channel (preserve original alpha values)
+     */
+    Skip: 0, "0": "Skip",
+    /**
+     * Apply blur to alpha channel as well
+     */
+    Blur: 1, "1": "Blur",
+});
+/**
+ * @enum {0 | 1 | 2}
+ */
+export const AntialiasMode = Object.freeze({
+    Nearest: 0, "0": "Nearest",
+    Bilinear: 1, "1": "Bilinear",
+    Bicubic: 2, "2": "Bicubic",
+});
+/**
+ * @enum {0 | 1 | 2 | 3 | 4 | 5 | 6 | 7 | 8 | 9 | 10 | 11 | 12 | 13 | 14 | 15 | 16 | 17}
+ */
+export const BlendMode = Object.freeze({
+    /**
+     * Plain source-over
+     */
+    Normal: 0, "0": "Normal",
+    Multiply: 1, "1": "Multiply",
+    Screen: 2, "2": "Screen",
+    Overlay: 3, "3": "Overlay",
+    Darken: 4, "4": "Darken",
+    Lighten: 5, "5": "Lighten",
+    ColorDodge: 6, "6": "ColorDodge",
+    ColorBurn: 7, "7": "ColorBurn",
+    HardLight: 8, "8": "HardLight",
+    SoftLight: 9, "9": "SoftLight",
+    Difference: 10, "10": "Difference",
+    Exclusion: 11, "11": "Exclusion",
+    /**
+     * Hue of the source with saturation and luminosity of the backdrop
+     */
+    Hue: 12, "12": "Hue",
+    /**
+     * Saturation of the source with hue and luminosity of the backdrop
+     */
+    Saturation: 13, "13": "Saturation",
+    /**
+     * Hue and saturation of the source with luminosity of the backdrop
+     */
+    Color: 14, "14": "Color",
+    /**
+     * Luminosity of the source with hue and saturation of the backdrop
+     */
+    Luminosity: 15, "15": "Luminosity",
+    /**
+     * Linear dodge (backdrop + source, clamped)
+     */
+    Add: 16, "16": "Add",
+    /**
+     * Backdrop - source, clamped
+     */
+    Subtract: 17, "17": "Subtract",
+});
+/**
+ * How the distance between two colors is measured when matching against a threshold.
+ * Every metric is scaled so that 255 roughly spans black to white.
+ * @enum {0 | 1 | 2 | 3 | 4}
+ */
+export const ColorMetric = Object.freeze({
+    /**
+     * Largest per-channel difference (the legacy behavior)
+     */
+    ChannelMax: 0, "0": "ChannelMax",
+    /**
+     * Euclidean distance over straight RGBA
+     */
+    Euclidean: 1, "1": "Euclidean",
+    /**
+     * Euclidean distance over premultiplied RGBA, so all fully transparent pixels match
+     */
+    Premultiplied: 2, "2": "Premultiplied",
+    /**
+     * Perceptual distance in OKLab
+     */
+    OkLab: 3, "3": "OkLab",
+    /**
+     * Perceptual CIEDE2000 distance in CIELAB (D65)
+     */
+    Ciede2000: 4, "4": "Ciede2000",
+});
+/**
+ * Which neighbors count as connected when growing a region.
+ * @enum {4 | 8}
+ */
+export const Connectivity = Object.freeze({
+    /**
+     * Edge neighbors only (left, right, up, down)
+     */
+    Four: 4, "4": "Four",
+    /**
+     * Edge and corner neighbors, so regions cross one-pixel diagonal lines
+     */
+    Eight: 8, "8": "Eight",
+});
+/**
+ * How mask values are applied when filling.
+ * @enum {0 | 1 | 2}
+ */
+export const CoverageMode = Object.freeze({
+    /**
+     * Any non-zero value is fully selected (the legacy behavior)
+     */
+    Binary: 0, "0": "Binary",
+    /**
+     * Values are 0-255 coverage; the color is interpolated in straight RGBA
+     */
+    Straight: 1, "1": "Straight",
+    /**
+     * Values are 0-255 coverage; the color is interpolated in premultiplied RGBA,
+     * so transparent pixels do not darken the edge
+     */
+    Premultiplied: 2, "2": "Premultiplied",
+});
+/**
+ * @enum {0 | 1 | 2 | 3 | 4}
+ */
+export const DiffKind = Object.freeze({
+    /**
+     * Buffers are identical
+     */
+    Unchanged: 0, "0": "Unchanged",
+    /**
+     * Position + previous packed RGBA32 per changed pixel
+     */
+    Pixel: 1, "1": "Pixel",
+    /**
+     * Tile patch (see `RgbaBuffer.swapTiles`)
+     */
+    Tile: 2, "2": "Tile",
+    /**
+     * Bounding box + previous contents as WebP
+     */
+    Partial: 3, "3": "Partial",
+    /**
+     * Whole previous buffer as WebP
+     */
+    Whole: 4, "4": "Whole",
+});
+/**
+ * @enum {0 | 1 | 2}
+ */
+export const DitheringMode = Object.freeze({
+    /**
+     * Random dithering (white noise)
+     */
+    Random: 0, "0": "Random",
+    /**
+     * Floyd-Steinberg error diffusion
+     */
+    ErrorDiffusion: 1, "1": "ErrorDiffusion",
+    /**
+     * Ordered dithering using Bayer matrix
+     */
+    Ordered: 2, "2": "Ordered",
+});
+/**
+ * Which pixels a fill may expand over.
+ * @enum {0 | 1 | 2}
+ */
+export const ExpandLimit = Object.freeze({
+    /**
+     * Expand over every pixel in reach
+     */
+    None: 0, "0": "None",
+    /**
+     * Only under pixels darker than the threshold (composited over white, so
+     * transparent pixels count as light)
+     */
+    Darker: 1, "1": "Darker",
+    /**
+     * Only under pixels whose alpha is above the threshold
+     */
+    Opaque: 2, "2": "Opaque",
+});
+/**
+ * Which areas of a self-intersecting or nested outline count as inside.
+ * @enum {0 | 1}
+ */
+export const FillRule = Object.freeze({
+    /**
+     * Inside wherever the outline winds around the point at least once
+     */
+    NonZero: 0, "0": "NonZero",
+    /**
+     * Inside wherever the outline crosses an odd number of times
+     */
+    EvenOdd: 1, "1": "EvenOdd",
+});
+/**
+ * @enum {0 | 1 | 2}
+ */
+export const GradientDither = Object.freeze({
+    /**
+     * Round to the nearest value
+     */
+    None: 0, "0": "None",
+    /**
+     * 4x4 Bayer matrix
+     */
+    Ordered: 1, "1": "Ordered",
+    /**
+     * 32x32 blue noise threshold map
+     */
+    BlueNoise: 2, "2": "BlueNoise",
+});
+/**
+ * Color space the stops are interpolated in. Alpha is always interpolated premultiplied.
+ * @enum {0 | 1 | 2}
+ */
+export const GradientInterpolation = Object.freeze({
+    Srgb: 0, "0": "Srgb",
+    LinearRgb: 1, "1": "LinearRgb",
+    OkLab: 2, "2": "OkLab",
+});
+/**
+ * What happens past the last stop.
+ * @enum {0 | 1 | 2}
+ */
+export const GradientRepeat = Object.freeze({
+    /**
+     * The end colors extend outward
+     */
+    None: 0, "0": "None",
+    /**
+     * The gradient starts over
+     */
+    Repeat: 1, "1": "Repeat",
+    /**
+     * The gradient runs back and forth
+     */
+    Reflect: 2, "2": "Reflect",
+});
+/**
+ * @enum {0 | 1 | 2 | 3 | 4}
+ */
+export const GradientShape = Object.freeze({
+    /**
+     * Along the line from the start point to the end point
+     */
+    Linear: 0, "0": "Linear",
+    /**
+     * Circles around the start point, reaching the last stop at the end point
+     */
+    Radial: 1, "1": "Radial",
+    /**
+     * Sweeps clockwise around the start point, beginning in the direction of the end point
+     */
+    Angular: 2, "2": "Angular",
+    /**
+     * Squares around the start point, with a corner at the end point
+     */
+    Diamond: 3, "3": "Diamond",
+    /**
+     * Linear, mirrored around the start point
+     */
+    Reflected: 4, "4": "Reflected",
+});
+/**
+ * Shape of open stroke ends.
+ * @enum {0 | 1 | 2}
+ */
+export const LineCap = Object.freeze({
+    /**
+     * Ends flush with the endpoint
+     */
+    Butt: 0, "0": "Butt",
+    Round: 1, "1": "Round",
+    /**
+     * Extends half the stroke width past the endpoint
+     */
+    Square: 2, "2": "Square",
+});
+/**
+ * Shape of stroke corners.
+ * @enum {0 | 1 | 2}
+ */
+export const LineJoin = Object.freeze({
+    /**
+     * Sharp corner, beveled past `miter_limit`
+     */
+    Miter: 0, "0": "Miter",
+    Round: 1, "1": "Round",
+    Bevel: 2, "2": "Bevel",
+});
+/**
+ * @enum {0 | 1}
+ */
+export const MaskShape = Object.freeze({
+    /**
+     * (2r+1) x (2r+1) square, works directly on soft coverage
+     */
+    Square: 0, "0": "Square",
+    /**
+     * Euclidean disc of radius r, measured from pixels with coverage >= 128
+     */
+    Round: 1, "1": "Round",
+});
+/**
+ * How a new selection is combined with an existing one.
+ * @enum {0 | 1 | 2 | 3}
+ */
+export const SelectionOp = Object.freeze({
+    /**
+     * Discard the existing selection
+     */
+    Replace: 0, "0": "Replace",
+    /**
+     * Union
+     */
+    Add: 1, "1": "Add",
+    /**
+     * Remove the new selection from the existing one
+     */
+    Subtract: 2, "2": "Subtract",
+    /**
+     * Keep only the overlap
+     */
+    Intersect: 3, "3": "Intersect",
+});
+
+const BrightnessContrastOptionFinalization = (typeof FinalizationRegistry === 'undefined')
+    ? { register: () => {}, unregister: () => {} }
+    : new FinalizationRegistry(ptr => wasm.__wbg_brightnesscontrastoption_free(ptr >>> 0, 1));
+
+export class BrightnessContrastOption {
+
+    __destroy_into_raw() {
+        const ptr = this.__wbg_ptr;
+        this.__wbg_ptr = 0;
+        BrightnessContrastOptionFinalization.unregister(this);
+        return ptr;
+    }
+
+    free() {
+        const ptr = this.__destroy_into_raw();
+        wasm.__wbg_brightnesscontrastoption_free(ptr, 0);
+    }
+    /**
+     * Brightness adjustment (-100.0 to 100.0, 0.0 = no change)
+     * @returns {number}
+     */
+    get brightness() {
+        const ret = wasm.__wbg_get_brightnesscontrastoption_brightness(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * Brightness adjustment (-100.0 to 100.0, 0.0 = no change)
+     * @param {number} arg0
+     */
+    set brightness(arg0) {
+        wasm.__wbg_set_brightnesscontrastoption_brightness(this.__wbg_ptr, arg0);
+    }
+    /**
+     * Contrast adjustment (-100.0 to 100.0, 0.0 = no change)
+     * @returns {number}
+     */
+    get contrast() {
+        const ret = wasm.__wbg_get_brightnesscontrastoption_contrast(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * Contrast adjustment (-100.0 to 100.0, 0.0 = no change)
+     * @param {number} arg0
+     */
+    set contrast(arg0) {
+        wasm.__wbg_set_brightnesscontrastoption_contrast(this.__wbg_ptr, arg0);
+    }
+    /**
+     * @param {number} brightness
+     * @param {number} contrast
+     */
+    constructor(brightness, contrast) {
+        const ret = wasm.brightnesscontrastoption_new(brightness, contrast);
+        this.__wbg_ptr = ret >>> 0;
+        BrightnessContrastOptionFinalization.register(this, this.__wbg_ptr, this);
+        return this;
+    }
+}
+if (Symbol.dispose) BrightnessContrastOption.prototype[Symbol.dispose] = BrightnessContrastOption.prototype.free;
+
+const BrushStampFinalization = (typeof FinalizationRegistry === 'undefined')
+    ? { register: () => {}, unregister: () => {} }
+    : new FinalizationRegistry(ptr => wasm.__wbg_brushstamp_free(ptr >>> 0, 1));
+/**
+ * Brush tip: a coverage mask placed relative to the dab center, like `ShapeMask` on
+ * the TS side (top-left corner at center + offset).
+ */
+export class BrushStamp {
+
+    static __wrap(ptr) {
+        ptr = ptr >>> 0;
+        const obj = Object.create(BrushStamp.prototype);
+        obj.__wbg_ptr = ptr;
+        BrushStampFinalization.register(obj, obj.__wbg_ptr, obj);
+        return obj;
+    }
+
+    __destroy_into_raw() {
+        const ptr = this.__wbg_ptr;
+        this.__wbg_ptr = 0;
+        BrushStampFinalization.unregister(this);
+        return ptr;
+    }
+
+    free() {
+        const ptr = this.__destroy_into_raw();
+        wasm.__wbg_brushstamp_free(ptr, 0);
+    }
+    /**
+     * Create a stamp from a `ShapeMask`-style flag mask where any non-zero value is fully covered.
+     * @param {Uint8Array} mask
+     * @param {number} width
+     * @param {number} height
+     * @param {number} offset_x
+     * @param {number} offset_y
+     * @returns {BrushStamp}
+     */
+    static fromFlags(mask, width, height, offset_x, offset_y) {
+        const ptr0 = passArray8ToWasm0(mask, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.brushstamp_fromFlags(ptr0, len0, width, height, offset_x, offset_y);
+        if (ret[2]) {
+            throw takeFromExternrefTable0(ret[1]);
+        }
+        return BrushStamp.__wrap(ret[0]);
+    }
+    /**
+     * Create a stamp from coverage bytes (0-255).
+     * @param {Uint8Array} mask
+     * @param {number} width
+     * @param {number} height
+     * @param {number} offset_x
+     * @param {number} offset_y
+     */
+    constructor(mask, width, height, offset_x, offset_y) {
+        const ptr0 = passArray8ToWasm0(mask, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.brushstamp_new(ptr0, len0, width, height, offset_x, offset_y);
+        if (ret[2]) {
+            throw takeFromExternrefTable0(ret[1]);
+        }
+        this.__wbg_ptr = ret[0] >>> 0;
+        BrushStampFinalization.register(this, this.__wbg_ptr, this);
+        return this;
+    }
+    /**
+     * @returns {number}
+     */
+    width() {
+        const ret = wasm.brushstamp_width(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    height() {
+        const ret = wasm.brushstamp_height(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+}
+if (Symbol.dispose) BrushStamp.prototype[Symbol.dispose] = BrushStamp.prototype.free;
+
+const BufferDiffFinalization = (typeof FinalizationRegistry === 'undefined')
+    ? { register: () => {}, unregister: () => {} }
+    : new FinalizationRegistry(ptr => wasm.__wbg_bufferdiff_free(ptr >>> 0, 1));
+/**
+ * Result of `diff`: the cheapest undo representation of a change.
+ */
+export class BufferDiff {
+
+    static __wrap(ptr) {
+        ptr = ptr >>> 0;
+        const obj = Object.create(BufferDiff.prototype);
+        obj.__wbg_ptr = ptr;
+        BufferDiffFinalization.register(obj, obj.__wbg_ptr, obj);
+        return obj;
+    }
+
+    __destroy_into_raw() {
+        const ptr = this.__wbg_ptr;
+        this.__wbg_ptr = 0;
+        BufferDiffFinalization.unregister(this);
+        return ptr;
+    }
+
+    free() {
+        const ptr = this.__destroy_into_raw();
+        wasm.__wbg_bufferdiff_free(ptr, 0);
+    }
+    /**
+     * Encoded previous contents: WebP for `Partial`/`Whole`, a tile patch for `Tile`.
+     * @returns {Uint8Array}
+     */
+    swapBuffer() {
+        const ret = wasm.bufferdiff_swapBuffer(this.__wbg_ptr);
+        var v1 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
+        return v1;
+    }
+    /**
+     * Row-major linear indices of the changed tiles.
+     * @returns {Uint32Array}
+     */
+    changedTiles() {
+        const ret = wasm.bufferdiff_changedTiles(this.__wbg_ptr);
+        var v1 = getArrayU32FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+        return v1;
+    }
+    /**
+     * @returns {number}
+     */
+    changedPixels() {
+        const ret = wasm.bufferdiff_changedPixels(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {DiffKind}
+     */
+    kind() {
+        const ret = wasm.bufferdiff_kind(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * Flattened [x, y, color] triples for `Pixel` diffs, with the previous color
+     * packed as (a << 24) | (r << 16) | (g << 8) | b like `rgbaToPackedU32`.
+     * @returns {Uint32Array}
+     */
+    pixels() {
+        const ret = wasm.bufferdiff_pixels(this.__wbg_ptr);
+        var v1 = getArrayU32FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+        return v1;
+    }
+    /**
+     * Bounding box of the changed pixels as [x, y, width, height].
+     * @returns {Uint32Array}
+     */
+    boundBox() {
+        const ret = wasm.bufferdiff_boundBox(this.__wbg_ptr);
+        var v1 = getArrayU32FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+        return v1;
+    }
+}
+if (Symbol.dispose) BufferDiff.prototype[Symbol.dispose] = BufferDiff.prototype.free;
+
+const DitheringOptionFinalization = (typeof FinalizationRegistry === 'undefined')
+    ? { register: () => {}, unregister: () => {} }
+    : new FinalizationRegistry(ptr => wasm.__wbg_ditheringoption_free(ptr >>> 0, 1));
+
+export class DitheringOption {
+
+    __destroy_into_raw() {
+        const ptr = this.__wbg_ptr;
+        this.__wbg_ptr = 0;
+        DitheringOptionFinalization.unregister(this);
+        return ptr;
+    }
+
+    free() {
+        const ptr = this.__destroy_into_raw();
+        wasm.__wbg_ditheringoption_free(ptr, 0);
+    }
+    /**
+     * @param {DitheringMode} mode
+     * @param {number} levels
+     * @param {number} strength
+     */
+    constructor(mode, levels, strength) {
+        const ret = wasm.ditheringoption_new(mode, levels, strength);
+        this.__wbg_ptr = ret >>> 0;
+        DitheringOptionFinalization.register(this, this.__wbg_ptr, this);
+        return this;
+    }
+    /**
+     * Dithering mode to use
+     * @returns {DitheringMode}
+     */
+    get mode() {
+        const ret = wasm.__wbg_get_ditheringoption_mode(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * Dithering mode to use
+     * @param {DitheringMode} arg0
+     */
+    set mode(arg0) {
+        wasm.__wbg_set_ditheringoption_mode(this.__wbg_ptr, arg0);
+    }
+    /**
+     * Number of levels per channel (2-32, affects quantization)
+     * @returns {number}
+     */
+    get levels() {
+        const ret = wasm.__wbg_get_ditheringoption_levels(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * Number of levels per channel (2-32, affects quantization)
+     * @param {number} arg0
+     */
+    set levels(arg0) {
+        wasm.__wbg_set_ditheringoption_levels(this.__wbg_ptr, arg0);
+    }
+    /**
+     * Strength of dithering effect (0.0-1.0)
+     * @returns {number}
+     */
+    get strength() {
+        const ret = wasm.__wbg_get_ditheringoption_strength(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * Strength of dithering effect (0.0-1.0)
+     * @param {number} arg0
+     */
+    set strength(arg0) {
+        wasm.__wbg_set_ditheringoption_strength(this.__wbg_ptr, arg0);
+    }
+}
+if (Symbol.dispose) DitheringOption.prototype[Symbol.dispose] = DitheringOption.prototype.free;
+
+const DustRemovalOptionFinalization = (typeof FinalizationRegistry === 'undefined')
+    ? { register: () => {}, unregister: () => {} }
+    : new FinalizationRegistry(ptr => wasm.__wbg_dustremovaloption_free(ptr >>> 0, 1));
+
+export class DustRemovalOption {
+
+    __destroy_into_raw() {
+        const ptr = this.__wbg_ptr;
+        this.__wbg_ptr = 0;
+        DustRemovalOptionFinalization.unregister(this);
+        return ptr;
+    }
+
+    free() {
+        const ptr = this.__destroy_into_raw();
+        wasm.__wbg_dustremovaloption_free(ptr, 0);
+    }
+    /**
+     * `connectivity` starts as `Eight` and is set as a property.
+     * @param {number} max_size
+     * @param {number} alpha_threshold
+     */
+    constructor(max_size, alpha_threshold) {
+        const ret = wasm.dustremovaloption_new(max_size, alpha_threshold);
+        this.__wbg_ptr = ret >>> 0;
+        DustRemovalOptionFinalization.register(this, this.__wbg_ptr, this);
+        return this;
+    }
+    /**
+     * Maximum size of pixel groups to remove (1-100, groups with this many pixels or fewer will be removed)
+     * @returns {number}
+     */
+    get max_size() {
+        const ret = wasm.__wbg_get_ditheringoption_levels(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * Maximum size of pixel groups to remove (1-100, groups with this many pixels or fewer will be removed)
+     * @param {number} arg0
+     */
+    set max_size(arg0) {
+        wasm.__wbg_set_ditheringoption_levels(this.__wbg_ptr, arg0);
+    }
+    /**
+     * Minimum alpha threshold to consider a pixel as non-transparent (0-255)
+     * @returns {number}
+     */
+    get alpha_threshold() {
+        const ret = wasm.__wbg_get_dustremovaloption_alpha_threshold(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * Minimum alpha threshold to consider a pixel as non-transparent (0-255)
+     * @param {number} arg0
+     */
+    set alpha_threshold(arg0) {
+        wasm.__wbg_set_dustremovaloption_alpha_threshold(this.__wbg_ptr, arg0);
+    }
+    /**
+     * Which neighbors belong to the same pixel group
+     * @returns {Connectivity}
+     */
+    get connectivity() {
+        const ret = wasm.__wbg_get_dustremovaloption_connectivity(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * Which neighbors belong to the same pixel group
+     * @param {Connectivity} arg0
+     */
+    set connectivity(arg0) {
+        wasm.__wbg_set_dustremovaloption_connectivity(this.__wbg_ptr, arg0);
+    }
+}
+if (Symbol.dispose) DustRemovalOption.prototype[Symbol.dispose] = DustRemovalOption.prototype.free;
+
+const FillResultFinalization = (typeof FinalizationRegistry === 'undefined')
+    ? { register: () => {}, unregister: () => {} }
+    : new FinalizationRegistry(ptr => wasm.__wbg_fillresult_free(ptr >>> 0, 1));
+/**
+ * What a fill changed, so the caller can record a partial or tile diff instead of
+ * snapshotting the whole buffer.
+ */
+export class FillResult {
+
+    static __wrap(ptr) {
+        ptr = ptr >>> 0;
+        const obj = Object.create(FillResult.prototype);
+        obj.__wbg_ptr = ptr;
+        FillResultFinalization.register(obj, obj.__wbg_ptr, obj);
+        return obj;
+    }
+
+    __destroy_into_raw() {
+        const ptr = this.__wbg_ptr;
+        this.__wbg_ptr = 0;
+        FillResultFinalization.unregister(this);
+        return ptr;
+    }
+
+    free() {
+        const ptr = this.__destroy_into_raw();
+        wasm.__wbg_fillresult_free(ptr, 0);
+    }
+    /**
+     * @returns {number}
+     */
+    changedPixels() {
+        const ret = wasm.fillresult_changedPixels(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * Coverage the fill was applied with, cropped to `boundBox` (row-major).
+     * Pixels that kept their color are 0. Empty unless the buffer was asked to record it
+     * with `setFillCoverage(true)`.
+     * @returns {Uint8Array}
+     */
+    coverage() {
+        const ret = wasm.fillresult_coverage(this.__wbg_ptr);
+        var v1 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
+        return v1;
+    }
+    /**
+     * Bounding box of the changed pixels as [x, y, width, height] (all zero if nothing changed).
+     * @returns {Uint32Array}
+     */
+    boundBox() {
+        const ret = wasm.fillresult_boundBox(this.__wbg_ptr);
+        var v1 = getArrayU32FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+        return v1;
+    }
+}
+if (Symbol.dispose) FillResult.prototype[Symbol.dispose] = FillResult.prototype.free;
+
+const FloodFillOptionFinalization = (typeof FinalizationRegistry === 'undefined')
+    ? { register: () => {}, unregister: () => {} }
+    : new FinalizationRegistry(ptr => wasm.__wbg_floodfilloption_free(ptr >>> 0, 1));
+
+export class FloodFillOption {
+
+    __destroy_into_raw() {
+        const ptr = this.__wbg_ptr;
+        this.__wbg_ptr = 0;
+        FloodFillOptionFinalization.unregister(this);
+        return ptr;
+    }
+
+    free() {
+        const ptr = this.__destroy_into_raw();
+        wasm.__wbg_floodfilloption_free(ptr, 0);
+    }
+    /**
+     * `connectivity` starts as `Four` and `gap_size`, `expand`, `expand_limit`,
+     * `expand_threshold`, `antialias` and `mask_mode` start disabled (`mask_mode` is
+     * `Binary`); all are set as properties.
+     * @param {number} threshold
+     * @param {ColorMetric} metric
+     * @param {boolean} include_alpha
+     */
+    constructor(threshold, metric, include_alpha) {
+        const ret = wasm.floodfilloption_new(threshold, metric, include_alpha);
+        this.__wbg_ptr = ret >>> 0;
+        FloodFillOptionFinalization.register(this, this.__wbg_ptr, this);
+        return this;
+    }
+    /**
+     * Largest color distance (0-255) still treated as the target color
+     * @returns {number}
+     */
+    get threshold() {
+        const ret = wasm.__wbg_get_floodfilloption_threshold(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * Largest color distance (0-255) still treated as the target color
+     * @param {number} arg0
+     */
+    set threshold(arg0) {
+        wasm.__wbg_set_floodfilloption_threshold(this.__wbg_ptr, arg0);
+    }
+    /**
+     * How color distance is measured
+     * @returns {ColorMetric}
+     */
+    get metric() {
+        const ret = wasm.__wbg_get_floodfilloption_metric(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * How color distance is measured
+     * @param {ColorMetric} arg0
+     */
+    set metric(arg0) {
+        wasm.__wbg_set_floodfilloption_metric(this.__wbg_ptr, arg0);
+    }
+    /**
+     * Whether alpha takes part in the comparison
+     * @returns {boolean}
+     */
+    get include_alpha() {
+        const ret = wasm.__wbg_get_floodfilloption_include_alpha(this.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * Whether alpha takes part in the comparison
+     * @param {boolean} arg0
+     */
+    set include_alpha(arg0) {
+        wasm.__wbg_set_floodfilloption_include_alpha(this.__wbg_ptr, arg0);
+    }
+    /**
+     * Neighborhood used to grow the region
+     * @returns {Connectivity}
+     */
+    get connectivity() {
+        const ret = wasm.__wbg_get_floodfilloption_connectivity(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * Neighborhood used to grow the region
+     * @param {Connectivity} arg0
+     */
+    set connectivity(arg0) {
+        wasm.__wbg_set_floodfilloption_connectivity(this.__wbg_ptr, arg0);
+    }
+    /**
+     * Openings in the boundary up to this many pixels wide are treated as closed (0 = off)
+     * @returns {number}
+     */
+    get gap_size() {
+        const ret = wasm.__wbg_get_floodfilloption_gap_size(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * Openings in the boundary up to this many pixels wide are treated as closed (0 = off)
+     * @param {number} arg0
+     */
+    set gap_size(arg0) {
+        wasm.__wbg_set_floodfilloption_gap_size(this.__wbg_ptr, arg0);
+    }
+    /**
+     * Grow the filled region by this many pixels after it is found (0 = off)
+     * @returns {number}
+     */
+    get expand() {
+        const ret = wasm.__wbg_get_floodfilloption_expand(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * Grow the filled region by this many pixels after it is found (0 = off)
+     * @param {number} arg0
+     */
+    set expand(arg0) {
+        wasm.__wbg_set_floodfilloption_expand(this.__wbg_ptr, arg0);
+    }
+    /**
+     * Which pixels the expansion may cover
+     * @returns {ExpandLimit}
+     */
+    get expand_limit() {
+        const ret = wasm.__wbg_get_floodfilloption_expand_limit(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * Which pixels the expansion may cover
+     * @param {ExpandLimit} arg0
+     */
+    set expand_limit(arg0) {
+        wasm.__wbg_set_floodfilloption_expand_limit(this.__wbg_ptr, arg0);
+    }
+    /**
+     * Lightness or alpha threshold for `expand_limit`
+     * @returns {number}
+     */
+    get expand_threshold() {
+        const ret = wasm.__wbg_get_floodfilloption_expand_threshold(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * Lightness or alpha threshold for `expand_limit`
+     * @param {number} arg0
+     */
+    set expand_threshold(arg0) {
+        wasm.__wbg_set_floodfilloption_expand_threshold(this.__wbg_ptr, arg0);
+    }
+    /**
+     * Give pixels just outside the fill partial coverage by how close their color is to
+     * the target, so antialiased boundaries blend in instead of leaving a hard edge
+     * @returns {boolean}
+     */
+    get antialias() {
+        const ret = wasm.__wbg_get_floodfilloption_antialias(this.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * Give pixels just outside the fill partial coverage by how close their color is to
+     * the target, so antialiased boundaries blend in instead of leaving a hard edge
+     * @param {boolean} arg0
+     */
+    set antialias(arg0) {
+        wasm.__wbg_set_floodfilloption_antialias(this.__wbg_ptr, arg0);
+    }
+    /**
+     * How `selection_mask` values limit the fill; soft modes fade the fill at feathered
+     * selection edges
+     * @returns {CoverageMode}
+     */
+    get mask_mode() {
+        const ret = wasm.__wbg_get_floodfilloption_mask_mode(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * How `selection_mask` values limit the fill; soft modes fade the fill at feathered
+     * selection edges
+     * @param {CoverageMode} arg0
+     */
+    set mask_mode(arg0) {
+        wasm.__wbg_set_floodfilloption_mask_mode(this.__wbg_ptr, arg0);
+    }
+}
+if (Symbol.dispose) FloodFillOption.prototype[Symbol.dispose] = FloodFillOption.prototype.free;
+
+const GaussianBlurOptionFinalization = (typeof FinalizationRegistry === 'undefined')
+    ? { register: () => {}, unregister: () => {} }
+    : new FinalizationRegistry(ptr => wasm.__wbg_gaussianbluroption_free(ptr >>> 0, 1));
+
+export class GaussianBlurOption {
+
+    __destroy_into_raw() {
+        const ptr = this.__wbg_ptr;
+        this.__wbg_ptr = 0;
+        GaussianBlurOptionFinalization.unregister(this);
+        return ptr;
+    }
+
+    free() {
+        const ptr = this.__destroy_into_raw();
+        wasm.__wbg_gaussianbluroption_free(ptr, 0);
+    }
+    /**
+     * @param {number} radius
+     * @param {AlphaBlurMode} alpha_mode
+     */
+    constructor(radius, alpha_mode) {
+        const ret = wasm.gaussianbluroption_new(radius, alpha_mode);
+        this.__wbg_ptr = ret >>> 0;
+        GaussianBlurOptionFinalization.register(this, this.__wbg_ptr, this);
+        return this;
+    }
+    /**
+     * Blur radius (higher values create stronger blur effect)
+     * @returns {number}
+     */
+    get radius() {
+        const ret = wasm.__wbg_get_brightnesscontrastoption_brightness(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * Blur radius (higher values create stronger blur effect)
+     * @param {number} arg0
+     */
+    set radius(arg0) {
+        wasm.__wbg_set_brightnesscontrastoption_brightness(this.__wbg_ptr, arg0);
+    }
+    /**
+     * How to handle the alpha channel
+     * @returns {AlphaBlurMode}
+     */
+    get alpha_mode() {
+        const ret = wasm.__wbg_get_gaussianbluroption_alpha_mode(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * How to handle the alpha channel
+     * @param {AlphaBlurMode} arg0
+     */
+    set alpha_mode(arg0) {
+        wasm.__wbg_set_gaussianbluroption_alpha_mode(this.__wbg_ptr, arg0);
+    }
+}
+if (Symbol.dispose) GaussianBlurOption.prototype[Symbol.dispose] = GaussianBlurOption.prototype.free;
+
+const GradientFillOptionFinalization = (typeof FinalizationRegistry === 'undefined')
+    ? { register: () => {}, unregister: () => {} }
+    : new FinalizationRegistry(ptr => wasm.__wbg_gradientfilloption_free(ptr >>> 0, 1));
+
+export class GradientFillOption {
+
+    __destroy_into_raw() {
+        const ptr = this.__wbg_ptr;
+        this.__wbg_ptr = 0;
+        GradientFillOptionFinalization.unregister(this);
+        return ptr;
+    }
+
+    free() {
+        const ptr = this.__destroy_into_raw();
+        wasm.__wbg_gradientfilloption_free(ptr, 0);
+    }
+    /**
+     * `interpolation`, `repeat` and `dither` default to sRGB, none and none and are set
+     * as properties.
+     * @param {GradientShape} shape
+     * @param {number} start_x
+     * @param {number} start_y
+     * @param {number} end_x
+     * @param {number} end_y
+     */
+    constructor(shape, start_x, start_y, end_x, end_y) {
+        const ret = wasm.gradientfilloption_new(shape, start_x, start_y, end_x, end_y);
+        this.__wbg_ptr = ret >>> 0;
+        GradientFillOptionFinalization.register(this, this.__wbg_ptr, this);
+        return this;
+    }
+    /**
+     * @returns {GradientShape}
+     */
+    get shape() {
+        const ret = wasm.__wbg_get_gradientfilloption_shape(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @param {GradientShape} arg0
+     */
+    set shape(arg0) {
+        wasm.__wbg_set_gradientfilloption_shape(this.__wbg_ptr, arg0);
+    }
+    /**
+     * Start point in pixels (pixel centers are at +0.5)
+     * @returns {number}
+     */
+    get start_x() {
+        const ret = wasm.__wbg_get_gradientfilloption_start_x(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * Start point in pixels (pixel centers are at +0.5)
+     * @param {number} arg0
+     */
+    set start_x(arg0) {
+        wasm.__wbg_set_gradientfilloption_start_x(this.__wbg_ptr, arg0);
+    }
+    /**
+     * @returns {number}
+     */
+    get start_y() {
+        const ret = wasm.__wbg_get_ditheringoption_strength(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @param {number} arg0
+     */
+    set start_y(arg0) {
+        wasm.__wbg_set_ditheringoption_strength(this.__wbg_ptr, arg0);
+    }
+    /**
+     * End point in pixels
+     * @returns {number}
+     */
+    get end_x() {
+        const ret = wasm.__wbg_get_gradientfilloption_end_x(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * End point in pixels
+     * @param {number} arg0
+     */
+    set end_x(arg0) {
+        wasm.__wbg_set_gradientfilloption_end_x(this.__wbg_ptr, arg0);
+    }
+    /**
+     * @returns {number}
+     */
+    get end_y() {
+        const ret = wasm.__wbg_get_gradientfilloption_end_y(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @param {number} arg0
+     */
+    set end_y(arg0) {
+        wasm.__wbg_set_gradientfilloption_end_y(this.__wbg_ptr, arg0);
+    }
+    /**
+     * @returns {GradientInterpolation}
+     */
+    get interpolation() {
+        const ret = wasm.__wbg_get_gradientfilloption_interpolation(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @param {GradientInterpolation} arg0
+     */
+    set interpolation(arg0) {
+        wasm.__wbg_set_gradientfilloption_interpolation(this.__wbg_ptr, arg0);
+    }
+    /**
+     * @returns {GradientRepeat}
+     */
+    get repeat() {
+        const ret = wasm.__wbg_get_gradientfilloption_repeat(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @param {GradientRepeat} arg0
+     */
+    set repeat(arg0) {
+        wasm.__wbg_set_gradientfilloption_repeat(this.__wbg_ptr, arg0);
+    }
+    /**
+     * @returns {GradientDither}
+     */
+    get dither() {
+        const ret = wasm.__wbg_get_gradientfilloption_dither(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @param {GradientDither} arg0
+     */
+    set dither(arg0) {
+        wasm.__wbg_set_gradientfilloption_dither(this.__wbg_ptr, arg0);
+    }
+}
+if (Symbol.dispose) GradientFillOption.prototype[Symbol.dispose] = GradientFillOption.prototype.free;
+
+const GroupOptionFinalization = (typeof FinalizationRegistry === 'undefined')
+    ? { register: () => {}, unregister: () => {} }
+    : new FinalizationRegistry(ptr => wasm.__wbg_groupoption_free(ptr >>> 0, 1));
+
+export class GroupOption {
+
+    __destroy_into_raw() {
+        const ptr = this.__wbg_ptr;
+        this.__wbg_ptr = 0;
+        GroupOptionFinalization.unregister(this);
+        return ptr;
+    }
+
+    free() {
+        const ptr = this.__destroy_into_raw();
+        wasm.__wbg_groupoption_free(ptr, 0);
+    }
+    /**
+     * @param {number} opacity
+     * @param {boolean} visible
+     * @param {BlendMode} blend_mode
+     * @param {boolean} clipping
+     * @param {boolean} pass_through
+     */
+    constructor(opacity, visible, blend_mode, clipping, pass_through) {
+        const ret = wasm.groupoption_new(opacity, visible, blend_mode, clipping, pass_through);
+        this.__wbg_ptr = ret >>> 0;
+        GroupOptionFinalization.register(this, this.__wbg_ptr, this);
+        return this;
+    }
+    /**
+     * Group opacity (0.0-1.0)
+     * @returns {number}
+     */
+    get opacity() {
+        const ret = wasm.__wbg_get_groupoption_opacity(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * Group opacity (0.0-1.0)
+     * @param {number} arg0
+     */
+    set opacity(arg0) {
+        wasm.__wbg_set_groupoption_opacity(this.__wbg_ptr, arg0);
+    }
+    /**
+     * Hidden groups skip every layer pushed inside them
+     * @returns {boolean}
+     */
+    get visible() {
+        const ret = wasm.__wbg_get_groupoption_visible(this.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * Hidden groups skip every layer pushed inside them
+     * @param {boolean} arg0
+     */
+    set visible(arg0) {
+        wasm.__wbg_set_groupoption_visible(this.__wbg_ptr, arg0);
+    }
+    /**
+     * Blend mode used when an isolated group is composited onto its parent
+     * @returns {BlendMode}
+     */
+    get blend_mode() {
+        const ret = wasm.__wbg_get_groupoption_blend_mode(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * Blend mode used when an isolated group is composited onto its parent
+     * @param {BlendMode} arg0
+     */
+    set blend_mode(arg0) {
+        wasm.__wbg_set_groupoption_blend_mode(this.__wbg_ptr, arg0);
+    }
+    /**
+     * Clip to the alpha of the nearest non-clipping layer below
+     * @returns {boolean}
+     */
+    get clipping() {
+        const ret = wasm.__wbg_get_groupoption_clipping(this.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * Clip to the alpha of the nearest non-clipping layer below
+     * @param {boolean} arg0
+     */
+    set clipping(arg0) {
+        wasm.__wbg_set_groupoption_clipping(this.__wbg_ptr, arg0);
+    }
+    /**
+     * Composite children directly onto the backdrop instead of into an isolated buffer
+     * @returns {boolean}
+     */
+    get pass_through() {
+        const ret = wasm.__wbg_get_groupoption_pass_through(this.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * Composite children directly onto the backdrop instead of into an isolated buffer
+     * @param {boolean} arg0
+     */
+    set pass_through(arg0) {
+        wasm.__wbg_set_groupoption_pass_through(this.__wbg_ptr, arg0);
+    }
+}
+if (Symbol.dispose) GroupOption.prototype[Symbol.dispose] = GroupOption.prototype.free;
+
+const HistoryReaderFinalization = (typeof FinalizationRegistry === 'undefined')
+    ? { register: () => {}, unregister: () => {} }
+    : new FinalizationRegistry(ptr => wasm.__wbg_historyreader_free(ptr >>> 0, 1));
+/**
+ * Sequential reader over a history log. Every record is validated as it is read.
+ */
+export class HistoryReader {
+
+    __destroy_into_raw() {
+        const ptr = this.__wbg_ptr;
+        this.__wbg_ptr = 0;
+        HistoryReaderFinalization.unregister(this);
+        return ptr;
+    }
+
+    free() {
+        const ptr = this.__destroy_into_raw();
+        wasm.__wbg_historyreader_free(ptr, 0);
+    }
+    /**
+     * @param {Uint8Array} bytes
+     */
+    constructor(bytes) {
+        const ptr0 = passArray8ToWasm0(bytes, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.historyreader_new(ptr0, len0);
+        if (ret[2]) {
+            throw takeFromExternrefTable0(ret[1]);
+        }
+        this.__wbg_ptr = ret[0] >>> 0;
+        HistoryReaderFinalization.register(this, this.__wbg_ptr, this);
+        return this;
+    }
+    /**
+     * Byte offset of the next unread record.
+     * @returns {number}
+     */
+    offset() {
+        const ret = wasm.historyreader_offset(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * Read the next record, or `undefined` at the end of the log.
+     * A corrupt or truncated record is an error; `offset()` then still points at it,
+     * so callers can truncate the log to the last valid record.
+     * @returns {PatchRecord | undefined}
+     */
+    next() {
+        const ret = wasm.historyreader_next(this.__wbg_ptr);
+        if (ret[2]) {
+            throw takeFromExternrefTable0(ret[1]);
+        }
+        return ret[0] === 0 ? undefined : PatchRecord.__wrap(ret[0]);
+    }
+}
+if (Symbol.dispose) HistoryReader.prototype[Symbol.dispose] = HistoryReader.prototype.free;
+
+const LayerOptionFinalization = (typeof FinalizationRegistry === 'undefined')
+    ? { register: () => {}, unregister: () => {} }
+    : new FinalizationRegistry(ptr => wasm.__wbg_layeroption_free(ptr >>> 0, 1));
+
+export class LayerOption {
+
+    __destroy_into_raw() {
+        const ptr = this.__wbg_ptr;
+        this.__wbg_ptr = 0;
+        LayerOptionFinalization.unregister(this);
+        return ptr;
+    }
+
+    free() {
+        const ptr = this.__destroy_into_raw();
+        wasm.__wbg_layeroption_free(ptr, 0);
+    }
+    /**
+     * @param {number} opacity
+     * @param {boolean} visible
+     * @param {BlendMode} blend_mode
+     * @param {boolean} clipping
+     */
+    constructor(opacity, visible, blend_mode, clipping) {
+        const ret = wasm.layeroption_new(opacity, visible, blend_mode, clipping);
+        this.__wbg_ptr = ret >>> 0;
+        LayerOptionFinalization.register(this, this.__wbg_ptr, this);
+        return this;
+    }
+    /**
+     * Layer opacity (0.0-1.0)
+     * @returns {number}
+     */
+    get opacity() {
+        const ret = wasm.__wbg_get_groupoption_opacity(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * Layer opacity (0.0-1.0)
+     * @param {number} arg0
+     */
+    set opacity(arg0) {
+        wasm.__wbg_set_groupoption_opacity(this.__wbg_ptr, arg0);
+    }
+    /**
+     * Hidden layers are skipped (and hide the layers clipped to them)
+     * @returns {boolean}
+     */
+    get visible() {
+        const ret = wasm.__wbg_get_groupoption_visible(this.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * Hidden layers are skipped (and hide the layers clipped to them)
+     * @param {boolean} arg0
+     */
+    set visible(arg0) {
+        wasm.__wbg_set_groupoption_visible(this.__wbg_ptr, arg0);
+    }
+    /**
+     * @returns {BlendMode}
+     */
+    get blend_mode() {
+        const ret = wasm.__wbg_get_groupoption_blend_mode(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @param {BlendMode} arg0
+     */
+    set blend_mode(arg0) {
+        wasm.__wbg_set_groupoption_blend_mode(this.__wbg_ptr, arg0);
+    }
+    /**
+     * Clip to the alpha of the nearest non-clipping layer below
+     * @returns {boolean}
+     */
+    get clipping() {
+        const ret = wasm.__wbg_get_groupoption_clipping(this.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * Clip to the alpha of the nearest non-clipping layer below
+     * @param {boolean} arg0
+     */
+    set clipping(arg0) {
+        wasm.__wbg_set_groupoption_clipping(this.__wbg_ptr, arg0);
+    }
+}
+if (Symbol.dispose) LayerOption.prototype[Symbol.dispose] = LayerOption.prototype.free;
+
+const LayerStackFinalization = (typeof FinalizationRegistry === 'undefined')
+    ? { register: () => {}, unregister: () => {} }
+    : new FinalizationRegistry(ptr => wasm.__wbg_layerstack_free(ptr >>> 0, 1));
+/**
+ * Multi-layer compositor.
+ *
+ * Layers are pushed bottom to top between a `begin*` call and `compositeInto`,
+ * so each `RgbaBuffer` is only borrowed for the duration of its `pushLayer` call.
+ * Groups are opened with `beginGroup` and closed with `endGroup`, following the
+ * layer tree depth-first. Only the region chosen by `begin*` is recomposited.
+ */
+export class LayerStack {
+
+    __destroy_into_raw() {
+        const ptr = this.__wbg_ptr;
+        this.__wbg_ptr = 0;
+        LayerStackFinalization.unregister(this);
+        return ptr;
+    }
+
+    free() {
+        const ptr = this.__destroy_into_raw();
+        wasm.__wbg_layerstack_free(ptr, 0);
+    }
+    /**
+     * Start compositing only the given rectangle (clipped to the canvas).
+     * @param {number} x
+     * @param {number} y
+     * @param {number} width
+     * @param {number} height
+     */
+    beginRect(x, y, width, height) {
+        wasm.layerstack_beginRect(this.__wbg_ptr, x, y, width, height);
+    }
+    /**
+     * Composite a layer over everything pushed so far.
+     * @param {RgbaBuffer} buffer
+     * @param {LayerOption} options
+     * @returns {boolean}
+     */
+    pushLayer(buffer, options) {
+        _assertClass(buffer, RgbaBuffer);
+        _assertClass(options, LayerOption);
+        const ret = wasm.layerstack_pushLayer(this.__wbg_ptr, buffer.__wbg_ptr, options.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * Open a group. Layers pushed until the matching `endGroup` belong to it.
+     * @param {GroupOption} options
+     */
+    beginGroup(options) {
+        _assertClass(options, GroupOption);
+        wasm.layerstack_beginGroup(this.__wbg_ptr, options.__wbg_ptr);
+    }
+    /**
+     * Start compositing only the given tiles (row-major linear indices).
+     * @param {number} tile_size
+     * @param {Uint32Array} tile_indices
+     */
+    beginTiles(tile_size, tile_indices) {
+        const ptr0 = passArray32ToWasm0(tile_indices, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        wasm.layerstack_beginTiles(this.__wbg_ptr, tile_size, ptr0, len0);
+    }
+    /**
+     * Copy the composited region into the output buffer.
+     * @param {RgbaBuffer} output
+     * @returns {boolean}
+     */
+    compositeInto(output) {
+        _assertClass(output, RgbaBuffer);
+        const ret = wasm.layerstack_compositeInto(this.__wbg_ptr, output.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * Composite a layer through an 8-bit mask (one byte per pixel, 255 = fully shown).
+     * @param {RgbaBuffer} buffer
+     * @param {LayerOption} options
+     * @param {Uint8Array} mask
+     * @returns {boolean}
+     */
+    pushLayerWithMask(buffer, options, mask) {
+        _assertClass(buffer, RgbaBuffer);
+        _assertClass(options, LayerOption);
+        const ptr0 = passArray8ToWasm0(mask, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.layerstack_pushLayerWithMask(this.__wbg_ptr, buffer.__wbg_ptr, options.__wbg_ptr, ptr0, len0);
+        return ret !== 0;
+    }
+    /**
+     * Open a group whose result is shown through an 8-bit mask.
+     * @param {GroupOption} options
+     * @param {Uint8Array} mask
+     * @returns {boolean}
+     */
+    beginGroupWithMask(options, mask) {
+        _assertClass(options, GroupOption);
+        const ptr0 = passArray8ToWasm0(mask, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.layerstack_beginGroupWithMask(this.__wbg_ptr, options.__wbg_ptr, ptr0, len0);
+        return ret !== 0;
+    }
+    /**
+     * @param {number} width
+     * @param {number} height
+     */
+    constructor(width, height) {
+        const ret = wasm.layerstack_new(width, height);
+        this.__wbg_ptr = ret >>> 0;
+        LayerStackFinalization.register(this, this.__wbg_ptr, this);
+        return this;
+    }
+    /**
+     * Start compositing the whole canvas.
+     */
+    begin() {
+        wasm.layerstack_begin(this.__wbg_ptr);
+    }
+    /**
+     * @returns {number}
+     */
+    width() {
+        const ret = wasm.layerstack_width(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    height() {
+        const ret = wasm.layerstack_height(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * Close the innermost group and composite it onto its parent.
+     * @returns {boolean}
+     */
+    endGroup() {
+        const ret = wasm.layerstack_endGroup(this.__wbg_ptr);
+        return ret !== 0;
+    }
+}
+if (Symbol.dispose) LayerStack.prototype[Symbol.dispose] = LayerStack.prototype.free;
+
+const PatchBufferRgbaOptionFinalization = (typeof FinalizationRegistry === 'undefined')
+    ? { register: () => {}, unregister: () => {} }
+    : new FinalizationRegistry(ptr => wasm.__wbg_patchbufferrgbaoption_free(ptr >>> 0, 1));
+
+export class PatchBufferRgbaOption {
+
+    __destroy_into_raw() {
+        const ptr = this.__wbg_ptr;
+        this.__wbg_ptr = 0;
+        PatchBufferRgbaOptionFinalization.unregister(this);
+        return ptr;
+    }
+
+    free() {
+        const ptr = this.__destroy_into_raw();
+        wasm.__wbg_patchbufferrgbaoption_free(ptr, 0);
+    }
+    /**
+     * @returns {AntialiasMode}
+     */
+    get antialias_mode() {
+        const ret = wasm.__wbg_get_patchbufferrgbaoption_antialias_mode(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @param {AntialiasMode} arg0
+     */
+    set antialias_mode(arg0) {
+        wasm.__wbg_set_patchbufferrgbaoption_antialias_mode(this.__wbg_ptr, arg0);
+    }
+    /**
+     * @returns {boolean}
+     */
+    get flip_x() {
+        const ret = wasm.__wbg_get_groupoption_clipping(this.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * @param {boolean} arg0
+     */
+    set flip_x(arg0) {
+        wasm.__wbg_set_groupoption_clipping(this.__wbg_ptr, arg0);
+    }
+    /**
+     * @returns {boolean}
+     */
+    get flip_y() {
+        const ret = wasm.__wbg_get_groupoption_pass_through(this.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * @param {boolean} arg0
+     */
+    set flip_y(arg0) {
+        wasm.__wbg_set_groupoption_pass_through(this.__wbg_ptr, arg0);
+    }
+    /**
+     * @returns {BlendMode}
+     */
+    get blend_mode() {
+        const ret = wasm.__wbg_get_groupoption_blend_mode(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @param {BlendMode} arg0
+     */
+    set blend_mode(arg0) {
+        wasm.__wbg_set_groupoption_blend_mode(this.__wbg_ptr, arg0);
+    }
+    /**
+     * Global opacity applied on top of the source alpha (0.0-1.0)
+     * @returns {number}
+     */
+    get opacity() {
+        const ret = wasm.__wbg_get_groupoption_opacity(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * Global opacity applied on top of the source alpha (0.0-1.0)
+     * @param {number} arg0
+     */
+    set opacity(arg0) {
+        wasm.__wbg_set_groupoption_opacity(this.__wbg_ptr, arg0);
+    }
+    /**
+     * `blend_mode` and `opacity` start as `Normal` and 1.0 and are set as properties.
+     * @param {AntialiasMode} antialias_mode
+     * @param {boolean} flip_x
+     * @param {boolean} flip_y
+     */
+    constructor(antialias_mode, flip_x, flip_y) {
+        const ret = wasm.patchbufferrgbaoption_new(antialias_mode, flip_x, flip_y);
+        this.__wbg_ptr = ret >>> 0;
+        PatchBufferRgbaOptionFinalization.register(this, this.__wbg_ptr, this);
+        return this;
+    }
+}
+if (Symbol.dispose) PatchBufferRgbaOption.prototype[Symbol.dispose] = PatchBufferRgbaOption.prototype.free;
+
+const PatchRecordFinalization = (typeof FinalizationRegistry === 'undefined')
+    ? { register: () => {}, unregister: () => {} }
+    : new FinalizationRegistry(ptr => wasm.__wbg_patchrecord_free(ptr >>> 0, 1));
+/**
+ * A serializable patch (PackedDiffs + PatchMetadata).
+ */
+export class PatchRecord {
+
+    static __wrap(ptr) {
+        ptr = ptr >>> 0;
+        const obj = Object.create(PatchRecord.prototype);
+        obj.__wbg_ptr = ptr;
+        PatchRecordFinalization.register(obj, obj.__wbg_ptr, obj);
+        return obj;
+    }
+
+    __destroy_into_raw() {
+        const ptr = this.__wbg_ptr;
+        this.__wbg_ptr = 0;
+        PatchRecordFinalization.unregister(this);
+        return ptr;
+    }
+
+    free() {
+        const ptr = this.__destroy_into_raw();
+        wasm.__wbg_patchrecord_free(ptr, 0);
+    }
+    /**
+     * Set pixel diffs as flattened [x, y, packed color] triples.
+     * @param {Uint32Array} pixels
+     * @returns {boolean}
+     */
+    setPixels(pixels) {
+        const ptr0 = passArray32ToWasm0(pixels, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.patchrecord_setPixels(this.__wbg_ptr, ptr0, len0);
+        return ret !== 0;
+    }
+    /**
+     * Buffer size of the tile patch as [width, height] (empty if absent).
+     * @returns {Uint32Array}
+     */
+    tilesSize() {
+        const ret = wasm.patchrecord_tilesSize(this.__wbg_ptr);
+        var v1 = getArrayU32FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+        return v1;
+    }
+    /**
+     * Whole buffer size as [width, height] (empty if absent).
+     * @returns {Uint32Array}
+     */
+    wholeSize() {
+        const ret = wasm.patchrecord_wholeSize(this.__wbg_ptr);
+        var v1 = getArrayU32FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+        return v1;
+    }
+    /**
+     * @returns {Uint8Array}
+     */
+    wholeWebp() {
+        const ret = wasm.patchrecord_wholeWebp(this.__wbg_ptr);
+        var v1 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
+        return v1;
+    }
+    /**
+     * @returns {boolean}
+     */
+    hasPartial() {
+        const ret = wasm.patchrecord_hasPartial(this.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * @returns {number | undefined}
+     */
+    pixelCount() {
+        const ret = wasm.patchrecord_pixelCount(this.__wbg_ptr);
+        return ret === 0x100000001 ? undefined : ret;
+    }
+    /**
+     * @param {number} x
+     * @param {number} y
+     * @param {number} width
+     * @param {number} height
+     * @param {Uint8Array} webp
+     */
+    setPartial(x, y, width, height, webp) {
+        const ptr0 = passArray8ToWasm0(webp, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        wasm.patchrecord_setPartial(this.__wbg_ptr, x, y, width, height, ptr0, len0);
+    }
+    /**
+     * @returns {Uint8Array}
+     */
+    partialWebp() {
+        const ret = wasm.patchrecord_partialWebp(this.__wbg_ptr);
+        var v1 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
+        return v1;
+    }
+    /**
+     * @param {string} layer_id
+     */
+    setLayerId(layer_id) {
+        const ptr0 = passStringToWasm0(layer_id, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        wasm.patchrecord_setLayerId(this.__wbg_ptr, ptr0, len0);
+    }
+    /**
+     * @param {number} timestamp
+     */
+    setTimestamp(timestamp) {
+        wasm.patchrecord_setTimestamp(this.__wbg_ptr, timestamp);
+    }
+    /**
+     * @param {number} pixel_count
+     */
+    setPixelCount(pixel_count) {
+        wasm.patchrecord_setPixelCount(this.__wbg_ptr, pixel_count);
+    }
+    /**
+     * Partial bound box as [x, y, width, height] (empty if absent).
+     * @returns {Int32Array}
+     */
+    partialBoundBox() {
+        const ret = wasm.patchrecord_partialBoundBox(this.__wbg_ptr);
+        var v1 = getArrayI32FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+        return v1;
+    }
+    constructor() {
+        const ret = wasm.patchrecord_new();
+        this.__wbg_ptr = ret >>> 0;
+        PatchRecordFinalization.register(this, this.__wbg_ptr, this);
+        return this;
+    }
+    /**
+     * @returns {string | undefined}
+     */
+    tool() {
+        const ret = wasm.patchrecord_tool(this.__wbg_ptr);
+        let v1;
+        if (ret[0] !== 0) {
+            v1 = getStringFromWasm0(ret[0], ret[1]).slice();
+            wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
+        }
+        return v1;
+    }
+    /**
+     * @returns {Uint8Array}
+     */
+    tiles() {
+        const ret = wasm.patchrecord_tiles(this.__wbg_ptr);
+        var v1 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
+        return v1;
+    }
+    /**
+     * Decode a single framed record produced by `encode`.
+     * @param {Uint8Array} record
+     * @returns {PatchRecord}
+     */
+    static decode(record) {
+        const ptr0 = passArray8ToWasm0(record, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.patchrecord_decode(ptr0, len0);
+        if (ret[2]) {
+            throw takeFromExternrefTable0(ret[1]);
+        }
+        return PatchRecord.__wrap(ret[0]);
+    }
+    /**
+     * Encode this patch as a single framed record, ready to append to a history log.
+     * @returns {Uint8Array}
+     */
+    encode() {
+        const ret = wasm.patchrecord_encode(this.__wbg_ptr);
+        var v1 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
+        return v1;
+    }
+    /**
+     * @returns {Uint32Array}
+     */
+    pixels() {
+        const ret = wasm.patchrecord_pixels(this.__wbg_ptr);
+        var v1 = getArrayU32FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+        return v1;
+    }
+    /**
+     * @returns {string | undefined}
+     */
+    layerId() {
+        const ret = wasm.patchrecord_layerId(this.__wbg_ptr);
+        let v1;
+        if (ret[0] !== 0) {
+            v1 = getStringFromWasm0(ret[0], ret[1]).slice();
+            wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
+        }
+        return v1;
+    }
+    /**
+     * @param {string} tool
+     */
+    setTool(tool) {
+        const ptr0 = passStringToWasm0(tool, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        wasm.patchrecord_setTool(this.__wbg_ptr, ptr0, len0);
+    }
+    /**
+     * @returns {boolean}
+     */
+    hasTiles() {
+        const ret = wasm.patchrecord_hasTiles(this.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * @returns {boolean}
+     */
+    hasWhole() {
+        const ret = wasm.patchrecord_hasWhole(this.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * Set a tile patch (see `RgbaBuffer.swapTiles`) for a buffer of the given size.
+     * Returns false if the patch does not decode for that size.
+     * @param {number} width
+     * @param {number} height
+     * @param {Uint8Array} tiles
+     * @returns {boolean}
+     */
+    setTiles(width, height, tiles) {
+        const ptr0 = passArray8ToWasm0(tiles, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.patchrecord_setTiles(this.__wbg_ptr, width, height, ptr0, len0);
+        return ret !== 0;
+    }
+    /**
+     * @param {number} width
+     * @param {number} height
+     * @param {Uint8Array} webp
+     */
+    setWhole(width, height, webp) {
+        const ptr0 = passArray8ToWasm0(webp, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        wasm.patchrecord_setWhole(this.__wbg_ptr, width, height, ptr0, len0);
+    }
+    /**
+     * @returns {number | undefined}
+     */
+    timestamp() {
+        const ret = wasm.patchrecord_timestamp(this.__wbg_ptr);
+        return ret[0] === 0 ? undefined : ret[1];
+    }
+}
+if (Symbol.dispose) PatchRecord.prototype[Symbol.dispose] = PatchRecord.prototype.free;
+
+const PathFinalization = (typeof FinalizationRegistry === 'undefined')
+    ? { register: () => {}, unregister: () => {} }
+    : new FinalizationRegistry(ptr => wasm.__wbg_path_free(ptr >>> 0, 1));
+/**
+ * Outline built from move/line/quadratic/cubic segments, in canvas pixels.
+ * Curves are flattened as they are added.
+ */
+export class Path {
+
+    __destroy_into_raw() {
+        const ptr = this.__wbg_ptr;
+        this.__wbg_ptr = 0;
+        PathFinalization.unregister(this);
+        return ptr;
+    }
+
+    free() {
+        const ptr = this.__destroy_into_raw();
+        wasm.__wbg_path_free(ptr, 0);
+    }
+    constructor() {
+        const ret = wasm.path_new();
+        this.__wbg_ptr = ret >>> 0;
+        PathFinalization.register(this, this.__wbg_ptr, this);
+        return this;
+    }
+    clear() {
+        wasm.path_clear(this.__wbg_ptr);
+    }
+    /**
+     * Close the current subpath back to its first point. The next segment starts from there.
+     */
+    close() {
+        wasm.path_close(this.__wbg_ptr);
+    }
+    /**
+     * @param {number} x
+     * @param {number} y
+     */
+    lineTo(x, y) {
+        wasm.path_lineTo(this.__wbg_ptr, x, y);
+    }
+    /**
+     * Start a new subpath at (x, y).
+     * @param {number} x
+     * @param {number} y
+     */
+    moveTo(x, y) {
+        wasm.path_moveTo(this.__wbg_ptr, x, y);
+    }
+    /**
+     * Quadratic Bézier through control point (cx, cy) to (x, y).
+     * @param {number} cx
+     * @param {number} cy
+     * @param {number} x
+     * @param {number} y
+     */
+    quadTo(cx, cy, x, y) {
+        wasm.path_quadTo(this.__wbg_ptr, cx, cy, x, y);
+    }
+    /**
+     * Cubic Bézier through control points (c1x, c1y) and (c2x, c2y) to (x, y).
+     * @param {number} c1x
+     * @param {number} c1y
+     * @param {number} c2x
+     * @param {number} c2y
+     * @param {number} x
+     * @param {number} y
+     */
+    cubicTo(c1x, c1y, c2x, c2y, x, y) {
+        wasm.path_cubicTo(this.__wbg_ptr, c1x, c1y, c2x, c2y, x, y);
+    }
+    /**
+     * @returns {boolean}
+     */
+    isEmpty() {
+        const ret = wasm.path_isEmpty(this.__wbg_ptr);
+        return ret !== 0;
+    }
+}
+if (Symbol.dispose) Path.prototype[Symbol.dispose] = Path.prototype.free;
+
+const PatternFillOptionFinalization = (typeof FinalizationRegistry === 'undefined')
+    ? { register: () => {}, unregister: () => {} }
+    : new FinalizationRegistry(ptr => wasm.__wbg_patternfilloption_free(ptr >>> 0, 1));
+
+export class PatternFillOption {
+
+    __destroy_into_raw() {
+        const ptr = this.__wbg_ptr;
+        this.__wbg_ptr = 0;
+        PatternFillOptionFinalization.unregister(this);
+        return ptr;
+    }
+
+    free() {
+        const ptr = this.__destroy_into_raw();
+        wasm.__wbg_patternfilloption_free(ptr, 0);
+    }
+    /**
+     * @param {number} offset_x
+     * @param {number} offset_y
+     * @param {number} scale
+     */
+    constructor(offset_x, offset_y, scale) {
+        const ret = wasm.patternfilloption_new(offset_x, offset_y, scale);
+        this.__wbg_ptr = ret >>> 0;
+        PatternFillOptionFinalization.register(this, this.__wbg_ptr, this);
+        return this;
+    }
+    /**
+     * Canvas position of the tile's top-left corner (the pattern repeats in every direction)
+     * @returns {number}
+     */
+    get offset_x() {
+        const ret = wasm.__wbg_get_patternfilloption_offset_x(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * Canvas position of the tile's top-left corner (the pattern repeats in every direction)
+     * @param {number} arg0
+     */
+    set offset_x(arg0) {
+        wasm.__wbg_set_patternfilloption_offset_x(this.__wbg_ptr, arg0);
+    }
+    /**
+     * @returns {number}
+     */
+    get offset_y() {
+        const ret = wasm.__wbg_get_patternfilloption_offset_y(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @param {number} arg0
+     */
+    set offset_y(arg0) {
+        wasm.__wbg_set_patternfilloption_offset_y(this.__wbg_ptr, arg0);
+    }
+    /**
+     * Integer magnification of the tile (nearest neighbor, at least 1)
+     * @returns {number}
+     */
+    get scale() {
+        const ret = wasm.__wbg_get_patternfilloption_scale(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * Integer magnification of the tile (nearest neighbor, at least 1)
+     * @param {number} arg0
+     */
+    set scale(arg0) {
+        wasm.__wbg_set_patternfilloption_scale(this.__wbg_ptr, arg0);
+    }
+}
+if (Symbol.dispose) PatternFillOption.prototype[Symbol.dispose] = PatternFillOption.prototype.free;
+
+const PosterizeOptionFinalization = (typeof FinalizationRegistry === 'undefined')
+    ? { register: () => {}, unregister: () => {} }
+    : new FinalizationRegistry(ptr => wasm.__wbg_posterizeoption_free(ptr >>> 0, 1));
+
+export class PosterizeOption {
+
+    __destroy_into_raw() {
+        const ptr = this.__wbg_ptr;
+        this.__wbg_ptr = 0;
+        PosterizeOptionFinalization.unregister(this);
+        return ptr;
+    }
+
+    free() {
+        const ptr = this.__destroy_into_raw();
+        wasm.__wbg_posterizeoption_free(ptr, 0);
+    }
+    /**
+     * @param {number} levels
+     */
+    constructor(levels) {
+        const ret = wasm.posterizeoption_new(levels);
+        this.__wbg_ptr = ret >>> 0;
+        PosterizeOptionFinalization.register(this, this.__wbg_ptr, this);
+        return this;
+    }
+    /**
+     * Number of levels per channel (1-32, higher values preserve more detail)
+     * @returns {number}
+     */
+    get levels() {
+        const ret = wasm.__wbg_get_ditheringoption_levels(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * Number of levels per channel (1-32, higher values preserve more detail)
+     * @param {number} arg0
+     */
+    set levels(arg0) {
+        wasm.__wbg_set_ditheringoption_levels(this.__wbg_ptr, arg0);
+    }
+}
+if (Symbol.dispose) PosterizeOption.prototype[Symbol.dispose] = PosterizeOption.prototype.free;
+
+const RgbaBufferFinalization = (typeof FinalizationRegistry === 'undefined')
+    ? { register: () => {}, unregister: () => {} }
+    : new FinalizationRegistry(ptr => wasm.__wbg_rgbabuffer_free(ptr >>> 0, 1));
+
+export class RgbaBuffer {
+
+    static __wrap(ptr) {
+        ptr = ptr >>> 0;
+        const obj = Object.create(RgbaBuffer.prototype);
+        obj.__wbg_ptr = ptr;
+        RgbaBufferFinalization.register(obj, obj.__wbg_ptr, obj);
+        return obj;
+    }
+
+    __destroy_into_raw() {
+        const ptr = this.__wbg_ptr;
+        this.__wbg_ptr = 0;
+        RgbaBufferFinalization.unregister(this);
+        return ptr;
+    }
+
+    free() {
+        const ptr = this.__destroy_into_raw();
+        wasm.__wbg_rgbabuffer_free(ptr, 0);
+    }
+    /**
+     * @param {Uint8Array} raw
+     * @param {number} width
+     * @param {number} height
+     * @returns {boolean}
+     */
+    overwriteWith(raw, width, height) {
+        const ptr0 = passArray8ToWasm0(raw, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.rgbabuffer_overwriteWith(this.__wbg_ptr, ptr0, len0, width, height);
+        return ret !== 0;
+    }
+    /**
+     * @param {number} x
+     * @param {number} y
+     * @returns {Array<any>}
+     */
+    get(x, y) {
+        const ret = wasm.rgbabuffer_get(this.__wbg_ptr, x, y);
+        return ret;
+    }
+    /**
+     * @returns {number}
+     */
+    len() {
+        const ret = wasm.rgbabuffer_len(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @param {number} width
+     * @param {number} height
+     */
+    constructor(width, height) {
+        const ret = wasm.rgbabuffer_new(width, height);
+        this.__wbg_ptr = ret >>> 0;
+        RgbaBufferFinalization.register(this, this.__wbg_ptr, this);
+        return this;
+    }
+    /**
+     * @returns {number}
+     */
+    ptr() {
+        const ret = wasm.rgbabuffer_ptr(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @param {number} x
+     * @param {number} y
+     * @param {number} r
+     * @param {number} g
+     * @param {number} b
+     * @param {number} a
+     * @returns {boolean}
+     */
+    set(x, y, r, g, b, a) {
+        const ret = wasm.rgbabuffer_set(this.__wbg_ptr, x, y, r, g, b, a);
+        return ret !== 0;
+    }
+    /**
+     * @returns {RgbaBuffer}
+     */
+    clone() {
+        const ret = wasm.rgbabuffer_clone(this.__wbg_ptr);
+        return RgbaBuffer.__wrap(ret);
+    }
+    /**
+     * @returns {number}
+     */
+    width() {
+        const ret = wasm.rgbabuffer_width(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @returns {number}
+     */
+    height() {
+        const ret = wasm.rgbabuffer_height(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * @param {number} width
+     * @param {number} height
+     * @param {Uint8Array} buf
+     * @returns {RgbaBuffer}
+     */
+    static fromRaw(width, height, buf) {
+        const ptr0 = passArray8ToWasm0(buf, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.rgbabuffer_fromRaw(width, height, ptr0, len0);
+        if (ret[2]) {
+            throw takeFromExternrefTable0(ret[1]);
+        }
+        return RgbaBuffer.__wrap(ret[0]);
+    }
+    /**
+     * @returns {boolean}
+     */
+    is_empty() {
+        const ret = wasm.rgbabuffer_is_empty(this.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * @returns {Uint8ClampedArray}
+     */
+    data() {
+        const ret = wasm.rgbabuffer_data(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * @param {number} width
+     * @param {number} height
+     * @param {Uint8Array} webp_buf
+     * @returns {RgbaBuffer}
+     */
+    static fromWebp(width, height, webp_buf) {
+        const ptr0 = passArray8ToWasm0(webp_buf, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.rgbabuffer_fromWebp(width, height, ptr0, len0);
+        if (ret[2]) {
+            throw takeFromExternrefTable0(ret[1]);
+        }
+        return RgbaBuffer.__wrap(ret[0]);
+    }
+    /**
+     * @param {number} x
+     * @param {number} y
+     * @returns {boolean}
+     */
+    isInBounds(x, y) {
+        const ret = wasm.rgbabuffer_isInBounds(this.__wbg_ptr, x, y);
+        return ret !== 0;
+    }
+    /**
+     * @param {number} idx
+     * @returns {Array<any>}
+     */
+    indexGet(idx) {
+        const ret = wasm.rgbabuffer_indexGet(this.__wbg_ptr, idx);
+        return ret;
+    }
+    /**
+     * @param {number} idx
+     * @param {number} r
+     * @param {number} g
+     * @param {number} b
+     * @param {number} a
+     * @returns {boolean}
+     */
+    indexSet(idx, r, g, b, a) {
+        const ret = wasm.rgbabuffer_indexSet(this.__wbg_ptr, idx, r, g, b, a);
+        return ret !== 0;
+    }
+    /**
+     * @param {number} rect_x
+     * @param {number} rect_y
+     * @param {number} rect_width
+     * @param {number} rect_height
+     * @param {Uint8Array} data
+     * @returns {boolean}
+     */
+    writeRect(rect_x, rect_y, rect_width, rect_height, data) {
+        const ptr0 = passArray8ToWasm0(data, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.rgbabuffer_writeRect(this.__wbg_ptr, rect_x, rect_y, rect_width, rect_height, ptr0, len0);
+        return ret !== 0;
+    }
+    /**
+     * @param {Uint32Array} coords
+     * @param {Uint8Array} colors
+     * @returns {boolean}
+     */
+    writePixels(coords, colors) {
+        const ptr0 = passArray32ToWasm0(coords, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ptr1 = passArray8ToWasm0(colors, wasm.__wbindgen_malloc);
+        const len1 = WASM_VECTOR_LEN;
+        const ret = wasm.rgbabuffer_writePixels(this.__wbg_ptr, ptr0, len0, ptr1, len1);
+        return ret !== 0;
+    }
+    /**
+     * @param {number} rect_x
+     * @param {number} rect_y
+     * @param {number} rect_width
+     * @param {number} rect_height
+     * @returns {Uint8Array}
+     */
+    readRect(rect_x, rect_y, rect_width, rect_height) {
+        const ret = wasm.rgbabuffer_readRect(this.__wbg_ptr, rect_x, rect_y, rect_width, rect_height);
+        var v1 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
+        return v1;
+    }
+    /**
+     * Swap the tiles stored in a tile patch with the current contents.
+     * Returns the tile patch to apply for the opposite direction (undo <-> redo),
+     * or an empty array if the patch is invalid for this buffer.
+     * @param {Uint8Array} encoded
+     * @returns {Uint8Array}
+     */
+    swapTiles(encoded) {
+        const ptr0 = passArray8ToWasm0(encoded, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.rgbabuffer_swapTiles(this.__wbg_ptr, ptr0, len0);
+        var v2 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
+        return v2;
+    }
+    /**
+     * Capture the current contents of the given tiles (row-major linear indices) as a tile patch.
+     * @param {number} tile_size
+     * @param {Uint32Array} tile_indices
+     * @returns {Uint8Array}
+     */
+    captureTiles(tile_size, tile_indices) {
+        const ptr0 = passArray32ToWasm0(tile_indices, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.rgbabuffer_captureTiles(this.__wbg_ptr, tile_size, ptr0, len0);
+        var v2 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
+        return v2;
+    }
+    /**
+     * @param {Uint8Array} png_buffer
+     * @param {number} width
+     * @param {number} height
+     * @returns {boolean}
+     */
+    importPng(png_buffer, width, height) {
+        const ptr0 = passArray8ToWasm0(png_buffer, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.rgbabuffer_importPng(this.__wbg_ptr, ptr0, len0, width, height);
+        return ret !== 0;
+    }
+    /**
+     * @param {Uint8Array} raw
+     * @param {number} width
+     * @param {number} height
+     * @returns {boolean}
+     */
+    importRaw(raw, width, height) {
+        const ptr0 = passArray8ToWasm0(raw, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.rgbabuffer_importRaw(this.__wbg_ptr, ptr0, len0, width, height);
+        return ret !== 0;
+    }
+    /**
+     * @param {Uint8Array} webp_buffer
+     * @param {number} width
+     * @param {number} height
+     * @returns {boolean}
+     */
+    importWebp(webp_buffer, width, height) {
+        const ptr0 = passArray8ToWasm0(webp_buffer, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.rgbabuffer_importWebp(this.__wbg_ptr, ptr0, len0, width, height);
+        return ret !== 0;
+    }
+    /**
+     * @param {number} max_size
+     * @param {number} alpha_threshold
+     */
+    dustRemoval(max_size, alpha_threshold) {
+        wasm.rgbabuffer_dustRemoval(this.__wbg_ptr, max_size, alpha_threshold);
+    }
+    /**
+     * @param {number} radius
+     * @param {AlphaBlurMode} alpha_mode
+     */
+    gaussianBlur(radius, alpha_mode) {
+        wasm.rgbabuffer_gaussianBlur(this.__wbg_ptr, radius, alpha_mode);
+    }
+    /**
+     * @param {number} brightness
+     * @param {number} contrast
+     */
+    brightnessAndContrast(brightness, contrast) {
+        wasm.rgbabuffer_brightnessAndContrast(this.__wbg_ptr, brightness, contrast);
+    }
+    /**
+     * @param {SelectionMask} selection
+     * @returns {boolean}
+     */
+    invertInSelection(selection) {
+        _assertClass(selection, SelectionMask);
+        const ret = wasm.rgbabuffer_invertInSelection(this.__wbg_ptr, selection.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * @param {SelectionMask} selection
+     * @param {DitheringMode} mode
+     * @param {number} levels
+     * @param {number} strength
+     * @returns {boolean}
+     */
+    ditheringInSelection(selection, mode, levels, strength) {
+        _assertClass(selection, SelectionMask);
+        const ret = wasm.rgbabuffer_ditheringInSelection(this.__wbg_ptr, selection.__wbg_ptr, mode, levels, strength);
+        return ret !== 0;
+    }
+    /**
+     * @param {SelectionMask} selection
+     * @returns {boolean}
+     */
+    grayscaleInSelection(selection) {
+        _assertClass(selection, SelectionMask);
+        const ret = wasm.rgbabuffer_grayscaleInSelection(this.__wbg_ptr, selection.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * @param {SelectionMask} selection
+     * @param {number} levels
+     * @returns {boolean}
+     */
+    posterizeInSelection(selection, levels) {
+        _assertClass(selection, SelectionMask);
+        const ret = wasm.rgbabuffer_posterizeInSelection(this.__wbg_ptr, selection.__wbg_ptr, levels);
+        return ret !== 0;
+    }
+    /**
+     * @param {SelectionMask} selection
+     * @param {number} max_size
+     * @param {number} alpha_threshold
+     * @returns {boolean}
+     */
+    dustRemovalInSelection(selection, max_size, alpha_threshold) {
+        _assertClass(selection, SelectionMask);
+        const ret = wasm.rgbabuffer_dustRemovalInSelection(this.__wbg_ptr, selection.__wbg_ptr, max_size, alpha_threshold);
+        return ret !== 0;
+    }
+    /**
+     * @param {DustRemovalOption} options
+     */
+    dustRemovalWithOptions(options) {
+        _assertClass(options, DustRemovalOption);
+        wasm.rgbabuffer_dustRemovalWithOptions(this.__wbg_ptr, options.__wbg_ptr);
+    }
+    /**
+     * @param {SelectionMask} selection
+     * @param {number} radius
+     * @param {AlphaBlurMode} alpha_mode
+     * @returns {boolean}
+     */
+    gaussianBlurInSelection(selection, radius, alpha_mode) {
+        _assertClass(selection, SelectionMask);
+        const ret = wasm.rgbabuffer_gaussianBlurInSelection(this.__wbg_ptr, selection.__wbg_ptr, radius, alpha_mode);
+        return ret !== 0;
+    }
+    /**
+     * @param {SelectionMask} selection
+     * @param {number} brightness
+     * @param {number} contrast
+     * @returns {boolean}
+     */
+    brightnessAndContrastInSelection(selection, brightness, contrast) {
+        _assertClass(selection, SelectionMask);
+        const ret = wasm.rgbabuffer_brightnessAndContrastInSelection(this.__wbg_ptr, selection.__wbg_ptr, brightness, contrast);
+        return ret !== 0;
+    }
+    invert() {
+        wasm.rgbabuffer_invert(this.__wbg_ptr);
+    }
+    /**
+     * @param {DitheringMode} mode
+     * @param {number} levels
+     * @param {number} strength
+     */
+    dithering(mode, levels, strength) {
+        wasm.rgbabuffer_dithering(this.__wbg_ptr, mode, levels, strength);
+    }
+    grayscale() {
+        wasm.rgbabuffer_grayscale(this.__wbg_ptr);
+    }
+    /**
+     * @param {number} levels
+     */
+    posterize(levels) {
+        wasm.rgbabuffer_posterize(this.__wbg_ptr, levels);
+    }
+    /**
+     * @param {Uint8Array} source
+     * @param {number} source_width
+     * @param {number} source_height
+     * @param {number} offset_x
+     * @param {number} offset_y
+     * @param {number} scale_x
+     * @param {number} scale_y
+     * @param {number} rotate_deg
+     * @param {AntialiasMode} antialias_mode
+     * @param {boolean} flip_x
+     * @param {boolean} flip_y
+     */
+    blitFromRaw(source, source_width, source_height, offset_x, offset_y, scale_x, scale_y, rotate_deg, antialias_mode, flip_x, flip_y) {
+        const ptr0 = passArray8ToWasm0(source, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        wasm.rgbabuffer_blitFromRaw(this.__wbg_ptr, ptr0, len0, source_width, source_height, offset_x, offset_y, scale_x, scale_y, rotate_deg, antialias_mode, flip_x, flip_y);
+    }
+    /**
+     * @param {Uint8Array} mask
+     * @param {number} mask_width
+     * @param {number} mask_height
+     * @param {number} mask_offset_x
+     * @param {number} mask_offset_y
+     * @returns {Uint8Array}
+     */
+    cropWithMask(mask, mask_width, mask_height, mask_offset_x, mask_offset_y) {
+        const ptr0 = passArray8ToWasm0(mask, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.rgbabuffer_cropWithMask(this.__wbg_ptr, ptr0, len0, mask_width, mask_height, mask_offset_x, mask_offset_y);
+        var v2 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
+        return v2;
+    }
+    /**
+     * @param {Uint8Array} mask
+     * @param {number} mask_width
+     * @param {number} mask_height
+     * @param {number} mask_offset_x
+     * @param {number} mask_offset_y
+     * @returns {Uint8Array}
+     */
+    sliceWithMask(mask, mask_width, mask_height, mask_offset_x, mask_offset_y) {
+        const ptr0 = passArray8ToWasm0(mask, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.rgbabuffer_sliceWithMask(this.__wbg_ptr, ptr0, len0, mask_width, mask_height, mask_offset_x, mask_offset_y);
+        var v2 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
+        return v2;
+    }
+    /**
+     * @param {RgbaBuffer} source
+     * @param {number} offset_x
+     * @param {number} offset_y
+     * @param {number} scale_x
+     * @param {number} scale_y
+     * @param {number} rotate_deg
+     * @param {AntialiasMode} antialias_mode
+     * @param {boolean} flip_x
+     * @param {boolean} flip_y
+     */
+    blitFromBuffer(source, offset_x, offset_y, scale_x, scale_y, rotate_deg, antialias_mode, flip_x, flip_y) {
+        _assertClass(source, RgbaBuffer);
+        wasm.rgbabuffer_blitFromBuffer(this.__wbg_ptr, source.__wbg_ptr, offset_x, offset_y, scale_x, scale_y, rotate_deg, antialias_mode, flip_x, flip_y);
+    }
+    /**
+     * @param {SelectionMask} selection
+     * @param {number} offset_x
+     * @param {number} offset_y
+     * @returns {Uint8Array}
+     */
+    cropWithSelection(selection, offset_x, offset_y) {
+        _assertClass(selection, SelectionMask);
+        const ret = wasm.rgbabuffer_cropWithSelection(this.__wbg_ptr, selection.__wbg_ptr, offset_x, offset_y);
+        var v1 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
+        return v1;
+    }
+    /**
+     * @param {SelectionMask} selection
+     * @param {number} offset_x
+     * @param {number} offset_y
+     * @returns {Uint8Array}
+     */
+    sliceWithSelection(selection, offset_x, offset_y) {
+        _assertClass(selection, SelectionMask);
+        const ret = wasm.rgbabuffer_sliceWithSelection(this.__wbg_ptr, selection.__wbg_ptr, offset_x, offset_y);
+        var v1 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
+        return v1;
+    }
+    /**
+     * @param {Uint8Array} source
+     * @param {number} source_width
+     * @param {number} source_height
+     * @param {number} offset_x
+     * @param {number} offset_y
+     * @param {number} scale_x
+     * @param {number} scale_y
+     * @param {number} rotate_deg
+     * @param {PatchBufferRgbaOption} options
+     */
+    blitFromRawWithOptions(source, source_width, source_height, offset_x, offset_y, scale_x, scale_y, rotate_deg, options) {
+        const ptr0 = passArray8ToWasm0(source, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        _assertClass(options, PatchBufferRgbaOption);
+        wasm.rgbabuffer_blitFromRawWithOptions(this.__wbg_ptr, ptr0, len0, source_width, source_height, offset_x, offset_y, scale_x, scale_y, rotate_deg, options.__wbg_ptr);
+    }
+    /**
+     * @param {RgbaBuffer} source
+     * @param {number} offset_x
+     * @param {number} offset_y
+     * @param {number} scale_x
+     * @param {number} scale_y
+     * @param {number} rotate_deg
+     * @param {PatchBufferRgbaOption} options
+     */
+    blitFromBufferWithOptions(source, offset_x, offset_y, scale_x, scale_y, rotate_deg, options) {
+        _assertClass(source, RgbaBuffer);
+        _assertClass(options, PatchBufferRgbaOption);
+        wasm.rgbabuffer_blitFromBufferWithOptions(this.__wbg_ptr, source.__wbg_ptr, offset_x, offset_y, scale_x, scale_y, rotate_deg, options.__wbg_ptr);
+    }
+    /**
+     * Flood fill and report what changed, or undefined if the start point is out of bounds.
+     * `connectivity` defaults to `Four`.
+     * @param {number} start_x
+     * @param {number} start_y
+     * @param {number} fill_color_r
+     * @param {number} fill_color_g
+     * @param {number} fill_color_b
+     * @param {number} fill_color_a
+     * @param {number} threshold
+     * @param {Connectivity | null} [connectivity]
+     * @returns {FillResult | undefined}
+     */
+    floodFill(start_x, start_y, fill_color_r, fill_color_g, fill_color_b, fill_color_a, threshold, connectivity) {
+        const ret = wasm.rgbabuffer_floodFill(this.__wbg_ptr, start_x, start_y, fill_color_r, fill_color_g, fill_color_b, fill_color_a, threshold, isLikeNone(connectivity) ? 0 : connectivity);
+        return ret === 0 ? undefined : FillResult.__wrap(ret);
+    }
+    /**
+     * Fill the whole buffer with `tile` repeated.
+     * @param {RgbaBuffer} tile
+     * @param {PatternFillOption} options
+     * @returns {FillResult | undefined}
+     */
+    patternFill(tile, options) {
+        _assertClass(tile, RgbaBuffer);
+        _assertClass(options, PatternFillOption);
+        const ret = wasm.rgbabuffer_patternFill(this.__wbg_ptr, tile.__wbg_ptr, options.__wbg_ptr);
+        return ret === 0 ? undefined : FillResult.__wrap(ret);
+    }
+    /**
+     * Paint a gradient over the whole buffer.
+     * `stop_colors` holds four RGBA bytes per entry of `stop_positions` (0-1).
+     * Undefined if there are no stops.
+     * @param {Float32Array} stop_positions
+     * @param {Uint8Array} stop_colors
+     * @param {GradientFillOption} options
+     * @returns {FillResult | undefined}
+     */
+    gradientFill(stop_positions, stop_colors, options) {
+        const ptr0 = passArrayF32ToWasm0(stop_positions, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ptr1 = passArray8ToWasm0(stop_colors, wasm.__wbindgen_malloc);
+        const len1 = WASM_VECTOR_LEN;
+        _assertClass(options, GradientFillOption);
+        const ret = wasm.rgbabuffer_gradientFill(this.__wbg_ptr, ptr0, len0, ptr1, len1, options.__wbg_ptr);
+        return ret === 0 ? undefined : FillResult.__wrap(ret);
+    }
+    /**
+     * @param {number} fill_code
+     * @returns {boolean}
+     */
+    fillAllCodes(fill_code) {
+        const ret = wasm.rgbabuffer_fillAllCodes(this.__wbg_ptr, fill_code);
+        return ret !== 0;
+    }
+    /**
+     * Fill every pixel whose mask flag is non-zero and report what changed.
+     * @param {Uint8Array} mask
+     * @param {number} fill_color_r
+     * @param {number} fill_color_g
+     * @param {number} fill_color_b
+     * @param {number} fill_color_a
+     * @returns {FillResult}
+     */
+    fillMaskArea(mask, fill_color_r, fill_color_g, fill_color_b, fill_color_a) {
+        const ptr0 = passArray8ToWasm0(mask, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.rgbabuffer_fillMaskArea(this.__wbg_ptr, ptr0, len0, fill_color_r, fill_color_g, fill_color_b, fill_color_a);
+        return FillResult.__wrap(ret);
+    }
+    /**
+     * Fill the selection, fading out where its coverage is partial (premultiplied).
+     * The selection must match the buffer size.
+     * @param {SelectionMask} selection
+     * @param {number} fill_color_r
+     * @param {number} fill_color_g
+     * @param {number} fill_color_b
+     * @param {number} fill_color_a
+     * @returns {FillResult | undefined}
+     */
+    fillSelection(selection, fill_color_r, fill_color_g, fill_color_b, fill_color_a) {
+        _assertClass(selection, SelectionMask);
+        const ret = wasm.rgbabuffer_fillSelection(this.__wbg_ptr, selection.__wbg_ptr, fill_color_r, fill_color_g, fill_color_b, fill_color_a);
+        return ret === 0 ? undefined : FillResult.__wrap(ret);
+    }
+    /**
+     * @param {number} fill_color_r
+     * @param {number} fill_color_g
+     * @param {number} fill_color_b
+     * @param {number} fill_color_a
+     * @returns {boolean}
+     */
+    fillAllPixels(fill_color_r, fill_color_g, fill_color_b, fill_color_a) {
+        const ret = wasm.rgbabuffer_fillAllPixels(this.__wbg_ptr, fill_color_r, fill_color_g, fill_color_b, fill_color_a);
+        return ret !== 0;
+    }
+    /**
+     * Make fills copy the coverage of the pixels they changed into `FillResult.coverage`.
+     * Off by default, since the copy is as large as the changed area.
+     * @param {boolean} enabled
+     */
+    setFillCoverage(enabled) {
+        wasm.rgbabuffer_setFillCoverage(this.__wbg_ptr, enabled);
+    }
+    /**
+     * `floodFill` limited by a selection mask. Undefined if the start point is not allowed.
+     * `connectivity` defaults to `Four`.
+     * @param {number} start_x
+     * @param {number} start_y
+     * @param {number} fill_color_r
+     * @param {number} fill_color_g
+     * @param {number} fill_color_b
+     * @param {number} fill_color_a
+     * @param {number} threshold
+     * @param {Uint8Array} selection_mask
+     * @param {string} limit_mode
+     * @param {Connectivity | null} [connectivity]
+     * @returns {FillResult | undefined}
+     */
+    floodFillWithMask(start_x, start_y, fill_color_r, fill_color_g, fill_color_b, fill_color_a, threshold, selection_mask, limit_mode, connectivity) {
+        const ptr0 = passArray8ToWasm0(selection_mask, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ptr1 = passStringToWasm0(limit_mode, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len1 = WASM_VECTOR_LEN;
+        const ret = wasm.rgbabuffer_floodFillWithMask(this.__wbg_ptr, start_x, start_y, fill_color_r, fill_color_g, fill_color_b, fill_color_a, threshold, ptr0, len0, ptr1, len1, isLikeNone(connectivity) ? 0 : connectivity);
+        return ret === 0 ? undefined : FillResult.__wrap(ret);
+    }
+    /**
+     * Flood fill with a configurable color metric and alpha handling.
+     * @param {number} start_x
+     * @param {number} start_y
+     * @param {number} fill_color_r
+     * @param {number} fill_color_g
+     * @param {number} fill_color_b
+     * @param {number} fill_color_a
+     * @param {FloodFillOption} options
+     * @returns {FillResult | undefined}
+     */
+    floodFillWithOptions(start_x, start_y, fill_color_r, fill_color_g, fill_color_b, fill_color_a, options) {
+        _assertClass(options, FloodFillOption);
+        const ret = wasm.rgbabuffer_floodFillWithOptions(this.__wbg_ptr, start_x, start_y, fill_color_r, fill_color_g, fill_color_b, fill_color_a, options.__wbg_ptr);
+        return ret === 0 ? undefined : FillResult.__wrap(ret);
+    }
+    /**
+     * Bucket fill with `tile` instead of a solid color. The region is found like
+     * `floodFillWithOptions`.
+     * @param {number} start_x
+     * @param {number} start_y
+     * @param {RgbaBuffer} tile
+     * @param {FloodFillOption} fill_options
+     * @param {PatternFillOption} options
+     * @returns {FillResult | undefined}
+     */
+    floodFillWithPattern(start_x, start_y, tile, fill_options, options) {
+        _assertClass(tile, RgbaBuffer);
+        _assertClass(fill_options, FloodFillOption);
+        _assertClass(options, PatternFillOption);
+        const ret = wasm.rgbabuffer_floodFillWithPattern(this.__wbg_ptr, start_x, start_y, tile.__wbg_ptr, fill_options.__wbg_ptr, options.__wbg_ptr);
+        return ret === 0 ? undefined : FillResult.__wrap(ret);
+    }
+    /**
+     * Flood fill whose region is computed from `reference` (e.g. the line art layer or a
+     * merged composite) while the fill color is written into this buffer.
+     * Both buffers must have the same size.
+     * @param {RgbaBuffer} reference
+     * @param {number} start_x
+     * @param {number} start_y
+     * @param {number} fill_color_r
+     * @param {number} fill_color_g
+     * @param {number} fill_color_b
+     * @param {number} fill_color_a
+     * @param {FloodFillOption} options
+     * @returns {FillResult | undefined}
+     */
+    floodFillFromReference(reference, start_x, start_y, fill_color_r, fill_color_g, fill_color_b, fill_color_a, options) {
+        _assertClass(reference, RgbaBuffer);
+        _assertClass(options, FloodFillOption);
+        const ret = wasm.rgbabuffer_floodFillFromReference(this.__wbg_ptr, reference.__wbg_ptr, start_x, start_y, fill_color_r, fill_color_g, fill_color_b, fill_color_a, options.__wbg_ptr);
+        return ret === 0 ? undefined : FillResult.__wrap(ret);
+    }
+    /**
+     * `floodFillWithMask` limited by a `SelectionMask` of the buffer size.
+     * @param {number} start_x
+     * @param {number} start_y
+     * @param {number} fill_color_r
+     * @param {number} fill_color_g
+     * @param {number} fill_color_b
+     * @param {number} fill_color_a
+     * @param {number} threshold
+     * @param {SelectionMask} selection
+     * @param {string} limit_mode
+     * @param {Connectivity | null} [connectivity]
+     * @returns {FillResult | undefined}
+     */
+    floodFillWithSelection(start_x, start_y, fill_color_r, fill_color_g, fill_color_b, fill_color_a, threshold, selection, limit_mode, connectivity) {
+        _assertClass(selection, SelectionMask);
+        const ptr0 = passStringToWasm0(limit_mode, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.rgbabuffer_floodFillWithSelection(this.__wbg_ptr, start_x, start_y, fill_color_r, fill_color_g, fill_color_b, fill_color_a, threshold, selection.__wbg_ptr, ptr0, len0, isLikeNone(connectivity) ? 0 : connectivity);
+        return ret === 0 ? undefined : FillResult.__wrap(ret);
+    }
+    /**
+     * `patternFill` limited to a `SelectionMask` of the buffer size, fading at partial
+     * coverage.
+     * @param {RgbaBuffer} tile
+     * @param {SelectionMask} selection
+     * @param {PatternFillOption} options
+     * @returns {FillResult | undefined}
+     */
+    patternFillWithSelection(tile, selection, options) {
+        _assertClass(tile, RgbaBuffer);
+        _assertClass(selection, SelectionMask);
+        _assertClass(options, PatternFillOption);
+        const ret = wasm.rgbabuffer_patternFillWithSelection(this.__wbg_ptr, tile.__wbg_ptr, selection.__wbg_ptr, options.__wbg_ptr);
+        return ret === 0 ? undefined : FillResult.__wrap(ret);
+    }
+    /**
+     * `fillMaskArea` where mask values are applied according to `mode`.
+     * @param {Uint8Array} mask
+     * @param {number} fill_color_r
+     * @param {number} fill_color_g
+     * @param {number} fill_color_b
+     * @param {number} fill_color_a
+     * @param {CoverageMode} mode
+     * @returns {FillResult}
+     */
+    fillMaskAreaWithCoverage(mask, fill_color_r, fill_color_g, fill_color_b, fill_color_a, mode) {
+        const ptr0 = passArray8ToWasm0(mask, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.rgbabuffer_fillMaskAreaWithCoverage(this.__wbg_ptr, ptr0, len0, fill_color_r, fill_color_g, fill_color_b, fill_color_a, mode);
+        return FillResult.__wrap(ret);
+    }
+    /**
+     * `gradientFill` limited to a `SelectionMask` of the buffer size, fading at partial
+     * coverage.
+     * @param {Float32Array} stop_positions
+     * @param {Uint8Array} stop_colors
+     * @param {SelectionMask} selection
+     * @param {GradientFillOption} options
+     * @returns {FillResult | undefined}
+     */
+    gradientFillWithSelection(stop_positions, stop_colors, selection, options) {
+        const ptr0 = passArrayF32ToWasm0(stop_positions, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ptr1 = passArray8ToWasm0(stop_colors, wasm.__wbindgen_malloc);
+        const len1 = WASM_VECTOR_LEN;
+        _assertClass(selection, SelectionMask);
+        _assertClass(options, GradientFillOption);
+        const ret = wasm.rgbabuffer_gradientFillWithSelection(this.__wbg_ptr, ptr0, len0, ptr1, len1, selection.__wbg_ptr, options.__wbg_ptr);
+        return ret === 0 ? undefined : FillResult.__wrap(ret);
+    }
+    /**
+     * `floodFillWithPattern` limited by a `SelectionMask` of the buffer size.
+     * @param {number} start_x
+     * @param {number} start_y
+     * @param {RgbaBuffer} tile
+     * @param {SelectionMask} selection
+     * @param {string} limit_mode
+     * @param {FloodFillOption} fill_options
+     * @param {PatternFillOption} options
+     * @returns {FillResult | undefined}
+     */
+    floodFillWithPatternAndSelection(start_x, start_y, tile, selection, limit_mode, fill_options, options) {
+        _assertClass(tile, RgbaBuffer);
+        _assertClass(selection, SelectionMask);
+        const ptr0 = passStringToWasm0(limit_mode, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        _assertClass(fill_options, FloodFillOption);
+        _assertClass(options, PatternFillOption);
+        const ret = wasm.rgbabuffer_floodFillWithPatternAndSelection(this.__wbg_ptr, start_x, start_y, tile.__wbg_ptr, selection.__wbg_ptr, ptr0, len0, fill_options.__wbg_ptr, options.__wbg_ptr);
+        return ret === 0 ? undefined : FillResult.__wrap(ret);
+    }
+    /**
+     * `floodFillWithOptions` limited by a `SelectionMask` of the buffer size.
+     * @param {number} start_x
+     * @param {number} start_y
+     * @param {number} fill_color_r
+     * @param {number} fill_color_g
+     * @param {number} fill_color_b
+     * @param {number} fill_color_a
+     * @param {SelectionMask} selection
+     * @param {string} limit_mode
+     * @param {FloodFillOption} options
+     * @returns {FillResult | undefined}
+     */
+    floodFillWithSelectionAndOptions(start_x, start_y, fill_color_r, fill_color_g, fill_color_b, fill_color_a, selection, limit_mode, options) {
+        _assertClass(selection, SelectionMask);
+        const ptr0 = passStringToWasm0(limit_mode, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        _assertClass(options, FloodFillOption);
+        const ret = wasm.rgbabuffer_floodFillWithSelectionAndOptions(this.__wbg_ptr, start_x, start_y, fill_color_r, fill_color_g, fill_color_b, fill_color_a, selection.__wbg_ptr, ptr0, len0, options.__wbg_ptr);
+        return ret === 0 ? undefined : FillResult.__wrap(ret);
+    }
+    /**
+     * `floodFillFromReference` limited by a `SelectionMask` of the buffer size.
+     * @param {RgbaBuffer} reference
+     * @param {number} start_x
+     * @param {number} start_y
+     * @param {number} fill_color_r
+     * @param {number} fill_color_g
+     * @param {number} fill_color_b
+     * @param {number} fill_color_a
+     * @param {SelectionMask} selection
+     * @param {string} limit_mode
+     * @param {FloodFillOption} options
+     * @returns {FillResult | undefined}
+     */
+    floodFillFromReferenceWithSelection(reference, start_x, start_y, fill_color_r, fill_color_g, fill_color_b, fill_color_a, selection, limit_mode, options) {
+        _assertClass(reference, RgbaBuffer);
+        _assertClass(selection, SelectionMask);
+        const ptr0 = passStringToWasm0(limit_mode, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len0 = WASM_VECTOR_LEN;
+        _assertClass(options, FloodFillOption);
+        const ret = wasm.rgbabuffer_floodFillFromReferenceWithSelection(this.__wbg_ptr, reference.__wbg_ptr, start_x, start_y, fill_color_r, fill_color_g, fill_color_b, fill_color_a, selection.__wbg_ptr, ptr0, len0, options.__wbg_ptr);
+        return ret === 0 ? undefined : FillResult.__wrap(ret);
+    }
+    /**
+     * Select pixels similar to the one at (x, y). The buffer is left untouched.
+     * An out-of-bounds start point yields an empty selection. `connectivity` defaults to `Four`.
+     * @param {number} x
+     * @param {number} y
+     * @param {number} tolerance
+     * @param {boolean} contiguous
+     * @param {boolean} antialias
+     * @param {Connectivity | null} [connectivity]
+     * @returns {SelectionMask}
+     */
+    magicWand(x, y, tolerance, contiguous, antialias, connectivity) {
+        const ret = wasm.rgbabuffer_magicWand(this.__wbg_ptr, x, y, tolerance, contiguous, antialias, isLikeNone(connectivity) ? 0 : connectivity);
+        return SelectionMask.__wrap(ret);
+    }
+    /**
+     * Run the magic wand and combine its result into an existing selection of the buffer size.
+     * `connectivity` defaults to `Four`.
+     * @param {SelectionMask} selection
+     * @param {number} x
+     * @param {number} y
+     * @param {number} tolerance
+     * @param {boolean} contiguous
+     * @param {boolean} antialias
+     * @param {SelectionOp} op
+     * @param {Connectivity | null} [connectivity]
+     * @returns {boolean}
+     */
+    magicWandInto(selection, x, y, tolerance, contiguous, antialias, op, connectivity) {
+        _assertClass(selection, SelectionMask);
+        const ret = wasm.rgbabuffer_magicWandInto(this.__wbg_ptr, selection.__wbg_ptr, x, y, tolerance, contiguous, antialias, op, isLikeNone(connectivity) ? 0 : connectivity);
+        return ret !== 0;
+    }
+    /**
+     * `magicWand` with a configurable color metric; `options.threshold` is the tolerance.
+     * @param {number} x
+     * @param {number} y
+     * @param {boolean} contiguous
+     * @param {boolean} antialias
+     * @param {FloodFillOption} options
+     * @returns {SelectionMask}
+     */
+    magicWandWithOptions(x, y, contiguous, antialias, options) {
+        _assertClass(options, FloodFillOption);
+        const ret = wasm.rgbabuffer_magicWandWithOptions(this.__wbg_ptr, x, y, contiguous, antialias, options.__wbg_ptr);
+        return SelectionMask.__wrap(ret);
+    }
+    /**
+     * @param {SelectionMask} selection
+     * @param {number} x
+     * @param {number} y
+     * @param {boolean} contiguous
+     * @param {boolean} antialias
+     * @param {FloodFillOption} options
+     * @param {SelectionOp} op
+     * @returns {boolean}
+     */
+    magicWandIntoWithOptions(selection, x, y, contiguous, antialias, options, op) {
+        _assertClass(selection, SelectionMask);
+        _assertClass(options, FloodFillOption);
+        const ret = wasm.rgbabuffer_magicWandIntoWithOptions(this.__wbg_ptr, selection.__wbg_ptr, x, y, contiguous, antialias, options.__wbg_ptr, op);
+        return ret !== 0;
+    }
+    /**
+     * Stop journaling and drop the transaction in progress.
+     */
+    endJournal() {
+        wasm.rgbabuffer_endJournal(this.__wbg_ptr);
+    }
+    /**
+     * Start journaling writes into tile snapshots.
+     * Any transaction in progress is discarded.
+     * @param {number} tile_size
+     * @param {number} budget_bytes
+     * @returns {boolean}
+     */
+    beginJournal(tile_size, budget_bytes) {
+        const ret = wasm.rgbabuffer_beginJournal(this.__wbg_ptr, tile_size, budget_bytes);
+        return ret !== 0;
+    }
+    /**
+     * @returns {boolean}
+     */
+    isJournaling() {
+        const ret = wasm.rgbabuffer_isJournaling(this.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * Bytes currently held by tile snapshots, raw or encoded. Never above the budget.
+     * @returns {number}
+     */
+    journalBytes() {
+        const ret = wasm.rgbabuffer_journalBytes(this.__wbg_ptr);
+        return ret >>> 0;
+    }
+    /**
+     * Close the current transaction and return its undo record, then start a new one.
+     * The record is a tile patch: apply it with `swapTiles`, which returns the redo record.
+     * Returns an empty array if nothing changed or the transaction overflowed.
+     * @returns {Uint8Array}
+     */
+    commitJournal() {
+        const ret = wasm.rgbabuffer_commitJournal(this.__wbg_ptr);
+        var v1 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
+        return v1;
+    }
+    /**
+     * Whether the transaction in progress outgrew the budget. Its undo record is lost,
+     * so `commitJournal` returns an empty array and the caller has to record the change
+     * another way.
+     * @returns {boolean}
+     */
+    journalOverflowed() {
+        const ret = wasm.rgbabuffer_journalOverflowed(this.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * Circle around (center_x, center_y). Aliased circles with an odd diameter need a
+     * center on a pixel center (e.g. 10.5).
+     * @param {number} center_x
+     * @param {number} center_y
+     * @param {number} radius
+     * @param {number} color_r
+     * @param {number} color_g
+     * @param {number} color_b
+     * @param {number} color_a
+     * @param {ShapeOption} options
+     * @returns {FillResult | undefined}
+     */
+    drawCircle(center_x, center_y, radius, color_r, color_g, color_b, color_a, options) {
+        _assertClass(options, ShapeOption);
+        const ret = wasm.rgbabuffer_drawCircle(this.__wbg_ptr, center_x, center_y, radius, color_r, color_g, color_b, color_a, options.__wbg_ptr);
+        return ret === 0 ? undefined : FillResult.__wrap(ret);
+    }
+    /**
+     * Ellipse inscribed in the box (x, y, width, height).
+     * @param {number} x
+     * @param {number} y
+     * @param {number} width
+     * @param {number} height
+     * @param {number} color_r
+     * @param {number} color_g
+     * @param {number} color_b
+     * @param {number} color_a
+     * @param {ShapeOption} options
+     * @returns {FillResult | undefined}
+     */
+    drawEllipse(x, y, width, height, color_r, color_g, color_b, color_a, options) {
+        _assertClass(options, ShapeOption);
+        const ret = wasm.rgbabuffer_drawEllipse(this.__wbg_ptr, x, y, width, height, color_r, color_g, color_b, color_a, options.__wbg_ptr);
+        return ret === 0 ? undefined : FillResult.__wrap(ret);
+    }
+    /**
+     * Closed polygon through `points` ([x0, y0, x1, y1, ...]).
+     * @param {Float32Array} points
+     * @param {number} color_r
+     * @param {number} color_g
+     * @param {number} color_b
+     * @param {number} color_a
+     * @param {ShapeOption} options
+     * @returns {FillResult | undefined}
+     */
+    drawPolygon(points, color_r, color_g, color_b, color_a, options) {
+        const ptr0 = passArrayF32ToWasm0(points, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        _assertClass(options, ShapeOption);
+        const ret = wasm.rgbabuffer_drawPolygon(this.__wbg_ptr, ptr0, len0, color_r, color_g, color_b, color_a, options.__wbg_ptr);
+        return ret === 0 ? undefined : FillResult.__wrap(ret);
+    }
+    /**
+     * @param {number} x
+     * @param {number} y
+     * @param {number} width
+     * @param {number} height
+     * @param {number} radius
+     * @param {number} color_r
+     * @param {number} color_g
+     * @param {number} color_b
+     * @param {number} color_a
+     * @param {ShapeOption} options
+     * @returns {FillResult | undefined}
+     */
+    drawRoundedRect(x, y, width, height, radius, color_r, color_g, color_b, color_a, options) {
+        _assertClass(options, ShapeOption);
+        const ret = wasm.rgbabuffer_drawRoundedRect(this.__wbg_ptr, x, y, width, height, radius, color_r, color_g, color_b, color_a, options.__wbg_ptr);
+        return ret === 0 ? undefined : FillResult.__wrap(ret);
+    }
+    /**
+     * @param {number} x0
+     * @param {number} y0
+     * @param {number} x1
+     * @param {number} y1
+     * @param {number} color_r
+     * @param {number} color_g
+     * @param {number} color_b
+     * @param {number} color_a
+     * @param {ShapeOption} options
+     * @returns {FillResult | undefined}
+     */
+    drawLine(x0, y0, x1, y1, color_r, color_g, color_b, color_a, options) {
+        _assertClass(options, ShapeOption);
+        const ret = wasm.rgbabuffer_drawLine(this.__wbg_ptr, x0, y0, x1, y1, color_r, color_g, color_b, color_a, options.__wbg_ptr);
+        return ret === 0 ? undefined : FillResult.__wrap(ret);
+    }
+    /**
+     * Fill and/or stroke `path` as set in `options`.
+     * @param {Path} path
+     * @param {number} color_r
+     * @param {number} color_g
+     * @param {number} color_b
+     * @param {number} color_a
+     * @param {ShapeOption} options
+     * @returns {FillResult | undefined}
+     */
+    drawPath(path, color_r, color_g, color_b, color_a, options) {
+        _assertClass(path, Path);
+        _assertClass(options, ShapeOption);
+        const ret = wasm.rgbabuffer_drawPath(this.__wbg_ptr, path.__wbg_ptr, color_r, color_g, color_b, color_a, options.__wbg_ptr);
+        return ret === 0 ? undefined : FillResult.__wrap(ret);
+    }
+    /**
+     * @param {number} x
+     * @param {number} y
+     * @param {number} width
+     * @param {number} height
+     * @param {number} color_r
+     * @param {number} color_g
+     * @param {number} color_b
+     * @param {number} color_a
+     * @param {ShapeOption} options
+     * @returns {FillResult | undefined}
+     */
+    drawRect(x, y, width, height, color_r, color_g, color_b, color_a, options) {
+        _assertClass(options, ShapeOption);
+        const ret = wasm.rgbabuffer_drawRect(this.__wbg_ptr, x, y, width, height, color_r, color_g, color_b, color_a, options.__wbg_ptr);
+        return ret === 0 ? undefined : FillResult.__wrap(ret);
+    }
+    /**
+     * @returns {Uint8Array}
+     */
+    exportPng() {
+        const ret = wasm.rgbabuffer_exportPng(this.__wbg_ptr);
+        var v1 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
+        return v1;
+    }
+    /**
+     * @returns {Uint8Array}
+     */
+    exportWebp() {
+        const ret = wasm.rgbabuffer_exportWebp(this.__wbg_ptr);
+        var v1 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
+        return v1;
+    }
+    /**
+     * @param {number} new_width
+     * @param {number} new_height
+     * @param {number} src_origin_x
+     * @param {number} src_origin_y
+     * @param {number} dest_origin_x
+     * @param {number} dest_origin_y
+     */
+    resizeWithOrigins(new_width, new_height, src_origin_x, src_origin_y, dest_origin_x, dest_origin_y) {
+        wasm.rgbabuffer_resizeWithOrigins(this.__wbg_ptr, new_width, new_height, src_origin_x, src_origin_y, dest_origin_x, dest_origin_y);
+    }
+    /**
+     * @param {number} new_width
+     * @param {number} new_height
+     */
+    resize(new_width, new_height) {
+        wasm.rgbabuffer_resize(this.__wbg_ptr, new_width, new_height);
+    }
+}
+if (Symbol.dispose) RgbaBuffer.prototype[Symbol.dispose] = RgbaBuffer.prototype.free;
+
+const SelectionMaskFinalization = (typeof FinalizationRegistry === 'undefined')
+    ? { register: () => {}, unregister: () => {} }
+    : new FinalizationRegistry(ptr => wasm.__wbg_selectionmask_free(ptr >>> 0, 1));
+/**
+ * Selection with 8-bit coverage per pixel (0 = unselected, 255 = fully selected).
+ */
+export class SelectionMask {
+
+    static __wrap(ptr) {
+        ptr = ptr >>> 0;
+        const obj = Object.create(SelectionMask.prototype);
+        obj.__wbg_ptr = ptr;
+        SelectionMaskFinalization.register(obj, obj.__wbg_ptr, obj);
+        return obj;
+    }
+
+    __destroy_into_raw() {
+        const ptr = this.__wbg_ptr;
+        this.__wbg_ptr = 0;
+        SelectionMaskFinalization.unregister(this);
+        return ptr;
+    }
+
+    free() {
+        const ptr = this.__destroy_into_raw();
+        wasm.__wbg_selectionmask_free(ptr, 0);
+    }
+    /**
+     * Selection from a free-hand or polygon lasso, see `lasso_mask`.
+     * @param {number} width
+     * @param {number} height
+     * @param {Float32Array} points
+     * @param {FillRule} rule
+     * @param {boolean} antialias
+     * @returns {SelectionMask}
+     */
+    static fromLasso(width, height, points, rule, antialias) {
+        const ptr0 = passArrayF32ToWasm0(points, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.selectionmask_fromLasso(width, height, ptr0, len0, rule, antialias);
+        return SelectionMask.__wrap(ret);
+    }
+    /**
+     * @param {SelectionMask} other
+     * @returns {boolean}
+     */
+    xor(other) {
+        _assertClass(other, SelectionMask);
+        const ret = wasm.selectionmask_xor(this.__wbg_ptr, other.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * Combine `other` into this selection according to `op`.
+     * @param {SelectionMask} other
+     * @param {SelectionOp} op
+     * @returns {boolean}
+     */
+    apply(other, op) {
+        _assertClass(other, SelectionMask);
+        const ret = wasm.selectionmask_apply(this.__wbg_ptr, other.__wbg_ptr, op);
+        return ret !== 0;
+    }
+    /**
+     * @param {SelectionMask} other
+     * @returns {boolean}
+     */
+    union(other) {
+        _assertClass(other, SelectionMask);
+        const ret = wasm.selectionmask_union(this.__wbg_ptr, other.__wbg_ptr);
+        return ret !== 0;
+    }
+    invert() {
+        wasm.selectionmask_invert(this.__wbg_ptr);
+    }
+    /**
+     * @param {SelectionMask} other
+     * @returns {boolean}
+     */
+    subtract(other) {
+        _assertClass(other, SelectionMask);
+        const ret = wasm.selectionmask_subtract(this.__wbg_ptr, other.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * @param {SelectionMask} other
+     * @returns {boolean}
+     */
+    intersect(other) {
+        _assertClass(other, SelectionMask);
+        const ret = wasm.selectionmask_intersect(this.__wbg_ptr, other.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * @param {number} radius
+     * @param {MaskShape} shape
+     */
+    grow(radius, shape) {
+        wasm.selectionmask_grow(this.__wbg_ptr, radius, shape);
+    }
+    /**
+     * @param {number} size
+     * @param {MaskShape} shape
+     */
+    border(size, shape) {
+        wasm.selectionmask_border(this.__wbg_ptr, size, shape);
+    }
+    /**
+     * @param {number} radius
+     * @param {MaskShape} shape
+     */
+    shrink(radius, shape) {
+        wasm.selectionmask_shrink(this.__wbg_ptr, radius, shape);
+    }
     /**
-     * Random dithering (white noise)
+     * @param {number} radius
      */
-    Random: 0, "0": "Random",
+    smooth(radius) {
+        wasm.selectionmask_smooth(this.__wbg_ptr, radius);
+    }
     /**
-     * Floyd-Steinberg error diffusion
+     * @param {number} radius
      */
-    ErrorDiffusion: 1, "1": "ErrorDiffusion",
+    feather(radius) {
+        wasm.selectionmask_feather(this.__wbg_ptr, radius);
+    }
     /**
-     * Ordered dithering using Bayer matrix
+     * Import a flag mask where any non-zero value means fully selected.
+     * @param {number} width
+     * @param {number} height
+     * @param {Uint8Array} buf
+     * @returns {SelectionMask}
      */
-    Ordered: 2, "2": "Ordered",
-});
-
-const BrightnessContrastOptionFinalization = (typeof FinalizationRegistry === 'undefined')
-    ? { register: () => {}, unregister: () => {} }
-    : new FinalizationRegistry(ptr => wasm.__wbg_brightnesscontrastoption_free(ptr >>> 0, 1));
-
-export class BrightnessContrastOption {
-
-    __destroy_into_raw() {
-        const ptr = this.__wbg_ptr;
-        this.__wbg_ptr = 0;
-        BrightnessContrastOptionFinalization.unregister(this);
-        return ptr;
+    static fromFlags(width, height, buf) {
+        const ptr0 = passArray8ToWasm0(buf, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.selectionmask_fromFlags(width, height, ptr0, len0);
+        if (ret[2]) {
+            throw takeFromExternrefTable0(ret[1]);
+        }
+        return SelectionMask.__wrap(ret[0]);
     }
-
-    free() {
-        const ptr = this.__destroy_into_raw();
-        wasm.__wbg_brightnesscontrastoption_free(ptr, 0);
+    selectAll() {
+        wasm.selectionmask_selectAll(this.__wbg_ptr);
     }
     /**
-     * Brightness adjustment (-100.0 to 100.0, 0.0 = no change)
+     * Coverage at a pixel, 0 outside the mask.
+     * @param {number} x
+     * @param {number} y
      * @returns {number}
      */
-    get brightness() {
-        const ret = wasm.__wbg_get_brightnesscontrastoption_brightness(this.__wbg_ptr);
+    get(x, y) {
+        const ret = wasm.selectionmask_get(this.__wbg_ptr, x, y);
         return ret;
     }
     /**
-     * Brightness adjustment (-100.0 to 100.0, 0.0 = no change)
-     * @param {number} arg0
+     * Create an empty selection.
+     * @param {number} width
+     * @param {number} height
      */
-    set brightness(arg0) {
-        wasm.__wbg_set_brightnesscontrastoption_brightness(this.__wbg_ptr, arg0);
+    constructor(width, height) {
+        const ret = wasm.selectionmask_new(width, height);
+        this.__wbg_ptr = ret >>> 0;
+        SelectionMaskFinalization.register(this, this.__wbg_ptr, this);
+        return this;
     }
     /**
-     * Contrast adjustment (-100.0 to 100.0, 0.0 = no change)
-     * @returns {number}
+     * @param {number} x
+     * @param {number} y
+     * @param {number} coverage
+     * @returns {boolean}
      */
-    get contrast() {
-        const ret = wasm.__wbg_get_brightnesscontrastoption_contrast(this.__wbg_ptr);
-        return ret;
+    set(x, y, coverage) {
+        const ret = wasm.selectionmask_set(this.__wbg_ptr, x, y, coverage);
+        return ret !== 0;
+    }
+    clear() {
+        wasm.selectionmask_clear(this.__wbg_ptr);
     }
     /**
-     * Contrast adjustment (-100.0 to 100.0, 0.0 = no change)
-     * @param {number} arg0
+     * @returns {SelectionMask}
      */
-    set contrast(arg0) {
-        wasm.__wbg_set_brightnesscontrastoption_contrast(this.__wbg_ptr, arg0);
+    clone() {
+        const ret = wasm.selectionmask_clone(this.__wbg_ptr);
+        return SelectionMask.__wrap(ret);
     }
     /**
-     * @param {number} brightness
-     * @param {number} contrast
+     * @returns {number}
      */
-    constructor(brightness, contrast) {
-        const ret = wasm.brightnesscontrastoption_new(brightness, contrast);
-        this.__wbg_ptr = ret >>> 0;
-        BrightnessContrastOptionFinalization.register(this, this.__wbg_ptr, this);
-        return this;
+    width() {
+        const ret = wasm.historyreader_offset(this.__wbg_ptr);
+        return ret >>> 0;
     }
-}
-if (Symbol.dispose) BrightnessContrastOption.prototype[Symbol.dispose] = BrightnessContrastOption.prototype.free;
-
-const DitheringOptionFinalization = (typeof FinalizationRegistry === 'undefined')
-    ? { register: () => {}, unregister: () => {} }
-    : new FinalizationRegistry(ptr => wasm.__wbg_ditheringoption_free(ptr >>> 0, 1));
-
-export class DitheringOption {
-
-    __destroy_into_raw() {
-        const ptr = this.__wbg_ptr;
-        this.__wbg_ptr = 0;
-        DitheringOptionFinalization.unregister(this);
-        return ptr;
+    /**
+     * @returns {number}
+     */
+    height() {
+        const ret = wasm.selectionmask_height(this.__wbg_ptr);
+        return ret >>> 0;
     }
-
-    free() {
-        const ptr = this.__destroy_into_raw();
-        wasm.__wbg_ditheringoption_free(ptr, 0);
+    /**
+     * Copy of the coverage bytes.
+     * @returns {Uint8Array}
+     */
+    toRaw() {
+        const ret = wasm.selectionmask_toRaw(this.__wbg_ptr);
+        var v1 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
+        return v1;
     }
     /**
-     * @param {DitheringMode} mode
-     * @param {number} levels
-     * @param {number} strength
+     * Import coverage bytes (0-255) as they are.
+     * @param {number} width
+     * @param {number} height
+     * @param {Uint8Array} buf
+     * @returns {SelectionMask}
      */
-    constructor(mode, levels, strength) {
-        const ret = wasm.ditheringoption_new(mode, levels, strength);
-        this.__wbg_ptr = ret >>> 0;
-        DitheringOptionFinalization.register(this, this.__wbg_ptr, this);
-        return this;
+    static fromRaw(width, height, buf) {
+        const ptr0 = passArray8ToWasm0(buf, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.selectionmask_fromRaw(width, height, ptr0, len0);
+        if (ret[2]) {
+            throw takeFromExternrefTable0(ret[1]);
+        }
+        return SelectionMask.__wrap(ret[0]);
     }
     /**
-     * Dithering mode to use
-     * @returns {DitheringMode}
+     * True when no pixel has any coverage.
+     * @returns {boolean}
      */
-    get mode() {
-        const ret = wasm.__wbg_get_ditheringoption_mode(this.__wbg_ptr);
-        return ret;
+    isEmpty() {
+        const ret = wasm.selectionmask_isEmpty(this.__wbg_ptr);
+        return ret !== 0;
     }
     /**
-     * Dithering mode to use
-     * @param {DitheringMode} arg0
+     * Copy as a flag mask (1 = selected, 0 = not) for the slice-based APIs.
+     * @returns {Uint8Array}
      */
-    set mode(arg0) {
-        wasm.__wbg_set_ditheringoption_mode(this.__wbg_ptr, arg0);
+    toFlags() {
+        const ret = wasm.selectionmask_toFlags(this.__wbg_ptr);
+        var v1 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
+        return v1;
     }
     /**
-     * Number of levels per channel (2-32, affects quantization)
-     * @returns {number}
+     * @returns {Uint8Array}
      */
-    get levels() {
-        const ret = wasm.__wbg_get_ditheringoption_levels(this.__wbg_ptr);
-        return ret >>> 0;
+    data() {
+        const ret = wasm.selectionmask_data(this.__wbg_ptr);
+        return ret;
     }
     /**
-     * Number of levels per channel (2-32, affects quantization)
-     * @param {number} arg0
+     * @param {number} x
+     * @param {number} y
+     * @returns {boolean}
      */
-    set levels(arg0) {
-        wasm.__wbg_set_ditheringoption_levels(this.__wbg_ptr, arg0);
+    isInBounds(x, y) {
+        const ret = wasm.selectionmask_isInBounds(this.__wbg_ptr, x, y);
+        return ret !== 0;
     }
     /**
-     * Strength of dithering effect (0.0-1.0)
-     * @returns {number}
+     * Combine the fill and/or stroke of `path` into this selection according to `op`.
+     * A selection set on `options` limits the path coverage first.
+     * Returns false if that selection does not match this mask's size.
+     * @param {Path} path
+     * @param {ShapeOption} options
+     * @param {SelectionOp} op
+     * @returns {boolean}
      */
-    get strength() {
-        const ret = wasm.__wbg_get_ditheringoption_strength(this.__wbg_ptr);
-        return ret;
+    drawPath(path, options, op) {
+        _assertClass(path, Path);
+        _assertClass(options, ShapeOption);
+        const ret = wasm.selectionmask_drawPath(this.__wbg_ptr, path.__wbg_ptr, options.__wbg_ptr, op);
+        return ret !== 0;
     }
     /**
-     * Strength of dithering effect (0.0-1.0)
-     * @param {number} arg0
+     * Bounding box of the covered pixels as [x, y, width, height].
+     * Returns an empty array if nothing is selected.
+     * @returns {Uint32Array}
      */
-    set strength(arg0) {
-        wasm.__wbg_set_ditheringoption_strength(this.__wbg_ptr, arg0);
+    boundBox() {
+        const ret = wasm.selectionmask_boundBox(this.__wbg_ptr);
+        var v1 = getArrayU32FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+        return v1;
+    }
+    /**
+     * Move the selection by whole pixels. Coverage shifted past the edges is dropped.
+     * @param {number} dx
+     * @param {number} dy
+     */
+    translate(dx, dy) {
+        wasm.selectionmask_translate(this.__wbg_ptr, dx, dy);
     }
 }
-if (Symbol.dispose) DitheringOption.prototype[Symbol.dispose] = DitheringOption.prototype.free;
+if (Symbol.dispose) SelectionMask.prototype[Symbol.dispose] = SelectionMask.prototype.free;
 
-const DustRemovalOptionFinalization = (typeof FinalizationRegistry === 'undefined')
+const ShapeOptionFinalization = (typeof FinalizationRegistry === 'undefined')
     ? { register: () => {}, unregister: () => {} }
-    : new FinalizationRegistry(ptr => wasm.__wbg_dustremovaloption_free(ptr >>> 0, 1));
-
-export class DustRemovalOption {
+    : new FinalizationRegistry(ptr => wasm.__wbg_shapeoption_free(ptr >>> 0, 1));
+/**
+ * How a shape is drawn. `selection` is set through `setSelection` and limits the shape
+ * like in the fill functions.
+ */
+export class ShapeOption {
 
     __destroy_into_raw() {
         const ptr = this.__wbg_ptr;
         this.__wbg_ptr = 0;
-        DustRemovalOptionFinalization.unregister(this);
+        ShapeOptionFinalization.unregister(this);
         return ptr;
     }
 
     free() {
         const ptr = this.__destroy_into_raw();
-        wasm.__wbg_dustremovaloption_free(ptr, 0);
+        wasm.__wbg_shapeoption_free(ptr, 0);
     }
     /**
-     * @param {number} max_size
-     * @param {number} alpha_threshold
+     * Limit drawing to `selection` (copied; it must match the target buffer size).
+     * @param {SelectionMask} selection
      */
-    constructor(max_size, alpha_threshold) {
-        const ret = wasm.dustremovaloption_new(max_size, alpha_threshold);
+    setSelection(selection) {
+        _assertClass(selection, SelectionMask);
+        wasm.shapeoption_setSelection(this.__wbg_ptr, selection.__wbg_ptr);
+    }
+    clearSelection() {
+        wasm.shapeoption_clearSelection(this.__wbg_ptr);
+    }
+    /**
+     * `fill_rule`, `line_join`, `line_cap` and `miter_limit` start as `NonZero`, `Miter`,
+     * `Butt` and 4 and are set as properties.
+     * @param {boolean} fill
+     * @param {number} stroke_width
+     * @param {boolean} antialias
+     */
+    constructor(fill, stroke_width, antialias) {
+        const ret = wasm.shapeoption_new(fill, stroke_width, antialias);
         this.__wbg_ptr = ret >>> 0;
-        DustRemovalOptionFinalization.register(this, this.__wbg_ptr, this);
+        ShapeOptionFinalization.register(this, this.__wbg_ptr, this);
         return this;
     }
     /**
-     * Maximum size of pixel groups to remove (1-100, groups with this many pixels or fewer will be removed)
-     * @returns {number}
+     * Paint the interior
+     * @returns {boolean}
      */
-    get max_size() {
-        const ret = wasm.__wbg_get_dustremovaloption_max_size(this.__wbg_ptr);
-        return ret >>> 0;
+    get fill() {
+        const ret = wasm.__wbg_get_shapeoption_fill(this.__wbg_ptr);
+        return ret !== 0;
     }
     /**
-     * Maximum size of pixel groups to remove (1-100, groups with this many pixels or fewer will be removed)
-     * @param {number} arg0
+     * Paint the interior
+     * @param {boolean} arg0
      */
-    set max_size(arg0) {
-        wasm.__wbg_set_dustremovaloption_max_size(this.__wbg_ptr, arg0);
+    set fill(arg0) {
+        wasm.__wbg_set_shapeoption_fill(this.__wbg_ptr, arg0);
     }
     /**
-     * Minimum alpha threshold to consider a pixel as non-transparent (0-255)
+     * Outline width in pixels (0 = no outline). Rectangles and ellipses draw it inside
+     * their bounds, polygons and paths center it on the outline, and lines always use
+     * at least 1.
      * @returns {number}
      */
-    get alpha_threshold() {
-        const ret = wasm.__wbg_get_dustremovaloption_alpha_threshold(this.__wbg_ptr);
+    get stroke_width() {
+        const ret = wasm.__wbg_get_shapeoption_stroke_width(this.__wbg_ptr);
         return ret;
     }
     /**
-     * Minimum alpha threshold to consider a pixel as non-transparent (0-255)
+     * Outline width in pixels (0 = no outline). Rectangles and ellipses draw it inside
+     * their bounds, polygons and paths center it on the outline, and lines always use
+     * at least 1.
      * @param {number} arg0
      */
-    set alpha_threshold(arg0) {
-        wasm.__wbg_set_dustremovaloption_alpha_threshold(this.__wbg_ptr, arg0);
+    set stroke_width(arg0) {
+        wasm.__wbg_set_shapeoption_stroke_width(this.__wbg_ptr, arg0);
     }
-}
-if (Symbol.dispose) DustRemovalOption.prototype[Symbol.dispose] = DustRemovalOption.prototype.free;
-
-const GaussianBlurOptionFinalization = (typeof FinalizationRegistry === 'undefined')
-    ? { register: () => {}, unregister: () => {} }
-    : new FinalizationRegistry(ptr => wasm.__wbg_gaussianbluroption_free(ptr >>> 0, 1));
-
-export class GaussianBlurOption {
-
-    __destroy_into_raw() {
-        const ptr = this.__wbg_ptr;
-        this.__wbg_ptr = 0;
-        GaussianBlurOptionFinalization.unregister(this);
-        return ptr;
+    /**
+     * Antialiased coverage instead of aliased pixel-art output
+     * @returns {boolean}
+     */
+    get antialias() {
+        const ret = wasm.__wbg_get_shapeoption_antialias(this.__wbg_ptr);
+        return ret !== 0;
     }
-
-    free() {
-        const ptr = this.__destroy_into_raw();
-        wasm.__wbg_gaussianbluroption_free(ptr, 0);
+    /**
+     * Antialiased coverage instead of aliased pixel-art output
+     * @param {boolean} arg0
+     */
+    set antialias(arg0) {
+        wasm.__wbg_set_shapeoption_antialias(this.__wbg_ptr, arg0);
     }
     /**
-     * @param {number} radius
-     * @param {AlphaBlurMode} alpha_mode
+     * Inside test for polygon and path fills
+     * @returns {FillRule}
      */
-    constructor(radius, alpha_mode) {
-        const ret = wasm.gaussianbluroption_new(radius, alpha_mode);
-        this.__wbg_ptr = ret >>> 0;
-        GaussianBlurOptionFinalization.register(this, this.__wbg_ptr, this);
-        return this;
+    get fill_rule() {
+        const ret = wasm.__wbg_get_shapeoption_fill_rule(this.__wbg_ptr);
+        return ret;
     }
     /**
-     * Blur radius (higher values create stronger blur effect)
-     * @returns {number}
+     * Inside test for polygon and path fills
+     * @param {FillRule} arg0
      */
-    get radius() {
-        const ret = wasm.__wbg_get_gaussianbluroption_radius(this.__wbg_ptr);
+    set fill_rule(arg0) {
+        wasm.__wbg_set_shapeoption_fill_rule(this.__wbg_ptr, arg0);
+    }
+    /**
+     * Corner shape of antialiased polygon and path outlines
+     * @returns {LineJoin}
+     */
+    get line_join() {
+        const ret = wasm.__wbg_get_shapeoption_line_join(this.__wbg_ptr);
         return ret;
     }
     /**
-     * Blur radius (higher values create stronger blur effect)
-     * @param {number} arg0
+     * Corner shape of antialiased polygon and path outlines
+     * @param {LineJoin} arg0
      */
-    set radius(arg0) {
-        wasm.__wbg_set_gaussianbluroption_radius(this.__wbg_ptr, arg0);
+    set line_join(arg0) {
+        wasm.__wbg_set_shapeoption_line_join(this.__wbg_ptr, arg0);
     }
     /**
-     * How to handle the alpha channel
-     * @returns {AlphaBlurMode}
+     * End shape of open path outlines
+     * @returns {LineCap}
      */
-    get alpha_mode() {
-        const ret = wasm.__wbg_get_gaussianbluroption_alpha_mode(this.__wbg_ptr);
+    get line_cap() {
+        const ret = wasm.__wbg_get_shapeoption_line_cap(this.__wbg_ptr);
+        return ret;
+    }
+    /**
+     * End shape of open path outlines
+     * @param {LineCap} arg0
+     */
+    set line_cap(arg0) {
+        wasm.__wbg_set_shapeoption_line_cap(this.__wbg_ptr, arg0);
+    }
+    /**
+     * Longest miter, in stroke widths, before a `Miter` join is beveled
+     * @returns {number}
+     */
+    get miter_limit() {
+        const ret = wasm.__wbg_get_shapeoption_miter_limit(this.__wbg_ptr);
         return ret;
     }
     /**
-     * How to handle the alpha channel
-     * @param {AlphaBlurMode} arg0
+     * Longest miter, in stroke widths, before a `Miter` join is beveled
+     * @param {number} arg0
      */
-    set alpha_mode(arg0) {
-        wasm.__wbg_set_gaussianbluroption_alpha_mode(this.__wbg_ptr, arg0);
+    set miter_limit(arg0) {
+        wasm.__wbg_set_shapeoption_miter_limit(this.__wbg_ptr, arg0);
     }
 }
-if (Symbol.dispose) GaussianBlurOption.prototype[Symbol.dispose] = GaussianBlurOption.prototype.free;
+if (Symbol.dispose) ShapeOption.prototype[Symbol.dispose] = ShapeOption.prototype.free;
 
-const PatchBufferRgbaOptionFinalization = (typeof FinalizationRegistry === 'undefined')
+const StrokeFinalization = (typeof FinalizationRegistry === 'undefined')
     ? { register: () => {}, unregister: () => {} }
-    : new FinalizationRegistry(ptr => wasm.__wbg_patchbufferrgbaoption_free(ptr >>> 0, 1));
-
-export class PatchBufferRgbaOption {
+    : new FinalizationRegistry(ptr => wasm.__wbg_stroke_free(ptr >>> 0, 1));
+/**
+ * One brush stroke drawn into an `RgbaBuffer` point by point.
+ *
+ * Dabs are placed along the pixel line between consecutive points with pressure, size
+ * and opacity interpolated. Within a stroke each pixel keeps the highest coverage it
+ * received, so overlapping dabs do not build up opacity. The stroke remembers the color
+ * every touched pixel had before it started, which is the undo diff of the stroke.
+ */
+export class Stroke {
 
     __destroy_into_raw() {
         const ptr = this.__wbg_ptr;
         this.__wbg_ptr = 0;
-        PatchBufferRgbaOptionFinalization.unregister(this);
+        StrokeFinalization.unregister(this);
         return ptr;
     }
 
     free() {
         const ptr = this.__destroy_into_raw();
-        wasm.__wbg_patchbufferrgbaoption_free(ptr, 0);
+        wasm.__wbg_stroke_free(ptr, 0);
     }
     /**
-     * @returns {AntialiasMode}
-     */
-    get antialias_mode() {
-        const ret = wasm.__wbg_get_patchbufferrgbaoption_antialias_mode(this.__wbg_ptr);
-        return ret;
-    }
-    /**
-     * @param {AntialiasMode} arg0
-     */
-    set antialias_mode(arg0) {
-        wasm.__wbg_set_patchbufferrgbaoption_antialias_mode(this.__wbg_ptr, arg0);
-    }
-    /**
-     * @returns {boolean}
+     * Number of pixels the stroke has touched.
+     * @returns {number}
      */
-    get flip_x() {
-        const ret = wasm.__wbg_get_patchbufferrgbaoption_flip_x(this.__wbg_ptr);
-        return ret !== 0;
+    changedPixels() {
+        const ret = wasm.stroke_changedPixels(this.__wbg_ptr);
+        return ret >>> 0;
     }
     /**
-     * @param {boolean} arg0
+     * @param {BrushStamp} stamp
+     * @param {number} color_r
+     * @param {number} color_g
+     * @param {number} color_b
+     * @param {number} color_a
+     * @param {StrokeOption} options
      */
-    set flip_x(arg0) {
-        wasm.__wbg_set_patchbufferrgbaoption_flip_x(this.__wbg_ptr, arg0);
+    constructor(stamp, color_r, color_g, color_b, color_a, options) {
+        _assertClass(stamp, BrushStamp);
+        _assertClass(options, StrokeOption);
+        const ret = wasm.stroke_new(stamp.__wbg_ptr, color_r, color_g, color_b, color_a, options.__wbg_ptr);
+        this.__wbg_ptr = ret >>> 0;
+        StrokeFinalization.register(this, this.__wbg_ptr, this);
+        return this;
     }
     /**
-     * @returns {boolean}
+     * Flattened [x, y, color] triples of every touched pixel with the color it had before
+     * the stroke, packed as (a << 24) | (r << 16) | (g << 8) | b like `rgbaToPackedU32`.
+     * @returns {Uint32Array}
      */
-    get flip_y() {
-        const ret = wasm.__wbg_get_patchbufferrgbaoption_flip_y(this.__wbg_ptr);
-        return ret !== 0;
+    pixels() {
+        const ret = wasm.stroke_pixels(this.__wbg_ptr);
+        var v1 = getArrayU32FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+        return v1;
     }
     /**
-     * @param {boolean} arg0
+     * Draw up to the next input point.
+     *
+     * `size` scales the stamp (1 = as given) and `opacity` (0-1) scales its coverage;
+     * `pressure` (0-1) scales either according to the options.
+     * Returns the bounds written by this call as [x, y, width, height], or an empty
+     * array if nothing changed.
+     * @param {RgbaBuffer} buffer
+     * @param {number} x
+     * @param {number} y
+     * @param {number} pressure
+     * @param {number} size
+     * @param {number} opacity
+     * @returns {Uint32Array}
      */
-    set flip_y(arg0) {
-        wasm.__wbg_set_patchbufferrgbaoption_flip_y(this.__wbg_ptr, arg0);
+    addPoint(buffer, x, y, pressure, size, opacity) {
+        _assertClass(buffer, RgbaBuffer);
+        const ret = wasm.stroke_addPoint(this.__wbg_ptr, buffer.__wbg_ptr, x, y, pressure, size, opacity);
+        var v1 = getArrayU32FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+        return v1;
     }
     /**
-     * @param {AntialiasMode} antialias_mode
-     * @param {boolean} flip_x
-     * @param {boolean} flip_y
+     * Bounding box of the touched pixels as [x, y, width, height] (all zero if none).
+     * @returns {Uint32Array}
      */
-    constructor(antialias_mode, flip_x, flip_y) {
-        const ret = wasm.patchbufferrgbaoption_new(antialias_mode, flip_x, flip_y);
-        this.__wbg_ptr = ret >>> 0;
-        PatchBufferRgbaOptionFinalization.register(this, this.__wbg_ptr, this);
-        return this;
+    boundBox() {
+        const ret = wasm.stroke_boundBox(this.__wbg_ptr);
+        var v1 = getArrayU32FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+        return v1;
     }
 }
-if (Symbol.dispose) PatchBufferRgbaOption.prototype[Symbol.dispose] = PatchBufferRgbaOption.prototype.free;
+if (Symbol.dispose) Stroke.prototype[Symbol.dispose] = Stroke.prototype.free;
 
-const PosterizeOptionFinalization = (typeof FinalizationRegistry === 'undefined')
+const StrokeOptionFinalization = (typeof FinalizationRegistry === 'undefined')
     ? { register: () => {}, unregister: () => {} }
-    : new FinalizationRegistry(ptr => wasm.__wbg_posterizeoption_free(ptr >>> 0, 1));
+    : new FinalizationRegistry(ptr => wasm.__wbg_strokeoption_free(ptr >>> 0, 1));
 
-export class PosterizeOption {
+export class StrokeOption {
 
     __destroy_into_raw() {
         const ptr = this.__wbg_ptr;
         this.__wbg_ptr = 0;
-        PosterizeOptionFinalization.unregister(this);
+        StrokeOptionFinalization.unregister(this);
         return ptr;
     }
 
     free() {
         const ptr = this.__destroy_into_raw();
-        wasm.__wbg_posterizeoption_free(ptr, 0);
+        wasm.__wbg_strokeoption_free(ptr, 0);
     }
     /**
-     * @param {number} levels
+     * `pixel_perfect` starts disabled and is set as a property.
+     * @param {number} spacing
+     * @param {boolean} pressure_size
+     * @param {boolean} pressure_opacity
      */
-    constructor(levels) {
-        const ret = wasm.posterizeoption_new(levels);
+    constructor(spacing, pressure_size, pressure_opacity) {
+        const ret = wasm.strokeoption_new(spacing, pressure_size, pressure_opacity);
         this.__wbg_ptr = ret >>> 0;
-        PosterizeOptionFinalization.register(this, this.__wbg_ptr, this);
+        StrokeOptionFinalization.register(this, this.__wbg_ptr, this);
         return this;
     }
     /**
-     * Number of levels per channel (1-32, higher values preserve more detail)
+     * Distance between dabs as a fraction of the dab size (0 = a dab on every pixel)
      * @returns {number}
      */
-    get levels() {
-        const ret = wasm.__wbg_get_dustremovaloption_max_size(this.__wbg_ptr);
-        return ret >>> 0;
+    get spacing() {
+        const ret = wasm.__wbg_get_strokeoption_spacing(this.__wbg_ptr);
+        return ret;
     }
     /**
-     * Number of levels per channel (1-32, higher values preserve more detail)
+     * Distance between dabs as a fraction of the dab size (0 = a dab on every pixel)
      * @param {number} arg0
      */
-    set levels(arg0) {
-        wasm.__wbg_set_dustremovaloption_max_size(this.__wbg_ptr, arg0);
+    set spacing(arg0) {
+        wasm.__wbg_set_strokeoption_spacing(this.__wbg_ptr, arg0);
+    }
+    /**
+     * Pressure scales the dab size
+     * @returns {boolean}
+     */
+    get pressure_size() {
+        const ret = wasm.__wbg_get_strokeoption_pressure_size(this.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * Pressure scales the dab size
+     * @param {boolean} arg0
+     */
+    set pressure_size(arg0) {
+        wasm.__wbg_set_strokeoption_pressure_size(this.__wbg_ptr, arg0);
+    }
+    /**
+     * Pressure scales the dab opacity
+     * @returns {boolean}
+     */
+    get pressure_opacity() {
+        const ret = wasm.__wbg_get_strokeoption_pressure_opacity(this.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * Pressure scales the dab opacity
+     * @param {boolean} arg0
+     */
+    set pressure_opacity(arg0) {
+        wasm.__wbg_set_strokeoption_pressure_opacity(this.__wbg_ptr, arg0);
+    }
+    /**
+     * Drop the corner pixel of every L-shaped step so 1px lines stay one pixel thin.
+     * Only affects 1x1 dabs.
+     * @returns {boolean}
+     */
+    get pixel_perfect() {
+        const ret = wasm.__wbg_get_strokeoption_pixel_perfect(this.__wbg_ptr);
+        return ret !== 0;
+    }
+    /**
+     * Drop the corner pixel of every L-shaped step so 1px lines stay one pixel thin.
+     * Only affects 1x1 dabs.
+     * @param {boolean} arg0
+     */
+    set pixel_perfect(arg0) {
+        wasm.__wbg_set_strokeoption_pixel_perfect(this.__wbg_ptr, arg0);
     }
 }
-if (Symbol.dispose) PosterizeOption.prototype[Symbol.dispose] = PosterizeOption.prototype.free;
+if (Symbol.dispose) StrokeOption.prototype[Symbol.dispose] = StrokeOption.prototype.free;
 
-const RgbaBufferFinalization = (typeof FinalizationRegistry === 'undefined')
+const TiledRgbaBufferFinalization = (typeof FinalizationRegistry === 'undefined')
     ? { register: () => {}, unregister: () => {} }
-    : new FinalizationRegistry(ptr => wasm.__wbg_rgbabuffer_free(ptr >>> 0, 1));
-
-export class RgbaBuffer {
+    : new FinalizationRegistry(ptr => wasm.__wbg_tiledrgbabuffer_free(ptr >>> 0, 1));
+/**
+ * Sparse RGBA storage for very large canvases.
+ *
+ * The canvas is split into square tiles. Uniform tiles keep a single color, and only
+ * tiles that actually hold differing pixels allocate memory.
+ */
+export class TiledRgbaBuffer {
 
     static __wrap(ptr) {
         ptr = ptr >>> 0;
-        const obj = Object.create(RgbaBuffer.prototype);
+        const obj = Object.create(TiledRgbaBuffer.prototype);
         obj.__wbg_ptr = ptr;
-        RgbaBufferFinalization.register(obj, obj.__wbg_ptr, obj);
+        TiledRgbaBufferFinalization.register(obj, obj.__wbg_ptr, obj);
         return obj;
     }
 
     __destroy_into_raw() {
         const ptr = this.__wbg_ptr;
         this.__wbg_ptr = 0;
-        RgbaBufferFinalization.unregister(this);
+        TiledRgbaBufferFinalization.unregister(this);
         return ptr;
     }
 
     free() {
         const ptr = this.__destroy_into_raw();
-        wasm.__wbg_rgbabuffer_free(ptr, 0);
-    }
-    /**
-     * @param {number} max_size
-     * @param {number} alpha_threshold
-     */
-    dustRemoval(max_size, alpha_threshold) {
-        wasm.rgbabuffer_dustRemoval(this.__wbg_ptr, max_size, alpha_threshold);
-    }
-    /**
-     * @param {number} radius
-     * @param {AlphaBlurMode} alpha_mode
-     */
-    gaussianBlur(radius, alpha_mode) {
-        wasm.rgbabuffer_gaussianBlur(this.__wbg_ptr, radius, alpha_mode);
-    }
-    /**
-     * @param {number} brightness
-     * @param {number} contrast
-     */
-    brightnessAndContrast(brightness, contrast) {
-        wasm.rgbabuffer_brightnessAndContrast(this.__wbg_ptr, brightness, contrast);
-    }
-    invert() {
-        wasm.rgbabuffer_invert(this.__wbg_ptr);
-    }
-    /**
-     * @param {DitheringMode} mode
-     * @param {number} levels
-     * @param {number} strength
-     */
-    dithering(mode, levels, strength) {
-        wasm.rgbabuffer_dithering(this.__wbg_ptr, mode, levels, strength);
-    }
-    grayscale() {
-        wasm.rgbabuffer_grayscale(this.__wbg_ptr);
-    }
-    /**
-     * @param {number} levels
-     */
-    posterize(levels) {
-        wasm.rgbabuffer_posterize(this.__wbg_ptr, levels);
+        wasm.__wbg_tiledrgbabuffer_free(ptr, 0);
     }
     /**
      * @param {Uint8Array} source
@@ -975,39 +4798,40 @@ export class RgbaBuffer {
     blitFromRaw(source, source_width, source_height, offset_x, offset_y, scale_x, scale_y, rotate_deg, antialias_mode, flip_x, flip_y) {
         const ptr0 = passArray8ToWasm0(source, wasm.__wbindgen_malloc);
         const len0 = WASM_VECTOR_LEN;
-        wasm.rgbabuffer_blitFromRaw(this.__wbg_ptr, ptr0, len0, source_width, source_height, offset_x, offset_y, scale_x, scale_y, rotate_deg, antialias_mode, flip_x, flip_y);
+        wasm.tiledrgbabuffer_blitFromRaw(this.__wbg_ptr, ptr0, len0, source_width, source_height, offset_x, offset_y, scale_x, scale_y, rotate_deg, antialias_mode, flip_x, flip_y);
     }
     /**
-     * @param {Uint8Array} mask
-     * @param {number} mask_width
-     * @param {number} mask_height
-     * @param {number} mask_offset_x
-     * @param {number} mask_offset_y
-     * @returns {Uint8Array}
+     * @param {RgbaBuffer} source
+     * @param {number} offset_x
+     * @param {number} offset_y
+     * @param {number} scale_x
+     * @param {number} scale_y
+     * @param {number} rotate_deg
+     * @param {AntialiasMode} antialias_mode
+     * @param {boolean} flip_x
+     * @param {boolean} flip_y
      */
-    cropWithMask(mask, mask_width, mask_height, mask_offset_x, mask_offset_y) {
-        const ptr0 = passArray8ToWasm0(mask, wasm.__wbindgen_malloc);
-        const len0 = WASM_VECTOR_LEN;
-        const ret = wasm.rgbabuffer_cropWithMask(this.__wbg_ptr, ptr0, len0, mask_width, mask_height, mask_offset_x, mask_offset_y);
-        var v2 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
-        wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
-        return v2;
+    blitFromBuffer(source, offset_x, offset_y, scale_x, scale_y, rotate_deg, antialias_mode, flip_x, flip_y) {
+        _assertClass(source, RgbaBuffer);
+        wasm.tiledrgbabuffer_blitFromBuffer(this.__wbg_ptr, source.__wbg_ptr, offset_x, offset_y, scale_x, scale_y, rotate_deg, antialias_mode, flip_x, flip_y);
     }
     /**
-     * @param {Uint8Array} mask
-     * @param {number} mask_width
-     * @param {number} mask_height
-     * @param {number} mask_offset_x
-     * @param {number} mask_offset_y
-     * @returns {Uint8Array}
+     * Blit only touches the tiles under the transformed source bounds.
+     * @param {Uint8Array} source
+     * @param {number} source_width
+     * @param {number} source_height
+     * @param {number} offset_x
+     * @param {number} offset_y
+     * @param {number} scale_x
+     * @param {number} scale_y
+     * @param {number} rotate_deg
+     * @param {PatchBufferRgbaOption} options
      */
-    sliceWithMask(mask, mask_width, mask_height, mask_offset_x, mask_offset_y) {
-        const ptr0 = passArray8ToWasm0(mask, wasm.__wbindgen_malloc);
+    blitFromRawWithOptions(source, source_width, source_height, offset_x, offset_y, scale_x, scale_y, rotate_deg, options) {
+        const ptr0 = passArray8ToWasm0(source, wasm.__wbindgen_malloc);
         const len0 = WASM_VECTOR_LEN;
-        const ret = wasm.rgbabuffer_sliceWithMask(this.__wbg_ptr, ptr0, len0, mask_width, mask_height, mask_offset_x, mask_offset_y);
-        var v2 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
-        wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
-        return v2;
+        _assertClass(options, PatchBufferRgbaOption);
+        wasm.tiledrgbabuffer_blitFromRawWithOptions(this.__wbg_ptr, ptr0, len0, source_width, source_height, offset_x, offset_y, scale_x, scale_y, rotate_deg, options.__wbg_ptr);
     }
     /**
      * @param {RgbaBuffer} source
@@ -1016,15 +4840,110 @@ export class RgbaBuffer {
      * @param {number} scale_x
      * @param {number} scale_y
      * @param {number} rotate_deg
-     * @param {AntialiasMode} antialias_mode
-     * @param {boolean} flip_x
-     * @param {boolean} flip_y
+     * @param {PatchBufferRgbaOption} options
+     */
+    blitFromBufferWithOptions(source, offset_x, offset_y, scale_x, scale_y, rotate_deg, options) {
+        _assertClass(source, RgbaBuffer);
+        _assertClass(options, PatchBufferRgbaOption);
+        wasm.tiledrgbabuffer_blitFromBufferWithOptions(this.__wbg_ptr, source.__wbg_ptr, offset_x, offset_y, scale_x, scale_y, rotate_deg, options.__wbg_ptr);
+    }
+    /**
+     * Flood fill from a seed point and report what changed, or undefined if the start
+     * point is out of bounds (see `RgbaBuffer.floodFill`). `connectivity` defaults to `Four`.
+     *
+     * The fill reads pixels tile by tile and keeps its walk state only for the tiles it
+     * reaches, so it never materializes the canvas. Only changed tiles are written back.
+     * @param {number} start_x
+     * @param {number} start_y
+     * @param {number} fill_color_r
+     * @param {number} fill_color_g
+     * @param {number} fill_color_b
+     * @param {number} fill_color_a
+     * @param {number} threshold
+     * @param {Connectivity | null} [connectivity]
+     * @returns {FillResult | undefined}
+     */
+    floodFill(start_x, start_y, fill_color_r, fill_color_g, fill_color_b, fill_color_a, threshold, connectivity) {
+        const ret = wasm.tiledrgbabuffer_floodFill(this.__wbg_ptr, start_x, start_y, fill_color_r, fill_color_g, fill_color_b, fill_color_a, threshold, isLikeNone(connectivity) ? 0 : connectivity);
+        return ret === 0 ? undefined : FillResult.__wrap(ret);
+    }
+    /**
+     * Fill pixels whose canvas-sized mask value is non-zero and report what changed.
+     * Fully covered tiles become uniform and untouched tiles stay unallocated.
+     * @param {Uint8Array} mask
+     * @param {number} fill_color_r
+     * @param {number} fill_color_g
+     * @param {number} fill_color_b
+     * @param {number} fill_color_a
+     * @returns {FillResult}
+     */
+    fillMaskArea(mask, fill_color_r, fill_color_g, fill_color_b, fill_color_a) {
+        const ptr0 = passArray8ToWasm0(mask, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.tiledrgbabuffer_fillMaskArea(this.__wbg_ptr, ptr0, len0, fill_color_r, fill_color_g, fill_color_b, fill_color_a);
+        return FillResult.__wrap(ret);
+    }
+    /**
+     * Fill the whole canvas. Every tile collapses to a single color.
+     * @param {number} fill_color_r
+     * @param {number} fill_color_g
+     * @param {number} fill_color_b
+     * @param {number} fill_color_a
+     * @returns {boolean}
+     */
+    fillAllPixels(fill_color_r, fill_color_g, fill_color_b, fill_color_a) {
+        const ret = wasm.tiledrgbabuffer_fillAllPixels(this.__wbg_ptr, fill_color_r, fill_color_g, fill_color_b, fill_color_a);
+        return ret !== 0;
+    }
+    /**
+     * Make fills copy the coverage of the pixels they changed into `FillResult.coverage`
+     * (see `RgbaBuffer.setFillCoverage`).
+     * @param {boolean} enabled
+     */
+    setFillCoverage(enabled) {
+        wasm.tiledrgbabuffer_setFillCoverage(this.__wbg_ptr, enabled);
+    }
+    /**
+     * `floodFill` limited by a selection mask (see `RgbaBuffer.floodFillWithMask`).
+     * @param {number} start_x
+     * @param {number} start_y
+     * @param {number} fill_color_r
+     * @param {number} fill_color_g
+     * @param {number} fill_color_b
+     * @param {number} fill_color_a
+     * @param {number} threshold
+     * @param {Uint8Array} selection_mask
+     * @param {string} limit_mode
+     * @param {Connectivity | null} [connectivity]
+     * @returns {FillResult | undefined}
+     */
+    floodFillWithMask(start_x, start_y, fill_color_r, fill_color_g, fill_color_b, fill_color_a, threshold, selection_mask, limit_mode, connectivity) {
+        const ptr0 = passArray8ToWasm0(selection_mask, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ptr1 = passStringToWasm0(limit_mode, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
+        const len1 = WASM_VECTOR_LEN;
+        const ret = wasm.tiledrgbabuffer_floodFillWithMask(this.__wbg_ptr, start_x, start_y, fill_color_r, fill_color_g, fill_color_b, fill_color_a, threshold, ptr0, len0, ptr1, len1, isLikeNone(connectivity) ? 0 : connectivity);
+        return ret === 0 ? undefined : FillResult.__wrap(ret);
+    }
+    /**
+     * `fillMaskArea` where mask values are applied according to `mode`
+     * (see `RgbaBuffer.fillMaskAreaWithCoverage`).
+     * @param {Uint8Array} mask
+     * @param {number} fill_color_r
+     * @param {number} fill_color_g
+     * @param {number} fill_color_b
+     * @param {number} fill_color_a
+     * @param {CoverageMode} mode
+     * @returns {FillResult}
      */
-    blitFromBuffer(source, offset_x, offset_y, scale_x, scale_y, rotate_deg, antialias_mode, flip_x, flip_y) {
-        _assertClass(source, RgbaBuffer);
-        wasm.rgbabuffer_blitFromBuffer(this.__wbg_ptr, source.__wbg_ptr, offset_x, offset_y, scale_x, scale_y, rotate_deg, antialias_mode, flip_x, flip_y);
+    fillMaskAreaWithCoverage(mask, fill_color_r, fill_color_g, fill_color_b, fill_color_a, mode) {
+        const ptr0 = passArray8ToWasm0(mask, wasm.__wbindgen_malloc);
+        const len0 = WASM_VECTOR_LEN;
+        const ret = wasm.tiledrgbabuffer_fillMaskAreaWithCoverage(this.__wbg_ptr, ptr0, len0, fill_color_r, fill_color_g, fill_color_b, fill_color_a, mode);
+        return FillResult.__wrap(ret);
     }
     /**
+     * Write a rectangle of pixels. Tiles that end up uniform are stored as a single color.
      * @param {number} rect_x
      * @param {number} rect_y
      * @param {number} rect_width
@@ -1035,7 +4954,7 @@ export class RgbaBuffer {
     writeRect(rect_x, rect_y, rect_width, rect_height, data) {
         const ptr0 = passArray8ToWasm0(data, wasm.__wbindgen_malloc);
         const len0 = WASM_VECTOR_LEN;
-        const ret = wasm.rgbabuffer_writeRect(this.__wbg_ptr, rect_x, rect_y, rect_width, rect_height, ptr0, len0);
+        const ret = wasm.tiledrgbabuffer_writeRect(this.__wbg_ptr, rect_x, rect_y, rect_width, rect_height, ptr0, len0);
         return ret !== 0;
     }
     /**
@@ -1048,7 +4967,7 @@ export class RgbaBuffer {
         const len0 = WASM_VECTOR_LEN;
         const ptr1 = passArray8ToWasm0(colors, wasm.__wbindgen_malloc);
         const len1 = WASM_VECTOR_LEN;
-        const ret = wasm.rgbabuffer_writePixels(this.__wbg_ptr, ptr0, len0, ptr1, len1);
+        const ret = wasm.tiledrgbabuffer_writePixels(this.__wbg_ptr, ptr0, len0, ptr1, len1);
         return ret !== 0;
     }
     /**
@@ -1059,58 +4978,40 @@ export class RgbaBuffer {
      * @returns {Uint8Array}
      */
     readRect(rect_x, rect_y, rect_width, rect_height) {
-        const ret = wasm.rgbabuffer_readRect(this.__wbg_ptr, rect_x, rect_y, rect_width, rect_height);
-        var v1 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
-        wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
-        return v1;
-    }
-    /**
-     * @returns {Uint8Array}
-     */
-    exportPng() {
-        const ret = wasm.rgbabuffer_exportPng(this.__wbg_ptr);
-        var v1 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
-        wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
-        return v1;
-    }
-    /**
-     * @returns {Uint8Array}
-     */
-    exportWebp() {
-        const ret = wasm.rgbabuffer_exportWebp(this.__wbg_ptr);
+        const ret = wasm.tiledrgbabuffer_readRect(this.__wbg_ptr, rect_x, rect_y, rect_width, rect_height);
         var v1 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
         wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
         return v1;
     }
     /**
-     * @param {number} new_width
-     * @param {number} new_height
-     * @param {number} src_origin_x
-     * @param {number} src_origin_y
-     * @param {number} dest_origin_x
-     * @param {number} dest_origin_y
+     * Build sparse storage from a dense buffer.
+     * @param {RgbaBuffer} buffer
+     * @param {number} tile_size
+     * @returns {TiledRgbaBuffer}
      */
-    resizeWithOrigins(new_width, new_height, src_origin_x, src_origin_y, dest_origin_x, dest_origin_y) {
-        wasm.rgbabuffer_resizeWithOrigins(this.__wbg_ptr, new_width, new_height, src_origin_x, src_origin_y, dest_origin_x, dest_origin_y);
+    static fromBuffer(buffer, tile_size) {
+        _assertClass(buffer, RgbaBuffer);
+        const ret = wasm.tiledrgbabuffer_fromBuffer(buffer.__wbg_ptr, tile_size);
+        if (ret[2]) {
+            throw takeFromExternrefTable0(ret[1]);
+        }
+        return TiledRgbaBuffer.__wrap(ret[0]);
     }
     /**
-     * @param {number} new_width
-     * @param {number} new_height
+     * Bytes used by dense tile storage.
+     * @returns {number}
      */
-    resize(new_width, new_height) {
-        wasm.rgbabuffer_resize(this.__wbg_ptr, new_width, new_height);
+    allocatedBytes() {
+        const ret = wasm.tiledrgbabuffer_allocatedBytes(this.__wbg_ptr);
+        return ret >>> 0;
     }
     /**
-     * @param {Uint8Array} raw
-     * @param {number} width
-     * @param {number} height
-     * @returns {boolean}
+     * Number of tiles that hold their own pixel storage.
+     * @returns {number}
      */
-    overwriteWith(raw, width, height) {
-        const ptr0 = passArray8ToWasm0(raw, wasm.__wbindgen_malloc);
-        const len0 = WASM_VECTOR_LEN;
-        const ret = wasm.rgbabuffer_overwriteWith(this.__wbg_ptr, ptr0, len0, width, height);
-        return ret !== 0;
+    allocatedTiles() {
+        const ret = wasm.tiledrgbabuffer_allocatedTiles(this.__wbg_ptr);
+        return ret >>> 0;
     }
     /**
      * @param {number} x
@@ -1118,33 +5019,23 @@ export class RgbaBuffer {
      * @returns {Array<any>}
      */
     get(x, y) {
-        const ret = wasm.rgbabuffer_get(this.__wbg_ptr, x, y);
+        const ret = wasm.tiledrgbabuffer_get(this.__wbg_ptr, x, y);
         return ret;
     }
-    /**
-     * @returns {number}
-     */
-    len() {
-        const ret = wasm.rgbabuffer_len(this.__wbg_ptr);
-        return ret >>> 0;
-    }
     /**
      * @param {number} width
      * @param {number} height
+     * @param {number} tile_size
      */
-    constructor(width, height) {
-        const ret = wasm.rgbabuffer_new(width, height);
-        this.__wbg_ptr = ret >>> 0;
-        RgbaBufferFinalization.register(this, this.__wbg_ptr, this);
+    constructor(width, height, tile_size) {
+        const ret = wasm.tiledrgbabuffer_new(width, height, tile_size);
+        if (ret[2]) {
+            throw takeFromExternrefTable0(ret[1]);
+        }
+        this.__wbg_ptr = ret[0] >>> 0;
+        TiledRgbaBufferFinalization.register(this, this.__wbg_ptr, this);
         return this;
     }
-    /**
-     * @returns {number}
-     */
-    ptr() {
-        const ret = wasm.rgbabuffer_ptr(this.__wbg_ptr);
-        return ret >>> 0;
-    }
     /**
      * @param {number} x
      * @param {number} y
@@ -1155,73 +5046,28 @@ export class RgbaBuffer {
      * @returns {boolean}
      */
     set(x, y, r, g, b, a) {
-        const ret = wasm.rgbabuffer_set(this.__wbg_ptr, x, y, r, g, b, a);
+        const ret = wasm.tiledrgbabuffer_set(this.__wbg_ptr, x, y, r, g, b, a);
         return ret !== 0;
     }
-    /**
-     * @returns {RgbaBuffer}
-     */
-    clone() {
-        const ret = wasm.rgbabuffer_clone(this.__wbg_ptr);
-        return RgbaBuffer.__wrap(ret);
-    }
     /**
      * @returns {number}
      */
     width() {
-        const ret = wasm.rgbabuffer_width(this.__wbg_ptr);
+        const ret = wasm.tiledrgbabuffer_width(this.__wbg_ptr);
         return ret >>> 0;
     }
     /**
      * @returns {number}
      */
     height() {
-        const ret = wasm.rgbabuffer_height(this.__wbg_ptr);
+        const ret = wasm.tiledrgbabuffer_height(this.__wbg_ptr);
         return ret >>> 0;
     }
     /**
-     * @param {number} width
-     * @param {number} height
-     * @param {Uint8Array} buf
-     * @returns {RgbaBuffer}
-     */
-    static fromRaw(width, height, buf) {
-        const ptr0 = passArray8ToWasm0(buf, wasm.__wbindgen_malloc);
-        const len0 = WASM_VECTOR_LEN;
-        const ret = wasm.rgbabuffer_fromRaw(width, height, ptr0, len0);
-        if (ret[2]) {
-            throw takeFromExternrefTable0(ret[1]);
-        }
-        return RgbaBuffer.__wrap(ret[0]);
-    }
-    /**
-     * @returns {boolean}
-     */
-    is_empty() {
-        const ret = wasm.rgbabuffer_is_empty(this.__wbg_ptr);
-        return ret !== 0;
-    }
-    /**
-     * @returns {Uint8ClampedArray}
-     */
-    data() {
-        const ret = wasm.rgbabuffer_data(this.__wbg_ptr);
-        return ret;
-    }
-    /**
-     * @param {number} width
-     * @param {number} height
-     * @param {Uint8Array} webp_buf
-     * @returns {RgbaBuffer}
+     * Collapse dense tiles whose pixels all share one color.
      */
-    static fromWebp(width, height, webp_buf) {
-        const ptr0 = passArray8ToWasm0(webp_buf, wasm.__wbindgen_malloc);
-        const len0 = WASM_VECTOR_LEN;
-        const ret = wasm.rgbabuffer_fromWebp(width, height, ptr0, len0);
-        if (ret[2]) {
-            throw takeFromExternrefTable0(ret[1]);
-        }
-        return RgbaBuffer.__wrap(ret[0]);
+    compact() {
+        wasm.tiledrgbabuffer_compact(this.__wbg_ptr);
     }
     /**
      * @param {number} x
@@ -1229,134 +5075,37 @@ export class RgbaBuffer {
      * @returns {boolean}
      */
     isInBounds(x, y) {
-        const ret = wasm.rgbabuffer_isInBounds(this.__wbg_ptr, x, y);
-        return ret !== 0;
-    }
-    /**
-     * @param {number} idx
-     * @returns {Array<any>}
-     */
-    indexGet(idx) {
-        const ret = wasm.rgbabuffer_indexGet(this.__wbg_ptr, idx);
-        return ret;
-    }
-    /**
-     * @param {number} idx
-     * @param {number} r
-     * @param {number} g
-     * @param {number} b
-     * @param {number} a
-     * @returns {boolean}
-     */
-    indexSet(idx, r, g, b, a) {
-        const ret = wasm.rgbabuffer_indexSet(this.__wbg_ptr, idx, r, g, b, a);
-        return ret !== 0;
-    }
-    /**
-     * @param {number} start_x
-     * @param {number} start_y
-     * @param {number} fill_color_r
-     * @param {number} fill_color_g
-     * @param {number} fill_color_b
-     * @param {number} fill_color_a
-     * @param {number} threshold
-     * @returns {boolean}
-     */
-    floodFill(start_x, start_y, fill_color_r, fill_color_g, fill_color_b, fill_color_a, threshold) {
-        const ret = wasm.rgbabuffer_floodFill(this.__wbg_ptr, start_x, start_y, fill_color_r, fill_color_g, fill_color_b, fill_color_a, threshold);
-        return ret !== 0;
-    }
-    /**
-     * @param {number} fill_code
-     * @returns {boolean}
-     */
-    fillAllCodes(fill_code) {
-        const ret = wasm.rgbabuffer_fillAllCodes(this.__wbg_ptr, fill_code);
-        return ret !== 0;
-    }
-    /**
-     * @param {Uint8Array} mask
-     * @param {number} fill_color_r
-     * @param {number} fill_color_g
-     * @param {number} fill_color_b
-     * @param {number} fill_color_a
-     * @returns {boolean}
-     */
-    fillMaskArea(mask, fill_color_r, fill_color_g, fill_color_b, fill_color_a) {
-        const ptr0 = passArray8ToWasm0(mask, wasm.__wbindgen_malloc);
-        const len0 = WASM_VECTOR_LEN;
-        const ret = wasm.rgbabuffer_fillMaskArea(this.__wbg_ptr, ptr0, len0, fill_color_r, fill_color_g, fill_color_b, fill_color_a);
-        return ret !== 0;
-    }
-    /**
-     * @param {number} fill_color_r
-     * @param {number} fill_color_g
-     * @param {number} fill_color_b
-     * @param {number} fill_color_a
-     * @returns {boolean}
-     */
-    fillAllPixels(fill_color_r, fill_color_g, fill_color_b, fill_color_a) {
-        const ret = wasm.rgbabuffer_fillAllPixels(this.__wbg_ptr, fill_color_r, fill_color_g, fill_color_b, fill_color_a);
-        return ret !== 0;
-    }
-    /**
-     * @param {number} start_x
-     * @param {number} start_y
-     * @param {number} fill_color_r
-     * @param {number} fill_color_g
-     * @param {number} fill_color_b
-     * @param {number} fill_color_a
-     * @param {number} threshold
-     * @param {Uint8Array} selection_mask
-     * @param {string} limit_mode
-     * @returns {boolean}
-     */
-    floodFillWithMask(start_x, start_y, fill_color_r, fill_color_g, fill_color_b, fill_color_a, threshold, selection_mask, limit_mode) {
-        const ptr0 = passArray8ToWasm0(selection_mask, wasm.__wbindgen_malloc);
-        const len0 = WASM_VECTOR_LEN;
-        const ptr1 = passStringToWasm0(limit_mode, wasm.__wbindgen_malloc, wasm.__wbindgen_realloc);
-        const len1 = WASM_VECTOR_LEN;
-        const ret = wasm.rgbabuffer_floodFillWithMask(this.__wbg_ptr, start_x, start_y, fill_color_r, fill_color_g, fill_color_b, fill_color_a, threshold, ptr0, len0, ptr1, len1);
+        const ret = wasm.tiledrgbabuffer_isInBounds(this.__wbg_ptr, x, y);
         return ret !== 0;
     }
     /**
-     * @param {Uint8Array} png_buffer
-     * @param {number} width
-     * @param {number} height
-     * @returns {boolean}
+     * @returns {number}
      */
-    importPng(png_buffer, width, height) {
-        const ptr0 = passArray8ToWasm0(png_buffer, wasm.__wbindgen_malloc);
-        const len0 = WASM_VECTOR_LEN;
-        const ret = wasm.rgbabuffer_importPng(this.__wbg_ptr, ptr0, len0, width, height);
-        return ret !== 0;
+    tileSize() {
+        const ret = wasm.tiledrgbabuffer_tileSize(this.__wbg_ptr);
+        return ret >>> 0;
     }
     /**
-     * @param {Uint8Array} raw
-     * @param {number} width
-     * @param {number} height
-     * @returns {boolean}
+     * Materialize the whole canvas as a dense buffer (width * height * 4 bytes).
+     * @returns {RgbaBuffer}
      */
-    importRaw(raw, width, height) {
-        const ptr0 = passArray8ToWasm0(raw, wasm.__wbindgen_malloc);
-        const len0 = WASM_VECTOR_LEN;
-        const ret = wasm.rgbabuffer_importRaw(this.__wbg_ptr, ptr0, len0, width, height);
-        return ret !== 0;
+    toBuffer() {
+        const ret = wasm.tiledrgbabuffer_toBuffer(this.__wbg_ptr);
+        return RgbaBuffer.__wrap(ret);
     }
     /**
-     * @param {Uint8Array} webp_buffer
-     * @param {number} width
-     * @param {number} height
-     * @returns {boolean}
+     * Encode the canvas as PNG one tile row at a time, without materializing the whole image.
+     * Returns an empty array on failure.
+     * @returns {Uint8Array}
      */
-    importWebp(webp_buffer, width, height) {
-        const ptr0 = passArray8ToWasm0(webp_buffer, wasm.__wbindgen_malloc);
-        const len0 = WASM_VECTOR_LEN;
-        const ret = wasm.rgbabuffer_importWebp(this.__wbg_ptr, ptr0, len0, width, height);
-        return ret !== 0;
+    exportPng() {
+        const ret = wasm.tiledrgbabuffer_exportPng(this.__wbg_ptr);
+        var v1 = getArrayU8FromWasm0(ret[0], ret[1]).slice();
+        wasm.__wbindgen_free(ret[0], ret[1] * 1, 1);
+        return v1;
     }
 }
-if (Symbol.dispose) RgbaBuffer.prototype[Symbol.dispose] = RgbaBuffer.prototype.free;
+if (Symbol.dispose) TiledRgbaBuffer.prototype[Symbol.dispose] = TiledRgbaBuffer.prototype.free;
 
 export function __wbg___wbindgen_copy_to_typed_array_33fbd71146904370(arg0, arg1, arg2) {
     new Uint8Array(arg2.buffer, arg2.byteOffset, arg2.byteLength).set(getArrayU8FromWasm0(arg0, arg1));
@@ -1391,6 +5140,12 @@ export function __wbindgen_cast_77927a9dcb96442f(arg0, arg1) {
     return ret;
 };
 
+export function __wbindgen_cast_cb9088102bce6b30(arg0, arg1) {
+    // Cast intrinsic for `Ref(Slice(U8)) -> NamedExternref("Uint8Array")`.
+    const ret = getArrayU8FromWasm0(arg0, arg1);
+    return ret;
+};
+
 export function __wbindgen_cast_d6cd19b81560fd6e(arg0) {
     // Cast intrinsic for `F64 -> Externref`.
     const ret = arg0;
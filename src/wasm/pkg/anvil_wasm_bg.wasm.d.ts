@@ -2,112 +2,409 @@
 /* eslint-disable */
 export const memory: WebAssembly.Memory;
 export const __wbg_ditheringoption_free: (a: number, b: number) => void;
+export const __wbg_dustremovaloption_free: (a: number, b: number) => void;
+export const __wbg_fillresult_free: (a: number, b: number) => void;
 export const __wbg_get_ditheringoption_levels: (a: number) => number;
 export const __wbg_get_ditheringoption_mode: (a: number) => number;
 export const __wbg_get_ditheringoption_strength: (a: number) => number;
+export const __wbg_get_dustremovaloption_alpha_threshold: (a: number) => number;
+export const __wbg_get_dustremovaloption_connectivity: (a: number) => number;
+export const __wbg_get_gradientfilloption_dither: (a: number) => number;
+export const __wbg_get_gradientfilloption_end_x: (a: number) => number;
+export const __wbg_get_gradientfilloption_end_y: (a: number) => number;
+export const __wbg_get_gradientfilloption_interpolation: (a: number) => number;
+export const __wbg_get_gradientfilloption_repeat: (a: number) => number;
+export const __wbg_get_gradientfilloption_shape: (a: number) => number;
+export const __wbg_get_gradientfilloption_start_x: (a: number) => number;
+export const __wbg_gradientfilloption_free: (a: number, b: number) => void;
+export const __wbg_posterizeoption_free: (a: number, b: number) => void;
 export const __wbg_set_ditheringoption_levels: (a: number, b: number) => void;
 export const __wbg_set_ditheringoption_mode: (a: number, b: number) => void;
 export const __wbg_set_ditheringoption_strength: (a: number, b: number) => void;
+export const __wbg_set_dustremovaloption_alpha_threshold: (a: number, b: number) => void;
+export const __wbg_set_dustremovaloption_connectivity: (a: number, b: number) => void;
+export const __wbg_set_gradientfilloption_dither: (a: number, b: number) => void;
+export const __wbg_set_gradientfilloption_end_x: (a: number, b: number) => void;
+export const __wbg_set_gradientfilloption_end_y: (a: number, b: number) => void;
+export const __wbg_set_gradientfilloption_interpolation: (a: number, b: number) => void;
+export const __wbg_set_gradientfilloption_repeat: (a: number, b: number) => void;
+export const __wbg_set_gradientfilloption_shape: (a: number, b: number) => void;
+export const __wbg_set_gradientfilloption_start_x: (a: number, b: number) => void;
 export const dithering: (a: number, b: number, c: any, d: number, e: number, f: number) => void;
 export const dithering_error_diffusion: (a: number, b: number, c: any, d: number, e: number, f: number) => void;
 export const dithering_ordered: (a: number, b: number, c: any, d: number, e: number, f: number) => void;
 export const dithering_random: (a: number, b: number, c: any, d: number, e: number, f: number) => void;
 export const ditheringoption_new: (a: number, b: number, c: number) => number;
-export const fill_mask_area: (a: number, b: number, c: any, d: number, e: number, f: number, g: number, h: number, i: number) => number;
-export const invert: (a: number, b: number, c: any, d: number, e: number) => void;
-export const rgbabuffer_blitFromBuffer: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number, j: number) => void;
-export const rgbabuffer_blitFromRaw: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number, j: number, k: number, l: number, m: number) => void;
-export const rgbabuffer_brightnessAndContrast: (a: number, b: number, c: number) => void;
-export const rgbabuffer_cropWithMask: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => [number, number];
-export const rgbabuffer_dithering: (a: number, b: number, c: number, d: number) => void;
-export const rgbabuffer_dustRemoval: (a: number, b: number, c: number) => void;
-export const rgbabuffer_gaussianBlur: (a: number, b: number, c: number) => void;
-export const rgbabuffer_grayscale: (a: number) => void;
-export const rgbabuffer_invert: (a: number) => void;
-export const rgbabuffer_posterize: (a: number, b: number) => void;
-export const rgbabuffer_sliceWithMask: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => [number, number];
-export const __wbg_dustremovaloption_free: (a: number, b: number) => void;
-export const __wbg_get_dustremovaloption_alpha_threshold: (a: number) => number;
-export const __wbg_get_dustremovaloption_max_size: (a: number) => number;
-export const __wbg_posterizeoption_free: (a: number, b: number) => void;
-export const __wbg_set_dustremovaloption_alpha_threshold: (a: number, b: number) => void;
-export const __wbg_set_dustremovaloption_max_size: (a: number, b: number) => void;
 export const dust_removal: (a: number, b: number, c: any, d: number, e: number, f: number) => void;
 export const dust_removal_simple: (a: number, b: number, c: any, d: number, e: number, f: number) => void;
 export const dustremovaloption_new: (a: number, b: number) => number;
+export const fillresult_boundBox: (a: number) => [number, number];
+export const fillresult_changedPixels: (a: number) => number;
+export const fillresult_coverage: (a: number) => [number, number];
+export const gradient_fill: (a: number, b: number, c: any, d: number, e: number, f: number, g: number, h: number, i: number, j: number, k: number, l: number) => number;
+export const gradientfilloption_new: (a: number, b: number, c: number, d: number, e: number) => number;
 export const posterize: (a: number, b: number, c: any, d: number, e: number, f: number) => void;
 export const posterize_simple: (a: number, b: number, c: any, d: number, e: number, f: number) => void;
 export const posterizeoption_new: (a: number) => number;
-export const rgbabuffer_exportPng: (a: number) => [number, number];
-export const rgbabuffer_exportWebp: (a: number) => [number, number];
-export const rgbabuffer_readRect: (a: number, b: number, c: number, d: number, e: number) => [number, number];
-export const rgbabuffer_resize: (a: number, b: number, c: number) => void;
-export const rgbabuffer_resizeWithOrigins: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => void;
-export const rgbabuffer_writePixels: (a: number, b: number, c: number, d: number, e: number) => number;
-export const rgbabuffer_writeRect: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => number;
+export const __wbg_set_dustremovaloption_max_size: (a: number, b: number) => void;
+export const __wbg_set_gradientfilloption_start_y: (a: number, b: number) => void;
 export const __wbg_set_posterizeoption_levels: (a: number, b: number) => void;
+export const __wbg_get_dustremovaloption_max_size: (a: number) => number;
+export const __wbg_get_gradientfilloption_start_y: (a: number) => number;
 export const __wbg_get_posterizeoption_levels: (a: number) => number;
-export const __wbg_brightnesscontrastoption_free: (a: number, b: number) => void;
-export const __wbg_get_brightnesscontrastoption_brightness: (a: number) => number;
-export const __wbg_get_brightnesscontrastoption_contrast: (a: number) => number;
-export const __wbg_set_brightnesscontrastoption_brightness: (a: number, b: number) => void;
-export const __wbg_set_brightnesscontrastoption_contrast: (a: number, b: number) => void;
-export const brightness: (a: number, b: number, c: any, d: number, e: number, f: number) => void;
-export const brightness_contrast: (a: number, b: number, c: any, d: number, e: number, f: number) => void;
-export const brightnesscontrastoption_new: (a: number, b: number) => number;
-export const contrast: (a: number, b: number, c: any, d: number, e: number, f: number) => void;
 export const __wbg_rgbabuffer_free: (a: number, b: number) => void;
-export const __wbg_gaussianbluroption_free: (a: number, b: number) => void;
-export const __wbg_get_gaussianbluroption_alpha_mode: (a: number) => number;
-export const __wbg_get_gaussianbluroption_radius: (a: number) => number;
-export const __wbg_set_gaussianbluroption_alpha_mode: (a: number, b: number) => void;
-export const __wbg_set_gaussianbluroption_radius: (a: number, b: number) => void;
-export const gaussian_blur: (a: number, b: number, c: any, d: number, e: number, f: number) => void;
-export const gaussianbluroption_new: (a: number, b: number) => number;
-export const grayscale: (a: number, b: number, c: any, d: number, e: number) => void;
+export const __wbg_floodfilloption_free: (a: number, b: number) => void;
+export const __wbg_get_floodfilloption_antialias: (a: number) => number;
+export const __wbg_get_floodfilloption_connectivity: (a: number) => number;
+export const __wbg_get_floodfilloption_expand: (a: number) => number;
+export const __wbg_get_floodfilloption_expand_limit: (a: number) => number;
+export const __wbg_get_floodfilloption_expand_threshold: (a: number) => number;
+export const __wbg_get_floodfilloption_gap_size: (a: number) => number;
+export const __wbg_get_floodfilloption_include_alpha: (a: number) => number;
+export const __wbg_get_floodfilloption_mask_mode: (a: number) => number;
+export const __wbg_get_floodfilloption_metric: (a: number) => number;
+export const __wbg_get_floodfilloption_threshold: (a: number) => number;
+export const __wbg_set_floodfilloption_antialias: (a: number, b: number) => void;
+export const __wbg_set_floodfilloption_connectivity: (a: number, b: number) => void;
+export const __wbg_set_floodfilloption_expand: (a: number, b: number) => void;
+export const __wbg_set_floodfilloption_expand_limit: (a: number, b: number) => void;
+export const __wbg_set_floodfilloption_expand_threshold: (a: number, b: number) => void;
+export const __wbg_set_floodfilloption_gap_size: (a: number, b: number) => void;
+export const __wbg_set_floodfilloption_include_alpha: (a: number, b: number) => void;
+export const __wbg_set_floodfilloption_mask_mode: (a: number, b: number) => void;
+export const __wbg_set_floodfilloption_metric: (a: number, b: number) => void;
+export const __wbg_set_floodfilloption_threshold: (a: number, b: number) => void;
+export const floodfilloption_new: (a: number, b: number, c: number) => number;
+export const magic_wand: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number, j: number) => [number, number];
+export const magic_wand_with_options: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number) => [number, number];
+export const scanline_flood_fill: (a: number, b: number, c: any, d: number, e: number, f: number, g: number, h: number, i: number, j: number, k: number, l: number, m: number) => number;
+export const scanline_flood_fill_from_reference: (a: number, b: number, c: any, d: number, e: number, f: number, g: number, h: number, i: number, j: number, k: number, l: number, m: number, n: number, o: number, p: number, q: number, r: number) => number;
+export const scanline_flood_fill_with_mask: (a: number, b: number, c: any, d: number, e: number, f: number, g: number, h: number, i: number, j: number, k: number, l: number, m: number, n: number, o: number, p: number, q: number) => number;
+export const scanline_flood_fill_with_options: (a: number, b: number, c: any, d: number, e: number, f: number, g: number, h: number, i: number, j: number, k: number, l: number, m: number, n: number, o: number, p: number) => number;
+export const lasso_mask: (a: number, b: number, c: number, d: number, e: number, f: number) => [number, number];
+export const png_to_raw: (a: number, b: number, c: number, d: number) => [number, number];
+export const raw_to_png: (a: number, b: number, c: number, d: number) => [number, number];
+export const raw_to_webp: (a: number, b: number, c: number, d: number) => [number, number];
+export const rgbabuffer_blitFromBuffer: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number, j: number) => void;
+export const rgbabuffer_blitFromBufferWithOptions: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number) => void;
+export const rgbabuffer_blitFromRaw: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number, j: number, k: number, l: number, m: number) => void;
+export const rgbabuffer_blitFromRawWithOptions: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number, j: number, k: number) => void;
+export const rgbabuffer_brightnessAndContrast: (a: number, b: number, c: number) => void;
+export const rgbabuffer_brightnessAndContrastInSelection: (a: number, b: number, c: number, d: number) => number;
+export const rgbabuffer_captureTiles: (a: number, b: number, c: number, d: number) => [number, number];
 export const rgbabuffer_clone: (a: number) => number;
+export const rgbabuffer_cropWithMask: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => [number, number];
+export const rgbabuffer_cropWithSelection: (a: number, b: number, c: number, d: number) => [number, number];
 export const rgbabuffer_data: (a: number) => any;
-export const rgbabuffer_fillAllCodes: (a: number, b: number) => number;
-export const rgbabuffer_fillAllPixels: (a: number, b: number, c: number, d: number, e: number) => number;
-export const rgbabuffer_fillMaskArea: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => number;
-export const rgbabuffer_floodFill: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number) => number;
-export const rgbabuffer_floodFillWithMask: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number, j: number, k: number, l: number) => number;
+export const rgbabuffer_dithering: (a: number, b: number, c: number, d: number) => void;
+export const rgbabuffer_ditheringInSelection: (a: number, b: number, c: number, d: number, e: number) => number;
+export const rgbabuffer_dustRemoval: (a: number, b: number, c: number) => void;
+export const rgbabuffer_dustRemovalInSelection: (a: number, b: number, c: number, d: number) => number;
+export const rgbabuffer_dustRemovalWithOptions: (a: number, b: number) => void;
 export const rgbabuffer_fromRaw: (a: number, b: number, c: number, d: number) => [number, number, number];
 export const rgbabuffer_fromWebp: (a: number, b: number, c: number, d: number) => [number, number, number];
+export const rgbabuffer_gaussianBlur: (a: number, b: number, c: number) => void;
+export const rgbabuffer_gaussianBlurInSelection: (a: number, b: number, c: number, d: number) => number;
 export const rgbabuffer_get: (a: number, b: number, c: number) => any;
+export const rgbabuffer_grayscale: (a: number) => void;
+export const rgbabuffer_grayscaleInSelection: (a: number, b: number) => number;
 export const rgbabuffer_height: (a: number) => number;
 export const rgbabuffer_importPng: (a: number, b: number, c: number, d: number, e: number) => number;
 export const rgbabuffer_importRaw: (a: number, b: number, c: number, d: number, e: number) => number;
 export const rgbabuffer_importWebp: (a: number, b: number, c: number, d: number, e: number) => number;
 export const rgbabuffer_indexGet: (a: number, b: number) => any;
 export const rgbabuffer_indexSet: (a: number, b: number, c: number, d: number, e: number, f: number) => number;
+export const rgbabuffer_invert: (a: number) => void;
+export const rgbabuffer_invertInSelection: (a: number, b: number) => number;
 export const rgbabuffer_isInBounds: (a: number, b: number, c: number) => number;
 export const rgbabuffer_is_empty: (a: number) => number;
 export const rgbabuffer_len: (a: number) => number;
 export const rgbabuffer_new: (a: number, b: number) => number;
 export const rgbabuffer_overwriteWith: (a: number, b: number, c: number, d: number, e: number) => number;
+export const rgbabuffer_posterize: (a: number, b: number) => void;
+export const rgbabuffer_posterizeInSelection: (a: number, b: number, c: number) => number;
 export const rgbabuffer_ptr: (a: number) => number;
+export const rgbabuffer_readRect: (a: number, b: number, c: number, d: number, e: number) => [number, number];
 export const rgbabuffer_set: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => number;
+export const rgbabuffer_sliceWithMask: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => [number, number];
+export const rgbabuffer_sliceWithSelection: (a: number, b: number, c: number, d: number) => [number, number];
+export const rgbabuffer_swapTiles: (a: number, b: number, c: number) => [number, number];
 export const rgbabuffer_width: (a: number) => number;
-export const scanline_flood_fill: (a: number, b: number, c: any, d: number, e: number, f: number, g: number, h: number, i: number, j: number, k: number, l: number) => number;
-export const scanline_flood_fill_with_mask: (a: number, b: number, c: any, d: number, e: number, f: number, g: number, h: number, i: number, j: number, k: number, l: number, m: number, n: number, o: number, p: number) => number;
+export const rgbabuffer_writePixels: (a: number, b: number, c: number, d: number, e: number) => number;
+export const rgbabuffer_writeRect: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => number;
+export const selectionmask_apply: (a: number, b: number, c: number) => number;
+export const selectionmask_fromLasso: (a: number, b: number, c: number, d: number, e: number, f: number) => number;
+export const selectionmask_intersect: (a: number, b: number) => number;
+export const selectionmask_invert: (a: number) => void;
+export const selectionmask_subtract: (a: number, b: number) => number;
+export const selectionmask_union: (a: number, b: number) => number;
+export const selectionmask_xor: (a: number, b: number) => number;
+export const tiledrgbabuffer_blitFromBuffer: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number, j: number) => void;
+export const tiledrgbabuffer_blitFromBufferWithOptions: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number) => void;
+export const tiledrgbabuffer_blitFromRaw: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number, j: number, k: number, l: number, m: number) => void;
+export const tiledrgbabuffer_blitFromRawWithOptions: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number, j: number, k: number) => void;
+export const tiledrgbabuffer_fillAllPixels: (a: number, b: number, c: number, d: number, e: number) => number;
+export const tiledrgbabuffer_fillMaskArea: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => number;
+export const tiledrgbabuffer_fillMaskAreaWithCoverage: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number) => number;
+export const tiledrgbabuffer_floodFill: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number) => number;
+export const tiledrgbabuffer_floodFillWithMask: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number, j: number, k: number, l: number, m: number) => number;
+export const tiledrgbabuffer_readRect: (a: number, b: number, c: number, d: number, e: number) => [number, number];
+export const tiledrgbabuffer_setFillCoverage: (a: number, b: number) => void;
+export const tiledrgbabuffer_writePixels: (a: number, b: number, c: number, d: number, e: number) => number;
+export const tiledrgbabuffer_writeRect: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => number;
+export const webp_to_raw: (a: number, b: number, c: number, d: number) => [number, number];
+export const __wbg_get_groupoption_blend_mode: (a: number) => number;
+export const __wbg_get_groupoption_clipping: (a: number) => number;
+export const __wbg_get_groupoption_opacity: (a: number) => number;
+export const __wbg_get_groupoption_pass_through: (a: number) => number;
+export const __wbg_get_groupoption_visible: (a: number) => number;
 export const __wbg_get_patchbufferrgbaoption_antialias_mode: (a: number) => number;
-export const __wbg_get_patchbufferrgbaoption_flip_x: (a: number) => number;
-export const __wbg_get_patchbufferrgbaoption_flip_y: (a: number) => number;
+export const __wbg_get_patternfilloption_offset_x: (a: number) => number;
+export const __wbg_get_patternfilloption_offset_y: (a: number) => number;
+export const __wbg_get_patternfilloption_scale: (a: number) => number;
+export const __wbg_groupoption_free: (a: number, b: number) => void;
+export const __wbg_layeroption_free: (a: number, b: number) => void;
+export const __wbg_layerstack_free: (a: number, b: number) => void;
 export const __wbg_patchbufferrgbaoption_free: (a: number, b: number) => void;
+export const __wbg_patternfilloption_free: (a: number, b: number) => void;
+export const __wbg_set_groupoption_blend_mode: (a: number, b: number) => void;
+export const __wbg_set_groupoption_clipping: (a: number, b: number) => void;
+export const __wbg_set_groupoption_opacity: (a: number, b: number) => void;
+export const __wbg_set_groupoption_pass_through: (a: number, b: number) => void;
+export const __wbg_set_groupoption_visible: (a: number, b: number) => void;
 export const __wbg_set_patchbufferrgbaoption_antialias_mode: (a: number, b: number) => void;
-export const __wbg_set_patchbufferrgbaoption_flip_x: (a: number, b: number) => void;
-export const __wbg_set_patchbufferrgbaoption_flip_y: (a: number, b: number) => void;
+export const __wbg_set_patternfilloption_offset_x: (a: number, b: number) => void;
+export const __wbg_set_patternfilloption_offset_y: (a: number, b: number) => void;
+export const __wbg_set_patternfilloption_scale: (a: number, b: number) => void;
+export const fill_mask_area: (a: number, b: number, c: any, d: number, e: number, f: number, g: number, h: number, i: number) => number;
+export const fill_mask_area_with_coverage: (a: number, b: number, c: any, d: number, e: number, f: number, g: number, h: number, i: number, j: number) => number;
+export const groupoption_new: (a: number, b: number, c: number, d: number, e: number) => number;
+export const layeroption_new: (a: number, b: number, c: number, d: number) => number;
+export const layerstack_begin: (a: number) => void;
+export const layerstack_beginGroup: (a: number, b: number) => void;
+export const layerstack_beginGroupWithMask: (a: number, b: number, c: number, d: number) => number;
+export const layerstack_beginRect: (a: number, b: number, c: number, d: number, e: number) => void;
+export const layerstack_beginTiles: (a: number, b: number, c: number, d: number) => void;
+export const layerstack_compositeInto: (a: number, b: number) => number;
+export const layerstack_endGroup: (a: number) => number;
+export const layerstack_height: (a: number) => number;
+export const layerstack_new: (a: number, b: number) => number;
+export const layerstack_pushLayer: (a: number, b: number, c: number) => number;
+export const layerstack_pushLayerWithMask: (a: number, b: number, c: number, d: number, e: number) => number;
+export const layerstack_width: (a: number) => number;
 export const patch_buffer_rgba: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number, j: number, k: number) => [number, number];
 export const patch_buffer_rgba_instant: (a: number, b: number, c: any, d: number, e: number, f: number, g: number, h: number, i: number, j: number, k: number, l: number, m: number, n: number, o: number) => void;
 export const patchbufferrgbaoption_new: (a: number, b: number, c: number) => number;
-export const png_to_raw: (a: number, b: number, c: number, d: number) => [number, number];
-export const raw_to_png: (a: number, b: number, c: number, d: number) => [number, number];
-export const raw_to_webp: (a: number, b: number, c: number, d: number) => [number, number];
-export const webp_to_raw: (a: number, b: number, c: number, d: number) => [number, number];
+export const pattern_fill: (a: number, b: number, c: any, d: number, e: number, f: number, g: number, h: number, i: number, j: number, k: number, l: number) => number;
+export const patternfilloption_new: (a: number, b: number, c: number) => number;
+export const rgbabuffer_fillAllCodes: (a: number, b: number) => number;
+export const rgbabuffer_fillAllPixels: (a: number, b: number, c: number, d: number, e: number) => number;
+export const rgbabuffer_fillMaskArea: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => number;
+export const rgbabuffer_fillMaskAreaWithCoverage: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number) => number;
+export const rgbabuffer_fillSelection: (a: number, b: number, c: number, d: number, e: number, f: number) => number;
+export const rgbabuffer_floodFill: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number) => number;
+export const rgbabuffer_floodFillFromReference: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number) => number;
+export const rgbabuffer_floodFillFromReferenceWithSelection: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number, j: number, k: number, l: number) => number;
+export const rgbabuffer_floodFillWithMask: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number, j: number, k: number, l: number, m: number) => number;
+export const rgbabuffer_floodFillWithOptions: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number) => number;
+export const rgbabuffer_floodFillWithPattern: (a: number, b: number, c: number, d: number, e: number, f: number) => number;
+export const rgbabuffer_floodFillWithPatternAndSelection: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number) => number;
+export const rgbabuffer_floodFillWithSelection: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number, j: number, k: number, l: number) => number;
+export const rgbabuffer_floodFillWithSelectionAndOptions: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number, j: number, k: number) => number;
+export const rgbabuffer_gradientFill: (a: number, b: number, c: number, d: number, e: number, f: number) => number;
+export const rgbabuffer_gradientFillWithSelection: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => number;
+export const rgbabuffer_magicWand: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => number;
+export const rgbabuffer_magicWandInto: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number) => number;
+export const rgbabuffer_magicWandIntoWithOptions: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number) => number;
+export const rgbabuffer_magicWandWithOptions: (a: number, b: number, c: number, d: number, e: number, f: number) => number;
+export const rgbabuffer_patternFill: (a: number, b: number, c: number) => number;
+export const rgbabuffer_patternFillWithSelection: (a: number, b: number, c: number, d: number) => number;
+export const rgbabuffer_setFillCoverage: (a: number, b: number) => void;
+export const scanline_pattern_flood_fill: (a: number, b: number, c: any, d: number, e: number, f: number, g: number, h: number, i: number, j: number, k: number, l: number, m: number, n: number, o: number, p: number, q: number) => number;
+export const __wbg_get_layeroption_blend_mode: (a: number) => number;
+export const __wbg_get_layeroption_clipping: (a: number) => number;
+export const __wbg_get_layeroption_visible: (a: number) => number;
+export const __wbg_get_patchbufferrgbaoption_blend_mode: (a: number) => number;
+export const __wbg_get_patchbufferrgbaoption_flip_x: (a: number) => number;
+export const __wbg_get_patchbufferrgbaoption_flip_y: (a: number) => number;
+export const __wbg_set_layeroption_clipping: (a: number, b: number) => void;
+export const __wbg_set_layeroption_visible: (a: number, b: number) => void;
+export const __wbg_set_patchbufferrgbaoption_flip_x: (a: number, b: number) => void;
+export const __wbg_set_patchbufferrgbaoption_flip_y: (a: number, b: number) => void;
+export const __wbg_set_layeroption_blend_mode: (a: number, b: number) => void;
+export const __wbg_set_patchbufferrgbaoption_blend_mode: (a: number, b: number) => void;
+export const __wbg_set_layeroption_opacity: (a: number, b: number) => void;
+export const __wbg_set_patchbufferrgbaoption_opacity: (a: number, b: number) => void;
+export const __wbg_get_layeroption_opacity: (a: number) => number;
+export const __wbg_get_patchbufferrgbaoption_opacity: (a: number) => number;
+export const __wbg_brightnesscontrastoption_free: (a: number, b: number) => void;
+export const __wbg_gaussianbluroption_free: (a: number, b: number) => void;
+export const __wbg_get_brightnesscontrastoption_brightness: (a: number) => number;
+export const __wbg_get_brightnesscontrastoption_contrast: (a: number) => number;
+export const __wbg_get_gaussianbluroption_alpha_mode: (a: number) => number;
+export const __wbg_get_shapeoption_antialias: (a: number) => number;
+export const __wbg_get_shapeoption_fill: (a: number) => number;
+export const __wbg_get_shapeoption_fill_rule: (a: number) => number;
+export const __wbg_get_shapeoption_line_cap: (a: number) => number;
+export const __wbg_get_shapeoption_line_join: (a: number) => number;
+export const __wbg_get_shapeoption_miter_limit: (a: number) => number;
+export const __wbg_get_shapeoption_stroke_width: (a: number) => number;
+export const __wbg_set_brightnesscontrastoption_brightness: (a: number, b: number) => void;
+export const __wbg_set_brightnesscontrastoption_contrast: (a: number, b: number) => void;
+export const __wbg_set_gaussianbluroption_alpha_mode: (a: number, b: number) => void;
+export const __wbg_set_shapeoption_antialias: (a: number, b: number) => void;
+export const __wbg_set_shapeoption_fill: (a: number, b: number) => void;
+export const __wbg_set_shapeoption_fill_rule: (a: number, b: number) => void;
+export const __wbg_set_shapeoption_line_cap: (a: number, b: number) => void;
+export const __wbg_set_shapeoption_line_join: (a: number, b: number) => void;
+export const __wbg_set_shapeoption_miter_limit: (a: number, b: number) => void;
+export const __wbg_set_shapeoption_stroke_width: (a: number, b: number) => void;
+export const __wbg_shapeoption_free: (a: number, b: number) => void;
+export const border_mask: (a: number, b: number, c: any, d: number, e: number, f: number, g: number) => void;
+export const brightness: (a: number, b: number, c: any, d: number, e: number, f: number) => void;
+export const brightness_contrast: (a: number, b: number, c: any, d: number, e: number, f: number) => void;
+export const brightnesscontrastoption_new: (a: number, b: number) => number;
+export const contrast: (a: number, b: number, c: any, d: number, e: number, f: number) => void;
+export const feather_mask: (a: number, b: number, c: any, d: number, e: number, f: number) => void;
+export const gaussian_blur: (a: number, b: number, c: any, d: number, e: number, f: number) => void;
+export const gaussianbluroption_new: (a: number, b: number) => number;
+export const grayscale: (a: number, b: number, c: any, d: number, e: number) => void;
+export const grow_mask: (a: number, b: number, c: any, d: number, e: number, f: number, g: number) => void;
+export const invert: (a: number, b: number, c: any, d: number, e: number) => void;
+export const selectionmask_border: (a: number, b: number, c: number) => void;
+export const selectionmask_feather: (a: number, b: number) => void;
+export const selectionmask_grow: (a: number, b: number, c: number) => void;
+export const selectionmask_shrink: (a: number, b: number, c: number) => void;
+export const selectionmask_smooth: (a: number, b: number) => void;
+export const shapeoption_clearSelection: (a: number) => void;
+export const shapeoption_new: (a: number, b: number, c: number) => number;
+export const shapeoption_setSelection: (a: number, b: number) => void;
+export const shrink_mask: (a: number, b: number, c: any, d: number, e: number, f: number, g: number) => void;
+export const smooth_mask: (a: number, b: number, c: any, d: number, e: number, f: number) => void;
+export const __wbg_set_gaussianbluroption_radius: (a: number, b: number) => void;
+export const __wbg_get_gaussianbluroption_radius: (a: number) => number;
+export const __wbg_brushstamp_free: (a: number, b: number) => void;
+export const __wbg_bufferdiff_free: (a: number, b: number) => void;
+export const __wbg_get_strokeoption_pixel_perfect: (a: number) => number;
+export const __wbg_get_strokeoption_pressure_opacity: (a: number) => number;
+export const __wbg_get_strokeoption_pressure_size: (a: number) => number;
+export const __wbg_get_strokeoption_spacing: (a: number) => number;
+export const __wbg_set_strokeoption_pixel_perfect: (a: number, b: number) => void;
+export const __wbg_set_strokeoption_pressure_opacity: (a: number, b: number) => void;
+export const __wbg_set_strokeoption_pressure_size: (a: number, b: number) => void;
+export const __wbg_set_strokeoption_spacing: (a: number, b: number) => void;
+export const __wbg_stroke_free: (a: number, b: number) => void;
+export const __wbg_strokeoption_free: (a: number, b: number) => void;
+export const brushstamp_fromFlags: (a: number, b: number, c: number, d: number, e: number, f: number) => [number, number, number];
+export const brushstamp_height: (a: number) => number;
+export const brushstamp_new: (a: number, b: number, c: number, d: number, e: number, f: number) => [number, number, number];
+export const brushstamp_width: (a: number) => number;
+export const bufferdiff_boundBox: (a: number) => [number, number];
+export const bufferdiff_changedPixels: (a: number) => number;
+export const bufferdiff_changedTiles: (a: number) => [number, number];
+export const bufferdiff_kind: (a: number) => number;
+export const bufferdiff_pixels: (a: number) => [number, number];
+export const bufferdiff_swapBuffer: (a: number) => [number, number];
+export const diff: (a: number, b: number, c: number) => [number, number, number];
+export const rgbabuffer_beginJournal: (a: number, b: number, c: number) => number;
+export const rgbabuffer_commitJournal: (a: number) => [number, number];
+export const rgbabuffer_endJournal: (a: number) => void;
+export const rgbabuffer_isJournaling: (a: number) => number;
+export const rgbabuffer_journalBytes: (a: number) => number;
+export const rgbabuffer_journalOverflowed: (a: number) => number;
+export const stroke_addPoint: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => [number, number];
+export const stroke_boundBox: (a: number) => [number, number];
+export const stroke_changedPixels: (a: number) => number;
+export const stroke_new: (a: number, b: number, c: number, d: number, e: number, f: number) => number;
+export const stroke_pixels: (a: number) => [number, number];
+export const strokeoption_new: (a: number, b: number, c: number) => number;
+export const __wbg_historyreader_free: (a: number, b: number) => void;
+export const __wbg_patchrecord_free: (a: number, b: number) => void;
+export const __wbg_selectionmask_free: (a: number, b: number) => void;
+export const history_log_header: () => [number, number];
+export const historyreader_new: (a: number, b: number) => [number, number, number];
+export const historyreader_next: (a: number) => [number, number, number];
+export const historyreader_offset: (a: number) => number;
+export const patchrecord_decode: (a: number, b: number) => [number, number, number];
+export const patchrecord_encode: (a: number) => [number, number];
+export const patchrecord_hasPartial: (a: number) => number;
+export const patchrecord_hasTiles: (a: number) => number;
+export const patchrecord_hasWhole: (a: number) => number;
+export const patchrecord_layerId: (a: number) => [number, number];
+export const patchrecord_new: () => number;
+export const patchrecord_partialBoundBox: (a: number) => [number, number];
+export const patchrecord_partialWebp: (a: number) => [number, number];
+export const patchrecord_pixelCount: (a: number) => number;
+export const patchrecord_pixels: (a: number) => [number, number];
+export const patchrecord_setLayerId: (a: number, b: number, c: number) => void;
+export const patchrecord_setPartial: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => void;
+export const patchrecord_setPixelCount: (a: number, b: number) => void;
+export const patchrecord_setPixels: (a: number, b: number, c: number) => number;
+export const patchrecord_setTiles: (a: number, b: number, c: number, d: number, e: number) => number;
+export const patchrecord_setTimestamp: (a: number, b: number) => void;
+export const patchrecord_setTool: (a: number, b: number, c: number) => void;
+export const patchrecord_setWhole: (a: number, b: number, c: number, d: number, e: number) => void;
+export const patchrecord_tiles: (a: number) => [number, number];
+export const patchrecord_tilesSize: (a: number) => [number, number];
+export const patchrecord_timestamp: (a: number) => [number, number];
+export const patchrecord_tool: (a: number) => [number, number];
+export const patchrecord_wholeSize: (a: number) => [number, number];
+export const patchrecord_wholeWebp: (a: number) => [number, number];
+export const selectionmask_clear: (a: number) => void;
+export const selectionmask_clone: (a: number) => number;
+export const selectionmask_data: (a: number) => any;
+export const selectionmask_fromFlags: (a: number, b: number, c: number, d: number) => [number, number, number];
+export const selectionmask_fromRaw: (a: number, b: number, c: number, d: number) => [number, number, number];
+export const selectionmask_get: (a: number, b: number, c: number) => number;
+export const selectionmask_height: (a: number) => number;
+export const selectionmask_isEmpty: (a: number) => number;
+export const selectionmask_isInBounds: (a: number, b: number, c: number) => number;
+export const selectionmask_new: (a: number, b: number) => number;
+export const selectionmask_selectAll: (a: number) => void;
+export const selectionmask_set: (a: number, b: number, c: number, d: number) => number;
+export const selectionmask_toFlags: (a: number) => [number, number];
+export const selectionmask_toRaw: (a: number) => [number, number];
+export const selectionmask_width: (a: number) => number;
+export const __wbg_path_free: (a: number, b: number) => void;
+export const __wbg_tiledrgbabuffer_free: (a: number, b: number) => void;
+export const path_clear: (a: number) => void;
+export const path_close: (a: number) => void;
+export const path_cubicTo: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => void;
+export const path_isEmpty: (a: number) => number;
+export const path_lineTo: (a: number, b: number, c: number) => void;
+export const path_moveTo: (a: number, b: number, c: number) => void;
+export const path_new: () => number;
+export const path_quadTo: (a: number, b: number, c: number, d: number, e: number) => void;
+export const rgbabuffer_drawCircle: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number) => number;
+export const rgbabuffer_drawEllipse: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number, j: number) => number;
+export const rgbabuffer_drawLine: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number, j: number) => number;
+export const rgbabuffer_drawPath: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => number;
+export const rgbabuffer_drawPolygon: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number) => number;
+export const rgbabuffer_drawRect: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number, j: number) => number;
+export const rgbabuffer_drawRoundedRect: (a: number, b: number, c: number, d: number, e: number, f: number, g: number, h: number, i: number, j: number, k: number) => number;
+export const rgbabuffer_exportPng: (a: number) => [number, number];
+export const rgbabuffer_exportWebp: (a: number) => [number, number];
+export const rgbabuffer_resize: (a: number, b: number, c: number) => void;
+export const rgbabuffer_resizeWithOrigins: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => void;
+export const selectionmask_boundBox: (a: number) => [number, number];
+export const selectionmask_drawPath: (a: number, b: number, c: number, d: number) => number;
+export const selectionmask_translate: (a: number, b: number, c: number) => void;
+export const tiledrgbabuffer_allocatedBytes: (a: number) => number;
+export const tiledrgbabuffer_allocatedTiles: (a: number) => number;
+export const tiledrgbabuffer_compact: (a: number) => void;
+export const tiledrgbabuffer_exportPng: (a: number) => [number, number];
+export const tiledrgbabuffer_fromBuffer: (a: number, b: number) => [number, number, number];
+export const tiledrgbabuffer_get: (a: number, b: number, c: number) => any;
+export const tiledrgbabuffer_height: (a: number) => number;
+export const tiledrgbabuffer_isInBounds: (a: number, b: number, c: number) => number;
+export const tiledrgbabuffer_new: (a: number, b: number, c: number) => [number, number, number];
+export const tiledrgbabuffer_set: (a: number, b: number, c: number, d: number, e: number, f: number, g: number) => number;
+export const tiledrgbabuffer_tileSize: (a: number) => number;
+export const tiledrgbabuffer_toBuffer: (a: number) => number;
+export const tiledrgbabuffer_width: (a: number) => number;
 export const __wbindgen_externrefs: WebAssembly.Table;
-export const __wbindgen_malloc: (a: number, b: number) => number;
 export const __wbindgen_free: (a: number, b: number, c: number) => void;
+export const __wbindgen_malloc: (a: number, b: number) => number;
 export const __wbindgen_realloc: (a: number, b: number, c: number, d: number) => number;
 export const __externref_table_dealloc: (a: number) => void;
 export const __wbindgen_start: () => void;
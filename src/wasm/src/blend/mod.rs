@@ -0,0 +1,234 @@
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BlendMode {
+    /// Plain source-over
+    Normal = 0,
+    Multiply = 1,
+    Screen = 2,
+    Overlay = 3,
+    Darken = 4,
+    Lighten = 5,
+    ColorDodge = 6,
+    ColorBurn = 7,
+    HardLight = 8,
+    SoftLight = 9,
+    Difference = 10,
+    Exclusion = 11,
+    /// Hue of the source with saturation and luminosity of the backdrop
+    Hue = 12,
+    /// Saturation of the source with hue and luminosity of the backdrop
+    Saturation = 13,
+    /// Hue and saturation of the source with luminosity of the backdrop
+    Color = 14,
+    /// Luminosity of the source with hue and saturation of the backdrop
+    Luminosity = 15,
+    /// Linear dodge (backdrop + source, clamped)
+    Add = 16,
+    /// Backdrop - source, clamped
+    Subtract = 17,
+}
+
+/// Mix a backdrop and a source color with the given blend mode.
+///
+/// Both colors are straight (non-premultiplied) RGB normalized to 0.0-1.0.
+/// Alpha is not handled here; callers composite the result with source-over.
+pub fn blend_rgb(mode: BlendMode, backdrop: [f32; 3], source: [f32; 3]) -> [f32; 3] {
+    match mode {
+        BlendMode::Hue => set_lum(set_sat(source, sat(backdrop)), lum(backdrop)),
+        BlendMode::Saturation => set_lum(set_sat(backdrop, sat(source)), lum(backdrop)),
+        BlendMode::Color => set_lum(source, lum(backdrop)),
+        BlendMode::Luminosity => set_lum(backdrop, lum(source)),
+        _ => [
+            blend_channel(mode, backdrop[0], source[0]),
+            blend_channel(mode, backdrop[1], source[1]),
+            blend_channel(mode, backdrop[2], source[2]),
+        ],
+    }
+}
+
+// Separable blend functions (W3C Compositing and Blending Level 1)
+fn blend_channel(mode: BlendMode, cb: f32, cs: f32) -> f32 {
+    match mode {
+        BlendMode::Multiply => cb * cs,
+        BlendMode::Screen => screen(cb, cs),
+        BlendMode::Overlay => hard_light(cs, cb),
+        BlendMode::Darken => cb.min(cs),
+        BlendMode::Lighten => cb.max(cs),
+        BlendMode::ColorDodge => {
+            if cb <= 0.0 {
+                0.0
+            } else if cs >= 1.0 {
+                1.0
+            } else {
+                (cb / (1.0 - cs)).min(1.0)
+            }
+        }
+        BlendMode::ColorBurn => {
+            if cb >= 1.0 {
+                1.0
+            } else if cs <= 0.0 {
+                0.0
+            } else {
+                1.0 - ((1.0 - cb) / cs).min(1.0)
+            }
+        }
+        BlendMode::HardLight => hard_light(cb, cs),
+        BlendMode::SoftLight => {
+            if cs <= 0.5 {
+                cb - (1.0 - 2.0 * cs) * cb * (1.0 - cb)
+            } else {
+                let d = if cb <= 0.25 {
+                    ((16.0 * cb - 12.0) * cb + 4.0) * cb
+                } else {
+                    cb.sqrt()
+                };
+                cb + (2.0 * cs - 1.0) * (d - cb)
+            }
+        }
+        BlendMode::Difference => (cb - cs).abs(),
+        BlendMode::Exclusion => cb + cs - 2.0 * cb * cs,
+        BlendMode::Add => (cb + cs).min(1.0),
+        BlendMode::Subtract => (cb - cs).max(0.0),
+        // Normal and the non-separable modes take the source as is
+        _ => cs,
+    }
+}
+
+fn screen(cb: f32, cs: f32) -> f32 {
+    cb + cs - cb * cs
+}
+
+fn hard_light(cb: f32, cs: f32) -> f32 {
+    if cs <= 0.5 {
+        cb * 2.0 * cs
+    } else {
+        screen(cb, 2.0 * cs - 1.0)
+    }
+}
+
+// Non-separable helpers
+fn lum(c: [f32; 3]) -> f32 {
+    0.3 * c[0] + 0.59 * c[1] + 0.11 * c[2]
+}
+
+fn clip_color(c: [f32; 3]) -> [f32; 3] {
+    let l = lum(c);
+    let n = c[0].min(c[1]).min(c[2]);
+    let x = c[0].max(c[1]).max(c[2]);
+    let mut out = c;
+    if n < 0.0 {
+        for v in out.iter_mut() {
+            *v = l + (*v - l) * l / (l - n);
+        }
+    }
+    if x > 1.0 {
+        for v in out.iter_mut() {
+            *v = l + (*v - l) * (1.0 - l) / (x - l);
+        }
+    }
+    out
+}
+
+fn set_lum(c: [f32; 3], l: f32) -> [f32; 3] {
+    let d = l - lum(c);
+    clip_color([c[0] + d, c[1] + d, c[2] + d])
+}
+
+fn sat(c: [f32; 3]) -> f32 {
+    c[0].max(c[1]).max(c[2]) - c[0].min(c[1]).min(c[2])
+}
+
+fn set_sat(c: [f32; 3], s: f32) -> [f32; 3] {
+    let max = c[0].max(c[1]).max(c[2]);
+    let min = c[0].min(c[1]).min(c[2]);
+    let range = max - min;
+    let mut out = [0.0; 3];
+    if range > 0.0 {
+        for (o, v) in out.iter_mut().zip(c.iter()) {
+            *o = (v - min) * s / range;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: [f32; 3], expected: [f32; 3]) {
+        for c in 0..3 {
+            assert!(
+                (actual[c] - expected[c]).abs() < 1e-4,
+                "{actual:?} != {expected:?}"
+            );
+        }
+    }
+
+    const BACKDROP: [f32; 3] = [0.2, 0.5, 0.8];
+    const SOURCE: [f32; 3] = [0.6, 0.3, 0.9];
+
+    #[test]
+    fn separable_modes() {
+        assert_close(blend_rgb(BlendMode::Normal, BACKDROP, SOURCE), SOURCE);
+        assert_close(
+            blend_rgb(BlendMode::Multiply, BACKDROP, SOURCE),
+            [0.12, 0.15, 0.72],
+        );
+        assert_close(
+            blend_rgb(BlendMode::Screen, BACKDROP, SOURCE),
+            [0.68, 0.65, 0.98],
+        );
+        // Hard light with the layers swapped: 2 * cb * cs below half, screen above
+        assert_close(
+            blend_rgb(BlendMode::Overlay, BACKDROP, SOURCE),
+            [0.24, 0.3, 0.96],
+        );
+        assert_close(
+            blend_rgb(BlendMode::Difference, BACKDROP, SOURCE),
+            [0.4, 0.2, 0.1],
+        );
+        assert_close(
+            blend_rgb(BlendMode::Exclusion, BACKDROP, SOURCE),
+            [0.56, 0.5, 0.26],
+        );
+        assert_close(blend_rgb(BlendMode::Add, BACKDROP, SOURCE), [0.8, 0.8, 1.0]);
+        assert_close(
+            blend_rgb(BlendMode::Subtract, BACKDROP, SOURCE),
+            [0.0, 0.2, 0.0],
+        );
+    }
+
+    #[test]
+    fn dodge_and_burn_handle_the_extremes() {
+        let black = [0.0; 3];
+        let white = [1.0; 3];
+        assert_close(blend_rgb(BlendMode::ColorDodge, black, white), black);
+        assert_close(blend_rgb(BlendMode::ColorDodge, BACKDROP, white), white);
+        assert_close(blend_rgb(BlendMode::ColorBurn, white, black), white);
+        assert_close(blend_rgb(BlendMode::ColorBurn, BACKDROP, black), black);
+    }
+
+    #[test]
+    fn non_separable_modes_keep_the_right_luminosity() {
+        let luminosity = blend_rgb(BlendMode::Luminosity, BACKDROP, SOURCE);
+        assert!((lum(luminosity) - lum(SOURCE)).abs() < 1e-4);
+        for mode in [BlendMode::Hue, BlendMode::Saturation, BlendMode::Color] {
+            let out = blend_rgb(mode, BACKDROP, SOURCE);
+            assert!((lum(out) - lum(BACKDROP)).abs() < 1e-4, "{mode:?}");
+        }
+        // A gray source has no hue or saturation to give
+        let gray = [0.5; 3];
+        let l = lum(BACKDROP);
+        assert_close(blend_rgb(BlendMode::Hue, BACKDROP, gray), [l, l, l]);
+        assert_close(blend_rgb(BlendMode::Color, BACKDROP, gray), [l, l, l]);
+    }
+
+    #[test]
+    fn luminosity_clips_into_gamut() {
+        // Red raised to luminosity 0.5 overflows and is pulled back toward gray
+        let out = blend_rgb(BlendMode::Luminosity, [1.0, 0.0, 0.0], [0.5, 0.5, 0.5]);
+        assert_close(out, [1.0, 0.5 - 0.3 * 0.5 / 0.7, 0.5 - 0.3 * 0.5 / 0.7]);
+    }
+}
@@ -0,0 +1,290 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    packing::{
+        raw_to_webp,
+        tile::{encode_tiles, tile_bounds, TileEntry},
+    },
+    rgba::RgbaBuffer,
+};
+
+// Rough per-entry cost of a packed pixel diff ({ x, y, color })
+const PIXEL_ENTRY_BYTES: usize = 12;
+// Smallest possible tile patch: the header plus a uniform entry per tile
+const TILE_HEADER_BYTES: usize = 8;
+const TILE_UNIFORM_BYTES: usize = 9;
+// Smallest possible WebP: the RIFF header and one chunk header
+const WEBP_MIN_BYTES: usize = 20;
+
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DiffKind {
+    /// Buffers are identical
+    Unchanged = 0,
+    /// Position + previous packed RGBA32 per changed pixel
+    Pixel = 1,
+    /// Tile patch (see `RgbaBuffer.swapTiles`)
+    Tile = 2,
+    /// Bounding box + previous contents as WebP
+    Partial = 3,
+    /// Whole previous buffer as WebP
+    Whole = 4,
+}
+
+/// Result of `diff`: the cheapest undo representation of a change.
+#[wasm_bindgen]
+pub struct BufferDiff {
+    kind: DiffKind,
+    changed_pixels: u32,
+    bound_box: [u32; 4],
+    changed_tiles: Vec<u32>,
+    pixels: Vec<u32>,
+    swap_buffer: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl BufferDiff {
+    pub fn kind(&self) -> DiffKind {
+        self.kind
+    }
+
+    #[wasm_bindgen(js_name = changedPixels)]
+    pub fn changed_pixels(&self) -> u32 {
+        self.changed_pixels
+    }
+
+    /// Bounding box of the changed pixels as [x, y, width, height].
+    #[wasm_bindgen(js_name = boundBox)]
+    pub fn bound_box(&self) -> Vec<u32> {
+        self.bound_box.to_vec()
+    }
+
+    /// Row-major linear indices of the changed tiles.
+    #[wasm_bindgen(js_name = changedTiles)]
+    pub fn changed_tiles(&self) -> Vec<u32> {
+        self.changed_tiles.clone()
+    }
+
+    /// Flattened [x, y, color] triples for `Pixel` diffs, with the previous color
+    /// packed as (a << 24) | (r << 16) | (g << 8) | b like `rgbaToPackedU32`.
+    pub fn pixels(&self) -> Vec<u32> {
+        self.pixels.clone()
+    }
+
+    /// Encoded previous contents: WebP for `Partial`/`Whole`, a tile patch for `Tile`.
+    #[wasm_bindgen(js_name = swapBuffer)]
+    pub fn swap_buffer(&self) -> Vec<u8> {
+        self.swap_buffer.clone()
+    }
+}
+
+/// Compare two buffers of the same size and pick the smallest patch that restores `before`.
+/// `packBufferDiff` turns the result into `PackedDiffs` for `Anvil.applyPatch`.
+#[wasm_bindgen]
+pub fn diff(
+    before: &RgbaBuffer,
+    after: &RgbaBuffer,
+    tile_size: u32,
+) -> Result<BufferDiff, JsValue> {
+    if before.width != after.width || before.height != after.height {
+        return Err(JsValue::from_str(
+            "Buffers must have the same width/height.",
+        ));
+    }
+    if tile_size == 0 {
+        return Err(JsValue::from_str("Tile size must be positive."));
+    }
+
+    let width = before.width;
+    let height = before.height;
+    let cols = width.div_ceil(tile_size);
+    let rows = height.div_ceil(tile_size);
+    let mut tile_flags = vec![false; (cols * rows) as usize];
+
+    // Pixel lists longer than the raw buffer can never win
+    let pixel_limit = (width as usize * height as usize * 4) / PIXEL_ENTRY_BYTES;
+    let mut pixels = Vec::new();
+    let mut changed = 0usize;
+    let (mut min_x, mut min_y, mut max_x, mut max_y) = (u32::MAX, u32::MAX, 0u32, 0u32);
+
+    for y in 0..height {
+        let row_start = (y * width) as usize * 4;
+        let row_end = row_start + width as usize * 4;
+        if before.data[row_start..row_end] == after.data[row_start..row_end] {
+            continue;
+        }
+        for x in 0..width {
+            let idx = row_start + x as usize * 4;
+            let prev = &before.data[idx..idx + 4];
+            if prev == &after.data[idx..idx + 4] {
+                continue;
+            }
+            changed += 1;
+            min_x = min_x.min(x);
+            min_y = min_y.min(y);
+            max_x = max_x.max(x);
+            max_y = max_y.max(y);
+            tile_flags[((y / tile_size) * cols + x / tile_size) as usize] = true;
+            if changed <= pixel_limit {
+                let color = ((prev[3] as u32) << 24)
+                    | ((prev[0] as u32) << 16)
+                    | ((prev[1] as u32) << 8)
+                    | prev[2] as u32;
+                pixels.extend_from_slice(&[x, y, color]);
+            }
+        }
+    }
+
+    if changed == 0 {
+        return Ok(BufferDiff {
+            kind: DiffKind::Unchanged,
+            changed_pixels: 0,
+            bound_box: [0; 4],
+            changed_tiles: Vec::new(),
+            pixels: Vec::new(),
+            swap_buffer: Vec::new(),
+        });
+    }
+
+    let bound_box = [min_x, min_y, max_x - min_x + 1, max_y - min_y + 1];
+    let changed_tiles: Vec<u32> = tile_flags
+        .iter()
+        .enumerate()
+        .filter_map(|(i, &flag)| flag.then_some(i as u32))
+        .collect();
+
+    let pixel_cost = if changed <= pixel_limit {
+        changed * PIXEL_ENTRY_BYTES
+    } else {
+        usize::MAX
+    };
+
+    // Tile patch of the previous contents, skipped when even all-uniform tiles would not
+    // beat the pixel list (which wins ties)
+    let tile_patch = if TILE_HEADER_BYTES + TILE_UNIFORM_BYTES * changed_tiles.len() < pixel_cost {
+        let tiles: Vec<TileEntry> = changed_tiles
+            .iter()
+            .filter_map(|&index| {
+                let (x, y, w, h) = tile_bounds(width, height, tile_size, index)?;
+                Some(TileEntry {
+                    index,
+                    width: w,
+                    height: h,
+                    pixels: before.read_rect(x as i32, y as i32, w, h),
+                })
+            })
+            .collect();
+        Some(encode_tiles(tile_size, &tiles))
+    } else {
+        None
+    };
+    let tile_cost = tile_patch.as_ref().map_or(usize::MAX, Vec::len);
+
+    // Partial (or whole, when the bounding box spans the buffer) WebP of the previous
+    // contents, skipped when it cannot beat the pixel list or the tile patch
+    let is_whole = bound_box == [0, 0, width, height];
+    let partial_webp = if WEBP_MIN_BYTES < pixel_cost && WEBP_MIN_BYTES <= tile_cost {
+        let partial_raw = before.read_rect(
+            bound_box[0] as i32,
+            bound_box[1] as i32,
+            bound_box[2],
+            bound_box[3],
+        );
+        Some(raw_to_webp(&partial_raw, bound_box[2], bound_box[3]))
+    } else {
+        None
+    };
+    let webp_cost = partial_webp.as_ref().map_or(usize::MAX, Vec::len);
+
+    let (kind, swap_buffer) = if pixel_cost <= tile_cost && pixel_cost <= webp_cost {
+        (DiffKind::Pixel, Vec::new())
+    } else if tile_cost < webp_cost {
+        (DiffKind::Tile, tile_patch.unwrap_or_default())
+    } else if is_whole {
+        (DiffKind::Whole, partial_webp.unwrap_or_default())
+    } else {
+        (DiffKind::Partial, partial_webp.unwrap_or_default())
+    };
+
+    Ok(BufferDiff {
+        kind,
+        changed_pixels: changed as u32,
+        bound_box,
+        changed_tiles,
+        pixels: if kind == DiffKind::Pixel {
+            pixels
+        } else {
+            Vec::new()
+        },
+        swap_buffer,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Noisy but deterministic contents, so the encodings are not trivially small
+    fn noise(width: u32, height: u32, seed: u32) -> RgbaBuffer {
+        let mut buffer = RgbaBuffer::new(width, height);
+        let mut state = seed;
+        for v in buffer.data.iter_mut() {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            *v = (state >> 16) as u8;
+        }
+        buffer
+    }
+
+    #[test]
+    fn few_changes_make_a_pixel_diff() {
+        let before = noise(64, 64, 1);
+        let mut after = noise(64, 64, 1);
+        after.data[(10 * 64 + 20) * 4] ^= 0xff;
+        after.data[(40 * 64 + 5) * 4 + 3] ^= 0xff;
+
+        let result = diff(&before, &after, 16).unwrap();
+        assert_eq!(result.kind(), DiffKind::Pixel);
+        assert_eq!(result.changed_pixels(), 2);
+        assert_eq!(result.bound_box(), [5, 10, 16, 31]);
+        assert_eq!(result.changed_tiles(), [1, 8]);
+        assert_eq!(result.pixels().len(), 6);
+        assert!(result.swap_buffer().is_empty());
+    }
+
+    #[test]
+    fn large_changes_pick_the_smaller_encoding() {
+        let before = noise(64, 64, 1);
+        // Overwrite the top-left quarter with other noise
+        let mut after = noise(64, 64, 1);
+        let patch = noise(32, 32, 2);
+        for y in 0..32 {
+            let (dst, src) = (y * 64 * 4, y * 32 * 4);
+            after.data[dst..dst + 32 * 4].copy_from_slice(&patch.data[src..src + 32 * 4]);
+        }
+
+        let result = diff(&before, &after, 16).unwrap();
+        assert_eq!(result.changed_tiles(), [0, 1, 4, 5]);
+        let tiles: Vec<TileEntry> = [0u32, 1, 4, 5]
+            .iter()
+            .map(|&index| {
+                let (x, y, w, h) = tile_bounds(64, 64, 16, index).unwrap();
+                TileEntry {
+                    index,
+                    width: w,
+                    height: h,
+                    pixels: before.read_rect(x as i32, y as i32, w, h),
+                }
+            })
+            .collect();
+        let tile_patch = encode_tiles(16, &tiles);
+        let webp = raw_to_webp(&before.read_rect(0, 0, 32, 32), 32, 32);
+        let (kind, expected) = if tile_patch.len() < webp.len() {
+            (DiffKind::Tile, tile_patch)
+        } else {
+            (DiffKind::Partial, webp)
+        };
+        assert_eq!(result.kind(), kind);
+        assert_eq!(result.swap_buffer(), expected);
+        assert!(result.pixels().is_empty());
+    }
+}
@@ -0,0 +1,521 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{selection::SelectionMask, stroke::line_pixels};
+
+mod outline;
+pub mod path;
+pub(crate) mod raster;
+mod shapes;
+
+use outline::{stroke_outline, StrokeStyle};
+use path::{Path, Subpath};
+use raster::{fill_contours, oriented, Mask};
+use shapes::{
+    capsule_contour, disc_offsets, ellipse_contour, ellipse_outline, ellipse_spans,
+    rounded_rect_contour,
+};
+
+/// Which areas of a self-intersecting or nested outline count as inside.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FillRule {
+    /// Inside wherever the outline winds around the point at least once
+    NonZero = 0,
+    /// Inside wherever the outline crosses an odd number of times
+    EvenOdd = 1,
+}
+
+impl FillRule {
+    pub(crate) fn is_inside(self, winding: i32) -> bool {
+        match self {
+            FillRule::NonZero => winding != 0,
+            FillRule::EvenOdd => winding % 2 != 0,
+        }
+    }
+}
+
+/// Shape of stroke corners.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LineJoin {
+    /// Sharp corner, beveled past `miter_limit`
+    Miter = 0,
+    Round = 1,
+    Bevel = 2,
+}
+
+/// Shape of open stroke ends.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LineCap {
+    /// Ends flush with the endpoint
+    Butt = 0,
+    Round = 1,
+    /// Extends half the stroke width past the endpoint
+    Square = 2,
+}
+
+/// How a shape is drawn. `selection` is set through `setSelection` and limits the shape
+/// like in the fill functions.
+#[wasm_bindgen]
+pub struct ShapeOption {
+    /// Paint the interior
+    pub fill: bool,
+    /// Outline width in pixels (0 = no outline). Rectangles and ellipses draw it inside
+    /// their bounds, polygons and paths center it on the outline, and lines always use
+    /// at least 1.
+    pub stroke_width: f32,
+    /// Antialiased coverage instead of aliased pixel-art output
+    pub antialias: bool,
+    /// Inside test for polygon and path fills
+    pub fill_rule: FillRule,
+    /// Corner shape of antialiased polygon and path outlines
+    pub line_join: LineJoin,
+    /// End shape of open path outlines
+    pub line_cap: LineCap,
+    /// Longest miter, in stroke widths, before a `Miter` join is beveled
+    pub miter_limit: f32,
+    selection: Option<SelectionMask>,
+}
+
+#[wasm_bindgen]
+impl ShapeOption {
+    /// `fill_rule`, `line_join`, `line_cap` and `miter_limit` start as `NonZero`, `Miter`,
+    /// `Butt` and 4 and are set as properties.
+    #[wasm_bindgen(constructor)]
+    pub fn new(fill: bool, stroke_width: f32, antialias: bool) -> ShapeOption {
+        ShapeOption {
+            fill,
+            stroke_width,
+            antialias,
+            fill_rule: FillRule::NonZero,
+            line_join: LineJoin::Miter,
+            line_cap: LineCap::Butt,
+            miter_limit: 4.0,
+            selection: None,
+        }
+    }
+
+    /// Limit drawing to `selection` (copied; it must match the target buffer size).
+    #[wasm_bindgen(js_name = setSelection)]
+    pub fn set_selection(&mut self, selection: &SelectionMask) {
+        self.selection = Some(selection.slice());
+    }
+
+    #[wasm_bindgen(js_name = clearSelection)]
+    pub fn clear_selection(&mut self) {
+        self.selection = None;
+    }
+}
+
+impl ShapeOption {
+    pub(crate) fn selection(&self) -> Option<&SelectionMask> {
+        self.selection.as_ref()
+    }
+
+    // Aliased outline width, rounded to whole pixels
+    fn pixel_width(&self) -> i64 {
+        self.stroke_width.round().max(1.0) as i64
+    }
+
+    fn has_stroke(&self) -> bool {
+        self.stroke_width > 0.0
+    }
+
+    fn stroke_style(&self) -> StrokeStyle {
+        StrokeStyle {
+            half_width: self.stroke_width / 2.0,
+            join: self.line_join,
+            cap: self.line_cap,
+            miter_limit: self.miter_limit,
+        }
+    }
+}
+
+/// Coverage of a line from (x0, y0) to (x1, y1). Aliased lines follow Bresenham between
+/// the pixels holding the endpoints and use a round dab when wider than 1; antialiased
+/// lines have round caps.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn line_mask(
+    width: u32,
+    height: u32,
+    x0: f32,
+    y0: f32,
+    x1: f32,
+    y1: f32,
+    options: &ShapeOption,
+) -> Option<Mask> {
+    let stroke_width = options.stroke_width.max(1.0);
+    if !options.antialias {
+        let pixels = line_pixels(
+            x0.floor() as i64,
+            y0.floor() as i64,
+            x1.floor() as i64,
+            y1.floor() as i64,
+        );
+        return stamped_mask(width, height, &pixels, options.pixel_width());
+    }
+    let contours = [capsule_contour((x0, y0), (x1, y1), stroke_width / 2.0)];
+    filled_mask(width, height, &contours, true)
+}
+
+/// Coverage of the rectangle (x, y, rect_width, rect_height), with corners rounded by
+/// `radius`. Aliased mode snaps the bounds to whole pixels first.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn rect_mask(
+    width: u32,
+    height: u32,
+    x: f32,
+    y: f32,
+    rect_width: f32,
+    rect_height: f32,
+    radius: f32,
+    options: &ShapeOption,
+) -> Option<Mask> {
+    let (mut left, mut top) = (x.min(x + rect_width), y.min(y + rect_height));
+    let (mut right, mut bottom) = (x.max(x + rect_width), y.max(y + rect_height));
+    let (mut radius, mut inset) = (radius.max(0.0), options.stroke_width);
+    if !options.antialias {
+        (left, top, right, bottom) = (left.round(), top.round(), right.round(), bottom.round());
+        radius = radius.round();
+        inset = options.pixel_width() as f32;
+    }
+    if left >= right || top >= bottom {
+        return None;
+    }
+
+    let mut contours = vec![oriented(
+        rounded_rect_contour(left, top, right, bottom, radius),
+        true,
+    )];
+    if !options.fill && options.has_stroke() {
+        let (inner_left, inner_top) = (left + inset, top + inset);
+        let (inner_right, inner_bottom) = (right - inset, bottom - inset);
+        if inner_left < inner_right && inner_top < inner_bottom {
+            contours.push(oriented(
+                rounded_rect_contour(
+                    inner_left,
+                    inner_top,
+                    inner_right,
+                    inner_bottom,
+                    (radius - inset).max(0.0),
+                ),
+                false,
+            ));
+        }
+    } else if !options.fill {
+        return None;
+    }
+    filled_mask(width, height, &contours, options.antialias)
+}
+
+/// Coverage of the ellipse inscribed in (x, y, box_width, box_height). Aliased mode uses
+/// the midpoint ellipse over the whole-pixel box.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn ellipse_mask(
+    width: u32,
+    height: u32,
+    x: f32,
+    y: f32,
+    box_width: f32,
+    box_height: f32,
+    options: &ShapeOption,
+) -> Option<Mask> {
+    if !options.fill && !options.has_stroke() {
+        return None;
+    }
+    let (left, top) = (x.min(x + box_width), y.min(y + box_height));
+    let (right, bottom) = (x.max(x + box_width), y.max(y + box_height));
+    if !options.antialias {
+        // Inclusive pixel box
+        let (x0, y0) = (left.round() as i64, top.round() as i64);
+        let (x1, y1) = (right.round() as i64 - 1, bottom.round() as i64 - 1);
+        if x0 > x1 || y0 > y1 {
+            return None;
+        }
+        let mut mask = Mask::new(x0, y0, x1 + 1, y1 + 1, width, height)?;
+        if !options.fill && options.pixel_width() == 1 {
+            for (px, py) in ellipse_outline(x0, y0, x1, y1) {
+                mask.set_max(px, py, 255);
+            }
+            return Some(mask);
+        }
+        fill_ellipse_spans(&mut mask, x0, y0, x1, y1, 255);
+        if !options.fill {
+            let inset = options.pixel_width();
+            if x0 + inset <= x1 - inset && y0 + inset <= y1 - inset {
+                fill_ellipse_spans(&mut mask, x0 + inset, y0 + inset, x1 - inset, y1 - inset, 0);
+            }
+        }
+        return Some(mask);
+    }
+
+    let (cx, cy) = ((left + right) / 2.0, (top + bottom) / 2.0);
+    let (rx, ry) = ((right - left) / 2.0, (bottom - top) / 2.0);
+    if rx <= 0.0 || ry <= 0.0 {
+        return None;
+    }
+    let mut contours = vec![oriented(ellipse_contour(cx, cy, rx, ry), true)];
+    let inset = options.stroke_width;
+    if !options.fill && rx > inset && ry > inset {
+        contours.push(oriented(
+            ellipse_contour(cx, cy, rx - inset, ry - inset),
+            false,
+        ));
+    }
+    filled_mask(width, height, &contours, true)
+}
+
+/// Coverage of the closed polygon through `points` ([x0, y0, x1, y1, ...]).
+pub(crate) fn polygon_mask(
+    width: u32,
+    height: u32,
+    points: &[f32],
+    options: &ShapeOption,
+) -> Option<Mask> {
+    let points: Vec<(f32, f32)> = points.chunks_exact(2).map(|p| (p[0], p[1])).collect();
+    if points.is_empty() || (!options.fill && !options.has_stroke()) {
+        return None;
+    }
+    let edges = (0..points.len()).map(|i| (points[i], points[(i + 1) % points.len()]));
+
+    if !options.antialias {
+        let mut outline = None;
+        if options.has_stroke() {
+            let mut pixels = Vec::new();
+            for (a, b) in edges {
+                pixels.extend(line_pixels(
+                    a.0.floor() as i64,
+                    a.1.floor() as i64,
+                    b.0.floor() as i64,
+                    b.1.floor() as i64,
+                ));
+            }
+            outline = stamped_mask(width, height, &pixels, options.pixel_width());
+        }
+        let fill = if options.fill {
+            let mut mask = Mask::around(std::slice::from_ref(&points), width, height)?;
+            fill_contours(&mut mask, &[points], options.fill_rule, false);
+            Some(mask)
+        } else {
+            None
+        };
+        return union(fill, outline, width, height);
+    }
+    subpaths_mask(width, height, &[Subpath::polygon(points)], options)
+}
+
+/// Coverage of `path`. Open subpaths are closed for the fill.
+pub(crate) fn path_mask(
+    width: u32,
+    height: u32,
+    path: &Path,
+    options: &ShapeOption,
+) -> Option<Mask> {
+    subpaths_mask(width, height, path.subpaths(), options)
+}
+
+// Fill with `fill_rule` and stroke with the join and cap style of `options`
+fn subpaths_mask(
+    width: u32,
+    height: u32,
+    subpaths: &[Subpath],
+    options: &ShapeOption,
+) -> Option<Mask> {
+    let fill: Vec<_> = if options.fill {
+        subpaths
+            .iter()
+            .filter(|s| s.points.len() > 2)
+            .map(|s| s.points.clone())
+            .collect()
+    } else {
+        Vec::new()
+    };
+    let outline: Vec<_> = if options.has_stroke() {
+        let style = options.stroke_style();
+        subpaths
+            .iter()
+            .flat_map(|s| stroke_outline(s, &style))
+            .collect()
+    } else {
+        Vec::new()
+    };
+    let bounds: Vec<_> = fill.iter().chain(&outline).cloned().collect();
+    let mut mask = Mask::around(&bounds, width, height)?;
+    // The outline pieces share one orientation so non-zero gives their union; the fill
+    // is rasterized separately so its own winding cannot cancel them out.
+    fill_contours(&mut mask, &fill, options.fill_rule, options.antialias);
+    fill_contours(&mut mask, &outline, FillRule::NonZero, options.antialias);
+    Some(mask)
+}
+
+// Stamp a round dab of `diameter` on every pixel of `pixels`
+fn stamped_mask(width: u32, height: u32, pixels: &[(i64, i64)], diameter: i64) -> Option<Mask> {
+    let offsets = disc_offsets(diameter as u32);
+    let (mut left, mut top, mut right, mut bottom) = (i64::MAX, i64::MAX, i64::MIN, i64::MIN);
+    for &(x, y) in pixels {
+        left = left.min(x - diameter);
+        top = top.min(y - diameter);
+        right = right.max(x + diameter + 1);
+        bottom = bottom.max(y + diameter + 1);
+    }
+    let mut mask = Mask::new(left, top, right, bottom, width, height)?;
+    for &(x, y) in pixels {
+        for &(dx, dy) in &offsets {
+            mask.set_max(x + dx, y + dy, 255);
+        }
+    }
+    Some(mask)
+}
+
+// Non-zero fill of `contours` in a mask fitted around them
+fn filled_mask(
+    width: u32,
+    height: u32,
+    contours: &[Vec<(f32, f32)>],
+    antialias: bool,
+) -> Option<Mask> {
+    let mut mask = Mask::around(contours, width, height)?;
+    fill_contours(&mut mask, contours, FillRule::NonZero, antialias);
+    Some(mask)
+}
+
+// Mask covering both inputs
+fn union(a: Option<Mask>, b: Option<Mask>, width: u32, height: u32) -> Option<Mask> {
+    let (a, b) = match (a, b) {
+        (Some(a), Some(b)) => (a, b),
+        (a, b) => return a.or(b),
+    };
+    let mut out = Mask::new(
+        a.x.min(b.x),
+        a.y.min(b.y),
+        (a.x + a.width as i64).max(b.x + b.width as i64),
+        (a.y + a.height as i64).max(b.y + b.height as i64),
+        width,
+        height,
+    )?;
+    for source in [&a, &b] {
+        for my in 0..source.height {
+            for mx in 0..source.width {
+                let value = source.data[my * source.width + mx];
+                out.set_max(source.x + mx as i64, source.y + my as i64, value);
+            }
+        }
+    }
+    Some(out)
+}
+
+// Write `value` over every pixel inside the midpoint ellipse of the inclusive box
+fn fill_ellipse_spans(mask: &mut Mask, x0: i64, y0: i64, x1: i64, y1: i64, value: u8) {
+    for (row, (start, end)) in ellipse_spans(x0, y0, x1, y1).into_iter().enumerate() {
+        let y = y0 + row as i64;
+        for x in start..=end {
+            mask.set(x, y, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count(mask: &Mask) -> usize {
+        mask.data.iter().filter(|v| **v == 255).count()
+    }
+
+    // Covered area in pixels
+    fn area(mask: &Mask) -> f32 {
+        mask.data.iter().map(|v| *v as f32).sum::<f32>() / 255.0
+    }
+
+    fn square_path(left: f32, top: f32, right: f32, bottom: f32) -> Path {
+        let mut path = Path::new();
+        path.move_to(left, top);
+        path.line_to(right, top);
+        path.line_to(right, bottom);
+        path.line_to(left, bottom);
+        path.close();
+        path
+    }
+
+    #[test]
+    fn aliased_rect_outline_and_fill_counts() {
+        let fill = rect_mask(
+            32,
+            32,
+            2.0,
+            3.0,
+            10.0,
+            6.0,
+            0.0,
+            &ShapeOption::new(true, 0.0, false),
+        );
+        assert_eq!(count(&fill.unwrap()), 60);
+
+        let outline = rect_mask(
+            32,
+            32,
+            2.0,
+            3.0,
+            10.0,
+            6.0,
+            0.0,
+            &ShapeOption::new(false, 1.0, false),
+        );
+        assert_eq!(count(&outline.unwrap()), 2 * (10 + 6) - 4);
+
+        let outline = rect_mask(
+            32,
+            32,
+            2.0,
+            3.0,
+            10.0,
+            6.0,
+            0.0,
+            &ShapeOption::new(false, 2.0, false),
+        );
+        assert_eq!(count(&outline.unwrap()), 60 - 6 * 2);
+    }
+
+    #[test]
+    fn aliased_polygon_fill_count() {
+        let points = [2.0, 2.0, 8.0, 2.0, 8.0, 6.0, 2.0, 6.0];
+        let fill = polygon_mask(32, 32, &points, &ShapeOption::new(true, 0.0, false));
+        assert_eq!(count(&fill.unwrap()), 6 * 4);
+    }
+
+    #[test]
+    fn antialiased_path_fill_and_outline_areas() {
+        let path = square_path(2.0, 2.0, 8.0, 6.0);
+        let fill = path_mask(32, 32, &path, &ShapeOption::new(true, 0.0, true)).unwrap();
+        assert_eq!(count(&fill), 24);
+        assert!((area(&fill) - 24.0).abs() < 0.1, "{}", area(&fill));
+
+        // A 2 pixel outline centered on the edge: 8x6 outside minus 4x2 inside
+        let outline = path_mask(32, 32, &path, &ShapeOption::new(false, 2.0, true)).unwrap();
+        assert!((area(&outline) - 40.0).abs() < 0.1, "{}", area(&outline));
+
+        // Half-pixel edges are half covered
+        let path = square_path(2.5, 2.0, 8.5, 6.0);
+        let fill = path_mask(32, 32, &path, &ShapeOption::new(true, 0.0, true)).unwrap();
+        assert_eq!(count(&fill), 20);
+        assert!((area(&fill) - 24.0).abs() < 0.1, "{}", area(&fill));
+    }
+
+    #[test]
+    fn open_path_caps_extend_the_outline() {
+        let mut path = Path::new();
+        path.move_to(2.0, 4.0);
+        path.line_to(8.0, 4.0);
+        let mut options = ShapeOption::new(false, 2.0, true);
+        for (cap, expected) in [(LineCap::Butt, 12.0), (LineCap::Square, 16.0)] {
+            options.line_cap = cap;
+            let outline = path_mask(32, 32, &path, &options).unwrap();
+            assert!(
+                (area(&outline) - expected).abs() < 0.1,
+                "{cap:?} {}",
+                area(&outline)
+            );
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use std::f32::consts::PI;
+
+use crate::draw::{
+    path::Subpath,
+    raster::oriented,
+    shapes::{arc_segments, ellipse_contour, rect_contour},
+    LineCap, LineJoin,
+};
+
+pub(crate) struct StrokeStyle {
+    pub(crate) half_width: f32,
+    pub(crate) join: LineJoin,
+    pub(crate) cap: LineCap,
+    pub(crate) miter_limit: f32,
+}
+
+/// Pieces whose non-zero union is the stroke of `subpath`: a quad per segment plus join
+/// and cap pieces, all turning the same way so overlaps never cancel out.
+pub(crate) fn stroke_outline(subpath: &Subpath, style: &StrokeStyle) -> Vec<Vec<(f32, f32)>> {
+    let hw = style.half_width;
+    let mut points: Vec<(f32, f32)> = Vec::with_capacity(subpath.points.len());
+    let mut smooth = Vec::with_capacity(subpath.points.len());
+    for (&point, &is_smooth) in subpath.points.iter().zip(&subpath.smooth) {
+        if points.last() != Some(&point) {
+            points.push(point);
+            smooth.push(is_smooth);
+        }
+    }
+    if subpath.closed && points.len() > 1 && points.first() == points.last() {
+        points.pop();
+        smooth.pop();
+    }
+
+    let mut pieces = Vec::new();
+    if hw <= 0.0 || points.is_empty() {
+        return pieces;
+    }
+    if points.len() == 1 {
+        // Zero-length subpath: only caps leave a mark
+        let (x, y) = points[0];
+        match style.cap {
+            LineCap::Butt => {}
+            LineCap::Round => pieces.push(ellipse_contour(x, y, hw, hw)),
+            LineCap::Square => pieces.push(rect_contour(x - hw, y - hw, x + hw, y + hw)),
+        }
+        return pieces.into_iter().map(|p| oriented(p, true)).collect();
+    }
+
+    let closed = subpath.closed && points.len() > 2;
+    let count = points.len();
+    let segments = if closed { count } else { count - 1 };
+    for i in 0..segments {
+        let (a, b) = (points[i], points[(i + 1) % count]);
+        let n = normal(a, b, hw);
+        pieces.push(vec![
+            (a.0 + n.0, a.1 + n.1),
+            (b.0 + n.0, b.1 + n.1),
+            (b.0 - n.0, b.1 - n.1),
+            (a.0 - n.0, a.1 - n.1),
+        ]);
+    }
+
+    let joints = if closed { 0..count } else { 1..count - 1 };
+    for i in joints {
+        let prev = points[(i + count - 1) % count];
+        let (p, next) = (points[i], points[(i + 1) % count]);
+        let join = if smooth[i] {
+            LineJoin::Miter
+        } else {
+            style.join
+        };
+        if let Some(piece) = join_piece(prev, p, next, hw, join, style.miter_limit) {
+            pieces.push(piece);
+        }
+    }
+
+    if !closed {
+        for (end, inner) in [
+            (points[0], points[1]),
+            (points[count - 1], points[count - 2]),
+        ] {
+            if let Some(piece) = cap_piece(end, inner, hw, style.cap) {
+                pieces.push(piece);
+            }
+        }
+    }
+    pieces.into_iter().map(|p| oriented(p, true)).collect()
+}
+
+// Offset perpendicular to a -> b with length `hw`
+fn normal(a: (f32, f32), b: (f32, f32), hw: f32) -> (f32, f32) {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let length = (dx * dx + dy * dy).sqrt();
+    (-dy / length * hw, dx / length * hw)
+}
+
+// Wedge filling the outer gap between the segments meeting at `p`
+fn join_piece(
+    prev: (f32, f32),
+    p: (f32, f32),
+    next: (f32, f32),
+    hw: f32,
+    join: LineJoin,
+    miter_limit: f32,
+) -> Option<Vec<(f32, f32)>> {
+    let (n0, n1) = (normal(prev, p, hw), normal(p, next, hw));
+    let (d0, d1) = ((p.0 - prev.0, p.1 - prev.1), (next.0 - p.0, next.1 - p.1));
+    let cross = d0.0 * d1.1 - d0.1 * d1.0;
+    if cross.abs() < 1e-9 && d0.0 * d1.0 + d0.1 * d1.1 >= 0.0 {
+        return None;
+    }
+    // The outer side is opposite the turn
+    let sign = if cross > 0.0 { -1.0 } else { 1.0 };
+    let a = (p.0 + n0.0 * sign, p.1 + n0.1 * sign);
+    let b = (p.0 + n1.0 * sign, p.1 + n1.1 * sign);
+
+    match join {
+        LineJoin::Round => Some(ellipse_contour(p.0, p.1, hw, hw)),
+        LineJoin::Bevel => Some(vec![p, a, b]),
+        LineJoin::Miter => {
+            // Miter length over stroke width is 1 / sin(theta / 2)
+            let cos_theta = (n0.0 * n1.0 + n0.1 * n1.1) / (hw * hw);
+            let half_sin = ((1.0 + cos_theta) / 2.0).sqrt();
+            if half_sin <= 1e-6 || 1.0 / half_sin > miter_limit {
+                return Some(vec![p, a, b]);
+            }
+            let (mx, my) = (a.0 + b.0 - 2.0 * p.0, a.1 + b.1 - 2.0 * p.1);
+            let scale = hw / half_sin / (mx * mx + my * my).sqrt();
+            Some(vec![p, a, (p.0 + mx * scale, p.1 + my * scale), b])
+        }
+    }
+}
+
+// Cap at `end`, pointing away from `inner`
+fn cap_piece(end: (f32, f32), inner: (f32, f32), hw: f32, cap: LineCap) -> Option<Vec<(f32, f32)>> {
+    let n = normal(inner, end, hw);
+    let d = (n.1, -n.0);
+    match cap {
+        LineCap::Butt => None,
+        LineCap::Square => Some(vec![
+            (end.0 + n.0, end.1 + n.1),
+            (end.0 + n.0 + d.0, end.1 + n.1 + d.1),
+            (end.0 - n.0 + d.0, end.1 - n.1 + d.1),
+            (end.0 - n.0, end.1 - n.1),
+        ]),
+        LineCap::Round => {
+            let start = n.1.atan2(n.0);
+            let segments = arc_segments(hw, PI);
+            Some(
+                (0..=segments)
+                    .map(|i| {
+                        let angle = start - PI * i as f32 / segments as f32;
+                        (end.0 + hw * angle.cos(), end.1 + hw * angle.sin())
+                    })
+                    .collect(),
+            )
+        }
+    }
+}
@@ -0,0 +1,161 @@
+use wasm_bindgen::prelude::*;
+
+use crate::draw::shapes::TOLERANCE;
+
+/// Outline built from move/line/quadratic/cubic segments, in canvas pixels.
+/// Curves are flattened as they are added.
+#[wasm_bindgen]
+#[derive(Clone, Default)]
+pub struct Path {
+    subpaths: Vec<Subpath>,
+}
+
+/// One connected run of a path. `smooth[i]` marks points produced by curve flattening,
+/// which the stroker joins without a visible corner.
+#[derive(Clone, Default)]
+pub(crate) struct Subpath {
+    pub(crate) points: Vec<(f32, f32)>,
+    pub(crate) smooth: Vec<bool>,
+    pub(crate) closed: bool,
+}
+
+impl Subpath {
+    pub(crate) fn polygon(points: Vec<(f32, f32)>) -> Subpath {
+        Subpath {
+            smooth: vec![false; points.len()],
+            points,
+            closed: true,
+        }
+    }
+
+    fn push(&mut self, point: (f32, f32), smooth: bool) {
+        self.points.push(point);
+        self.smooth.push(smooth);
+    }
+}
+
+#[wasm_bindgen]
+impl Path {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Path {
+        Path::default()
+    }
+
+    /// Start a new subpath at (x, y).
+    #[wasm_bindgen(js_name = moveTo)]
+    pub fn move_to(&mut self, x: f32, y: f32) {
+        if self.subpaths.last().is_some_and(|s| s.points.len() == 1) {
+            self.subpaths.pop();
+        }
+        let mut subpath = Subpath::default();
+        subpath.push((x, y), false);
+        self.subpaths.push(subpath);
+    }
+
+    #[wasm_bindgen(js_name = lineTo)]
+    pub fn line_to(&mut self, x: f32, y: f32) {
+        self.current().push((x, y), false);
+    }
+
+    /// Quadratic Bézier through control point (cx, cy) to (x, y).
+    #[wasm_bindgen(js_name = quadTo)]
+    pub fn quad_to(&mut self, cx: f32, cy: f32, x: f32, y: f32) {
+        let subpath = self.current();
+        let p0 = *subpath.points.last().unwrap_or(&(cx, cy));
+        let (p1, p2) = ((cx, cy), (x, y));
+        // Wang's formula for degree 2
+        let dd = length((p0.0 - 2.0 * p1.0 + p2.0, p0.1 - 2.0 * p1.1 + p2.1));
+        let segments = segment_count(0.25 * dd);
+        for i in 1..=segments {
+            let t = i as f32 / segments as f32;
+            let u = 1.0 - t;
+            subpath.push(
+                (
+                    u * u * p0.0 + 2.0 * u * t * p1.0 + t * t * p2.0,
+                    u * u * p0.1 + 2.0 * u * t * p1.1 + t * t * p2.1,
+                ),
+                i != segments,
+            );
+        }
+    }
+
+    /// Cubic Bézier through control points (c1x, c1y) and (c2x, c2y) to (x, y).
+    #[wasm_bindgen(js_name = cubicTo)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn cubic_to(&mut self, c1x: f32, c1y: f32, c2x: f32, c2y: f32, x: f32, y: f32) {
+        let subpath = self.current();
+        let p0 = *subpath.points.last().unwrap_or(&(c1x, c1y));
+        let (p1, p2, p3) = ((c1x, c1y), (c2x, c2y), (x, y));
+        // Wang's formula for degree 3
+        let dd = length((p0.0 - 2.0 * p1.0 + p2.0, p0.1 - 2.0 * p1.1 + p2.1))
+            .max(length((p1.0 - 2.0 * p2.0 + p3.0, p1.1 - 2.0 * p2.1 + p3.1)));
+        let segments = segment_count(0.75 * dd);
+        for i in 1..=segments {
+            let t = i as f32 / segments as f32;
+            let u = 1.0 - t;
+            let (a, b, c, d) = (u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t);
+            subpath.push(
+                (
+                    a * p0.0 + b * p1.0 + c * p2.0 + d * p3.0,
+                    a * p0.1 + b * p1.1 + c * p2.1 + d * p3.1,
+                ),
+                i != segments,
+            );
+        }
+    }
+
+    /// Close the current subpath back to its first point. The next segment starts from there.
+    pub fn close(&mut self) {
+        let Some(subpath) = self.subpaths.last_mut() else {
+            return;
+        };
+        if subpath.closed {
+            return;
+        }
+        subpath.closed = true;
+        if subpath.points.len() > 1 && subpath.points.first() == subpath.points.last() {
+            subpath.points.pop();
+            subpath.smooth.pop();
+        }
+    }
+
+    #[wasm_bindgen(js_name = isEmpty)]
+    pub fn is_empty(&self) -> bool {
+        self.subpaths.iter().all(|s| s.points.len() < 2)
+    }
+
+    pub fn clear(&mut self) {
+        self.subpaths.clear();
+    }
+}
+
+impl Path {
+    pub(crate) fn subpaths(&self) -> &[Subpath] {
+        &self.subpaths
+    }
+
+    // Subpath that the next segment extends, opening a new one after `close`
+    fn current(&mut self) -> &mut Subpath {
+        let start = match self.subpaths.last() {
+            Some(subpath) if !subpath.closed => None,
+            Some(subpath) => subpath.points.first().copied(),
+            None => None,
+        };
+        if self.subpaths.last().is_none_or(|s| s.closed) {
+            let mut subpath = Subpath::default();
+            if let Some(start) = start {
+                subpath.push(start, false);
+            }
+            self.subpaths.push(subpath);
+        }
+        self.subpaths.last_mut().unwrap()
+    }
+}
+
+fn length(v: (f32, f32)) -> f32 {
+    (v.0 * v.0 + v.1 * v.1).sqrt()
+}
+
+fn segment_count(deviation: f32) -> usize {
+    ((deviation / TOLERANCE).sqrt().ceil() as usize).clamp(1, 1024)
+}
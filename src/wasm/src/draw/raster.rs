@@ -0,0 +1,198 @@
+use crate::draw::FillRule;
+
+/// Sub-scanlines per pixel row in antialiased mode (horizontal coverage is exact).
+const SUBSAMPLES: usize = 16;
+
+/// Coverage for a canvas-space rectangle, so shapes only touch the pixels they can reach.
+pub(crate) struct Mask {
+    pub(crate) x: i64,
+    pub(crate) y: i64,
+    pub(crate) width: usize,
+    pub(crate) height: usize,
+    pub(crate) data: Vec<u8>,
+}
+
+impl Mask {
+    /// Mask for [left, right) x [top, bottom) clipped to the canvas. None if nothing is left.
+    pub(crate) fn new(
+        left: i64,
+        top: i64,
+        right: i64,
+        bottom: i64,
+        canvas_width: u32,
+        canvas_height: u32,
+    ) -> Option<Mask> {
+        let (left, top) = (left.max(0), top.max(0));
+        let right = right.min(canvas_width as i64);
+        let bottom = bottom.min(canvas_height as i64);
+        if left >= right || top >= bottom {
+            return None;
+        }
+        let (width, height) = ((right - left) as usize, (bottom - top) as usize);
+        Some(Mask {
+            x: left,
+            y: top,
+            width,
+            height,
+            data: vec![0; width * height],
+        })
+    }
+
+    /// Mask covering the float bounds of `contours` plus a pixel of margin.
+    pub(crate) fn around(
+        contours: &[Vec<(f32, f32)>],
+        canvas_width: u32,
+        canvas_height: u32,
+    ) -> Option<Mask> {
+        let mut bounds = [f32::INFINITY, f32::INFINITY, -f32::INFINITY, -f32::INFINITY];
+        for &(x, y) in contours.iter().flatten() {
+            bounds[0] = bounds[0].min(x);
+            bounds[1] = bounds[1].min(y);
+            bounds[2] = bounds[2].max(x);
+            bounds[3] = bounds[3].max(y);
+        }
+        if !bounds.iter().all(|v| v.is_finite()) {
+            return None;
+        }
+        Mask::new(
+            bounds[0].floor() as i64 - 1,
+            bounds[1].floor() as i64 - 1,
+            bounds[2].ceil() as i64 + 1,
+            bounds[3].ceil() as i64 + 1,
+            canvas_width,
+            canvas_height,
+        )
+    }
+
+    /// Raise the coverage at canvas position (x, y); positions outside the mask are ignored.
+    pub(crate) fn set_max(&mut self, x: i64, y: i64, value: u8) {
+        if x < self.x || y < self.y {
+            return;
+        }
+        let (mx, my) = ((x - self.x) as usize, (y - self.y) as usize);
+        if mx < self.width && my < self.height {
+            let slot = &mut self.data[my * self.width + mx];
+            *slot = (*slot).max(value);
+        }
+    }
+
+    /// Overwrite the coverage at canvas position (x, y), ignoring positions outside the mask.
+    pub(crate) fn set(&mut self, x: i64, y: i64, value: u8) {
+        if x < self.x || y < self.y {
+            return;
+        }
+        let (mx, my) = ((x - self.x) as usize, (y - self.y) as usize);
+        if mx < self.width && my < self.height {
+            self.data[my * self.width + mx] = value;
+        }
+    }
+}
+
+/// Signed area of a closed contour (positive when it turns clockwise on screen).
+pub(crate) fn signed_area(contour: &[(f32, f32)]) -> f32 {
+    let mut area = 0.0;
+    for (i, &(x0, y0)) in contour.iter().enumerate() {
+        let (x1, y1) = contour[(i + 1) % contour.len()];
+        area += x0 * y1 - x1 * y0;
+    }
+    area / 2.0
+}
+
+/// Reverse `contour` if needed so its orientation matches `clockwise`. Outlines and holes
+/// built this way combine correctly under the non-zero rule.
+pub(crate) fn oriented(mut contour: Vec<(f32, f32)>, clockwise: bool) -> Vec<(f32, f32)> {
+    if (signed_area(&contour) > 0.0) != clockwise {
+        contour.reverse();
+    }
+    contour
+}
+
+/// Scanline-fill closed `contours` into `mask`, keeping the larger coverage where the mask
+/// is already painted.
+///
+/// Antialiased mode averages `SUBSAMPLES` sub-scanlines with exact horizontal coverage;
+/// aliased mode covers a pixel when its center is inside.
+pub(crate) fn fill_contours(
+    mask: &mut Mask,
+    contours: &[Vec<(f32, f32)>],
+    rule: FillRule,
+    antialias: bool,
+) {
+    // (x0, y0, x1, y1, winding) with y0 < y1
+    let mut edges = Vec::new();
+    for contour in contours {
+        for (i, &(ax, ay)) in contour.iter().enumerate() {
+            let (bx, by) = contour[(i + 1) % contour.len()];
+            if ay == by || !(ax.is_finite() && ay.is_finite() && bx.is_finite() && by.is_finite()) {
+                continue;
+            }
+            if ay < by {
+                edges.push((ax, ay, bx, by, 1));
+            } else {
+                edges.push((bx, by, ax, ay, -1));
+            }
+        }
+    }
+    if edges.is_empty() {
+        return;
+    }
+
+    let samples = if antialias { SUBSAMPLES } else { 1 };
+    let weight = 1.0 / samples as f32;
+    let (left, right) = (mask.x as f32, (mask.x + mask.width as i64) as f32);
+    let mut row = vec![0f32; mask.width];
+    let mut crossings: Vec<(f32, i32)> = Vec::new();
+    for my in 0..mask.height {
+        row.fill(0.0);
+        let y = (mask.y + my as i64) as f32;
+        for s in 0..samples {
+            let sy = y + (s as f32 + 0.5) * weight;
+            crossings.clear();
+            for &(x0, y0, x1, y1, winding) in &edges {
+                if sy >= y0 && sy < y1 {
+                    crossings.push((x0 + (sy - y0) * (x1 - x0) / (y1 - y0), winding));
+                }
+            }
+            crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+            let mut winding = 0;
+            for pair in crossings.windows(2) {
+                winding += pair[0].1;
+                if !rule.is_inside(winding) {
+                    continue;
+                }
+                let (start, end) = (pair[0].0.max(left), pair[1].0.min(right));
+                if start >= end {
+                    continue;
+                }
+                if antialias {
+                    add_span(&mut row, start - left, end - left, weight);
+                } else {
+                    // Pixels whose centers fall in [start, end)
+                    let first = (start - left - 0.5).ceil().max(0.0) as usize;
+                    let last = ((end - left - 0.5).ceil().max(0.0) as usize).min(mask.width);
+                    for value in &mut row[first.min(last)..last] {
+                        *value = 1.0;
+                    }
+                }
+            }
+        }
+        for (mx, value) in row.iter().enumerate() {
+            if *value > 0.0 {
+                let slot = &mut mask.data[my * mask.width + mx];
+                *slot = (*slot).max((value.min(1.0) * 255.0).round() as u8);
+            }
+        }
+    }
+}
+
+// Add `weight` times the horizontal overlap of [start, end) with each pixel
+fn add_span(row: &mut [f32], start: f32, end: f32, weight: f32) {
+    let (first, last) = (start.floor() as usize, end.ceil() as usize);
+    for (x, value) in row.iter_mut().enumerate().take(last).skip(first) {
+        let overlap = end.min(x as f32 + 1.0) - start.max(x as f32);
+        if overlap > 0.0 {
+            *value += overlap * weight;
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+
+/// Largest distance a flattened curve may stray from the true one, in pixels.
+pub(crate) const TOLERANCE: f32 = 0.1;
+
+/// Segment count that keeps an arc of `radius` spanning `angle` within `TOLERANCE`.
+pub(crate) fn arc_segments(radius: f32, angle: f32) -> usize {
+    if radius <= TOLERANCE {
+        return 1;
+    }
+    let step = 2.0 * (1.0 - TOLERANCE / radius).acos();
+    ((angle.abs() / step).ceil() as usize).clamp(1, 4096)
+}
+
+pub(crate) fn rect_contour(left: f32, top: f32, right: f32, bottom: f32) -> Vec<(f32, f32)> {
+    vec![(left, top), (right, top), (right, bottom), (left, bottom)]
+}
+
+/// Rectangle with quarter-circle corners; `radius` is limited to half the shorter side.
+pub(crate) fn rounded_rect_contour(
+    left: f32,
+    top: f32,
+    right: f32,
+    bottom: f32,
+    radius: f32,
+) -> Vec<(f32, f32)> {
+    let radius = radius.min((right - left) / 2.0).min((bottom - top) / 2.0);
+    if radius <= 0.0 {
+        return rect_contour(left, top, right, bottom);
+    }
+    let segments = arc_segments(radius, FRAC_PI_2);
+    let corners = [
+        (right - radius, top + radius, -FRAC_PI_2),
+        (right - radius, bottom - radius, 0.0),
+        (left + radius, bottom - radius, FRAC_PI_2),
+        (left + radius, top + radius, PI),
+    ];
+    let mut contour = Vec::with_capacity((segments + 1) * 4);
+    for (cx, cy, start) in corners {
+        for i in 0..=segments {
+            let angle = start + FRAC_PI_2 * i as f32 / segments as f32;
+            contour.push((cx + radius * angle.cos(), cy + radius * angle.sin()));
+        }
+    }
+    contour
+}
+
+pub(crate) fn ellipse_contour(cx: f32, cy: f32, rx: f32, ry: f32) -> Vec<(f32, f32)> {
+    let segments = arc_segments(rx.max(ry), TAU).max(8);
+    (0..segments)
+        .map(|i| {
+            let angle = TAU * i as f32 / segments as f32;
+            (cx + rx * angle.cos(), cy + ry * angle.sin())
+        })
+        .collect()
+}
+
+/// Segment from `a` to `b` widened by `half_width` on each side, with round ends.
+pub(crate) fn capsule_contour(a: (f32, f32), b: (f32, f32), half_width: f32) -> Vec<(f32, f32)> {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let length = (dx * dx + dy * dy).sqrt();
+    if length < 1e-6 {
+        return ellipse_contour(a.0, a.1, half_width, half_width);
+    }
+    let direction = dy.atan2(dx);
+    let segments = arc_segments(half_width, PI);
+    let mut contour = Vec::with_capacity((segments + 1) * 2);
+    for (center, start) in [(b, direction - FRAC_PI_2), (a, direction + FRAC_PI_2)] {
+        for i in 0..=segments {
+            let angle = start + PI * i as f32 / segments as f32;
+            contour.push((
+                center.0 + half_width * angle.cos(),
+                center.1 + half_width * angle.sin(),
+            ));
+        }
+    }
+    contour
+}
+
+/// Outline pixels of the ellipse inscribed in the inclusive box (x0, y0)-(x1, y1), using
+/// the midpoint (Bresenham) ellipse from Zingl's "A Rasterizing Algorithm for Drawing
+/// Curves". Even and odd sizes both come out mirror-symmetric.
+pub(crate) fn ellipse_outline(x0: i64, y0: i64, x1: i64, y1: i64) -> Vec<(i64, i64)> {
+    let (mut x0, mut x1) = (x0.min(x1), x0.max(x1));
+    let (top, bottom) = (y0.min(y1), y0.max(y1));
+    let a = x1 - x0;
+    let b = bottom - top;
+    let b1 = b & 1;
+    let (a, b, b1f) = (a as f64, b as f64, b1 as f64);
+    let mut dx = 4.0 * (1.0 - a) * b * b;
+    let mut dy = 4.0 * (b1f + 1.0) * a * a;
+    let mut err = dx + dy + b1f * a * a;
+    let mut y0 = top + (b as i64 + 1) / 2;
+    let mut y1 = y0 - b1;
+    let (a8, b8) = (8.0 * a * a, 8.0 * b * b);
+
+    let mut out = Vec::new();
+    loop {
+        out.extend([(x1, y0), (x0, y0), (x0, y1), (x1, y1)]);
+        let e2 = 2.0 * err;
+        if e2 <= dy {
+            y0 += 1;
+            y1 -= 1;
+            dy += a8;
+            err += dy;
+        }
+        if e2 >= dx || 2.0 * err > dy {
+            x0 += 1;
+            x1 -= 1;
+            dx += b8;
+            err += dx;
+        }
+        if x0 > x1 {
+            break;
+        }
+    }
+    // Finish the tips of very thin ellipses
+    while y0 - y1 <= b as i64 {
+        out.extend([(x0 - 1, y0), (x1 + 1, y0), (x0 - 1, y1), (x1 + 1, y1)]);
+        y0 += 1;
+        y1 -= 1;
+    }
+    out
+}
+
+/// Per-row [left, right] extents of `ellipse_outline`, indexed from `top`.
+pub(crate) fn ellipse_spans(x0: i64, y0: i64, x1: i64, y1: i64) -> Vec<(i64, i64)> {
+    let top = y0.min(y1);
+    let mut spans = vec![(i64::MAX, i64::MIN); (y0 - y1).unsigned_abs() as usize + 1];
+    for (x, y) in ellipse_outline(x0, y0, x1, y1) {
+        if let Some(span) = spans.get_mut((y - top) as usize) {
+            span.0 = span.0.min(x);
+            span.1 = span.1.max(x);
+        }
+    }
+    spans
+}
+
+/// Offsets of a round pixel-art dab `diameter` pixels across, centered on (0, 0)
+/// (even sizes lean toward the top-left).
+pub(crate) fn disc_offsets(diameter: u32) -> Vec<(i64, i64)> {
+    let d = diameter.max(1) as i64;
+    let radius = d as f32 / 2.0;
+    let mut offsets = Vec::new();
+    for j in 0..d {
+        for i in 0..d {
+            let (cx, cy) = (i as f32 + 0.5 - radius, j as f32 + 0.5 - radius);
+            if d <= 2 || cx * cx + cy * cy <= radius * radius {
+                offsets.push((i - (d - 1) / 2, j - (d - 1) / 2));
+            }
+        }
+    }
+    offsets
+}
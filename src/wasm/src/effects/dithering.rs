@@ -37,7 +37,7 @@ impl DitheringOption {
 }
 
 // 4x4 Bayer matrix for ordered dithering
-const BAYER_MATRIX_4X4: [[f32; 4]; 4] = [
+pub(crate) const BAYER_MATRIX_4X4: [[f32; 4]; 4] = [
     [0.0 / 16.0, 8.0 / 16.0, 2.0 / 16.0, 10.0 / 16.0],
     [12.0 / 16.0, 4.0 / 16.0, 14.0 / 16.0, 6.0 / 16.0],
     [3.0 / 16.0, 11.0 / 16.0, 1.0 / 16.0, 9.0 / 16.0],
@@ -1,7 +1,7 @@
 use std::collections::VecDeque;
 use wasm_bindgen::prelude::*;
 
-use crate::console_log;
+use crate::{console_log, fill::Connectivity};
 
 #[wasm_bindgen]
 #[derive(Clone, Copy)]
@@ -10,15 +10,19 @@ pub struct DustRemovalOption {
     pub max_size: u32,
     /// Minimum alpha threshold to consider a pixel as non-transparent (0-255)
     pub alpha_threshold: u8,
+    /// Which neighbors belong to the same pixel group
+    pub connectivity: Connectivity,
 }
 
 #[wasm_bindgen]
 impl DustRemovalOption {
+    /// `connectivity` starts as `Eight` and is set as a property.
     #[wasm_bindgen(constructor)]
     pub fn new(max_size: u32, alpha_threshold: u8) -> DustRemovalOption {
         DustRemovalOption {
             max_size: max_size.clamp(1, 1000),
             alpha_threshold,
+            connectivity: Connectivity::Eight,
         }
     }
 }
@@ -43,18 +47,6 @@ pub fn dust_removal(pixels: &mut [u8], width: u32, height: u32, options: &DustRe
     // Track processed pixels to avoid duplicate work
     let mut processed = vec![false; pixel_count];
 
-    // 8-directional neighbors
-    let directions = [
-        (-1, -1),
-        (-1, 0),
-        (-1, 1),
-        (0, -1),
-        (0, 1),
-        (1, -1),
-        (1, 0),
-        (1, 1),
-    ];
-
     for y in 0..h {
         for x in 0..w {
             let index = (y * w + x) as usize;
@@ -84,8 +76,8 @@ pub fn dust_removal(pixels: &mut [u8], width: u32, height: u32, options: &DustRe
             while let Some((cx, cy)) = queue.pop_front() {
                 component_pixels.push((cx, cy));
 
-                // Check all 8 neighbors
-                for (dx, dy) in directions.iter() {
+                // Check neighbors
+                for (dx, dy) in options.connectivity.offsets() {
                     let nx = cx + dx;
                     let ny = cy + dy;
 
@@ -1,5 +1,7 @@
 use wasm_bindgen::prelude::*;
 
+use crate::fill::coverage::{paint_coverage, CoverageMode};
+
 #[wasm_bindgen]
 #[allow(clippy::too_many_arguments)]
 pub fn fill_mask_area(
@@ -11,15 +13,40 @@ pub fn fill_mask_area(
     fill_color_a: u8,
 ) -> bool {
     let fill_color = [fill_color_r, fill_color_g, fill_color_b, fill_color_a];
-    for (mi, flag) in mask.iter().enumerate() {
-        if *flag != 0 {
-            let bi = mi * 4;
-            buffer[bi] = fill_color[0];
-            buffer[bi + 1] = fill_color[1];
-            buffer[bi + 2] = fill_color[2];
-            buffer[bi + 3] = fill_color[3];
-        }
-    }
+    paint_mask_area(buffer, mask, fill_color, CoverageMode::Binary, |_, _| {});
 
     true
 }
+
+/// `fill_mask_area` where mask values are applied according to `mode`,
+/// so feathered selections fade out instead of being filled at full strength.
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn fill_mask_area_with_coverage(
+    buffer: &mut [u8],
+    mask: &[u8],
+    fill_color_r: u8,
+    fill_color_g: u8,
+    fill_color_b: u8,
+    fill_color_a: u8,
+    mode: CoverageMode,
+) -> bool {
+    let fill_color = [fill_color_r, fill_color_g, fill_color_b, fill_color_a];
+    paint_mask_area(buffer, mask, fill_color, mode, |_, _| {});
+
+    true
+}
+
+/// Fill the mask area and return the per-pixel changes (see `paint_coverage`).
+/// `before_paint` receives the buffer and the coverage right before painting.
+pub(crate) fn paint_mask_area(
+    buffer: &mut [u8],
+    mask: &[u8],
+    fill_color: [u8; 4],
+    mode: CoverageMode,
+    before_paint: impl FnOnce(&[u8], &[u8]),
+) -> Vec<u8> {
+    let coverage: Vec<u8> = mask.iter().map(|value| mode.coverage(*value)).collect();
+    before_paint(buffer, &coverage);
+    paint_coverage(buffer, &coverage, fill_color, mode)
+}
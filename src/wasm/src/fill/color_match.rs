@@ -0,0 +1,250 @@
+use wasm_bindgen::prelude::*;
+
+/// How the distance between two colors is measured when matching against a threshold.
+/// Every metric is scaled so that 255 roughly spans black to white.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ColorMetric {
+    /// Largest per-channel difference (the legacy behavior)
+    ChannelMax = 0,
+    /// Euclidean distance over straight RGBA
+    Euclidean = 1,
+    /// Euclidean distance over premultiplied RGBA, so all fully transparent pixels match
+    Premultiplied = 2,
+    /// Perceptual distance in OKLab
+    OkLab = 3,
+    /// Perceptual CIEDE2000 distance in CIELAB (D65)
+    Ciede2000 = 4,
+}
+
+/// Distance between two RGBA colors on a 0-255 scale.
+/// With `include_alpha` false, only the color channels take part.
+pub(crate) fn color_distance(
+    a: &[u8; 4],
+    b: &[u8; 4],
+    metric: ColorMetric,
+    include_alpha: bool,
+) -> f32 {
+    let channels = if include_alpha { 4 } else { 3 };
+    let alpha_diff = (a[3] as f32 - b[3] as f32) / 255.0;
+    let distance = match metric {
+        ColorMetric::ChannelMax => (0..channels)
+            .map(|c| (a[c] as i16 - b[c] as i16).unsigned_abs())
+            .max()
+            .unwrap_or(0) as f32,
+        ColorMetric::Euclidean => {
+            let sum: f32 = (0..channels)
+                .map(|c| (a[c] as f32 - b[c] as f32).powi(2))
+                .sum();
+            (sum / channels as f32).sqrt()
+        }
+        ColorMetric::Premultiplied => {
+            let (pa, pb) = (premultiply(a), premultiply(b));
+            let sum: f32 = (0..channels).map(|c| (pa[c] - pb[c]).powi(2)).sum();
+            (sum / channels as f32).sqrt()
+        }
+        ColorMetric::OkLab => {
+            let (la, lb) = (srgb_to_oklab(a), srgb_to_oklab(b));
+            let mut sum: f32 = (0..3).map(|c| (la[c] - lb[c]).powi(2)).sum();
+            if include_alpha {
+                sum += alpha_diff * alpha_diff;
+            }
+            sum.sqrt() * 255.0
+        }
+        ColorMetric::Ciede2000 => {
+            let delta = ciede2000(&srgb_to_lab(a), &srgb_to_lab(b)) / 100.0;
+            let mut sum = delta * delta;
+            if include_alpha {
+                sum += alpha_diff * alpha_diff;
+            }
+            sum.sqrt() * 255.0
+        }
+    };
+    distance.min(255.0)
+}
+
+fn premultiply(color: &[u8; 4]) -> [f32; 4] {
+    let a = color[3] as f32 / 255.0;
+    [
+        color[0] as f32 * a,
+        color[1] as f32 * a,
+        color[2] as f32 * a,
+        color[3] as f32,
+    ]
+}
+
+pub(crate) fn srgb_to_linear(value: u8) -> f32 {
+    let c = value as f32 / 255.0;
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+pub(crate) fn linear_to_oklab(rgb: [f32; 3]) -> [f32; 3] {
+    let [r, g, b] = rgb;
+    let l = (0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b).cbrt();
+    let m = (0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b).cbrt();
+    let s = (0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b).cbrt();
+    [
+        0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+        1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+        0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+    ]
+}
+
+/// Inverse of `srgb_to_linear`, on a 0-1 scale.
+pub(crate) fn linear_to_srgb(value: f32) -> f32 {
+    let c = value.clamp(0.0, 1.0);
+    if c <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+pub(crate) fn oklab_to_linear(lab: [f32; 3]) -> [f32; 3] {
+    let [l, a, b] = lab;
+    let l_ = (l + 0.396_337_78 * a + 0.215_803_76 * b).powi(3);
+    let m_ = (l - 0.105_561_346 * a - 0.063_854_17 * b).powi(3);
+    let s_ = (l - 0.089_484_18 * a - 1.291_485_5 * b).powi(3);
+    [
+        4.076_741_7 * l_ - 3.307_711_6 * m_ + 0.230_969_94 * s_,
+        -1.268_438 * l_ + 2.609_757_4 * m_ - 0.341_319_38 * s_,
+        -0.004_196_086_3 * l_ - 0.703_418_6 * m_ + 1.707_614_7 * s_,
+    ]
+}
+
+fn srgb_to_oklab(color: &[u8; 4]) -> [f32; 3] {
+    linear_to_oklab([
+        srgb_to_linear(color[0]),
+        srgb_to_linear(color[1]),
+        srgb_to_linear(color[2]),
+    ])
+}
+
+fn srgb_to_lab(color: &[u8; 4]) -> [f32; 3] {
+    let (r, g, b) = (
+        srgb_to_linear(color[0]),
+        srgb_to_linear(color[1]),
+        srgb_to_linear(color[2]),
+    );
+    // XYZ relative to the D65 white point
+    let x = (0.412_456_4 * r + 0.357_576_1 * g + 0.180_437_5 * b) / 0.950_47;
+    let y = 0.212_672_9 * r + 0.715_152_2 * g + 0.072_175 * b;
+    let z = (0.019_333_9 * r + 0.119_192 * g + 0.950_304_1 * b) / 1.088_83;
+    let f = |t: f32| {
+        if t > 216.0 / 24389.0 {
+            t.cbrt()
+        } else {
+            (24389.0 / 27.0 * t + 16.0) / 116.0
+        }
+    };
+    let (fx, fy, fz) = (f(x), f(y), f(z));
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+// CIEDE2000 color difference (Sharma et al. 2005) with kL = kC = kH = 1
+fn ciede2000(lab1: &[f32; 3], lab2: &[f32; 3]) -> f32 {
+    let [l1, a1, b1] = *lab1;
+    let [l2, a2, b2] = *lab2;
+
+    let c_bar = ((a1 * a1 + b1 * b1).sqrt() + (a2 * a2 + b2 * b2).sqrt()) / 2.0;
+    let c_bar7 = c_bar.powi(7);
+    let g = 0.5 * (1.0 - (c_bar7 / (c_bar7 + 25f32.powi(7))).sqrt());
+    let (a1p, a2p) = ((1.0 + g) * a1, (1.0 + g) * a2);
+    let (c1p, c2p) = ((a1p * a1p + b1 * b1).sqrt(), (a2p * a2p + b2 * b2).sqrt());
+    let hue = |b: f32, a: f32| {
+        if a == 0.0 && b == 0.0 {
+            0.0
+        } else {
+            b.atan2(a).to_degrees().rem_euclid(360.0)
+        }
+    };
+    let (h1p, h2p) = (hue(b1, a1p), hue(b2, a2p));
+
+    let dl = l2 - l1;
+    let dc = c2p - c1p;
+    let dh = if c1p * c2p == 0.0 {
+        0.0
+    } else if (h2p - h1p).abs() <= 180.0 {
+        h2p - h1p
+    } else if h2p - h1p > 180.0 {
+        h2p - h1p - 360.0
+    } else {
+        h2p - h1p + 360.0
+    };
+    let dh_big = 2.0 * (c1p * c2p).sqrt() * (dh / 2.0).to_radians().sin();
+
+    let l_bar = (l1 + l2) / 2.0;
+    let c_bar_p = (c1p + c2p) / 2.0;
+    let h_bar = if c1p * c2p == 0.0 {
+        h1p + h2p
+    } else if (h1p - h2p).abs() <= 180.0 {
+        (h1p + h2p) / 2.0
+    } else if h1p + h2p < 360.0 {
+        (h1p + h2p + 360.0) / 2.0
+    } else {
+        (h1p + h2p - 360.0) / 2.0
+    };
+
+    let t = 1.0 - 0.17 * (h_bar - 30.0).to_radians().cos()
+        + 0.24 * (2.0 * h_bar).to_radians().cos()
+        + 0.32 * (3.0 * h_bar + 6.0).to_radians().cos()
+        - 0.20 * (4.0 * h_bar - 63.0).to_radians().cos();
+    let d_theta = 30.0 * (-((h_bar - 275.0) / 25.0).powi(2)).exp();
+    let c_bar_p7 = c_bar_p.powi(7);
+    let rc = 2.0 * (c_bar_p7 / (c_bar_p7 + 25f32.powi(7))).sqrt();
+    let l50 = (l_bar - 50.0).powi(2);
+    let sl = 1.0 + 0.015 * l50 / (20.0 + l50).sqrt();
+    let sc = 1.0 + 0.045 * c_bar_p;
+    let sh = 1.0 + 0.015 * c_bar_p * t;
+    let rt = -(2.0 * d_theta).to_radians().sin() * rc;
+
+    let (tl, tc, th) = (dl / sl, dc / sc, dh_big / sh);
+    (tl * tl + tc * tc + th * th + rt * tc * th).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ciede2000_matches_sharmas_reference_pairs() {
+        // Pairs 1, 7, 11, 13, 17, 19 and 25 from Sharma, Wu and Dalal's test data
+        let pairs: [([f32; 3], [f32; 3], f32); 7] = [
+            ([50.0, 2.6772, -79.7751], [50.0, 0.0, -82.7485], 2.0425),
+            ([50.0, 0.0, 0.0], [50.0, -1.0, 2.0], 2.3669),
+            ([50.0, 2.49, -0.001], [50.0, -2.49, 0.0009], 7.1792),
+            ([50.0, 2.49, -0.001], [50.0, -2.49, 0.0011], 7.2195),
+            ([50.0, 2.5, 0.0], [73.0, 25.0, -18.0], 27.1492),
+            ([50.0, 2.5, 0.0], [50.0, 3.1736, 0.5854], 1.0),
+            (
+                [60.2574, -34.0099, 36.2677],
+                [60.4626, -34.1751, 39.4387],
+                1.2644,
+            ),
+        ];
+        for (lab1, lab2, expected) in pairs {
+            for delta in [ciede2000(&lab1, &lab2), ciede2000(&lab2, &lab1)] {
+                assert!(
+                    (delta - expected).abs() < 1e-3,
+                    "{lab1:?} {lab2:?}: {delta}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn ciede2000_metric_spans_black_to_white() {
+        let black = [0, 0, 0, 255];
+        let white = [255, 255, 255, 255];
+        let distance = color_distance(&black, &white, ColorMetric::Ciede2000, false);
+        assert!((distance - 255.0).abs() < 0.5, "{distance}");
+        assert_eq!(
+            color_distance(&white, &white, ColorMetric::Ciede2000, true),
+            0.0
+        );
+    }
+}
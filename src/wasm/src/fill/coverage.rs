@@ -0,0 +1,193 @@
+use wasm_bindgen::prelude::*;
+
+use crate::fill::Connectivity;
+
+/// How mask values are applied when filling.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CoverageMode {
+    /// Any non-zero value is fully selected (the legacy behavior)
+    Binary = 0,
+    /// Values are 0-255 coverage; the color is interpolated in straight RGBA
+    Straight = 1,
+    /// Values are 0-255 coverage; the color is interpolated in premultiplied RGBA,
+    /// so transparent pixels do not darken the edge
+    Premultiplied = 2,
+}
+
+impl CoverageMode {
+    /// Coverage a mask value stands for.
+    pub(crate) fn coverage(self, value: u8) -> u8 {
+        match self {
+            CoverageMode::Binary if value != 0 => 255,
+            _ => value,
+        }
+    }
+}
+
+/// Paint `fill_color` with per-pixel coverage and return the coverage applied to each
+/// pixel whose color actually changed (0 elsewhere), for `FillResult::from_changes`.
+///
+/// Full coverage replaces the pixel like the plain fills. Partial coverage is interpolated
+/// in straight RGBA for `Straight`, and in premultiplied RGBA otherwise (antialiased
+/// edges of a `Binary` fill included).
+pub(crate) fn paint_coverage(
+    buffer: &mut [u8],
+    coverage: &[u8],
+    fill_color: [u8; 4],
+    mode: CoverageMode,
+) -> Vec<u8> {
+    paint_coverage_with(buffer, coverage, mode, |_| fill_color)
+}
+
+/// `paint_coverage` with a per-pixel fill color (gradients, patterns).
+/// `color_at` receives the pixel index and is only called for covered pixels.
+pub(crate) fn paint_coverage_with(
+    buffer: &mut [u8],
+    coverage: &[u8],
+    mode: CoverageMode,
+    mut color_at: impl FnMut(usize) -> [u8; 4],
+) -> Vec<u8> {
+    let mut changes = vec![0u8; coverage.len()];
+    for (i, ((pixel, c), change)) in buffer
+        .chunks_exact_mut(4)
+        .zip(coverage.iter())
+        .zip(changes.iter_mut())
+        .enumerate()
+    {
+        if *c == 0 {
+            continue;
+        }
+        let painted = blend_coverage(pixel, color_at(i), *c, mode);
+        if pixel != painted {
+            pixel.copy_from_slice(&painted);
+            *change = *c;
+        }
+    }
+    changes
+}
+
+/// The color `paint_coverage` writes over `pixel` at coverage `c`.
+pub(crate) fn blend_coverage(
+    pixel: &[u8],
+    fill_color: [u8; 4],
+    c: u8,
+    mode: CoverageMode,
+) -> [u8; 4] {
+    match (c, mode) {
+        (0, _) => [pixel[0], pixel[1], pixel[2], pixel[3]],
+        (255, _) => fill_color,
+        (c, CoverageMode::Straight) => lerp_straight(pixel, fill_color, c),
+        (c, _) => lerp_premultiplied(pixel, fill_color, c),
+    }
+}
+
+fn lerp_straight(pixel: &[u8], fill_color: [u8; 4], coverage: u8) -> [u8; 4] {
+    let t = coverage as f32 / 255.0;
+    let mut out = [0u8; 4];
+    for ch in 0..4 {
+        let dst = pixel[ch] as f32;
+        out[ch] = (dst + (fill_color[ch] as f32 - dst) * t).round() as u8;
+    }
+    out
+}
+
+fn lerp_premultiplied(pixel: &[u8], fill_color: [u8; 4], coverage: u8) -> [u8; 4] {
+    let t = coverage as f32 / 255.0;
+    let fill_a = fill_color[3] as f32 / 255.0;
+    let dst_a = pixel[3] as f32 / 255.0;
+    let out_a = dst_a + (fill_a - dst_a) * t;
+    if out_a <= 0.0 {
+        return [0, 0, 0, 0];
+    }
+    let mut out = [0u8; 4];
+    for ch in 0..3 {
+        let dst = pixel[ch] as f32 * dst_a;
+        let src = fill_color[ch] as f32 * fill_a;
+        out[ch] = ((dst + (src - dst) * t) / out_a).round().clamp(0.0, 255.0) as u8;
+    }
+    out[3] = (out_a * 255.0).round() as u8;
+    out
+}
+
+/// Region flags as 0/255 coverage.
+pub(crate) fn region_coverage(region: &[bool]) -> Vec<u8> {
+    region
+        .iter()
+        .map(|inside| if *inside { 255 } else { 0 })
+        .collect()
+}
+
+/// Antialias the edge of a fill region. Pixels outside the region that touch it (including
+/// diagonally) get the coverage `edge_coverage` returns for their index, so antialiased
+/// boundaries such as line art blend into the fill. Region pixels keep full coverage.
+pub(crate) fn antialias_edge(
+    coverage: &mut [u8],
+    region: &[bool],
+    width: usize,
+    height: usize,
+    edge_coverage: impl Fn(usize) -> u8,
+) {
+    for y in 0..height {
+        for x in 0..width {
+            let i = y * width + x;
+            if region[i] {
+                continue;
+            }
+            let touches_region = Connectivity::Eight.offsets().iter().any(|(dx, dy)| {
+                let (nx, ny) = (x as i64 + *dx as i64, y as i64 + *dy as i64);
+                nx >= 0
+                    && ny >= 0
+                    && (nx as usize) < width
+                    && (ny as usize) < height
+                    && region[ny as usize * width + nx as usize]
+            });
+            if touches_region {
+                coverage[i] = coverage[i].max(edge_coverage(i));
+            }
+        }
+    }
+}
+
+/// Coverage of an edge pixel whose color is `distance` (0-255) away from the fill target:
+/// full at `threshold`, the largest distance the region takes in, fading to none at 255.
+pub(crate) fn distance_coverage(distance: f32, threshold: u8) -> u8 {
+    let threshold = threshold as f32;
+    if distance <= threshold {
+        return 255;
+    }
+    if threshold >= 255.0 {
+        return 0;
+    }
+    ((255.0 - distance).max(0.0) / (255.0 - threshold) * 255.0).round() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distance_coverage_fades_linearly_past_the_threshold() {
+        assert_eq!(distance_coverage(0.0, 0), 255);
+        assert_eq!(distance_coverage(64.0, 64), 255);
+        assert_eq!(distance_coverage(255.0, 64), 0);
+        for distance in [0.0, 51.0, 102.0, 153.0, 204.0, 255.0] {
+            assert_eq!(distance_coverage(distance, 0), 255 - distance as u8);
+        }
+        // Halfway between the threshold and 255
+        assert_eq!(distance_coverage(159.5, 64), 128);
+        assert_eq!(distance_coverage(255.0, 255), 255);
+    }
+
+    #[test]
+    fn antialias_edge_only_raises_pixels_touching_the_region() {
+        // 5x3 region with its middle column filled
+        let (width, height) = (5, 3);
+        let region: Vec<bool> = (0..width * height).map(|i| i % width == 2).collect();
+        let mut coverage = region_coverage(&region);
+        antialias_edge(&mut coverage, &region, width, height, |i| {
+            (i % width * 50) as u8
+        });
+        assert_eq!(coverage, [0, 50, 255, 150, 0].repeat(height));
+    }
+}
@@ -0,0 +1,120 @@
+use wasm_bindgen::prelude::*;
+
+use crate::selection::morphology::squared_distance;
+
+/// Which pixels a fill may expand over.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExpandLimit {
+    /// Expand over every pixel in reach
+    None = 0,
+    /// Only under pixels darker than the threshold (composited over white, so
+    /// transparent pixels count as light)
+    Darker = 1,
+    /// Only under pixels whose alpha is above the threshold
+    Opaque = 2,
+}
+
+/// Grow `region` by up to `distance` pixels (Euclidean) over eligible pixels.
+///
+/// Eligibility is judged on `reference`, the buffer the region was found in, so the
+/// fill creeps under line art instead of leaving a halo between the fill and the line.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn expand_region(
+    reference: &[u8],
+    region: &mut [bool],
+    width: usize,
+    height: usize,
+    distance: u32,
+    limit: ExpandLimit,
+    threshold: u8,
+    is_allowed: impl Fn(usize, usize) -> bool,
+) {
+    if distance == 0 {
+        return;
+    }
+    let flags: Vec<u8> = region.iter().map(|inside| *inside as u8).collect();
+    let to_region = squared_distance(&flags, width, height, |v| v != 0);
+    let reach = (distance as f32) * (distance as f32);
+    for (i, (inside, d)) in region.iter_mut().zip(to_region.iter()).enumerate() {
+        if *inside || *d > reach || !is_allowed(i % width, i / width) {
+            continue;
+        }
+        let px = &reference[i * 4..i * 4 + 4];
+        *inside = match limit {
+            ExpandLimit::None => true,
+            ExpandLimit::Darker => lightness_over_white(px) < threshold as f32,
+            ExpandLimit::Opaque => px[3] > threshold,
+        };
+    }
+}
+
+// Rec. 709 luma of the pixel composited over white
+fn lightness_over_white(px: &[u8]) -> f32 {
+    let luma = 0.2126 * px[0] as f32 + 0.7152 * px[1] as f32 + 0.0722 * px[2] as f32;
+    let alpha = px[3] as f32 / 255.0;
+    luma * alpha + 255.0 * (1.0 - alpha)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIZE: usize = 21;
+    const CENTER: usize = 10;
+
+    fn squared_offset(i: usize) -> usize {
+        (i % SIZE).abs_diff(CENTER).pow(2) + (i / SIZE).abs_diff(CENTER).pow(2)
+    }
+
+    fn grown(reference: &[u8], distance: u32, limit: ExpandLimit) -> Vec<bool> {
+        let mut region = vec![false; SIZE * SIZE];
+        region[CENTER * SIZE + CENTER] = true;
+        expand_region(
+            reference,
+            &mut region,
+            SIZE,
+            SIZE,
+            distance,
+            limit,
+            128,
+            |_, _| true,
+        );
+        region
+    }
+
+    #[test]
+    fn grows_by_exactly_the_distance() {
+        let reference = vec![255u8; SIZE * SIZE * 4];
+        for distance in 0..=4u32 {
+            let region = grown(&reference, distance, ExpandLimit::None);
+            for (i, inside) in region.iter().enumerate() {
+                let within = squared_offset(i) <= (distance * distance) as usize;
+                assert_eq!(*inside, within, "distance {distance}, pixel {i}");
+            }
+        }
+    }
+
+    #[test]
+    fn limits_growth_to_eligible_pixels() {
+        // Left half black, right half white; only the black side is darker than 128
+        let mut reference = vec![255u8; SIZE * SIZE * 4];
+        for (i, px) in reference.chunks_exact_mut(4).enumerate() {
+            if i % SIZE < CENTER {
+                px[..3].fill(0);
+            }
+        }
+        let region = grown(&reference, 3, ExpandLimit::Darker);
+        for (i, inside) in region.iter().enumerate() {
+            let expected =
+                i == CENTER * SIZE + CENTER || (squared_offset(i) <= 9 && i % SIZE < CENTER);
+            assert_eq!(*inside, expected, "pixel {i}");
+        }
+
+        // Transparent pixels are skipped with `Opaque`
+        let mut reference = vec![0u8; SIZE * SIZE * 4];
+        reference[(CENTER * SIZE + CENTER + 1) * 4 + 3] = 255;
+        let region = grown(&reference, 3, ExpandLimit::Opaque);
+        assert_eq!(region.iter().filter(|inside| **inside).count(), 2);
+    }
+}
@@ -1,6 +1,74 @@
 use wasm_bindgen::prelude::*;
 
-use crate::fill::colors_match;
+use crate::fill::{
+    color_match::{color_distance, ColorMetric},
+    colors_match,
+    coverage::{antialias_edge, distance_coverage, paint_coverage, region_coverage, CoverageMode},
+    expand::{expand_region, ExpandLimit},
+    gap_close::gap_closed_region,
+    Connectivity,
+};
+
+#[wasm_bindgen]
+#[derive(Clone, Copy)]
+pub struct FloodFillOption {
+    /// Largest color distance (0-255) still treated as the target color
+    pub threshold: u8,
+    /// How color distance is measured
+    pub metric: ColorMetric,
+    /// Whether alpha takes part in the comparison
+    pub include_alpha: bool,
+    /// Neighborhood used to grow the region
+    pub connectivity: Connectivity,
+    /// Openings in the boundary up to this many pixels wide are treated as closed (0 = off)
+    pub gap_size: u32,
+    /// Grow the filled region by this many pixels after it is found (0 = off)
+    pub expand: u32,
+    /// Which pixels the expansion may cover
+    pub expand_limit: ExpandLimit,
+    /// Lightness or alpha threshold for `expand_limit`
+    pub expand_threshold: u8,
+    /// Give pixels just outside the fill partial coverage by how close their color is to
+    /// the target, so antialiased boundaries blend in instead of leaving a hard edge
+    pub antialias: bool,
+    /// How `selection_mask` values limit the fill; soft modes fade the fill at feathered
+    /// selection edges
+    pub mask_mode: CoverageMode,
+}
+
+#[wasm_bindgen]
+impl FloodFillOption {
+    /// `connectivity` starts as `Four` and `gap_size`, `expand`, `expand_limit`,
+    /// `expand_threshold`, `antialias` and `mask_mode` start disabled (`mask_mode` is
+    /// `Binary`); all are set as properties.
+    #[wasm_bindgen(constructor)]
+    pub fn new(threshold: u8, metric: ColorMetric, include_alpha: bool) -> FloodFillOption {
+        FloodFillOption {
+            threshold,
+            metric,
+            include_alpha,
+            connectivity: Connectivity::Four,
+            gap_size: 0,
+            expand: 0,
+            expand_limit: ExpandLimit::None,
+            expand_threshold: 128,
+            antialias: false,
+            mask_mode: CoverageMode::Binary,
+        }
+    }
+}
+
+impl FloodFillOption {
+    pub(crate) fn matches(&self, color: &[u8; 4], target: &[u8; 4]) -> bool {
+        color_distance(color, target, self.metric, self.include_alpha) <= self.threshold as f32
+    }
+
+    /// Antialiased coverage of a pixel just outside the region (see `distance_coverage`).
+    pub(crate) fn edge_coverage(&self, color: &[u8; 4], target: &[u8; 4]) -> u8 {
+        let distance = color_distance(color, target, self.metric, self.include_alpha);
+        distance_coverage(distance, self.threshold)
+    }
+}
 
 /// スキャンライン方式のFloodFill実装
 ///
@@ -9,6 +77,8 @@ use crate::fill::colors_match;
 /// - スタックオーバーフロー回避
 /// - 高速な隣接色判定
 /// - 選択範囲制限サポート
+///
+/// `connectivity` を省略すると4近傍で連結します。
 #[wasm_bindgen]
 #[allow(clippy::too_many_arguments)]
 pub fn scanline_flood_fill(
@@ -22,173 +92,77 @@ pub fn scanline_flood_fill(
     fill_color_b: u8,
     fill_color_a: u8,
     threshold: u8,
+    connectivity: Option<Connectivity>,
 ) -> bool {
+    flood_fill_result(
+        buffer,
+        width,
+        height,
+        start_x,
+        start_y,
+        [fill_color_r, fill_color_g, fill_color_b, fill_color_a],
+        threshold,
+        connectivity.unwrap_or(Connectivity::Four),
+        |_, _| {},
+    )
+    .is_some()
+}
+
+/// `scanline_flood_fill` の本体。変更内容を返し、開始位置が無効な場合は None
+///
+/// `connectivity` は領域の連結方法です。
+/// `before_paint` は塗る直前にバッファと被覆率を受け取ります（Undo ジャーナルが
+/// 書き込まれるタイルを退避するため）。
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn flood_fill_result(
+    buffer: &mut [u8],
+    width: u32,
+    height: u32,
+    start_x: u32,
+    start_y: u32,
+    fill_color: [u8; 4],
+    threshold: u8,
+    connectivity: Connectivity,
+    before_paint: impl FnOnce(&[u8], &[u8]),
+) -> Option<Vec<u8>> {
     let width = width as usize;
     let height = height as usize;
     let start_x = start_x as usize;
     let start_y = start_y as usize;
 
     if start_x >= width || start_y >= height {
-        return false;
+        return None;
     }
 
-    let start_index = (start_y * width + start_x) * 4;
-    let target_color = [
-        buffer[start_index],
-        buffer[start_index + 1],
-        buffer[start_index + 2],
-        buffer[start_index + 3],
-    ];
-
-    let fill_color = [fill_color_r, fill_color_g, fill_color_b, fill_color_a];
-
     // 既に同じ色の場合は何もしない
     // if colors_match(&target_color, &fill_color, 0) {
     //     return false;
     // }
 
     // しきい値が最大の場合、マスク条件に合致する全画素を高速に塗りつぶす
-    if threshold == 255 {
-        for y in 0..height {
-            for x in 0..width {
-                let idx = (y * width + x) * 4;
-                buffer[idx] = fill_color[0];
-                buffer[idx + 1] = fill_color[1];
-                buffer[idx + 2] = fill_color[2];
-                buffer[idx + 3] = fill_color[3];
-            }
-        }
-        return true;
-    }
-
-    let mut stack = Vec::new();
-    let mut visited = vec![false; width * height];
-    stack.push((start_x, start_y));
-
-    while let Some((x, y)) = stack.pop() {
-        if x >= width || y >= height {
-            continue;
-        }
-
-        let flat_index = y * width + x;
-        if visited[flat_index] {
-            continue;
-        }
-
-        let pixel_index = flat_index * 4;
-        let current_color = [
-            buffer[pixel_index],
-            buffer[pixel_index + 1],
-            buffer[pixel_index + 2],
-            buffer[pixel_index + 3],
-        ];
-
-        // 対象色でない場合は継続
-        if !colors_match(&current_color, &target_color, threshold) {
-            continue;
-        }
-
-        // スキャンライン方式：左右に拡張
-        let mut left = x;
-        let mut right = x;
-
-        // 左方向に拡張
-        while left > 0 {
-            let lf = y * width + (left - 1);
-            if visited[lf] {
-                break;
-            }
-            let left_index = lf * 4;
-            let left_color = [
-                buffer[left_index],
-                buffer[left_index + 1],
-                buffer[left_index + 2],
-                buffer[left_index + 3],
-            ];
-            if colors_match(&left_color, &target_color, threshold) {
-                left -= 1;
-                visited[lf] = true;
-            } else {
-                break;
-            }
-        }
-
-        // 右方向に拡張
-        while right < width - 1 {
-            let rf = y * width + (right + 1);
-            if visited[rf] {
-                break;
-            }
-            let right_index = rf * 4;
-            let right_color = [
-                buffer[right_index],
-                buffer[right_index + 1],
-                buffer[right_index + 2],
-                buffer[right_index + 3],
-            ];
-            if colors_match(&right_color, &target_color, threshold) {
-                right += 1;
-                visited[rf] = true;
-            } else {
-                break;
-            }
-        }
-
-        // 水平ラインを塗りつぶし
-        for scan_x in left..=right {
-            let flat = y * width + scan_x;
-            visited[flat] = true;
-            let scan_index = flat * 4;
-            buffer[scan_index] = fill_color[0];
-            buffer[scan_index + 1] = fill_color[1];
-            buffer[scan_index + 2] = fill_color[2];
-            buffer[scan_index + 3] = fill_color[3];
-        }
-
-        // 上下のピクセルをスタックに追加
-        for scan_x in left..=right {
-            // 上の行
-            if y > 0 {
-                let up_y = y - 1;
-                let up_flat = up_y * width + scan_x;
-                if !visited[up_flat] {
-                    let up_index = up_flat * 4;
-                    let up_color = [
-                        buffer[up_index],
-                        buffer[up_index + 1],
-                        buffer[up_index + 2],
-                        buffer[up_index + 3],
-                    ];
-                    if colors_match(&up_color, &target_color, threshold) {
-                        stack.push((scan_x, up_y));
-                    }
-                }
-            }
-
-            // 下の行
-            if y < height - 1 {
-                let down_y = y + 1;
-                let down_flat = down_y * width + scan_x;
-                if !visited[down_flat] {
-                    let down_index = down_flat * 4;
-                    let down_color = [
-                        buffer[down_index],
-                        buffer[down_index + 1],
-                        buffer[down_index + 2],
-                        buffer[down_index + 3],
-                    ];
-                    if colors_match(&down_color, &target_color, threshold) {
-                        stack.push((scan_x, down_y));
-                    }
-                }
-            }
-        }
-    }
-
-    true
+    let coverage = if threshold == 255 {
+        vec![255; width * height]
+    } else {
+        let region = scanline_region(
+            buffer,
+            width,
+            height,
+            start_x,
+            start_y,
+            |color, target| colors_match(color, target, threshold),
+            connectivity,
+            |_, _| true,
+        );
+        region_coverage(&region)
+    };
+    before_paint(buffer, &coverage);
+    let changes = paint_coverage(buffer, &coverage, fill_color, CoverageMode::Binary);
+    Some(changes)
 }
 
 /// 選択範囲制限付きスキャンライン FloodFill
+///
+/// `connectivity` を省略すると4近傍で連結します。
 #[wasm_bindgen]
 #[allow(clippy::too_many_arguments)]
 pub fn scanline_flood_fill_with_mask(
@@ -204,14 +178,46 @@ pub fn scanline_flood_fill_with_mask(
     threshold: u8,
     selection_mask: &[u8],
     limit_mode: &str,
+    connectivity: Option<Connectivity>,
 ) -> bool {
+    flood_fill_with_mask_result(
+        buffer,
+        width,
+        height,
+        start_x,
+        start_y,
+        [fill_color_r, fill_color_g, fill_color_b, fill_color_a],
+        threshold,
+        connectivity.unwrap_or(Connectivity::Four),
+        selection_mask,
+        limit_mode,
+        |_, _| {},
+    )
+    .is_some()
+}
+
+/// `scanline_flood_fill_with_mask` の本体。変更内容を返し、開始位置が無効な場合は None
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn flood_fill_with_mask_result(
+    buffer: &mut [u8],
+    width: u32,
+    height: u32,
+    start_x: u32,
+    start_y: u32,
+    fill_color: [u8; 4],
+    threshold: u8,
+    connectivity: Connectivity,
+    selection_mask: &[u8],
+    limit_mode: &str,
+    before_paint: impl FnOnce(&[u8], &[u8]),
+) -> Option<Vec<u8>> {
     let width = width as usize;
     let height = height as usize;
     let start_x = start_x as usize;
     let start_y = start_y as usize;
 
     if start_x >= width || start_y >= height {
-        return false;
+        return None;
     }
 
     // 選択範囲制限チェック関数
@@ -220,7 +226,7 @@ pub fn scanline_flood_fill_with_mask(
         if mask_index >= selection_mask.len() {
             return false;
         }
-        let is_in_selection = selection_mask[mask_index] == 1;
+        let is_in_selection = selection_mask[mask_index] != 0;
 
         match limit_mode {
             "inside" => is_in_selection,
@@ -231,159 +237,568 @@ pub fn scanline_flood_fill_with_mask(
 
     // 開始位置が制限に違反していないかチェック
     if !is_allowed(start_x, start_y) {
-        return false;
+        return None;
+    }
+
+    // しきい値が最大の場合、マスク条件に合致する全画素を高速に塗りつぶす
+    let coverage = if threshold == 255 {
+        (0..width * height)
+            .map(|i| {
+                if is_allowed(i % width, i / width) {
+                    255
+                } else {
+                    0
+                }
+            })
+            .collect()
+    } else {
+        let region = scanline_region(
+            buffer,
+            width,
+            height,
+            start_x,
+            start_y,
+            |color, target| colors_match(color, target, threshold),
+            connectivity,
+            is_allowed,
+        );
+        region_coverage(&region)
+    };
+    before_paint(buffer, &coverage);
+    let changes = paint_coverage(buffer, &coverage, fill_color, CoverageMode::Binary);
+    Some(changes)
+}
+
+/// 色距離の指定付きスキャンライン FloodFill
+///
+/// `selection_mask` と `limit_mode` は `scanline_flood_fill_with_mask` と同じです。
+/// 選択範囲で制限しない場合は空の配列と "none" を渡します。
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn scanline_flood_fill_with_options(
+    buffer: &mut [u8],
+    width: u32,
+    height: u32,
+    start_x: u32,
+    start_y: u32,
+    fill_color_r: u8,
+    fill_color_g: u8,
+    fill_color_b: u8,
+    fill_color_a: u8,
+    selection_mask: &[u8],
+    limit_mode: &str,
+    options: &FloodFillOption,
+) -> bool {
+    options_fill_result(
+        buffer,
+        width,
+        height,
+        start_x,
+        start_y,
+        [fill_color_r, fill_color_g, fill_color_b, fill_color_a],
+        selection_mask,
+        limit_mode,
+        options,
+        |_, _| {},
+    )
+    .is_some()
+}
+
+/// `scanline_flood_fill_with_options` の本体。変更内容を返し、開始位置が無効な場合は None
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn options_fill_result(
+    buffer: &mut [u8],
+    width: u32,
+    height: u32,
+    start_x: u32,
+    start_y: u32,
+    fill_color: [u8; 4],
+    selection_mask: &[u8],
+    limit_mode: &str,
+    options: &FloodFillOption,
+    before_paint: impl FnOnce(&[u8], &[u8]),
+) -> Option<Vec<u8>> {
+    let coverage = limited_coverage(
+        buffer,
+        width,
+        height,
+        start_x,
+        start_y,
+        selection_mask,
+        limit_mode,
+        options,
+    )?;
+    before_paint(buffer, &coverage);
+    let changes = paint_coverage(buffer, &coverage, fill_color, options.mask_mode);
+    Some(changes)
+}
+
+/// 参照バッファで領域を求め、別のバッファに塗る FloodFill
+///
+/// 線画レイヤー（または合成結果）を `reference` に渡し、塗りを `target` に書き込みます。
+/// 両者は同じ width * height である必要があります。
+/// `selection_mask` と `limit_mode` は `scanline_flood_fill_with_options` と同じです。
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn scanline_flood_fill_from_reference(
+    target: &mut [u8],
+    reference: &[u8],
+    width: u32,
+    height: u32,
+    start_x: u32,
+    start_y: u32,
+    fill_color_r: u8,
+    fill_color_g: u8,
+    fill_color_b: u8,
+    fill_color_a: u8,
+    selection_mask: &[u8],
+    limit_mode: &str,
+    options: &FloodFillOption,
+) -> bool {
+    reference_fill_result(
+        target,
+        reference,
+        width,
+        height,
+        start_x,
+        start_y,
+        [fill_color_r, fill_color_g, fill_color_b, fill_color_a],
+        selection_mask,
+        limit_mode,
+        options,
+        |_, _| {},
+    )
+    .is_some()
+}
+
+/// `scanline_flood_fill_from_reference` の本体。変更内容を返し、無効な場合は None
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn reference_fill_result(
+    target: &mut [u8],
+    reference: &[u8],
+    width: u32,
+    height: u32,
+    start_x: u32,
+    start_y: u32,
+    fill_color: [u8; 4],
+    selection_mask: &[u8],
+    limit_mode: &str,
+    options: &FloodFillOption,
+    before_paint: impl FnOnce(&[u8], &[u8]),
+) -> Option<Vec<u8>> {
+    let expected = width as usize * height as usize * 4;
+    if target.len() != expected || reference.len() != expected {
+        return None;
+    }
+    let coverage = limited_coverage(
+        reference,
+        width,
+        height,
+        start_x,
+        start_y,
+        selection_mask,
+        limit_mode,
+        options,
+    )?;
+    before_paint(target, &coverage);
+    let changes = paint_coverage(target, &coverage, fill_color, options.mask_mode);
+    Some(changes)
+}
+
+// 選択範囲制限付きで領域を求め、拡張とアンチエイリアスを適用した被覆率を返す。
+// 開始位置が無効な場合は None
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn limited_coverage(
+    reference: &[u8],
+    width: u32,
+    height: u32,
+    start_x: u32,
+    start_y: u32,
+    selection_mask: &[u8],
+    limit_mode: &str,
+    options: &FloodFillOption,
+) -> Option<Vec<u8>> {
+    let width = width as usize;
+    let height = height as usize;
+    let start_x = start_x as usize;
+    let start_y = start_y as usize;
+
+    if start_x >= width || start_y >= height {
+        return None;
+    }
+
+    // 選択範囲による塗りの強さ（0-255）。Binary 以外ではぼかした縁で弱まる
+    let strength = |x: usize, y: usize| -> u8 {
+        let selected = options
+            .mask_mode
+            .coverage(selection_mask.get(y * width + x).copied().unwrap_or(0));
+        match limit_mode {
+            "inside" => selected,
+            "outside" => 255 - selected,
+            _ => 255,
+        }
+    };
+    let is_allowed = |x: usize, y: usize| strength(x, y) != 0;
+    if !is_allowed(start_x, start_y) {
+        return None;
+    }
+
+    let mut region = options_region(
+        reference, width, height, start_x, start_y, options, is_allowed,
+    );
+    expand_region(
+        reference,
+        &mut region,
+        width,
+        height,
+        options.expand,
+        options.expand_limit,
+        options.expand_threshold,
+        is_allowed,
+    );
+    let mut coverage = region_coverage(&region);
+    if options.antialias {
+        let target = pixel_at(reference, start_y * width + start_x);
+        antialias_edge(&mut coverage, &region, width, height, |i| {
+            options.edge_coverage(&pixel_at(reference, i), &target)
+        });
+    }
+    // 選択範囲の強さを掛ける（アンチエイリアスで広がった縁も選択範囲の外には塗らない）
+    for (i, value) in coverage.iter_mut().enumerate() {
+        let s = strength(i % width, i / width) as u32;
+        *value = ((*value as u32 * s + 127) / 255) as u8;
     }
+    Some(coverage)
+}
+
+/// 開始点から連結した対象色の領域を求める（塗りつぶしは行わない）
+///
+/// 戻り値は width * height のフラグ列で、領域内の画素が true になります。
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn scanline_region(
+    buffer: &[u8],
+    width: usize,
+    height: usize,
+    start_x: usize,
+    start_y: usize,
+    matches: impl Fn(&[u8; 4], &[u8; 4]) -> bool,
+    connectivity: Connectivity,
+    is_allowed: impl Fn(usize, usize) -> bool,
+) -> Vec<bool> {
+    if start_x >= width || start_y >= height || !is_allowed(start_x, start_y) {
+        return vec![false; width * height];
+    }
+
+    let target_color = pixel_at(buffer, start_y * width + start_x);
+    scanline_walk(width, height, start_x, start_y, connectivity, |x, y| {
+        is_allowed(x, y) && matches(&pixel_at(buffer, y * width + x), &target_color)
+    })
+}
 
-    let start_index = (start_y * width + start_x) * 4;
+/// flat 番目の画素の色
+pub(crate) fn pixel_at(buffer: &[u8], flat: usize) -> [u8; 4] {
+    let i = flat * 4;
+    [buffer[i], buffer[i + 1], buffer[i + 2], buffer[i + 3]]
+}
+
+/// `FloodFillOption` に従って領域を求める（隙間閉じを含む）
+pub(crate) fn options_region(
+    buffer: &[u8],
+    width: usize,
+    height: usize,
+    start_x: usize,
+    start_y: usize,
+    options: &FloodFillOption,
+    is_allowed: impl Fn(usize, usize) -> bool,
+) -> Vec<bool> {
+    if options.gap_size == 0 || start_x >= width || start_y >= height {
+        return scanline_region(
+            buffer,
+            width,
+            height,
+            start_x,
+            start_y,
+            |color, target| options.matches(color, target),
+            options.connectivity,
+            is_allowed,
+        );
+    }
+
+    let start = (start_y * width + start_x) * 4;
     let target_color = [
-        buffer[start_index],
-        buffer[start_index + 1],
-        buffer[start_index + 2],
-        buffer[start_index + 3],
+        buffer[start],
+        buffer[start + 1],
+        buffer[start + 2],
+        buffer[start + 3],
     ];
+    let fillable: Vec<bool> = buffer
+        .chunks_exact(4)
+        .take(width * height)
+        .enumerate()
+        .map(|(i, px)| {
+            is_allowed(i % width, i / width)
+                && options.matches(&[px[0], px[1], px[2], px[3]], &target_color)
+        })
+        .collect();
+    gap_closed_region(
+        &fillable,
+        width,
+        height,
+        start_x,
+        start_y,
+        options.gap_size,
+        options.connectivity,
+    )
+}
+
+/// 判定関数が true を返す画素のうち、開始点から連結した領域を求める
+pub(crate) fn scanline_walk(
+    width: usize,
+    height: usize,
+    start_x: usize,
+    start_y: usize,
+    connectivity: Connectivity,
+    is_target: impl Fn(usize, usize) -> bool,
+) -> Vec<bool> {
+    let mut state = DenseWalk {
+        width,
+        visited: vec![false; width * height],
+        region: vec![false; width * height],
+    };
+    scanline_walk_with(
+        &mut state,
+        width,
+        height,
+        start_x,
+        start_y,
+        connectivity,
+        is_target,
+    );
+    state.region
+}
 
-    let fill_color = [fill_color_r, fill_color_g, fill_color_b, fill_color_a];
+/// `scanline_walk_with` が訪問済みの画素と領域を記録する先
+pub(crate) trait WalkState {
+    fn is_visited(&self, x: usize, y: usize) -> bool;
+    fn visit(&mut self, x: usize, y: usize);
+    /// y 行の left..=right を領域に追加する（訪問済みとして記録済み）
+    fn add_span(&mut self, y: usize, left: usize, right: usize);
+}
 
-    // 既に同じ色の場合は何もしない
-    // if colors_match(&target_color, &fill_color, 0) {
-    //     return false;
-    // }
+// width * height の配列で記録する
+struct DenseWalk {
+    width: usize,
+    visited: Vec<bool>,
+    region: Vec<bool>,
+}
 
-    // しきい値が最大の場合、マスク条件に合致する全画素を高速に塗りつぶす
-    if threshold == 255 {
-        for y in 0..height {
-            for x in 0..width {
-                if is_allowed(x, y) {
-                    let idx = (y * width + x) * 4;
-                    buffer[idx] = fill_color[0];
-                    buffer[idx + 1] = fill_color[1];
-                    buffer[idx + 2] = fill_color[2];
-                    buffer[idx + 3] = fill_color[3];
-                }
-            }
-        }
-        return true;
+impl WalkState for DenseWalk {
+    fn is_visited(&self, x: usize, y: usize) -> bool {
+        self.visited[y * self.width + x]
+    }
+    fn visit(&mut self, x: usize, y: usize) {
+        self.visited[y * self.width + x] = true;
+    }
+    fn add_span(&mut self, y: usize, left: usize, right: usize) {
+        self.region[y * self.width + left..=y * self.width + right].fill(true);
+    }
+}
+
+/// `scanline_walk` の本体。結果は `state` に記録する
+pub(crate) fn scanline_walk_with(
+    state: &mut impl WalkState,
+    width: usize,
+    height: usize,
+    start_x: usize,
+    start_y: usize,
+    connectivity: Connectivity,
+    is_target: impl Fn(usize, usize) -> bool,
+) {
+    if start_x >= width || start_y >= height {
+        return;
     }
 
     let mut stack = Vec::new();
-    let mut visited = vec![false; width * height];
     stack.push((start_x, start_y));
 
     while let Some((x, y)) = stack.pop() {
-        if x >= width || y >= height {
-            continue;
-        }
-
-        let flat_index = y * width + x;
-        if visited[flat_index] {
-            continue;
-        }
-        visited[flat_index] = true;
-
-        // 選択範囲制限チェック
-        if !is_allowed(x, y) {
+        if state.is_visited(x, y) {
             continue;
         }
-
-        let pixel_index = flat_index * 4;
-        let current_color = [
-            buffer[pixel_index],
-            buffer[pixel_index + 1],
-            buffer[pixel_index + 2],
-            buffer[pixel_index + 3],
-        ];
+        state.visit(x, y);
 
         // 対象色でない場合は継続
-        if !colors_match(&current_color, &target_color, threshold) {
+        if !is_target(x, y) {
             continue;
         }
 
-        // スキャンライン方式：左右に拡張（選択範囲制限付き）
+        // スキャンライン方式：左右に拡張
         let mut left = x;
         let mut right = x;
 
         // 左方向に拡張
-        while left > 0 && is_allowed(left - 1, y) {
-            let left_index = (y * width + (left - 1)) * 4;
-            let left_color = [
-                buffer[left_index],
-                buffer[left_index + 1],
-                buffer[left_index + 2],
-                buffer[left_index + 3],
-            ];
-            if colors_match(&left_color, &target_color, threshold) {
-                left -= 1;
-                visited[y * width + left] = true;
-            } else {
-                break;
-            }
+        while left > 0 && !state.is_visited(left - 1, y) && is_target(left - 1, y) {
+            left -= 1;
+            state.visit(left, y);
         }
 
         // 右方向に拡張
-        while right < width - 1 && is_allowed(right + 1, y) {
-            let right_index = (y * width + (right + 1)) * 4;
-            let right_color = [
-                buffer[right_index],
-                buffer[right_index + 1],
-                buffer[right_index + 2],
-                buffer[right_index + 3],
-            ];
-            if colors_match(&right_color, &target_color, threshold) {
-                right += 1;
-                visited[y * width + right] = true;
-            } else {
-                break;
-            }
+        while right < width - 1 && !state.is_visited(right + 1, y) && is_target(right + 1, y) {
+            right += 1;
+            state.visit(right, y);
         }
 
-        // 水平ラインを塗りつぶし
-        for scan_x in left..=right {
-            let scan_index = (y * width + scan_x) * 4;
-            buffer[scan_index] = fill_color[0];
-            buffer[scan_index + 1] = fill_color[1];
-            buffer[scan_index + 2] = fill_color[2];
-            buffer[scan_index + 3] = fill_color[3];
-        }
+        // 水平ラインを領域に追加
+        state.add_span(y, left, right);
 
-        // 上下のピクセルをスタックに追加
-        for scan_x in left..=right {
+        // 上下のピクセルをスタックに追加（8近傍では斜め方向の1画素も含める）
+        let (scan_left, scan_right) = match connectivity {
+            Connectivity::Four => (left, right),
+            Connectivity::Eight => (left.saturating_sub(1), (right + 1).min(width - 1)),
+        };
+        for scan_x in scan_left..=scan_right {
             // 上の行
-            if y > 0 {
-                let up_y = y - 1;
-                let up_flat = up_y * width + scan_x;
-                if !visited[up_flat] && is_allowed(scan_x, up_y) {
-                    let up_index = up_flat * 4;
-                    let up_color = [
-                        buffer[up_index],
-                        buffer[up_index + 1],
-                        buffer[up_index + 2],
-                        buffer[up_index + 3],
-                    ];
-                    if colors_match(&up_color, &target_color, threshold) {
-                        stack.push((scan_x, up_y));
-                    }
-                }
+            if y > 0 && !state.is_visited(scan_x, y - 1) && is_target(scan_x, y - 1) {
+                stack.push((scan_x, y - 1));
             }
 
             // 下の行
-            if y < height - 1 {
-                let down_y = y + 1;
-                let down_flat = down_y * width + scan_x;
-                if !visited[down_flat] && is_allowed(scan_x, down_y) {
-                    let down_index = down_flat * 4;
-                    let down_color = [
-                        buffer[down_index],
-                        buffer[down_index + 1],
-                        buffer[down_index + 2],
-                        buffer[down_index + 3],
-                    ];
-                    if colors_match(&down_color, &target_color, threshold) {
-                        stack.push((scan_x, down_y));
-                    }
-                }
+            if y < height - 1 && !state.is_visited(scan_x, y + 1) && is_target(scan_x, y + 1) {
+                stack.push((scan_x, y + 1));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fill::coverage::blend_coverage;
+
+    const WHITE: [u8; 4] = [255, 255, 255, 255];
+    const GRAY: [u8; 4] = [128, 128, 128, 255];
+    const BLACK: [u8; 4] = [0, 0, 0, 255];
+    const RED: [u8; 4] = [255, 0, 0, 255];
+
+    // Rows of white | gray | black, the gray column being an antialiased line edge
+    fn line_art(width: usize, height: usize) -> Vec<u8> {
+        (0..width * height)
+            .flat_map(|i| match i % width {
+                0..=2 => WHITE,
+                3 => GRAY,
+                _ => BLACK,
+            })
+            .collect()
+    }
+
+    fn column(buffer: &[u8], width: usize, x: usize) -> Vec<[u8; 4]> {
+        (0..buffer.len() / 4 / width)
+            .map(|y| pixel_at(buffer, y * width + x))
+            .collect()
+    }
+
+    #[test]
+    fn antialiased_fill_fades_into_the_line_edge() {
+        let reference = line_art(6, 3);
+        let mut target = vec![0u8; reference.len()];
+        let mut options = FloodFillOption::new(0, ColorMetric::ChannelMax, false);
+        options.antialias = true;
+        let changes = reference_fill_result(
+            &mut target,
+            &reference,
+            6,
+            3,
+            0,
+            1,
+            RED,
+            &[],
+            "none",
+            &options,
+            |_, _| {},
+        )
+        .unwrap();
+
+        // The gray edge is 127 away from white, so it gets the remaining 128 of coverage
+        let edge = distance_coverage(127.0, 0);
+        assert_eq!(edge, 128);
+        let painted = blend_coverage(&[0, 0, 0, 0], RED, edge, CoverageMode::Premultiplied);
+        assert_eq!(painted, [255, 0, 0, 128]);
+        for y in 0..3 {
+            for x in 0..3 {
+                assert_eq!(pixel_at(&target, y * 6 + x), RED);
+                assert_eq!(changes[y * 6 + x], 255);
             }
+            assert_eq!(pixel_at(&target, y * 6 + 3), painted);
+            assert_eq!(changes[y * 6 + 3], edge);
         }
+        assert!(column(&target, 6, 4).iter().all(|p| *p == [0, 0, 0, 0]));
+
+        // Without antialiasing the edge stays untouched
+        options.antialias = false;
+        let mut target = vec![0u8; reference.len()];
+        reference_fill_result(
+            &mut target,
+            &reference,
+            6,
+            3,
+            0,
+            1,
+            RED,
+            &[],
+            "none",
+            &options,
+            |_, _| {},
+        )
+        .unwrap();
+        assert!(column(&target, 6, 3).iter().all(|p| *p == [0, 0, 0, 0]));
     }
 
-    true
+    #[test]
+    fn reference_fill_finds_the_region_in_the_reference_buffer() {
+        let reference = line_art(6, 3);
+        // A uniform target, so the region can only come from the reference
+        let blue = [0, 0, 255, 255];
+        let mut target: Vec<u8> = [blue; 18].concat();
+        let options = FloodFillOption::new(0, ColorMetric::ChannelMax, false);
+        assert!(scanline_flood_fill_from_reference(
+            &mut target,
+            &reference,
+            6,
+            3,
+            5,
+            2,
+            255,
+            0,
+            0,
+            255,
+            &[],
+            "none",
+            &options,
+        ));
+        for y in 0..3 {
+            for x in 0..6 {
+                let expected = if x >= 4 { RED } else { blue };
+                assert_eq!(pixel_at(&target, y * 6 + x), expected, "({x}, {y})");
+            }
+        }
+        assert_eq!(reference, line_art(6, 3));
+
+        // Both buffers must be width * height
+        let mut short = vec![0u8; 4 * 17];
+        assert!(!scanline_flood_fill_from_reference(
+            &mut short,
+            &reference,
+            6,
+            3,
+            0,
+            0,
+            255,
+            0,
+            0,
+            255,
+            &[],
+            "none",
+            &options,
+        ));
+    }
 }
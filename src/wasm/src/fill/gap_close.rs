@@ -0,0 +1,153 @@
+use crate::{
+    fill::{flood_fill::scanline_walk, Connectivity},
+    selection::morphology::squared_distance,
+};
+
+/// Fill region that treats openings up to `gap_size` pixels wide as closed.
+///
+/// The fillable area is eroded by half the gap so narrow openings disappear, the
+/// region containing the seed is walked there, and then it is grown back by the same
+/// distance over fillable pixels so the result still reaches the original lines.
+/// Only the seed's own connected area is considered, so nothing jumps across a line.
+/// Leaks through a closed gap are limited to that distance.
+pub(crate) fn gap_closed_region(
+    fillable: &[bool],
+    width: usize,
+    height: usize,
+    start_x: usize,
+    start_y: usize,
+    gap_size: u32,
+    connectivity: Connectivity,
+) -> Vec<bool> {
+    if start_x >= width || start_y >= height || !fillable[start_y * width + start_x] {
+        return vec![false; width * height];
+    }
+    let plain = scanline_walk(width, height, start_x, start_y, connectivity, |x, y| {
+        fillable[y * width + x]
+    });
+
+    let radius = gap_size.div_ceil(2) as f32;
+    let flags: Vec<u8> = fillable.iter().map(|f| *f as u8).collect();
+
+    // Erode: keep fillable pixels farther than `radius` from any boundary pixel
+    let to_boundary = squared_distance(&flags, width, height, |v| v == 0);
+    let core: Vec<bool> = plain
+        .iter()
+        .zip(to_boundary.iter())
+        .map(|(f, d)| *f && *d > radius * radius)
+        .collect();
+
+    // Seeds close to a line fall outside the eroded area; start from the nearest core pixel
+    let Some((core_x, core_y)) = nearest_core(&core, width, height, start_x, start_y, radius)
+    else {
+        // Too narrow to erode at all: fall back to the plain region
+        return plain;
+    };
+    let core_region = scanline_walk(width, height, core_x, core_y, connectivity, |x, y| {
+        core[y * width + x]
+    });
+
+    // Grow back: a fillable pixel joins when the seed's core is at least as close as any
+    // other core area (so corners are reached but the far side of a gap is not), and no
+    // farther than the erosion could have removed
+    let own_flags: Vec<u8> = core_region.iter().map(|f| *f as u8).collect();
+    let other_flags: Vec<u8> = core
+        .iter()
+        .zip(core_region.iter())
+        .map(|(c, own)| (*c && !*own) as u8)
+        .collect();
+    let to_own = squared_distance(&own_flags, width, height, |v| v != 0);
+    let to_other = squared_distance(&other_flags, width, height, |v| v != 0);
+    let reach = (2.0 * radius + 1.0).powi(2);
+    let grown: Vec<bool> = plain
+        .iter()
+        .zip(to_own.iter().zip(to_other.iter()))
+        .map(|(f, (own, other))| *f && *own <= reach && *own <= *other)
+        .collect();
+    let mut region = scanline_walk(width, height, core_x, core_y, connectivity, |x, y| {
+        grown[y * width + x]
+    });
+    // The seed itself is always painted
+    region[start_y * width + start_x] = true;
+    region
+}
+
+fn nearest_core(
+    core: &[bool],
+    width: usize,
+    height: usize,
+    start_x: usize,
+    start_y: usize,
+    radius: f32,
+) -> Option<(usize, usize)> {
+    if core[start_y * width + start_x] {
+        return Some((start_x, start_y));
+    }
+    let reach = radius.ceil() as usize + 1;
+    let mut best: Option<(usize, usize, usize)> = None;
+    for y in start_y.saturating_sub(reach)..(start_y + reach + 1).min(height) {
+        for x in start_x.saturating_sub(reach)..(start_x + reach + 1).min(width) {
+            if !core[y * width + x] {
+                continue;
+            }
+            let d = x.abs_diff(start_x).pow(2) + y.abs_diff(start_y).pow(2);
+            if best.is_none_or(|(_, _, bd)| d < bd) {
+                best = Some((x, y, d));
+            }
+        }
+    }
+    best.map(|(x, y, _)| (x, y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIZE: usize = 20;
+    const WALL_X: usize = 10;
+
+    // Open canvas split by a vertical line at WALL_X with a `gap` pixel opening at y = 9
+    fn split_canvas(gap: usize) -> Vec<bool> {
+        (0..SIZE * SIZE)
+            .map(|i| {
+                let (x, y) = (i % SIZE, i / SIZE);
+                x != WALL_X || (9..9 + gap).contains(&y)
+            })
+            .collect()
+    }
+
+    fn right_side(region: &[bool]) -> usize {
+        region
+            .iter()
+            .enumerate()
+            .filter(|(i, inside)| **inside && i % SIZE > WALL_X)
+            .count()
+    }
+
+    #[test]
+    fn closes_gaps_up_to_the_gap_size() {
+        for gap in 1..=4 {
+            let fillable = split_canvas(gap);
+            let region =
+                gap_closed_region(&fillable, SIZE, SIZE, 2, 2, gap as u32, Connectivity::Four);
+            assert_eq!(right_side(&region), 0, "gap {gap}");
+            // The seed side is still filled up to the line
+            assert!(region[9 * SIZE + WALL_X - 1], "gap {gap}");
+            assert!(region[(SIZE - 1) * SIZE], "gap {gap}");
+        }
+    }
+
+    #[test]
+    fn leaks_without_gap_closing() {
+        let fillable = split_canvas(2);
+        let region = gap_closed_region(&fillable, SIZE, SIZE, 2, 2, 0, Connectivity::Four);
+        assert_eq!(right_side(&region), (SIZE - WALL_X - 1) * SIZE);
+    }
+
+    #[test]
+    fn wider_gaps_stay_open() {
+        let fillable = split_canvas(6);
+        let region = gap_closed_region(&fillable, SIZE, SIZE, 2, 2, 2, Connectivity::Four);
+        assert!(right_side(&region) > 0);
+    }
+}
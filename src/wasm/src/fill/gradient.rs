@@ -0,0 +1,495 @@
+use std::{f32::consts::TAU, sync::OnceLock};
+
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    effects::dithering::BAYER_MATRIX_4X4,
+    fill::{
+        color_match::{linear_to_oklab, linear_to_srgb, oklab_to_linear, srgb_to_linear},
+        coverage::{paint_coverage_with, CoverageMode},
+    },
+};
+
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GradientShape {
+    /// Along the line from the start point to the end point
+    Linear = 0,
+    /// Circles around the start point, reaching the last stop at the end point
+    Radial = 1,
+    /// Sweeps clockwise around the start point, beginning in the direction of the end point
+    Angular = 2,
+    /// Squares around the start point, with a corner at the end point
+    Diamond = 3,
+    /// Linear, mirrored around the start point
+    Reflected = 4,
+}
+
+/// Color space the stops are interpolated in. Alpha is always interpolated premultiplied.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GradientInterpolation {
+    Srgb = 0,
+    LinearRgb = 1,
+    OkLab = 2,
+}
+
+/// What happens past the last stop.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GradientRepeat {
+    /// The end colors extend outward
+    None = 0,
+    /// The gradient starts over
+    Repeat = 1,
+    /// The gradient runs back and forth
+    Reflect = 2,
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GradientDither {
+    /// Round to the nearest value
+    None = 0,
+    /// 4x4 Bayer matrix
+    Ordered = 1,
+    /// 32x32 blue noise threshold map
+    BlueNoise = 2,
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Copy)]
+pub struct GradientFillOption {
+    pub shape: GradientShape,
+    /// Start point in pixels (pixel centers are at +0.5)
+    pub start_x: f32,
+    pub start_y: f32,
+    /// End point in pixels
+    pub end_x: f32,
+    pub end_y: f32,
+    pub interpolation: GradientInterpolation,
+    pub repeat: GradientRepeat,
+    pub dither: GradientDither,
+}
+
+#[wasm_bindgen]
+impl GradientFillOption {
+    /// `interpolation`, `repeat` and `dither` default to sRGB, none and none and are set
+    /// as properties.
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        shape: GradientShape,
+        start_x: f32,
+        start_y: f32,
+        end_x: f32,
+        end_y: f32,
+    ) -> GradientFillOption {
+        GradientFillOption {
+            shape,
+            start_x,
+            start_y,
+            end_x,
+            end_y,
+            interpolation: GradientInterpolation::Srgb,
+            repeat: GradientRepeat::None,
+            dither: GradientDither::None,
+        }
+    }
+}
+
+// Resolution of the precomputed color ramp
+const RAMP_SIZE: usize = 4096;
+const BLUE_NOISE_SIZE: usize = 32;
+
+/// Paint a gradient over the buffer.
+///
+/// `stop_positions` (0-1) and `stop_colors` (flattened RGBA, four per stop) describe the
+/// color stops; stops need not be sorted. A non-empty `selection_mask` (width * height,
+/// 0-255 coverage) limits the fill and fades it at partial coverage.
+/// Returns false if there are no stops or the sizes do not match.
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn gradient_fill(
+    buffer: &mut [u8],
+    width: u32,
+    height: u32,
+    stop_positions: &[f32],
+    stop_colors: &[u8],
+    selection_mask: &[u8],
+    options: &GradientFillOption,
+) -> bool {
+    gradient_fill_result(
+        buffer,
+        width,
+        height,
+        stop_positions,
+        stop_colors,
+        selection_mask,
+        options,
+        |_, _| {},
+    )
+    .is_some()
+}
+
+/// `gradient_fill` returning the per-pixel changes (see `paint_coverage`). `before_paint` receives the buffer and the
+/// coverage right before painting.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn gradient_fill_result(
+    buffer: &mut [u8],
+    width: u32,
+    height: u32,
+    stop_positions: &[f32],
+    stop_colors: &[u8],
+    selection_mask: &[u8],
+    options: &GradientFillOption,
+    before_paint: impl FnOnce(&[u8], &[u8]),
+) -> Option<Vec<u8>> {
+    let (width, height) = (width as usize, height as usize);
+    if buffer.len() < width * height * 4
+        || (!selection_mask.is_empty() && selection_mask.len() < width * height)
+    {
+        return None;
+    }
+    let ramp = color_ramp(stop_positions, stop_colors, options.interpolation)?;
+    let coverage = if selection_mask.is_empty() {
+        vec![255; width * height]
+    } else {
+        selection_mask[..width * height].to_vec()
+    };
+
+    before_paint(buffer, &coverage);
+    let changes = paint_coverage_with(buffer, &coverage, CoverageMode::Premultiplied, |i| {
+        let (x, y) = (i % width, i / width);
+        let t = gradient_position(options, x as f32 + 0.5, y as f32 + 0.5);
+        let color = ramp[(repeat(t, options.repeat) * (RAMP_SIZE - 1) as f32).round() as usize];
+        let offset = dither_offset(options.dither, x, y);
+        color.map(|v| (v + offset).floor().clamp(0.0, 255.0) as u8)
+    });
+    Some(changes)
+}
+
+// Unrepeated gradient parameter at a point; 0 at the start, 1 at the end
+fn gradient_position(options: &GradientFillOption, x: f32, y: f32) -> f32 {
+    let (dx, dy) = (
+        options.end_x - options.start_x,
+        options.end_y - options.start_y,
+    );
+    let (px, py) = (x - options.start_x, y - options.start_y);
+    let len2 = dx * dx + dy * dy;
+    if len2 <= f32::EPSILON {
+        return 0.0;
+    }
+    let along = (px * dx + py * dy) / len2;
+    match options.shape {
+        GradientShape::Linear => along,
+        GradientShape::Reflected => along.abs(),
+        GradientShape::Radial => ((px * px + py * py) / len2).sqrt(),
+        GradientShape::Diamond => along.abs() + ((py * dx - px * dy) / len2).abs(),
+        GradientShape::Angular => ((py.atan2(px) - dy.atan2(dx)) / TAU).rem_euclid(1.0),
+    }
+}
+
+fn repeat(t: f32, mode: GradientRepeat) -> f32 {
+    match mode {
+        GradientRepeat::None => t.clamp(0.0, 1.0),
+        GradientRepeat::Repeat => t.rem_euclid(1.0),
+        GradientRepeat::Reflect => {
+            let m = t.rem_euclid(2.0);
+            if m > 1.0 {
+                2.0 - m
+            } else {
+                m
+            }
+        }
+    }
+}
+
+// Added before flooring; 0.5 without dithering rounds to nearest
+fn dither_offset(dither: GradientDither, x: usize, y: usize) -> f32 {
+    match dither {
+        GradientDither::None => 0.5,
+        GradientDither::Ordered => BAYER_MATRIX_4X4[y % 4][x % 4] + 1.0 / 32.0,
+        GradientDither::BlueNoise => {
+            blue_noise()[(y % BLUE_NOISE_SIZE) * BLUE_NOISE_SIZE + x % BLUE_NOISE_SIZE]
+        }
+    }
+}
+
+// Straight sRGB colors (0-255, unrounded) sampled evenly over 0..=1
+fn color_ramp(
+    positions: &[f32],
+    colors: &[u8],
+    interpolation: GradientInterpolation,
+) -> Option<Vec<[f32; 4]>> {
+    if positions.is_empty() || colors.len() < positions.len() * 4 {
+        return None;
+    }
+    let mut stops: Vec<(f32, [f32; 4])> = positions
+        .iter()
+        .zip(colors.chunks_exact(4))
+        .map(|(p, c)| (*p, to_working(c, interpolation)))
+        .collect();
+    stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let ramp = (0..RAMP_SIZE)
+        .map(|k| {
+            let t = k as f32 / (RAMP_SIZE - 1) as f32;
+            let next = stops.partition_point(|(p, _)| *p <= t);
+            let color = if next == 0 {
+                stops[0].1
+            } else if next == stops.len() {
+                stops[next - 1].1
+            } else {
+                let ((p0, c0), (p1, c1)) = (stops[next - 1], stops[next]);
+                let f = (t - p0) / (p1 - p0);
+                std::array::from_fn(|ch| c0[ch] + (c1[ch] - c0[ch]) * f)
+            };
+            from_working(color, interpolation)
+        })
+        .collect();
+    Some(ramp)
+}
+
+// Premultiplied color in the interpolation space
+fn to_working(color: &[u8], interpolation: GradientInterpolation) -> [f32; 4] {
+    let a = color[3] as f32 / 255.0;
+    let [c0, c1, c2] = match interpolation {
+        GradientInterpolation::Srgb => [0, 1, 2].map(|ch| color[ch] as f32 / 255.0),
+        GradientInterpolation::LinearRgb => [0, 1, 2].map(|ch| srgb_to_linear(color[ch])),
+        GradientInterpolation::OkLab => {
+            linear_to_oklab([0, 1, 2].map(|ch| srgb_to_linear(color[ch])))
+        }
+    };
+    [c0 * a, c1 * a, c2 * a, a]
+}
+
+fn from_working(color: [f32; 4], interpolation: GradientInterpolation) -> [f32; 4] {
+    let a = color[3];
+    if a <= 0.0 {
+        return [0.0; 4];
+    }
+    let c = [color[0] / a, color[1] / a, color[2] / a];
+    let srgb = match interpolation {
+        GradientInterpolation::Srgb => c,
+        GradientInterpolation::LinearRgb => c.map(linear_to_srgb),
+        GradientInterpolation::OkLab => oklab_to_linear(c).map(linear_to_srgb),
+    };
+    [srgb[0] * 255.0, srgb[1] * 255.0, srgb[2] * 255.0, a * 255.0]
+}
+
+// Blue noise thresholds in (0, 1), generated once with void-and-cluster (Ulichney 1993)
+fn blue_noise() -> &'static [f32] {
+    static NOISE: OnceLock<Vec<f32>> = OnceLock::new();
+    NOISE.get_or_init(|| void_and_cluster(BLUE_NOISE_SIZE))
+}
+
+fn void_and_cluster(size: usize) -> Vec<f32> {
+    let n = size * size;
+    // Gaussian energy (sigma 1.5) on a torus so the map tiles seamlessly
+    let kernel: Vec<f32> = (0..n)
+        .map(|i| {
+            let (dx, dy) = (i % size, i / size);
+            let (dx, dy) = (dx.min(size - dx) as f32, dy.min(size - dy) as f32);
+            (-(dx * dx + dy * dy) / (2.0 * 1.5 * 1.5)).exp()
+        })
+        .collect();
+    let update = |energy: &mut [f32], p: usize, sign: f32| {
+        let (px, py) = (p % size, p / size);
+        for (q, e) in energy.iter_mut().enumerate() {
+            let dx = (q % size + size - px) % size;
+            let dy = (q / size + size - py) % size;
+            *e += sign * kernel[dy * size + dx];
+        }
+    };
+    // Tightest cluster among set points, or largest void among unset ones
+    let extreme = |pattern: &[bool], energy: &[f32], set: bool| -> usize {
+        let candidates = (0..n).filter(|i| pattern[*i] == set);
+        if set {
+            candidates.max_by(|a, b| energy[*a].total_cmp(&energy[*b]))
+        } else {
+            candidates.min_by(|a, b| energy[*a].total_cmp(&energy[*b]))
+        }
+        .unwrap_or(0)
+    };
+
+    // Initial random pattern of about 10% points
+    let mut pattern = vec![false; n];
+    let mut energy = vec![0f32; n];
+    let mut state: u32 = 1;
+    let mut ones = 0;
+    while ones < n / 10 {
+        state = state.wrapping_mul(1103515245).wrapping_add(12345);
+        let p = (state >> 8) as usize % n;
+        if !pattern[p] {
+            pattern[p] = true;
+            update(&mut energy, p, 1.0);
+            ones += 1;
+        }
+    }
+    // Move points from clusters into voids until the pattern is even
+    loop {
+        let cluster = extreme(&pattern, &energy, true);
+        pattern[cluster] = false;
+        update(&mut energy, cluster, -1.0);
+        let void = extreme(&pattern, &energy, false);
+        pattern[void] = true;
+        update(&mut energy, void, 1.0);
+        if void == cluster {
+            break;
+        }
+    }
+
+    let mut rank = vec![0usize; n];
+    // Rank the initial points by removing the tightest clusters first
+    let (mut removed, mut removed_energy) = (pattern.clone(), energy.clone());
+    for r in (0..ones).rev() {
+        let cluster = extreme(&removed, &removed_energy, true);
+        removed[cluster] = false;
+        update(&mut removed_energy, cluster, -1.0);
+        rank[cluster] = r;
+    }
+    // Rank the rest by filling the largest voids
+    for r in ones..n {
+        let void = extreme(&pattern, &energy, false);
+        pattern[void] = true;
+        update(&mut energy, void, 1.0);
+        rank[void] = r;
+    }
+    rank.iter().map(|r| (*r as f32 + 0.5) / n as f32).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLACK_TO_WHITE: [u8; 8] = [0, 0, 0, 255, 255, 255, 255, 255];
+
+    fn linear(end_x: f32) -> GradientFillOption {
+        GradientFillOption::new(GradientShape::Linear, 0.0, 0.0, end_x, 0.0)
+    }
+
+    fn fill(
+        width: u32,
+        height: u32,
+        positions: &[f32],
+        colors: &[u8],
+        options: &GradientFillOption,
+    ) -> Vec<u8> {
+        let mut buffer = vec![0u8; (width * height * 4) as usize];
+        assert!(gradient_fill(
+            &mut buffer,
+            width,
+            height,
+            positions,
+            colors,
+            &[],
+            options
+        ));
+        buffer
+    }
+
+    fn pixel(buffer: &[u8], width: u32, x: u32, y: u32) -> &[u8] {
+        let i = ((y * width + x) * 4) as usize;
+        &buffer[i..i + 4]
+    }
+
+    #[test]
+    fn reaches_the_stop_colors_at_the_ends() {
+        for shape in [GradientShape::Linear, GradientShape::Radial] {
+            let mut options = GradientFillOption::new(shape, 0.5, 0.5, 15.5, 0.5);
+            for interpolation in [
+                GradientInterpolation::Srgb,
+                GradientInterpolation::LinearRgb,
+                GradientInterpolation::OkLab,
+            ] {
+                options.interpolation = interpolation;
+                let buffer = fill(16, 1, &[0.0, 1.0], &BLACK_TO_WHITE, &options);
+                assert_eq!(
+                    pixel(&buffer, 16, 0, 0),
+                    [0, 0, 0, 255],
+                    "{shape:?} {interpolation:?}"
+                );
+                assert_eq!(
+                    pixel(&buffer, 16, 15, 0),
+                    [255, 255, 255, 255],
+                    "{shape:?} {interpolation:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn repeat_starts_over_and_reflect_runs_back() {
+        let mut options = linear(4.0);
+        options.repeat = GradientRepeat::Repeat;
+        let repeated = fill(16, 1, &[0.0, 1.0], &BLACK_TO_WHITE, &options);
+        options.repeat = GradientRepeat::Reflect;
+        let reflected = fill(16, 1, &[0.0, 1.0], &BLACK_TO_WHITE, &options);
+        options.repeat = GradientRepeat::None;
+        let clamped = fill(16, 1, &[0.0, 1.0], &BLACK_TO_WHITE, &options);
+
+        for x in 0..4 {
+            assert_eq!(pixel(&repeated, 16, x, 0), pixel(&repeated, 16, x + 4, 0));
+            assert_eq!(pixel(&repeated, 16, x, 0), pixel(&repeated, 16, x + 8, 0));
+            // The second period mirrors the first
+            assert_eq!(pixel(&reflected, 16, x, 0), pixel(&reflected, 16, 7 - x, 0));
+            assert_eq!(pixel(&reflected, 16, x, 0), pixel(&reflected, 16, x + 8, 0));
+            assert_eq!(pixel(&clamped, 16, x + 4, 0), [255, 255, 255, 255]);
+        }
+        assert!(pixel(&repeated, 16, 0, 0)[0] < pixel(&repeated, 16, 3, 0)[0]);
+    }
+
+    #[test]
+    fn stops_at_the_same_position_make_a_hard_edge() {
+        let colors = [
+            255, 0, 0, 255, //
+            255, 0, 0, 255, //
+            0, 0, 255, 255, //
+            0, 0, 255, 255,
+        ];
+        let options = linear(16.0);
+        let buffer = fill(16, 1, &[0.0, 0.5, 0.5, 1.0], &colors, &options);
+        for x in 0..16 {
+            let expected = if x < 8 {
+                [255, 0, 0, 255]
+            } else {
+                [0, 0, 255, 255]
+            };
+            assert_eq!(pixel(&buffer, 16, x, 0), expected, "x {x}");
+        }
+
+        // Every stop at one position splits the buffer there
+        let buffer = fill(16, 1, &[0.25, 0.25], &colors[4..12], &options);
+        assert_eq!(pixel(&buffer, 16, 3, 0), [255, 0, 0, 255]);
+        assert_eq!(pixel(&buffer, 16, 4, 0), [0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn dithering_stays_within_one_step_of_the_ramp() {
+        let (width, height) = (64, 8);
+        let mut options = linear(width as f32);
+        let plain = fill(
+            width,
+            height,
+            &[0.0, 1.0],
+            &[0, 0, 0, 255, 16, 32, 64, 255],
+            &options,
+        );
+        for dither in [GradientDither::Ordered, GradientDither::BlueNoise] {
+            options.dither = dither;
+            let dithered = fill(
+                width,
+                height,
+                &[0.0, 1.0],
+                &[0, 0, 0, 255, 16, 32, 64, 255],
+                &options,
+            );
+            let mut differs = false;
+            for (d, p) in dithered.iter().zip(plain.iter()) {
+                assert!(d.abs_diff(*p) <= 1, "{dither:?}");
+                differs |= d != p;
+            }
+            assert!(differs, "{dither:?}");
+        }
+    }
+}
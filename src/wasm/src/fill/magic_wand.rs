@@ -0,0 +1,172 @@
+use wasm_bindgen::prelude::*;
+
+use crate::fill::{
+    color_match::{color_distance, ColorMetric},
+    colors_match,
+    coverage::{antialias_edge, distance_coverage, region_coverage},
+    flood_fill::{options_region, pixel_at, scanline_region, FloodFillOption},
+    Connectivity,
+};
+
+/// Select pixels similar to the color at (start_x, start_y) and return their coverage mask.
+///
+/// Contiguous mode walks the region connected through `connectivity` (default `Four`) like
+/// `scanline_flood_fill`; otherwise every matching pixel in the buffer is selected. With
+/// `antialias`, pixels just outside the selection get partial coverage by how close their
+/// color is to the start color. Returns an empty array if the start point is out of bounds.
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn magic_wand(
+    buffer: &[u8],
+    width: u32,
+    height: u32,
+    start_x: u32,
+    start_y: u32,
+    tolerance: u8,
+    contiguous: bool,
+    antialias: bool,
+    connectivity: Option<Connectivity>,
+) -> Vec<u8> {
+    let (width, height, start_x, start_y) =
+        match wand_bounds(buffer, width, height, start_x, start_y) {
+            Some(bounds) => bounds,
+            None => return Vec::new(),
+        };
+    let matches = |color: &[u8; 4], target: &[u8; 4]| colors_match(color, target, tolerance);
+    let region = if contiguous {
+        scanline_region(
+            buffer,
+            width,
+            height,
+            start_x,
+            start_y,
+            matches,
+            connectivity.unwrap_or(Connectivity::Four),
+            |_, _| true,
+        )
+    } else {
+        global_region(buffer, width, height, start_x, start_y, matches)
+    };
+    let mut coverage = region_coverage(&region);
+    if antialias {
+        let target = pixel_at(buffer, start_y * width + start_x);
+        antialias_edge(&mut coverage, &region, width, height, |i| {
+            let distance = color_distance(
+                &pixel_at(buffer, i),
+                &target,
+                ColorMetric::ChannelMax,
+                false,
+            );
+            distance_coverage(distance, tolerance)
+        });
+    }
+    coverage
+}
+
+/// `magic_wand` with a configurable color metric, connectivity and gap closing.
+/// `options.threshold` is the tolerance.
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn magic_wand_with_options(
+    buffer: &[u8],
+    width: u32,
+    height: u32,
+    start_x: u32,
+    start_y: u32,
+    contiguous: bool,
+    antialias: bool,
+    options: &FloodFillOption,
+) -> Vec<u8> {
+    let (width, height, start_x, start_y) =
+        match wand_bounds(buffer, width, height, start_x, start_y) {
+            Some(bounds) => bounds,
+            None => return Vec::new(),
+        };
+    let region = if contiguous {
+        options_region(buffer, width, height, start_x, start_y, options, |_, _| {
+            true
+        })
+    } else {
+        global_region(buffer, width, height, start_x, start_y, |color, target| {
+            options.matches(color, target)
+        })
+    };
+    let mut coverage = region_coverage(&region);
+    if antialias {
+        let target = pixel_at(buffer, start_y * width + start_x);
+        antialias_edge(&mut coverage, &region, width, height, |i| {
+            options.edge_coverage(&pixel_at(buffer, i), &target)
+        });
+    }
+    coverage
+}
+
+fn wand_bounds(
+    buffer: &[u8],
+    width: u32,
+    height: u32,
+    start_x: u32,
+    start_y: u32,
+) -> Option<(usize, usize, usize, usize)> {
+    let (width, height) = (width as usize, height as usize);
+    let (start_x, start_y) = (start_x as usize, start_y as usize);
+    if start_x >= width || start_y >= height || buffer.len() < width * height * 4 {
+        return None;
+    }
+    Some((width, height, start_x, start_y))
+}
+
+// Every pixel matching the start color, connected or not
+fn global_region(
+    buffer: &[u8],
+    width: usize,
+    height: usize,
+    start_x: usize,
+    start_y: usize,
+    matches: impl Fn(&[u8; 4], &[u8; 4]) -> bool,
+) -> Vec<bool> {
+    let target_color = pixel_at(buffer, start_y * width + start_x);
+    buffer
+        .chunks_exact(4)
+        .take(width * height)
+        .map(|px| matches(&[px[0], px[1], px[2], px[3]], &target_color))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Red islands separated by blue columns: x = 0, 2 and 4 are red-ish
+    fn striped() -> Vec<u8> {
+        let row: [[u8; 4]; 5] = [
+            [255, 0, 0, 255],
+            [0, 0, 255, 255],
+            [250, 5, 0, 255],
+            [0, 0, 255, 255],
+            [200, 0, 0, 255],
+        ];
+        [row.concat(), row.concat()].concat()
+    }
+
+    #[test]
+    fn non_contiguous_selects_every_matching_pixel() {
+        let buffer = striped();
+        let contiguous = magic_wand(&buffer, 5, 2, 0, 0, 10, true, false, None);
+        assert_eq!(contiguous, [255, 0, 0, 0, 0, 255, 0, 0, 0, 0]);
+
+        let global = magic_wand(&buffer, 5, 2, 0, 0, 10, false, false, None);
+        assert_eq!(global, [255, 0, 255, 0, 0, 255, 0, 255, 0, 0]);
+
+        // A wider tolerance takes in the darker island too, and still no blue
+        let global = magic_wand(&buffer, 5, 2, 0, 1, 55, false, false, None);
+        assert_eq!(global, [255, 0, 255, 0, 255, 255, 0, 255, 0, 255]);
+    }
+
+    #[test]
+    fn out_of_bounds_start_selects_nothing() {
+        let buffer = striped();
+        assert!(magic_wand(&buffer, 5, 2, 5, 0, 10, false, false, None).is_empty());
+        assert!(magic_wand(&buffer, 5, 2, 0, 2, 10, true, false, None).is_empty());
+    }
+}
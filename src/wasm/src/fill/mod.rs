@@ -1,8 +1,47 @@
 pub mod area_fill;
+pub mod color_match;
+pub mod coverage;
+pub mod expand;
 pub mod flood_fill;
+mod gap_close;
+pub mod gradient;
+pub mod magic_wand;
+pub mod pattern;
+pub mod result;
+
+use wasm_bindgen::prelude::*;
+
+/// Which neighbors count as connected when growing a region.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Connectivity {
+    /// Edge neighbors only (left, right, up, down)
+    Four = 4,
+    /// Edge and corner neighbors, so regions cross one-pixel diagonal lines
+    Eight = 8,
+}
+
+impl Connectivity {
+    /// Neighbor offsets as (dx, dy).
+    pub(crate) fn offsets(self) -> &'static [(i32, i32)] {
+        match self {
+            Connectivity::Four => &[(0, -1), (-1, 0), (1, 0), (0, 1)],
+            Connectivity::Eight => &[
+                (-1, -1),
+                (0, -1),
+                (1, -1),
+                (-1, 0),
+                (1, 0),
+                (-1, 1),
+                (0, 1),
+                (1, 1),
+            ],
+        }
+    }
+}
 
 /// 色の類似性判定（threshold付き）
-fn colors_match(color1: &[u8; 4], color2: &[u8; 4], threshold: u8) -> bool {
+pub(crate) fn colors_match(color1: &[u8; 4], color2: &[u8; 4], threshold: u8) -> bool {
     if threshold == 0 {
         color1 == color2
     } else {
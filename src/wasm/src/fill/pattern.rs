@@ -0,0 +1,301 @@
+use wasm_bindgen::prelude::*;
+
+use crate::fill::{
+    coverage::{paint_coverage_with, CoverageMode},
+    flood_fill::{limited_coverage, FloodFillOption},
+};
+
+#[wasm_bindgen]
+#[derive(Clone, Copy)]
+pub struct PatternFillOption {
+    /// Canvas position of the tile's top-left corner (the pattern repeats in every direction)
+    pub offset_x: i32,
+    pub offset_y: i32,
+    /// Integer magnification of the tile (nearest neighbor, at least 1)
+    pub scale: u32,
+}
+
+#[wasm_bindgen]
+impl PatternFillOption {
+    #[wasm_bindgen(constructor)]
+    pub fn new(offset_x: i32, offset_y: i32, scale: u32) -> PatternFillOption {
+        PatternFillOption {
+            offset_x,
+            offset_y,
+            scale: scale.max(1),
+        }
+    }
+}
+
+/// Fill the buffer with a repeating tile.
+///
+/// A non-empty `selection_mask` (width * height, 0-255 coverage) limits the fill and fades
+/// it at partial coverage. Returns false if the sizes do not match or the tile is empty.
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn pattern_fill(
+    buffer: &mut [u8],
+    width: u32,
+    height: u32,
+    tile: &[u8],
+    tile_width: u32,
+    tile_height: u32,
+    selection_mask: &[u8],
+    options: &PatternFillOption,
+) -> bool {
+    pattern_fill_result(
+        buffer,
+        width,
+        height,
+        tile,
+        tile_width,
+        tile_height,
+        selection_mask,
+        options,
+        |_, _| {},
+    )
+    .is_some()
+}
+
+/// `scanline_flood_fill_with_options` that paints the tile instead of a solid color.
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn scanline_pattern_flood_fill(
+    buffer: &mut [u8],
+    width: u32,
+    height: u32,
+    start_x: u32,
+    start_y: u32,
+    tile: &[u8],
+    tile_width: u32,
+    tile_height: u32,
+    selection_mask: &[u8],
+    limit_mode: &str,
+    fill_options: &FloodFillOption,
+    options: &PatternFillOption,
+) -> bool {
+    pattern_flood_fill_result(
+        buffer,
+        width,
+        height,
+        start_x,
+        start_y,
+        tile,
+        tile_width,
+        tile_height,
+        selection_mask,
+        limit_mode,
+        fill_options,
+        options,
+        |_, _| {},
+    )
+    .is_some()
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn pattern_fill_result(
+    buffer: &mut [u8],
+    width: u32,
+    height: u32,
+    tile: &[u8],
+    tile_width: u32,
+    tile_height: u32,
+    selection_mask: &[u8],
+    options: &PatternFillOption,
+    before_paint: impl FnOnce(&[u8], &[u8]),
+) -> Option<Vec<u8>> {
+    let len = width as usize * height as usize;
+    if buffer.len() < len * 4
+        || !tile_is_valid(tile, tile_width, tile_height)
+        || (!selection_mask.is_empty() && selection_mask.len() < len)
+    {
+        return None;
+    }
+    let coverage = if selection_mask.is_empty() {
+        vec![255; len]
+    } else {
+        selection_mask[..len].to_vec()
+    };
+    let tile = Tile::new(tile, tile_width, tile_height, options);
+    before_paint(buffer, &coverage);
+    let changes = paint_coverage_with(buffer, &coverage, CoverageMode::Premultiplied, |i| {
+        tile.color_at(i % width as usize, i / width as usize)
+    });
+    Some(changes)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn pattern_flood_fill_result(
+    buffer: &mut [u8],
+    width: u32,
+    height: u32,
+    start_x: u32,
+    start_y: u32,
+    tile: &[u8],
+    tile_width: u32,
+    tile_height: u32,
+    selection_mask: &[u8],
+    limit_mode: &str,
+    fill_options: &FloodFillOption,
+    options: &PatternFillOption,
+    before_paint: impl FnOnce(&[u8], &[u8]),
+) -> Option<Vec<u8>> {
+    if buffer.len() < width as usize * height as usize * 4
+        || !tile_is_valid(tile, tile_width, tile_height)
+    {
+        return None;
+    }
+    let coverage = limited_coverage(
+        buffer,
+        width,
+        height,
+        start_x,
+        start_y,
+        selection_mask,
+        limit_mode,
+        fill_options,
+    )?;
+    let tile = Tile::new(tile, tile_width, tile_height, options);
+    before_paint(buffer, &coverage);
+    let changes = paint_coverage_with(buffer, &coverage, fill_options.mask_mode, |i| {
+        tile.color_at(i % width as usize, i / width as usize)
+    });
+    Some(changes)
+}
+
+fn tile_is_valid(tile: &[u8], tile_width: u32, tile_height: u32) -> bool {
+    tile_width > 0
+        && tile_height > 0
+        && tile.len() >= tile_width as usize * tile_height as usize * 4
+}
+
+struct Tile<'a> {
+    pixels: &'a [u8],
+    width: i64,
+    height: i64,
+    offset_x: i64,
+    offset_y: i64,
+    scale: i64,
+}
+
+impl<'a> Tile<'a> {
+    fn new(pixels: &'a [u8], width: u32, height: u32, options: &PatternFillOption) -> Tile<'a> {
+        Tile {
+            pixels,
+            width: width as i64,
+            height: height as i64,
+            offset_x: options.offset_x as i64,
+            offset_y: options.offset_y as i64,
+            scale: options.scale.max(1) as i64,
+        }
+    }
+
+    fn color_at(&self, x: usize, y: usize) -> [u8; 4] {
+        let tx = (x as i64 - self.offset_x)
+            .div_euclid(self.scale)
+            .rem_euclid(self.width);
+        let ty = (y as i64 - self.offset_y)
+            .div_euclid(self.scale)
+            .rem_euclid(self.height);
+        let i = ((ty * self.width + tx) * 4) as usize;
+        [
+            self.pixels[i],
+            self.pixels[i + 1],
+            self.pixels[i + 2],
+            self.pixels[i + 3],
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fill::coverage::blend_coverage;
+
+    const RED: [u8; 4] = [255, 0, 0, 255];
+    const GREEN: [u8; 4] = [0, 255, 0, 255];
+    const BLUE: [u8; 4] = [0, 0, 255, 255];
+    const WHITE: [u8; 4] = [255, 255, 255, 255];
+
+    // 2x2 tile: red green / blue white
+    fn tile() -> Vec<u8> {
+        [RED, GREEN, BLUE, WHITE].concat()
+    }
+
+    fn fill(
+        width: u32,
+        height: u32,
+        selection_mask: &[u8],
+        options: &PatternFillOption,
+    ) -> Vec<u8> {
+        let mut buffer = vec![0u8; (width * height * 4) as usize];
+        assert!(pattern_fill(
+            &mut buffer,
+            width,
+            height,
+            &tile(),
+            2,
+            2,
+            selection_mask,
+            options
+        ));
+        buffer
+    }
+
+    fn pixel(buffer: &[u8], width: u32, x: u32, y: u32) -> [u8; 4] {
+        let i = ((y * width + x) * 4) as usize;
+        [buffer[i], buffer[i + 1], buffer[i + 2], buffer[i + 3]]
+    }
+
+    #[test]
+    fn negative_offsets_wrap_the_tile() {
+        let buffer = fill(4, 4, &[], &PatternFillOption::new(-1, -3, 1));
+        // The tile starts one pixel left of and three pixels above the canvas
+        assert_eq!(pixel(&buffer, 4, 0, 0), WHITE);
+        assert_eq!(pixel(&buffer, 4, 1, 0), BLUE);
+        assert_eq!(pixel(&buffer, 4, 0, 1), GREEN);
+        assert_eq!(pixel(&buffer, 4, 1, 1), RED);
+        assert_eq!(pixel(&buffer, 4, 3, 3), RED);
+        assert_eq!(buffer, fill(4, 4, &[], &PatternFillOption::new(1, 1, 1)));
+    }
+
+    #[test]
+    fn scale_repeats_each_tile_pixel() {
+        let buffer = fill(12, 1, &[], &PatternFillOption::new(0, 0, 3));
+        for x in 0..12 {
+            let expected = if (x / 3) % 2 == 0 { RED } else { GREEN };
+            assert_eq!(pixel(&buffer, 12, x, 0), expected, "x {x}");
+        }
+
+        // Scaling keeps the offset in canvas pixels
+        let buffer = fill(6, 1, &[], &PatternFillOption::new(-1, 0, 2));
+        let expected = [RED, GREEN, GREEN, RED, RED, GREEN];
+        for (x, color) in expected.iter().enumerate() {
+            assert_eq!(pixel(&buffer, 6, x as u32, 0), *color, "x {x}");
+        }
+    }
+
+    #[test]
+    fn partial_selection_fades_the_tile() {
+        let mask = [255, 128, 64, 0];
+        let mut buffer = [WHITE; 4].concat();
+        assert!(pattern_fill(
+            &mut buffer,
+            4,
+            1,
+            &tile(),
+            2,
+            2,
+            &mask,
+            &PatternFillOption::new(0, 0, 1),
+        ));
+        assert_eq!(pixel(&buffer, 4, 0, 0), RED);
+        assert_eq!(
+            pixel(&buffer, 4, 1, 0),
+            blend_coverage(&WHITE, GREEN, 128, CoverageMode::Premultiplied)
+        );
+        assert_eq!(pixel(&buffer, 4, 1, 0), [127, 255, 127, 255]);
+        assert_eq!(pixel(&buffer, 4, 2, 0), [255, 191, 191, 255]);
+        assert_eq!(pixel(&buffer, 4, 3, 0), WHITE);
+    }
+}
@@ -0,0 +1,149 @@
+use wasm_bindgen::prelude::*;
+
+/// What a fill changed, so the caller can record a partial or tile diff instead of
+/// snapshotting the whole buffer.
+#[wasm_bindgen]
+pub struct FillResult {
+    changed_pixels: u32,
+    bound_box: [u32; 4],
+    coverage: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl FillResult {
+    #[wasm_bindgen(js_name = changedPixels)]
+    pub fn changed_pixels(&self) -> u32 {
+        self.changed_pixels
+    }
+
+    /// Bounding box of the changed pixels as [x, y, width, height] (all zero if nothing changed).
+    #[wasm_bindgen(js_name = boundBox)]
+    pub fn bound_box(&self) -> Vec<u32> {
+        self.bound_box.to_vec()
+    }
+
+    /// Coverage the fill was applied with, cropped to `boundBox` (row-major).
+    /// Pixels that kept their color are 0. Empty unless the buffer was asked to record it
+    /// with `setFillCoverage(true)`.
+    pub fn coverage(&self) -> Vec<u8> {
+        self.coverage.clone()
+    }
+}
+
+impl FillResult {
+    /// Build from a width * height map holding the applied coverage of each changed pixel.
+    /// The coverage is copied only if `with_coverage` is set.
+    pub(crate) fn from_changes(
+        width: usize,
+        height: usize,
+        changes: &[u8],
+        with_coverage: bool,
+    ) -> FillResult {
+        FillResult::from_changes_at(0, 0, width, height, changes, with_coverage)
+    }
+
+    /// `from_changes` for maps that each cover one rectangle (x, y, width, height) of the
+    /// buffer, such as the tiles of a `TiledRgbaBuffer`.
+    pub(crate) fn from_rect_changes(
+        rects: &[([usize; 4], Vec<u8>)],
+        with_coverage: bool,
+    ) -> FillResult {
+        if !with_coverage {
+            let mut result = FillResult::from_changes(0, 0, &[], false);
+            for ([x, y, w, h], map) in rects {
+                result.merge(&FillResult::from_changes_at(*x, *y, *w, *h, map, false));
+            }
+            return result;
+        }
+
+        let left = rects.iter().map(|([x, ..], _)| *x).min().unwrap_or(0);
+        let top = rects.iter().map(|([_, y, ..], _)| *y).min().unwrap_or(0);
+        let right = rects
+            .iter()
+            .map(|([x, _, w, _], _)| x + w)
+            .max()
+            .unwrap_or(0);
+        let bottom = rects
+            .iter()
+            .map(|([_, y, _, h], _)| y + h)
+            .max()
+            .unwrap_or(0);
+        let (width, height) = (right - left, bottom - top);
+
+        let mut changes = vec![0u8; width * height];
+        for ([x, y, w, h], map) in rects {
+            for row in 0..*h {
+                let dst = (y - top + row) * width + (x - left);
+                changes[dst..dst + w].copy_from_slice(&map[row * w..(row + 1) * w]);
+            }
+        }
+        FillResult::from_changes_at(left, top, width, height, &changes, true)
+    }
+
+    /// `from_changes` for a map that covers only the region starting at (left, top).
+    pub(crate) fn from_changes_at(
+        left: usize,
+        top: usize,
+        width: usize,
+        height: usize,
+        changes: &[u8],
+        with_coverage: bool,
+    ) -> FillResult {
+        let (mut min_x, mut min_y, mut max_x, mut max_y) = (usize::MAX, usize::MAX, 0, 0);
+        let mut changed_pixels = 0;
+        for (i, value) in changes.iter().take(width * height).enumerate() {
+            if *value == 0 {
+                continue;
+            }
+            let (x, y) = (i % width, i / width);
+            min_x = min_x.min(x);
+            min_y = min_y.min(y);
+            max_x = max_x.max(x);
+            max_y = max_y.max(y);
+            changed_pixels += 1;
+        }
+        if changed_pixels == 0 {
+            return FillResult {
+                changed_pixels: 0,
+                bound_box: [0; 4],
+                coverage: Vec::new(),
+            };
+        }
+
+        let (box_w, box_h) = (max_x - min_x + 1, max_y - min_y + 1);
+        let mut coverage = Vec::new();
+        if with_coverage {
+            coverage.reserve(box_w * box_h);
+            for y in min_y..=max_y {
+                coverage.extend_from_slice(&changes[y * width + min_x..=y * width + max_x]);
+            }
+        }
+        FillResult {
+            changed_pixels,
+            bound_box: [
+                (left + min_x) as u32,
+                (top + min_y) as u32,
+                box_w as u32,
+                box_h as u32,
+            ],
+            coverage,
+        }
+    }
+
+    // Add the pixels and bounds of a result without coverage
+    fn merge(&mut self, other: &FillResult) {
+        if other.changed_pixels == 0 {
+            return;
+        }
+        if self.changed_pixels == 0 {
+            self.bound_box = other.bound_box;
+        } else {
+            let [ax, ay, aw, ah] = self.bound_box;
+            let [bx, by, bw, bh] = other.bound_box;
+            let (x, y) = (ax.min(bx), ay.min(by));
+            let (right, bottom) = ((ax + aw).max(bx + bw), (ay + ah).max(by + bh));
+            self.bound_box = [x, y, right - x, bottom - y];
+        }
+        self.changed_pixels += other.changed_pixels;
+    }
+}
@@ -0,0 +1,471 @@
+use wasm_bindgen::prelude::*;
+
+use crate::packing::{decode_webp, tile::decode_tiles};
+
+// History log layout (little endian):
+//   header: [magic "ANVH"][version: u16][reserved: u16]
+//   record: [body length: u32][body][crc32 of body: u32]
+//   body:   sequence of [tag: u8][length: u32][payload] sections
+// Logs with a newer version are rejected. Within a supported version, unknown section
+// tags are skipped so optional sections can be added without a version bump.
+const LOG_MAGIC: &[u8; 4] = b"ANVH";
+const LOG_VERSION: u16 = 1;
+const LOG_HEADER_LEN: usize = 8;
+
+const SECTION_METADATA: u8 = 1;
+const SECTION_PIXELS: u8 = 2;
+const SECTION_PARTIAL: u8 = 3;
+const SECTION_WHOLE: u8 = 4;
+const SECTION_TILES: u8 = 5;
+
+// Presence flags of the metadata section
+const META_LAYER_ID: u8 = 1;
+const META_TOOL: u8 = 1 << 1;
+const META_TIMESTAMP: u8 = 1 << 2;
+const META_PIXEL_COUNT: u8 = 1 << 3;
+
+/// A serializable patch (PackedDiffs + PatchMetadata).
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct PatchRecord {
+    layer_id: Option<String>,
+    tool: Option<String>,
+    timestamp: Option<f64>,
+    pixel_count: Option<u32>,
+    // flattened [x, y, packed color] triples
+    pixels: Vec<u32>,
+    partial: Option<([i32; 2], [u32; 2], Vec<u8>)>,
+    whole: Option<([u32; 2], Vec<u8>)>,
+    tiles: Option<([u32; 2], Vec<u8>)>,
+}
+
+#[wasm_bindgen]
+impl PatchRecord {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> PatchRecord {
+        PatchRecord::default()
+    }
+
+    #[wasm_bindgen(js_name = setLayerId)]
+    pub fn set_layer_id(&mut self, layer_id: &str) {
+        self.layer_id = Some(layer_id.to_string());
+    }
+    #[wasm_bindgen(js_name = layerId)]
+    pub fn layer_id(&self) -> Option<String> {
+        self.layer_id.clone()
+    }
+
+    #[wasm_bindgen(js_name = setTool)]
+    pub fn set_tool(&mut self, tool: &str) {
+        self.tool = Some(tool.to_string());
+    }
+    pub fn tool(&self) -> Option<String> {
+        self.tool.clone()
+    }
+
+    #[wasm_bindgen(js_name = setTimestamp)]
+    pub fn set_timestamp(&mut self, timestamp: f64) {
+        self.timestamp = Some(timestamp);
+    }
+    pub fn timestamp(&self) -> Option<f64> {
+        self.timestamp
+    }
+
+    #[wasm_bindgen(js_name = setPixelCount)]
+    pub fn set_pixel_count(&mut self, pixel_count: u32) {
+        self.pixel_count = Some(pixel_count);
+    }
+    #[wasm_bindgen(js_name = pixelCount)]
+    pub fn pixel_count(&self) -> Option<u32> {
+        self.pixel_count
+    }
+
+    /// Set pixel diffs as flattened [x, y, packed color] triples.
+    #[wasm_bindgen(js_name = setPixels)]
+    pub fn set_pixels(&mut self, pixels: Vec<u32>) -> bool {
+        if !pixels.len().is_multiple_of(3) {
+            return false;
+        }
+        self.pixels = pixels;
+        true
+    }
+    pub fn pixels(&self) -> Vec<u32> {
+        self.pixels.clone()
+    }
+
+    #[wasm_bindgen(js_name = setPartial)]
+    pub fn set_partial(&mut self, x: i32, y: i32, width: u32, height: u32, webp: Vec<u8>) {
+        self.partial = Some(([x, y], [width, height], webp));
+    }
+    #[wasm_bindgen(js_name = hasPartial)]
+    pub fn has_partial(&self) -> bool {
+        self.partial.is_some()
+    }
+    /// Partial bound box as [x, y, width, height] (empty if absent).
+    #[wasm_bindgen(js_name = partialBoundBox)]
+    pub fn partial_bound_box(&self) -> Vec<i32> {
+        match &self.partial {
+            Some((pos, size, _)) => vec![pos[0], pos[1], size[0] as i32, size[1] as i32],
+            None => Vec::new(),
+        }
+    }
+    #[wasm_bindgen(js_name = partialWebp)]
+    pub fn partial_webp(&self) -> Vec<u8> {
+        self.partial
+            .as_ref()
+            .map(|(_, _, webp)| webp.clone())
+            .unwrap_or_default()
+    }
+
+    #[wasm_bindgen(js_name = setWhole)]
+    pub fn set_whole(&mut self, width: u32, height: u32, webp: Vec<u8>) {
+        self.whole = Some(([width, height], webp));
+    }
+    #[wasm_bindgen(js_name = hasWhole)]
+    pub fn has_whole(&self) -> bool {
+        self.whole.is_some()
+    }
+    /// Whole buffer size as [width, height] (empty if absent).
+    #[wasm_bindgen(js_name = wholeSize)]
+    pub fn whole_size(&self) -> Vec<u32> {
+        self.whole
+            .as_ref()
+            .map(|(size, _)| size.to_vec())
+            .unwrap_or_default()
+    }
+    #[wasm_bindgen(js_name = wholeWebp)]
+    pub fn whole_webp(&self) -> Vec<u8> {
+        self.whole
+            .as_ref()
+            .map(|(_, webp)| webp.clone())
+            .unwrap_or_default()
+    }
+
+    /// Set a tile patch (see `RgbaBuffer.swapTiles`) for a buffer of the given size.
+    /// Returns false if the patch does not decode for that size.
+    #[wasm_bindgen(js_name = setTiles)]
+    pub fn set_tiles(&mut self, width: u32, height: u32, tiles: Vec<u8>) -> bool {
+        if decode_tiles(&tiles, width, height).is_none() {
+            return false;
+        }
+        self.tiles = Some(([width, height], tiles));
+        true
+    }
+    #[wasm_bindgen(js_name = hasTiles)]
+    pub fn has_tiles(&self) -> bool {
+        self.tiles.is_some()
+    }
+    /// Buffer size of the tile patch as [width, height] (empty if absent).
+    #[wasm_bindgen(js_name = tilesSize)]
+    pub fn tiles_size(&self) -> Vec<u32> {
+        self.tiles
+            .as_ref()
+            .map(|(size, _)| size.to_vec())
+            .unwrap_or_default()
+    }
+    pub fn tiles(&self) -> Vec<u8> {
+        self.tiles
+            .as_ref()
+            .map(|(_, tiles)| tiles.clone())
+            .unwrap_or_default()
+    }
+
+    /// Encode this patch as a single framed record, ready to append to a history log.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut body = Vec::new();
+
+        let mut meta = Vec::new();
+        let mut flags = 0u8;
+        if let Some(layer_id) = &self.layer_id {
+            flags |= META_LAYER_ID;
+            write_bytes(&mut meta, layer_id.as_bytes());
+        }
+        if let Some(tool) = &self.tool {
+            flags |= META_TOOL;
+            write_bytes(&mut meta, tool.as_bytes());
+        }
+        if let Some(timestamp) = self.timestamp {
+            flags |= META_TIMESTAMP;
+            meta.extend_from_slice(&timestamp.to_le_bytes());
+        }
+        if let Some(pixel_count) = self.pixel_count {
+            flags |= META_PIXEL_COUNT;
+            meta.extend_from_slice(&pixel_count.to_le_bytes());
+        }
+        meta.insert(0, flags);
+        write_section(&mut body, SECTION_METADATA, &meta);
+
+        if !self.pixels.is_empty() {
+            let payload: Vec<u8> = self.pixels.iter().flat_map(|v| v.to_le_bytes()).collect();
+            write_section(&mut body, SECTION_PIXELS, &payload);
+        }
+        if let Some((pos, size, webp)) = &self.partial {
+            let mut payload = Vec::with_capacity(16 + webp.len());
+            payload.extend_from_slice(&pos[0].to_le_bytes());
+            payload.extend_from_slice(&pos[1].to_le_bytes());
+            payload.extend_from_slice(&size[0].to_le_bytes());
+            payload.extend_from_slice(&size[1].to_le_bytes());
+            payload.extend_from_slice(webp);
+            write_section(&mut body, SECTION_PARTIAL, &payload);
+        }
+        if let Some((size, webp)) = &self.whole {
+            let mut payload = Vec::with_capacity(8 + webp.len());
+            payload.extend_from_slice(&size[0].to_le_bytes());
+            payload.extend_from_slice(&size[1].to_le_bytes());
+            payload.extend_from_slice(webp);
+            write_section(&mut body, SECTION_WHOLE, &payload);
+        }
+        if let Some((size, tiles)) = &self.tiles {
+            let mut payload = Vec::with_capacity(8 + tiles.len());
+            payload.extend_from_slice(&size[0].to_le_bytes());
+            payload.extend_from_slice(&size[1].to_le_bytes());
+            payload.extend_from_slice(tiles);
+            write_section(&mut body, SECTION_TILES, &payload);
+        }
+
+        let mut out = Vec::with_capacity(body.len() + 8);
+        out.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        out.extend_from_slice(&body);
+        out.extend_from_slice(&crc32(&body).to_le_bytes());
+        out
+    }
+
+    /// Decode a single framed record produced by `encode`.
+    pub fn decode(record: &[u8]) -> Result<PatchRecord, JsValue> {
+        let mut cursor = 0usize;
+        let patch = read_record(record, &mut cursor).map_err(JsValue::from_str)?;
+        if cursor != record.len() {
+            return Err(JsValue::from_str("Trailing bytes after record."));
+        }
+        Ok(patch)
+    }
+}
+
+/// Header that starts a history log. Append `PatchRecord.encode()` output after it.
+#[wasm_bindgen]
+pub fn history_log_header() -> Vec<u8> {
+    let mut out = Vec::with_capacity(LOG_HEADER_LEN);
+    out.extend_from_slice(LOG_MAGIC);
+    out.extend_from_slice(&LOG_VERSION.to_le_bytes());
+    out.extend_from_slice(&0u16.to_le_bytes());
+    out
+}
+
+/// Sequential reader over a history log. Every record is validated as it is read.
+#[wasm_bindgen]
+pub struct HistoryReader {
+    bytes: Vec<u8>,
+    cursor: usize,
+}
+
+#[wasm_bindgen]
+impl HistoryReader {
+    #[wasm_bindgen(constructor)]
+    pub fn new(bytes: Vec<u8>) -> Result<HistoryReader, JsValue> {
+        if bytes.len() < LOG_HEADER_LEN || &bytes[0..4] != LOG_MAGIC {
+            return Err(JsValue::from_str("Not an anvil history log."));
+        }
+        let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+        if version == 0 || version > LOG_VERSION {
+            return Err(JsValue::from_str("Unsupported history log version."));
+        }
+        Ok(HistoryReader {
+            bytes,
+            cursor: LOG_HEADER_LEN,
+        })
+    }
+
+    /// Read the next record, or `undefined` at the end of the log.
+    /// A corrupt or truncated record is an error; `offset()` then still points at it,
+    /// so callers can truncate the log to the last valid record.
+    #[wasm_bindgen(js_name = next)]
+    pub fn read_next(&mut self) -> Result<Option<PatchRecord>, JsValue> {
+        if self.cursor >= self.bytes.len() {
+            return Ok(None);
+        }
+        let mut cursor = self.cursor;
+        let patch = read_record(&self.bytes, &mut cursor).map_err(JsValue::from_str)?;
+        self.cursor = cursor;
+        Ok(Some(patch))
+    }
+
+    /// Byte offset of the next unread record.
+    pub fn offset(&self) -> usize {
+        self.cursor
+    }
+}
+
+fn write_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    out.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+    out.extend_from_slice(bytes);
+}
+
+fn write_section(out: &mut Vec<u8>, tag: u8, payload: &[u8]) {
+    out.push(tag);
+    write_bytes(out, payload);
+}
+
+fn read_u32(bytes: &[u8], cursor: &mut usize) -> Result<u32, &'static str> {
+    let slice = bytes
+        .get(*cursor..*cursor + 4)
+        .ok_or("Unexpected end of data.")?;
+    *cursor += 4;
+    Ok(u32::from_le_bytes([slice[0], slice[1], slice[2], slice[3]]))
+}
+
+fn read_slice<'a>(bytes: &'a [u8], cursor: &mut usize) -> Result<&'a [u8], &'static str> {
+    let len = read_u32(bytes, cursor)? as usize;
+    let end = cursor.checked_add(len).ok_or("Invalid length.")?;
+    let slice = bytes.get(*cursor..end).ok_or("Unexpected end of data.")?;
+    *cursor = end;
+    Ok(slice)
+}
+
+fn read_string(bytes: &[u8], cursor: &mut usize) -> Result<String, &'static str> {
+    let slice = read_slice(bytes, cursor)?;
+    String::from_utf8(slice.to_vec()).map_err(|_| "Invalid UTF-8 string.")
+}
+
+fn read_record(bytes: &[u8], cursor: &mut usize) -> Result<PatchRecord, &'static str> {
+    let body = read_slice(bytes, cursor)?;
+    let checksum = read_u32(bytes, cursor)?;
+    if crc32(body) != checksum {
+        return Err("Record checksum mismatch.");
+    }
+
+    let mut patch = PatchRecord::default();
+    let mut pos = 0usize;
+    while pos < body.len() {
+        let tag = body[pos];
+        pos += 1;
+        let payload = read_slice(body, &mut pos)?;
+        match tag {
+            SECTION_METADATA => read_metadata(payload, &mut patch)?,
+            SECTION_PIXELS => {
+                if !payload.len().is_multiple_of(12) {
+                    return Err("Invalid pixel section length.");
+                }
+                patch.pixels = payload
+                    .chunks_exact(4)
+                    .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+                    .collect();
+            }
+            SECTION_PARTIAL => {
+                let mut p = 0usize;
+                let x = read_u32(payload, &mut p)? as i32;
+                let y = read_u32(payload, &mut p)? as i32;
+                let width = read_u32(payload, &mut p)?;
+                let height = read_u32(payload, &mut p)?;
+                if width == 0 || height == 0 {
+                    return Err("Invalid partial bound box.");
+                }
+                let webp = &payload[p..];
+                if decode_webp(webp, width, height).is_none() {
+                    return Err("Invalid partial webp.");
+                }
+                patch.partial = Some(([x, y], [width, height], webp.to_vec()));
+            }
+            SECTION_WHOLE => {
+                let mut p = 0usize;
+                let width = read_u32(payload, &mut p)?;
+                let height = read_u32(payload, &mut p)?;
+                if width == 0 || height == 0 {
+                    return Err("Invalid whole buffer size.");
+                }
+                let webp = &payload[p..];
+                if decode_webp(webp, width, height).is_none() {
+                    return Err("Invalid whole buffer webp.");
+                }
+                patch.whole = Some(([width, height], webp.to_vec()));
+            }
+            SECTION_TILES => {
+                let mut p = 0usize;
+                let width = read_u32(payload, &mut p)?;
+                let height = read_u32(payload, &mut p)?;
+                let tiles = &payload[p..];
+                if decode_tiles(tiles, width, height).is_none() {
+                    return Err("Invalid tile patch.");
+                }
+                patch.tiles = Some(([width, height], tiles.to_vec()));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(patch)
+}
+
+fn read_metadata(payload: &[u8], patch: &mut PatchRecord) -> Result<(), &'static str> {
+    let flags = *payload.first().ok_or("Invalid metadata section.")?;
+    let mut p = 1usize;
+    if flags & META_LAYER_ID != 0 {
+        patch.layer_id = Some(read_string(payload, &mut p)?);
+    }
+    if flags & META_TOOL != 0 {
+        patch.tool = Some(read_string(payload, &mut p)?);
+    }
+    if flags & META_TIMESTAMP != 0 {
+        let slice = payload.get(p..p + 8).ok_or("Invalid metadata section.")?;
+        let mut raw = [0u8; 8];
+        raw.copy_from_slice(slice);
+        patch.timestamp = Some(f64::from_le_bytes(raw));
+        p += 8;
+    }
+    if flags & META_PIXEL_COUNT != 0 {
+        patch.pixel_count = Some(read_u32(payload, &mut p)?);
+    }
+    Ok(())
+}
+
+// CRC-32 (IEEE 802.3, reflected)
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packing::raw_to_webp;
+
+    fn webp(width: u32, height: u32) -> Vec<u8> {
+        let pixels: Vec<u8> = (0..width * height * 4).map(|i| (i * 7) as u8).collect();
+        raw_to_webp(&pixels, width, height)
+    }
+
+    fn read(record: &PatchRecord) -> Result<PatchRecord, &'static str> {
+        read_record(&record.encode(), &mut 0)
+    }
+
+    #[test]
+    fn reads_webp_sections_of_the_stated_size() {
+        let mut record = PatchRecord::default();
+        record.set_partial(-2, 5, 10, 12, webp(10, 12));
+        record.set_whole(4, 3, webp(4, 3));
+        let decoded = read(&record).unwrap();
+        assert_eq!(decoded.partial, record.partial);
+        assert_eq!(decoded.whole, record.whole);
+    }
+
+    #[test]
+    fn rejects_webp_sections_of_another_size() {
+        let mut record = PatchRecord::default();
+        record.set_partial(0, 0, 10, 12, webp(12, 10));
+        assert_eq!(read(&record).err(), Some("Invalid partial webp."));
+
+        let mut record = PatchRecord::default();
+        record.set_whole(4, 3, webp(4, 4));
+        assert_eq!(read(&record).err(), Some("Invalid whole buffer webp."));
+
+        let mut record = PatchRecord::default();
+        record.set_whole(4, 3, vec![1, 2, 3]);
+        assert_eq!(read(&record).err(), Some("Invalid whole buffer webp."));
+    }
+}
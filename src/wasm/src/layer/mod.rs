@@ -0,0 +1,656 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    blend::BlendMode,
+    patch::apply_alpha_blend,
+    rgba::{base::mask_is_valid, RgbaBuffer},
+};
+
+#[wasm_bindgen]
+#[derive(Clone, Copy)]
+pub struct LayerOption {
+    /// Layer opacity (0.0-1.0)
+    pub opacity: f32,
+    /// Hidden layers are skipped (and hide the layers clipped to them)
+    pub visible: bool,
+    pub blend_mode: BlendMode,
+    /// Clip to the alpha of the nearest non-clipping layer below
+    pub clipping: bool,
+}
+
+#[wasm_bindgen]
+impl LayerOption {
+    #[wasm_bindgen(constructor)]
+    pub fn new(opacity: f32, visible: bool, blend_mode: BlendMode, clipping: bool) -> LayerOption {
+        LayerOption {
+            opacity: opacity.clamp(0.0, 1.0),
+            visible,
+            blend_mode,
+            clipping,
+        }
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Copy)]
+pub struct GroupOption {
+    /// Group opacity (0.0-1.0)
+    pub opacity: f32,
+    /// Hidden groups skip every layer pushed inside them
+    pub visible: bool,
+    /// Blend mode used when an isolated group is composited onto its parent
+    pub blend_mode: BlendMode,
+    /// Clip to the alpha of the nearest non-clipping layer below
+    pub clipping: bool,
+    /// Composite children directly onto the backdrop instead of into an isolated buffer
+    pub pass_through: bool,
+}
+
+#[wasm_bindgen]
+impl GroupOption {
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        opacity: f32,
+        visible: bool,
+        blend_mode: BlendMode,
+        clipping: bool,
+        pass_through: bool,
+    ) -> GroupOption {
+        GroupOption {
+            opacity: opacity.clamp(0.0, 1.0),
+            visible,
+            blend_mode,
+            clipping,
+            pass_through,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Region {
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+    // first pixel of this region in the frame buffers
+    offset: usize,
+}
+
+impl Region {
+    // Canvas and frame pixel indices of every pixel in the region
+    fn pixels(&self, canvas_width: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..self.height).flat_map(move |row| {
+            let canvas = (self.y + row) * canvas_width + self.x;
+            let frame = self.offset + row * self.width;
+            (0..self.width).map(move |col| (canvas + col, frame + col))
+        })
+    }
+}
+
+// Compositing target of the root or of an open group. Buffers only hold the pixels of
+// the current regions, packed one region after another.
+struct Frame {
+    canvas: Vec<u8>,
+    // alpha of the current clipping base (the last non-clipping layer)
+    clip_base: Vec<u8>,
+    // union of the alpha pushed into this frame
+    coverage: Vec<u8>,
+    group: Option<GroupOption>,
+    mask: Option<Vec<u8>>,
+    hidden: bool,
+}
+
+impl Frame {
+    fn new(
+        pixels: usize,
+        group: Option<GroupOption>,
+        mask: Option<Vec<u8>>,
+        hidden: bool,
+    ) -> Frame {
+        Frame {
+            canvas: vec![0u8; pixels * 4],
+            clip_base: vec![0u8; pixels],
+            coverage: vec![0u8; pixels],
+            group,
+            mask,
+            hidden,
+        }
+    }
+}
+
+/// Multi-layer compositor.
+///
+/// Layers are pushed bottom to top between a `begin*` call and `compositeInto`,
+/// so each `RgbaBuffer` is only borrowed for the duration of its `pushLayer` call.
+/// Groups are opened with `beginGroup` and closed with `endGroup`, following the
+/// layer tree depth-first. Only the region chosen by `begin*` is recomposited.
+#[wasm_bindgen]
+pub struct LayerStack {
+    width: u32,
+    height: u32,
+    regions: Vec<Region>,
+    frames: Vec<Frame>,
+}
+
+#[wasm_bindgen]
+impl LayerStack {
+    #[wasm_bindgen(constructor)]
+    pub fn new(width: u32, height: u32) -> LayerStack {
+        LayerStack {
+            width,
+            height,
+            regions: Vec::new(),
+            frames: vec![Frame::new(0, None, None, false)],
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Start compositing the whole canvas.
+    #[wasm_bindgen(js_name = begin)]
+    pub fn begin(&mut self) {
+        self.regions = vec![Region {
+            x: 0,
+            y: 0,
+            width: self.width as usize,
+            height: self.height as usize,
+            offset: 0,
+        }];
+        self.reset_regions();
+    }
+
+    /// Start compositing only the given rectangle (clipped to the canvas).
+    #[wasm_bindgen(js_name = beginRect)]
+    pub fn begin_rect(&mut self, x: i32, y: i32, width: u32, height: u32) {
+        self.regions.clear();
+        if let Some(region) = self.clip_region(x, y, width, height) {
+            self.regions.push(region);
+        }
+        self.reset_regions();
+    }
+
+    /// Start compositing only the given tiles (row-major linear indices).
+    #[wasm_bindgen(js_name = beginTiles)]
+    pub fn begin_tiles(&mut self, tile_size: u32, tile_indices: &[u32]) {
+        self.regions.clear();
+        if tile_size == 0 {
+            return;
+        }
+        let cols = self.width.div_ceil(tile_size);
+        for &linear in tile_indices {
+            if cols == 0 {
+                break;
+            }
+            let x = (linear % cols) * tile_size;
+            let y = (linear / cols) * tile_size;
+            if let Some(region) = self.clip_region(x as i32, y as i32, tile_size, tile_size) {
+                self.regions.push(region);
+            }
+        }
+        self.reset_regions();
+    }
+
+    /// Composite a layer over everything pushed so far.
+    #[wasm_bindgen(js_name = pushLayer)]
+    pub fn push_layer(&mut self, buffer: &RgbaBuffer, options: &LayerOption) -> bool {
+        if buffer.width != self.width || buffer.height != self.height {
+            return false;
+        }
+        self.composite_source(
+            &buffer.data,
+            false,
+            options.opacity,
+            options.visible,
+            options.blend_mode,
+            options.clipping,
+            None,
+        );
+        true
+    }
+
+    /// Composite a layer through an 8-bit mask (one byte per pixel, 255 = fully shown).
+    #[wasm_bindgen(js_name = pushLayerWithMask)]
+    pub fn push_layer_with_mask(
+        &mut self,
+        buffer: &RgbaBuffer,
+        options: &LayerOption,
+        mask: &[u8],
+    ) -> bool {
+        if buffer.width != self.width || buffer.height != self.height {
+            return false;
+        }
+        if !mask_is_valid(self.width, self.height, mask) {
+            return false;
+        }
+        self.composite_source(
+            &buffer.data,
+            false,
+            options.opacity,
+            options.visible,
+            options.blend_mode,
+            options.clipping,
+            Some(mask),
+        );
+        true
+    }
+
+    /// Open a group. Layers pushed until the matching `endGroup` belong to it.
+    #[wasm_bindgen(js_name = beginGroup)]
+    pub fn begin_group(&mut self, options: &GroupOption) {
+        self.open_frame(*options, None);
+    }
+
+    /// Open a group whose result is shown through an 8-bit mask.
+    #[wasm_bindgen(js_name = beginGroupWithMask)]
+    pub fn begin_group_with_mask(&mut self, options: &GroupOption, mask: &[u8]) -> bool {
+        if !mask_is_valid(self.width, self.height, mask) {
+            return false;
+        }
+        self.open_frame(*options, Some(mask.to_vec()));
+        true
+    }
+
+    /// Close the innermost group and composite it onto its parent.
+    #[wasm_bindgen(js_name = endGroup)]
+    pub fn end_group(&mut self) -> bool {
+        if self.frames.len() <= 1 {
+            return false;
+        }
+        let Some(frame) = self.frames.pop() else {
+            return false;
+        };
+        let Some(group) = frame.group else {
+            return false;
+        };
+        let mask = frame.mask.as_deref();
+
+        if frame.hidden {
+            if !group.clipping {
+                self.clear_clip_base();
+            }
+        } else if group.pass_through {
+            self.merge_pass_through(&frame, &group, mask);
+        } else {
+            self.composite_source(
+                &frame.canvas,
+                true,
+                group.opacity,
+                group.visible,
+                group.blend_mode,
+                group.clipping,
+                mask,
+            );
+        }
+        true
+    }
+
+    /// Copy the composited region into the output buffer.
+    #[wasm_bindgen(js_name = compositeInto)]
+    pub fn composite_into(&self, output: &mut RgbaBuffer) -> bool {
+        if output.width != self.width || output.height != self.height {
+            return false;
+        }
+        let width = self.width as usize;
+        let canvas = &self.frames[0].canvas;
+        for region in &self.regions {
+            for row in 0..region.height {
+                let start = ((region.y + row) * width + region.x) * 4;
+                let from = (region.offset + row * region.width) * 4;
+                let len = region.width * 4;
+                output.data[start..start + len].copy_from_slice(&canvas[from..from + len]);
+            }
+        }
+        true
+    }
+}
+
+impl LayerStack {
+    fn clip_region(&self, x: i32, y: i32, width: u32, height: u32) -> Option<Region> {
+        let left = x.max(0) as i64;
+        let top = y.max(0) as i64;
+        let right = (x as i64 + width as i64).min(self.width as i64);
+        let bottom = (y as i64 + height as i64).min(self.height as i64);
+        if left >= right || top >= bottom {
+            return None;
+        }
+        Some(Region {
+            x: left as usize,
+            y: top as usize,
+            width: (right - left) as usize,
+            height: (bottom - top) as usize,
+            offset: 0,
+        })
+    }
+
+    // Lay the regions out back to back and start a cleared root frame sized to them
+    fn reset_regions(&mut self) {
+        let mut pixels = 0;
+        for region in &mut self.regions {
+            region.offset = pixels;
+            pixels += region.width * region.height;
+        }
+        self.frames = vec![Frame::new(pixels, None, None, false)];
+    }
+
+    fn region_pixels(&self) -> usize {
+        self.regions.iter().map(|r| r.width * r.height).sum()
+    }
+
+    fn open_frame(&mut self, group: GroupOption, mask: Option<Vec<u8>>) {
+        let parent_hidden = self.frames.last().is_some_and(|f| f.hidden);
+        let mut frame = Frame::new(
+            self.region_pixels(),
+            Some(group),
+            mask,
+            parent_hidden || !group.visible,
+        );
+        if group.pass_through && !frame.hidden {
+            // children composite straight onto a copy of the backdrop
+            if let Some(parent) = self.frames.last() {
+                frame.canvas.copy_from_slice(&parent.canvas);
+            }
+        }
+        self.frames.push(frame);
+    }
+
+    // Composite an RGBA source onto the innermost frame. `source` covers the full canvas,
+    // or only the current regions in frame layout when `packed` (a closed group).
+    #[allow(clippy::too_many_arguments)]
+    fn composite_source(
+        &mut self,
+        source: &[u8],
+        packed: bool,
+        opacity: f32,
+        visible: bool,
+        blend_mode: BlendMode,
+        clipping: bool,
+        mask: Option<&[u8]>,
+    ) {
+        let width = self.width as usize;
+        let Some(frame) = self.frames.last_mut() else {
+            return;
+        };
+        if frame.hidden {
+            return;
+        }
+
+        for region in &self.regions {
+            for (pi, fi) in region.pixels(width) {
+                let si = if packed { fi } else { pi };
+                let src = &source[si * 4..si * 4 + 4];
+
+                let mut alpha = src[3] as f32;
+                if let Some(mask) = mask {
+                    alpha *= mask[pi] as f32 / 255.0;
+                }
+
+                if !clipping {
+                    // This layer becomes the base for the clipping layers above it
+                    frame.clip_base[fi] = if visible { alpha.round() as u8 } else { 0 };
+                }
+                if !visible {
+                    continue;
+                }
+
+                if clipping {
+                    alpha *= frame.clip_base[fi] as f32 / 255.0;
+                }
+                if alpha <= 0.0 {
+                    continue;
+                }
+
+                let covered = alpha * opacity / 255.0;
+                let prev = frame.coverage[fi] as f32 / 255.0;
+                frame.coverage[fi] = ((covered + prev * (1.0 - covered)) * 255.0).round() as u8;
+
+                let alpha_norm = alpha / 255.0;
+                apply_alpha_blend(
+                    &mut frame.canvas,
+                    fi * 4,
+                    src[0] as f32 * alpha_norm,
+                    src[1] as f32 * alpha_norm,
+                    src[2] as f32 * alpha_norm,
+                    alpha,
+                    blend_mode,
+                    opacity,
+                );
+            }
+        }
+    }
+
+    // Fade a closed pass-through group between the backdrop and its result
+    fn merge_pass_through(&mut self, frame: &Frame, group: &GroupOption, mask: Option<&[u8]>) {
+        let width = self.width as usize;
+        let Some(parent) = self.frames.last_mut() else {
+            return;
+        };
+        if parent.hidden {
+            return;
+        }
+
+        for region in &self.regions {
+            for (pi, fi) in region.pixels(width) {
+                let mut t = group.opacity;
+                if let Some(mask) = mask {
+                    t *= mask[pi] as f32 / 255.0;
+                }
+                if group.clipping {
+                    t *= parent.clip_base[fi] as f32 / 255.0;
+                }
+
+                let coverage = frame.coverage[fi] as f32 * t;
+                if !group.clipping {
+                    parent.clip_base[fi] = coverage.round() as u8;
+                }
+                let prev = parent.coverage[fi] as f32 / 255.0;
+                let covered = coverage / 255.0;
+                parent.coverage[fi] = ((covered + prev * (1.0 - covered)) * 255.0).round() as u8;
+
+                if t <= 0.0 {
+                    continue;
+                }
+                let idx = fi * 4;
+                let dst = &mut parent.canvas[idx..idx + 4];
+                let src = &frame.canvas[idx..idx + 4];
+                if t >= 1.0 {
+                    dst.copy_from_slice(src);
+                    continue;
+                }
+
+                // Interpolate in premultiplied space so transparent pixels don't bleed
+                let dst_a = dst[3] as f32 / 255.0;
+                let src_a = src[3] as f32 / 255.0;
+                let out_a = dst_a + (src_a - dst_a) * t;
+                for c in 0..3 {
+                    let d = dst[c] as f32 * dst_a;
+                    let s = src[c] as f32 * src_a;
+                    let v = d + (s - d) * t;
+                    dst[c] = if out_a > 0.0 {
+                        (v / out_a).round().clamp(0.0, 255.0) as u8
+                    } else {
+                        0
+                    };
+                }
+                dst[3] = (out_a * 255.0).round().clamp(0.0, 255.0) as u8;
+            }
+        }
+    }
+
+    // Reset the clip base of the innermost frame (a hidden base hides its clipped layers)
+    fn clear_clip_base(&mut self) {
+        if let Some(frame) = self.frames.last_mut() {
+            frame.clip_base.fill(0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIZE: u32 = 8;
+
+    fn solid(color: [u8; 4]) -> RgbaBuffer {
+        let mut buffer = RgbaBuffer::new(SIZE, SIZE);
+        for px in buffer.data.chunks_exact_mut(4) {
+            px.copy_from_slice(&color);
+        }
+        buffer
+    }
+
+    // Opaque `color` in the square (2, 2)-(6, 6), transparent elsewhere
+    fn square(color: [u8; 4]) -> RgbaBuffer {
+        let mut buffer = RgbaBuffer::new(SIZE, SIZE);
+        for y in 2..6 {
+            for x in 2..6 {
+                buffer.set(x, y, color[0], color[1], color[2], color[3]);
+            }
+        }
+        buffer
+    }
+
+    fn layer(visible: bool, blend_mode: BlendMode, clipping: bool) -> LayerOption {
+        LayerOption::new(1.0, visible, blend_mode, clipping)
+    }
+
+    fn pixel(buffer: &RgbaBuffer, x: u32, y: u32) -> [u8; 4] {
+        let i = ((y * buffer.width + x) * 4) as usize;
+        [
+            buffer.data[i],
+            buffer.data[i + 1],
+            buffer.data[i + 2],
+            buffer.data[i + 3],
+        ]
+    }
+
+    fn composite(stack: &LayerStack) -> RgbaBuffer {
+        let mut output = RgbaBuffer::new(SIZE, SIZE);
+        assert!(stack.composite_into(&mut output));
+        output
+    }
+
+    #[test]
+    fn clipped_layer_follows_its_base() {
+        let base = square([255, 0, 0, 255]);
+        let clipped = solid([0, 0, 255, 255]);
+
+        let mut stack = LayerStack::new(SIZE, SIZE);
+        stack.begin();
+        stack.push_layer(&base, &layer(true, BlendMode::Normal, false));
+        stack.push_layer(&clipped, &layer(true, BlendMode::Normal, true));
+        let output = composite(&stack);
+        assert_eq!(pixel(&output, 3, 3), [0, 0, 255, 255]);
+        assert_eq!(pixel(&output, 0, 0), [0, 0, 0, 0]);
+
+        // A hidden base hides the layers clipped to it
+        stack.begin();
+        stack.push_layer(&base, &layer(false, BlendMode::Normal, false));
+        stack.push_layer(&clipped, &layer(true, BlendMode::Normal, true));
+        let output = composite(&stack);
+        assert!(output.data.iter().all(|v| *v == 0));
+    }
+
+    #[test]
+    fn rect_recomposite_matches_full_composite() {
+        let layers = [
+            solid([40, 80, 120, 255]),
+            square([255, 0, 0, 200]),
+            solid([0, 255, 0, 128]),
+            square([0, 0, 255, 255]),
+        ];
+        let mut mask = vec![0u8; (SIZE * SIZE) as usize];
+        for (i, value) in mask.iter_mut().enumerate() {
+            *value = (i * 4) as u8;
+        }
+        let push_all = |stack: &mut LayerStack| {
+            stack.push_layer(&layers[0], &layer(true, BlendMode::Normal, false));
+            stack.push_layer(&layers[1], &layer(true, BlendMode::Overlay, false));
+            stack.push_layer(&layers[2], &layer(true, BlendMode::Multiply, true));
+            stack.begin_group(&GroupOption::new(
+                0.6,
+                true,
+                BlendMode::Screen,
+                false,
+                false,
+            ));
+            stack.push_layer_with_mask(&layers[3], &layer(true, BlendMode::Normal, false), &mask);
+            stack.end_group();
+        };
+
+        let mut stack = LayerStack::new(SIZE, SIZE);
+        stack.begin();
+        push_all(&mut stack);
+        let full = composite(&stack);
+
+        let mut partial = solid([1, 2, 3, 4]);
+        stack.begin_rect(1, 3, 4, 4);
+        push_all(&mut stack);
+        assert!(stack.composite_into(&mut partial));
+        for y in 0..SIZE {
+            for x in 0..SIZE {
+                let inside = (1..5).contains(&x) && (3..7).contains(&y);
+                let expected = if inside {
+                    pixel(&full, x, y)
+                } else {
+                    [1, 2, 3, 4]
+                };
+                assert_eq!(pixel(&partial, x, y), expected, "({x}, {y})");
+            }
+        }
+    }
+
+    #[test]
+    fn mask_scales_layer_alpha() {
+        let mut mask = vec![0u8; (SIZE * SIZE) as usize];
+        for (i, value) in mask.iter_mut().enumerate() {
+            *value = match i as u32 % SIZE {
+                0..=1 => 0,
+                2..=5 => 128,
+                _ => 255,
+            };
+        }
+
+        let mut stack = LayerStack::new(SIZE, SIZE);
+        stack.begin();
+        assert!(stack.push_layer_with_mask(
+            &solid([255, 0, 0, 255]),
+            &layer(true, BlendMode::Normal, false),
+            &mask
+        ));
+        let output = composite(&stack);
+        assert_eq!(pixel(&output, 0, 4), [0, 0, 0, 0]);
+        assert_eq!(pixel(&output, 3, 4), [255, 0, 0, 128]);
+        assert_eq!(pixel(&output, 7, 4), [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn pass_through_group_blends_children_with_the_backdrop() {
+        let backdrop = solid([128, 128, 128, 255]);
+        let child = solid([255, 0, 0, 255]);
+        let run = |pass_through: bool| {
+            let mut stack = LayerStack::new(SIZE, SIZE);
+            stack.begin();
+            stack.push_layer(&backdrop, &layer(true, BlendMode::Normal, false));
+            stack.begin_group(&GroupOption::new(
+                1.0,
+                true,
+                BlendMode::Screen,
+                false,
+                pass_through,
+            ));
+            stack.push_layer(&child, &layer(true, BlendMode::Multiply, false));
+            assert!(stack.end_group());
+            pixel(&composite(&stack), 4, 4)
+        };
+
+        // The child multiplies the backdrop; the group blend mode is ignored
+        assert_eq!(run(true), [128, 0, 0, 255]);
+        // The child multiplies nothing inside the group, and the group screens the backdrop
+        assert_eq!(run(false), [255, 128, 128, 255]);
+    }
+}
@@ -1,10 +1,18 @@
 use wasm_bindgen::prelude::*;
 
+pub mod blend;
+pub mod diff;
+pub mod draw;
 pub mod effects;
 pub mod fill;
+pub mod history;
+pub mod layer;
 pub mod packing;
 pub mod patch;
 pub mod rgba;
+pub mod selection;
+pub mod stroke;
+pub mod tiled;
 
 #[wasm_bindgen]
 extern "C" {
@@ -2,6 +2,8 @@ use image::{codecs::png::PngEncoder, ExtendedColorType, ImageEncoder};
 use image_webp::{WebPDecoder, WebPEncoder};
 use wasm_bindgen::prelude::*;
 
+pub mod tile;
+
 #[wasm_bindgen]
 pub fn raw_to_webp(buffer: &[u8], width: u32, height: u32) -> Vec<u8> {
     let mut output = Vec::new();
@@ -30,6 +32,18 @@ pub fn webp_to_raw(webp_buffer: &[u8], width: u32, height: u32) -> Vec<u8> {
     output
 }
 
+/// Decode an RGBA WebP, or None if it is invalid or not exactly width x height.
+pub(crate) fn decode_webp(webp: &[u8], width: u32, height: u32) -> Option<Vec<u8>> {
+    let mut decoder = WebPDecoder::new(std::io::Cursor::new(webp)).ok()?;
+    let len = width as usize * height as usize * 4;
+    if decoder.dimensions() != (width, height) || decoder.output_buffer_size() != Some(len) {
+        return None;
+    }
+    let mut pixels = vec![0u8; len];
+    decoder.read_image(&mut pixels).ok()?;
+    Some(pixels)
+}
+
 #[wasm_bindgen]
 pub fn raw_to_png(buffer: &[u8], width: u32, height: u32) -> Vec<u8> {
     let mut output = Vec::new();
@@ -0,0 +1,120 @@
+use crate::packing::{decode_webp, raw_to_webp};
+
+// Tile patch layout (little endian):
+//   [tile_size: u32][tile_count: u32]
+//   per tile: [linear index: u32][kind: u8][payload]
+//     kind 0 (uniform): [r, g, b, a]
+//     kind 1 (webp):    [byte length: u32][lossless webp bytes]
+const TILE_KIND_UNIFORM: u8 = 0;
+const TILE_KIND_WEBP: u8 = 1;
+
+/// A single tile: its row-major linear index, clipped size and raw RGBA pixels.
+pub struct TileEntry {
+    pub index: u32,
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+/// Bounds (x, y, width, height) of a tile clipped to the buffer, or None if it lies outside.
+pub fn tile_bounds(
+    width: u32,
+    height: u32,
+    tile_size: u32,
+    index: u32,
+) -> Option<(u32, u32, u32, u32)> {
+    if tile_size == 0 || width == 0 || height == 0 {
+        return None;
+    }
+    let cols = width.div_ceil(tile_size);
+    let rows = height.div_ceil(tile_size);
+    if index >= cols.saturating_mul(rows) {
+        return None;
+    }
+    let x = (index % cols) * tile_size;
+    let y = (index / cols) * tile_size;
+    Some((x, y, tile_size.min(width - x), tile_size.min(height - y)))
+}
+
+/// Encode tiles into the tile patch layout. Uniform tiles are stored as a single color.
+pub fn encode_tiles(tile_size: u32, tiles: &[TileEntry]) -> Vec<u8> {
+    let entries: Vec<Vec<u8>> = tiles.iter().map(encode_tile).collect();
+    join_tiles(tile_size, &entries)
+}
+
+/// Encode one tile as a patch entry (index, kind and payload).
+pub fn encode_tile(tile: &TileEntry) -> Vec<u8> {
+    let mut out = Vec::new();
+    out.extend_from_slice(&tile.index.to_le_bytes());
+    let first = &tile.pixels[0..4];
+    if tile.pixels.chunks_exact(4).all(|px| px == first) {
+        out.push(TILE_KIND_UNIFORM);
+        out.extend_from_slice(first);
+    } else {
+        let webp = raw_to_webp(&tile.pixels, tile.width, tile.height);
+        out.push(TILE_KIND_WEBP);
+        out.extend_from_slice(&(webp.len() as u32).to_le_bytes());
+        out.extend_from_slice(&webp);
+    }
+    out
+}
+
+/// Build a tile patch from entries made by `encode_tile`.
+pub fn join_tiles(tile_size: u32, entries: &[Vec<u8>]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(8 + entries.iter().map(Vec::len).sum::<usize>());
+    out.extend_from_slice(&tile_size.to_le_bytes());
+    out.extend_from_slice(&(entries.len() as u32).to_le_bytes());
+    for entry in entries {
+        out.extend_from_slice(entry);
+    }
+    out
+}
+
+/// Decode a tile patch for a buffer of the given size. Returns the tile size and the tiles,
+/// or None if a tile lies outside the buffer, a payload does not decode to its tile size
+/// or bytes are left over.
+pub fn decode_tiles(encoded: &[u8], width: u32, height: u32) -> Option<(u32, Vec<TileEntry>)> {
+    let mut cursor = 0usize;
+    let tile_size = read_u32(encoded, &mut cursor)?;
+    let count = read_u32(encoded, &mut cursor)?;
+
+    let mut tiles = Vec::new();
+    for _ in 0..count {
+        let index = read_u32(encoded, &mut cursor)?;
+        let (_, _, tile_w, tile_h) = tile_bounds(width, height, tile_size, index)?;
+        let kind = *encoded.get(cursor)?;
+        cursor += 1;
+
+        let pixels = match kind {
+            TILE_KIND_UNIFORM => {
+                let color = encoded.get(cursor..cursor + 4)?;
+                cursor += 4;
+                color.repeat((tile_w * tile_h) as usize)
+            }
+            TILE_KIND_WEBP => {
+                let len = read_u32(encoded, &mut cursor)? as usize;
+                let webp = encoded.get(cursor..cursor.checked_add(len)?)?;
+                cursor += len;
+                decode_webp(webp, tile_w, tile_h)?
+            }
+            _ => return None,
+        };
+        tiles.push(TileEntry {
+            index,
+            width: tile_w,
+            height: tile_h,
+            pixels,
+        });
+    }
+
+    if cursor != encoded.len() {
+        return None;
+    }
+    Some((tile_size, tiles))
+}
+
+fn read_u32(bytes: &[u8], cursor: &mut usize) -> Option<u32> {
+    let slice = bytes.get(*cursor..*cursor + 4)?;
+    *cursor += 4;
+    Some(u32::from_le_bytes([slice[0], slice[1], slice[2], slice[3]]))
+}
@@ -1,5 +1,7 @@
 use wasm_bindgen::prelude::*;
 
+use crate::blend::{blend_rgb, BlendMode};
+
 #[wasm_bindgen]
 #[derive(Clone, Copy)]
 pub enum AntialiasMode {
@@ -14,16 +16,22 @@ pub struct PatchBufferRgbaOption {
     pub antialias_mode: AntialiasMode,
     pub flip_x: bool,
     pub flip_y: bool,
+    pub blend_mode: BlendMode,
+    /// Global opacity applied on top of the source alpha (0.0-1.0)
+    pub opacity: f32,
 }
 
 #[wasm_bindgen]
 impl PatchBufferRgbaOption {
+    /// `blend_mode` and `opacity` start as `Normal` and 1.0 and are set as properties.
     #[wasm_bindgen(constructor)]
     pub fn new(antialias_mode: AntialiasMode, flip_x: bool, flip_y: bool) -> PatchBufferRgbaOption {
         PatchBufferRgbaOption {
             antialias_mode,
             flip_x,
             flip_y,
+            blend_mode: BlendMode::Normal,
+            opacity: 1.0,
         }
     }
 }
@@ -198,17 +206,21 @@ fn sample_bicubic(
     )
 }
 
-// Apply alpha blending
-fn apply_alpha_blend(
+// Source-over as blits have always done it: the color is mixed by source alpha alone, so
+// it stays bit-identical to earlier releases. Over a transparent destination this darkens
+// semi-transparent colors; `apply_alpha_blend` is the alpha-correct version.
+fn apply_source_over(
     target: &mut [u8],
     tgt_start: usize,
     src_pr: f32,
     src_pg: f32,
     src_pb: f32,
     src_a: f32,
+    opacity: f32,
 ) {
     // Skip nearly transparent pixels
-    if src_a < 0.5 {
+    let src_a_eff = src_a * opacity;
+    if src_a_eff < 0.5 {
         return;
     }
 
@@ -236,7 +248,7 @@ fn apply_alpha_blend(
     let dst_b = target[tgt_start + 2] as f32;
     let dst_a = target[tgt_start + 3] as f32;
 
-    let src_a_norm = src_a / 255.0;
+    let src_a_norm = src_a_eff / 255.0;
     let dst_a_norm = dst_a / 255.0;
 
     let out_r = (src_r * src_a_norm + dst_r * (1.0 - src_a_norm))
@@ -258,6 +270,83 @@ fn apply_alpha_blend(
     target[tgt_start + 3] = out_a;
 }
 
+// Apply alpha blending with any blend mode, separable or not (Hue, Saturation, Color and
+// Luminosity), weighting colors by both alphas (W3C Compositing). The layer compositor
+// uses this for every mode, Normal included.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn apply_alpha_blend(
+    target: &mut [u8],
+    tgt_start: usize,
+    src_pr: f32,
+    src_pg: f32,
+    src_pb: f32,
+    src_a: f32,
+    blend_mode: BlendMode,
+    opacity: f32,
+) {
+    // Skip nearly transparent pixels
+    let src_a_eff = src_a * opacity;
+    if src_a_eff < 0.5 {
+        return;
+    }
+
+    // Convert back from premultiplied alpha
+    let alpha_norm = src_a / 255.0;
+    let src_r = if alpha_norm > 0.001 {
+        src_pr / alpha_norm
+    } else {
+        0.0
+    };
+    let src_g = if alpha_norm > 0.001 {
+        src_pg / alpha_norm
+    } else {
+        0.0
+    };
+    let src_b = if alpha_norm > 0.001 {
+        src_pb / alpha_norm
+    } else {
+        0.0
+    };
+
+    let dst_r = target[tgt_start] as f32;
+    let dst_g = target[tgt_start + 1] as f32;
+    let dst_b = target[tgt_start + 2] as f32;
+    let dst_a = target[tgt_start + 3] as f32;
+
+    let src_a_norm = src_a_eff / 255.0;
+    let dst_a_norm = dst_a / 255.0;
+
+    // Mix the source with the backdrop where the backdrop is present
+    let (mix_r, mix_g, mix_b) = if blend_mode == BlendMode::Normal || dst_a_norm <= 0.0 {
+        (src_r, src_g, src_b)
+    } else {
+        let blended = blend_rgb(
+            blend_mode,
+            [dst_r / 255.0, dst_g / 255.0, dst_b / 255.0],
+            [src_r / 255.0, src_g / 255.0, src_b / 255.0],
+        );
+        (
+            (1.0 - dst_a_norm) * src_r + dst_a_norm * blended[0] * 255.0,
+            (1.0 - dst_a_norm) * src_g + dst_a_norm * blended[1] * 255.0,
+            (1.0 - dst_a_norm) * src_b + dst_a_norm * blended[2] * 255.0,
+        )
+    };
+
+    // Alpha blend (source over)
+    let out_a_norm = src_a_norm + dst_a_norm * (1.0 - src_a_norm);
+    let dst_weight = dst_a_norm * (1.0 - src_a_norm);
+    let composite = |s: f32, d: f32| -> u8 {
+        ((s * src_a_norm + d * dst_weight) / out_a_norm)
+            .round()
+            .clamp(0.0, 255.0) as u8
+    };
+
+    target[tgt_start] = composite(mix_r, dst_r);
+    target[tgt_start + 1] = composite(mix_g, dst_g);
+    target[tgt_start + 2] = composite(mix_b, dst_b);
+    target[tgt_start + 3] = (out_a_norm * 255.0).round().clamp(0.0, 255.0) as u8;
+}
+
 #[wasm_bindgen]
 #[allow(clippy::too_many_arguments)]
 pub fn patch_buffer_rgba_instant(
@@ -342,7 +431,129 @@ pub fn patch_buffer_rgba_instant(
                 AntialiasMode::Bicubic => sample_bicubic(patch, src_x, src_y, src_w, src_h),
             };
 
-            apply_alpha_blend(target, tgt_start, src_pr, src_pg, src_pb, src_a);
+            let opacity = options.opacity.clamp(0.0, 1.0);
+            if options.blend_mode == BlendMode::Normal {
+                apply_source_over(target, tgt_start, src_pr, src_pg, src_pb, src_a, opacity);
+            } else {
+                apply_alpha_blend(
+                    target,
+                    tgt_start,
+                    src_pr,
+                    src_pg,
+                    src_pb,
+                    src_a,
+                    options.blend_mode,
+                    opacity,
+                );
+            }
         }
     }
 }
+
+// Target-space bounds touched by a blit (see patch_buffer_rgba_instant for the mapping),
+// padded for the interpolation kernels
+pub(crate) fn transformed_bounds(
+    scaled_w: f32,
+    scaled_h: f32,
+    offset_x: f32,
+    offset_y: f32,
+    rotate_deg: f32,
+) -> (i32, i32, u32, u32) {
+    let rotate_rad = rotate_deg.to_radians();
+    let (sin_r, cos_r) = rotate_rad.sin_cos();
+    let cx = scaled_w / 2.0;
+    let cy = scaled_h / 2.0;
+
+    let mut min_x = f32::MAX;
+    let mut min_y = f32::MAX;
+    let mut max_x = f32::MIN;
+    let mut max_y = f32::MIN;
+    for (px, py) in [
+        (0.0, 0.0),
+        (scaled_w, 0.0),
+        (0.0, scaled_h),
+        (scaled_w, scaled_h),
+    ] {
+        let dx = px - cx;
+        let dy = py - cy;
+        let tx = dx * cos_r - dy * sin_r + cx + offset_x;
+        let ty = dx * sin_r + dy * cos_r + cy + offset_y;
+        min_x = min_x.min(tx);
+        min_y = min_y.min(ty);
+        max_x = max_x.max(tx);
+        max_y = max_y.max(ty);
+    }
+
+    let x = min_x.floor() as i32 - 2;
+    let y = min_y.floor() as i32 - 2;
+    let w = (max_x.ceil() as i32 + 2 - x).max(0) as u32;
+    let h = (max_y.ceil() as i32 + 2 - y).max(0) as u32;
+    (x, y, w, h)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blend::blend_rgb;
+
+    // W3C Compositing: Cs' = (1 - ab) * Cs + ab * B(Cb, Cs), then source-over
+    fn w3c_composite(source: [u8; 4], backdrop: [u8; 4], mode: BlendMode, opacity: f32) -> [u8; 4] {
+        let norm = |c: [u8; 4]| {
+            [
+                c[0] as f32 / 255.0,
+                c[1] as f32 / 255.0,
+                c[2] as f32 / 255.0,
+            ]
+        };
+        let (cs, cb) = (norm(source), norm(backdrop));
+        let a_s = source[3] as f32 / 255.0 * opacity;
+        let a_b = backdrop[3] as f32 / 255.0;
+        let blended = blend_rgb(mode, cb, cs);
+        let a_o = a_s + a_b * (1.0 - a_s);
+        let mut out = [0u8; 4];
+        for c in 0..3 {
+            let mixed = (1.0 - a_b) * cs[c] + a_b * blended[c];
+            let co = a_s * mixed + a_b * cb[c] * (1.0 - a_s);
+            out[c] = (co / a_o * 255.0).round() as u8;
+        }
+        out[3] = (a_o * 255.0).round() as u8;
+        out
+    }
+
+    fn blit(source: [u8; 4], backdrop: [u8; 4], mode: BlendMode, opacity: f32) -> [u8; 4] {
+        let mut options = PatchBufferRgbaOption::new(AntialiasMode::Nearest, false, false);
+        options.blend_mode = mode;
+        options.opacity = opacity;
+        let out = patch_buffer_rgba(&backdrop, 1, 1, &source, 1, 1, 0.0, 0.0, &options);
+        [out[0], out[1], out[2], out[3]]
+    }
+
+    #[test]
+    fn blended_blit_matches_w3c_compositing() {
+        let source = [200, 100, 50, 128];
+        let backdrop = [60, 120, 240, 192];
+        for mode in [
+            BlendMode::Multiply,
+            BlendMode::Screen,
+            BlendMode::Overlay,
+            BlendMode::Difference,
+            BlendMode::Hue,
+            BlendMode::Luminosity,
+        ] {
+            let actual = blit(source, backdrop, mode, 0.6);
+            let expected = w3c_composite(source, backdrop, mode, 0.6);
+            for c in 0..4 {
+                assert!(
+                    actual[c].abs_diff(expected[c]) <= 1,
+                    "{mode:?}: {actual:?} != {expected:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn transparent_backdrop_takes_the_source_as_is() {
+        let out = blit([200, 100, 50, 128], [0, 0, 0, 0], BlendMode::Multiply, 1.0);
+        assert_eq!(out, [200, 100, 50, 128]);
+    }
+}
@@ -29,6 +29,8 @@ impl RgbaBuffer {
             width,
             height,
             data: vec![0u8; size],
+            journal: None,
+            fill_coverage: false,
         }
     }
 
@@ -44,6 +46,8 @@ impl RgbaBuffer {
             width,
             height,
             data: buf,
+            journal: None,
+            fill_coverage: false,
         })
     }
 
@@ -61,6 +65,8 @@ impl RgbaBuffer {
             width,
             height,
             data: raw,
+            journal: None,
+            fill_coverage: false,
         })
     }
 
@@ -70,6 +76,8 @@ impl RgbaBuffer {
             width: self.width,
             height: self.height,
             data: self.data.clone(),
+            journal: None,
+            fill_coverage: false,
         }
     }
 
@@ -105,6 +113,7 @@ impl RgbaBuffer {
         if raw.len() != expected {
             return false;
         }
+        self.journal_discard();
         self.width = width;
         self.height = height;
         self.data = raw;
@@ -128,6 +137,7 @@ impl RgbaBuffer {
         if !self.in_bounds(x, y) {
             return false;
         }
+        self.journal_rect(x as i32, y as i32, 1, 1);
         let idx = ((y * self.width + x) * 4) as usize;
         let slice = &mut self.data[idx..idx + 4];
         let changed = slice != [r, g, b, a];
@@ -151,6 +161,13 @@ impl RgbaBuffer {
         if start + 4 > self.data.len() {
             return false;
         }
+        let pixel = (start / 4) as u32;
+        self.journal_rect(
+            (pixel % self.width) as i32,
+            (pixel / self.width) as i32,
+            1,
+            1,
+        );
         let slice = &mut self.data[start..start + 4];
         let changed = slice != [r, g, b, a];
         slice.copy_from_slice(&[r, g, b, a]);
@@ -1,9 +1,10 @@
 use crate::{
-    patch::{patch_buffer_rgba_instant, AntialiasMode, PatchBufferRgbaOption},
+    patch::{patch_buffer_rgba_instant, transformed_bounds, AntialiasMode, PatchBufferRgbaOption},
     rgba::{
         base::{mask_is_valid, mask_pixel_count, pixel_byte_len},
         RgbaBuffer,
     },
+    selection::SelectionMask,
 };
 use wasm_bindgen::prelude::*;
 
@@ -24,15 +25,48 @@ impl RgbaBuffer {
         antialias_mode: AntialiasMode,
         flip_x: bool,
         flip_y: bool,
+    ) {
+        let options = PatchBufferRgbaOption::new(antialias_mode, flip_x, flip_y);
+        self.blit_from_raw_with_options(
+            source,
+            source_width,
+            source_height,
+            offset_x,
+            offset_y,
+            scale_x,
+            scale_y,
+            rotate_deg,
+            &options,
+        );
+    }
+
+    #[wasm_bindgen(js_name = blitFromRawWithOptions)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn blit_from_raw_with_options(
+        &mut self,
+        source: &[u8],
+        source_width: u32,
+        source_height: u32,
+        offset_x: f32,
+        offset_y: f32,
+        scale_x: f32,
+        scale_y: f32,
+        rotate_deg: f32,
+        options: &PatchBufferRgbaOption,
     ) {
         if pixel_byte_len(source_width, source_height) != source.len() {
             return;
         }
-        let options = PatchBufferRgbaOption {
-            antialias_mode,
-            flip_x,
-            flip_y,
-        };
+        if self.journal.is_some() {
+            let (x, y, w, h) = transformed_bounds(
+                source_width as f32 * scale_x,
+                source_height as f32 * scale_y,
+                offset_x,
+                offset_y,
+                rotate_deg,
+            );
+            self.journal_rect(x, y, w, h);
+        }
         patch_buffer_rgba_instant(
             &mut self.data,
             self.width,
@@ -45,7 +79,7 @@ impl RgbaBuffer {
             scale_x,
             scale_y,
             rotate_deg,
-            &options,
+            options,
         );
     }
 
@@ -78,6 +112,31 @@ impl RgbaBuffer {
         );
     }
 
+    #[wasm_bindgen(js_name = blitFromBufferWithOptions)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn blit_from_buffer_with_options(
+        &mut self,
+        source: &RgbaBuffer,
+        offset_x: f32,
+        offset_y: f32,
+        scale_x: f32,
+        scale_y: f32,
+        rotate_deg: f32,
+        options: &PatchBufferRgbaOption,
+    ) {
+        self.blit_from_raw_with_options(
+            &source.data,
+            source.width,
+            source.height,
+            offset_x,
+            offset_y,
+            scale_x,
+            scale_y,
+            rotate_deg,
+            options,
+        );
+    }
+
     #[wasm_bindgen(js_name = sliceWithMask)]
     pub fn slice_with_mask(
         &self,
@@ -174,4 +233,36 @@ impl RgbaBuffer {
 
         result
     }
+
+    #[wasm_bindgen(js_name = sliceWithSelection)]
+    pub fn slice_with_selection(
+        &self,
+        selection: &SelectionMask,
+        offset_x: f32,
+        offset_y: f32,
+    ) -> Vec<u8> {
+        self.slice_with_mask(
+            &selection.data,
+            selection.width,
+            selection.height,
+            offset_x,
+            offset_y,
+        )
+    }
+
+    #[wasm_bindgen(js_name = cropWithSelection)]
+    pub fn crop_with_selection(
+        &self,
+        selection: &SelectionMask,
+        offset_x: f32,
+        offset_y: f32,
+    ) -> Vec<u8> {
+        self.crop_with_mask(
+            &selection.data,
+            selection.width,
+            selection.height,
+            offset_x,
+            offset_y,
+        )
+    }
 }
@@ -0,0 +1,215 @@
+use crate::{
+    draw::{
+        ellipse_mask, line_mask, path::Path, path_mask, polygon_mask, raster::Mask, rect_mask,
+        ShapeOption,
+    },
+    fill::{
+        coverage::{blend_coverage, CoverageMode},
+        result::FillResult,
+    },
+    rgba::RgbaBuffer,
+};
+use wasm_bindgen::prelude::*;
+
+/// Shape drawing. Every method returns what changed, or undefined if the selection set
+/// on `options` does not match the buffer size.
+#[wasm_bindgen]
+impl RgbaBuffer {
+    #[wasm_bindgen(js_name = drawLine)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_line(
+        &mut self,
+        x0: f32,
+        y0: f32,
+        x1: f32,
+        y1: f32,
+        color_r: u8,
+        color_g: u8,
+        color_b: u8,
+        color_a: u8,
+        options: &ShapeOption,
+    ) -> Option<FillResult> {
+        let mask = line_mask(self.width, self.height, x0, y0, x1, y1, options);
+        self.paint_shape(mask, [color_r, color_g, color_b, color_a], options)
+    }
+
+    #[wasm_bindgen(js_name = drawRect)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_rect(
+        &mut self,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        color_r: u8,
+        color_g: u8,
+        color_b: u8,
+        color_a: u8,
+        options: &ShapeOption,
+    ) -> Option<FillResult> {
+        self.draw_rounded_rect(
+            x, y, width, height, 0.0, color_r, color_g, color_b, color_a, options,
+        )
+    }
+
+    #[wasm_bindgen(js_name = drawRoundedRect)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_rounded_rect(
+        &mut self,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        radius: f32,
+        color_r: u8,
+        color_g: u8,
+        color_b: u8,
+        color_a: u8,
+        options: &ShapeOption,
+    ) -> Option<FillResult> {
+        let mask = rect_mask(
+            self.width,
+            self.height,
+            x,
+            y,
+            width,
+            height,
+            radius,
+            options,
+        );
+        self.paint_shape(mask, [color_r, color_g, color_b, color_a], options)
+    }
+
+    /// Ellipse inscribed in the box (x, y, width, height).
+    #[wasm_bindgen(js_name = drawEllipse)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_ellipse(
+        &mut self,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        color_r: u8,
+        color_g: u8,
+        color_b: u8,
+        color_a: u8,
+        options: &ShapeOption,
+    ) -> Option<FillResult> {
+        let mask = ellipse_mask(self.width, self.height, x, y, width, height, options);
+        self.paint_shape(mask, [color_r, color_g, color_b, color_a], options)
+    }
+
+    /// Circle around (center_x, center_y). Aliased circles with an odd diameter need a
+    /// center on a pixel center (e.g. 10.5).
+    #[wasm_bindgen(js_name = drawCircle)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_circle(
+        &mut self,
+        center_x: f32,
+        center_y: f32,
+        radius: f32,
+        color_r: u8,
+        color_g: u8,
+        color_b: u8,
+        color_a: u8,
+        options: &ShapeOption,
+    ) -> Option<FillResult> {
+        self.draw_ellipse(
+            center_x - radius,
+            center_y - radius,
+            radius * 2.0,
+            radius * 2.0,
+            color_r,
+            color_g,
+            color_b,
+            color_a,
+            options,
+        )
+    }
+
+    /// Closed polygon through `points` ([x0, y0, x1, y1, ...]).
+    #[wasm_bindgen(js_name = drawPolygon)]
+    pub fn draw_polygon(
+        &mut self,
+        points: &[f32],
+        color_r: u8,
+        color_g: u8,
+        color_b: u8,
+        color_a: u8,
+        options: &ShapeOption,
+    ) -> Option<FillResult> {
+        let mask = polygon_mask(self.width, self.height, points, options);
+        self.paint_shape(mask, [color_r, color_g, color_b, color_a], options)
+    }
+
+    /// Fill and/or stroke `path` as set in `options`.
+    #[wasm_bindgen(js_name = drawPath)]
+    pub fn draw_path(
+        &mut self,
+        path: &Path,
+        color_r: u8,
+        color_g: u8,
+        color_b: u8,
+        color_a: u8,
+        options: &ShapeOption,
+    ) -> Option<FillResult> {
+        let mask = path_mask(self.width, self.height, path, options);
+        self.paint_shape(mask, [color_r, color_g, color_b, color_a], options)
+    }
+}
+
+impl RgbaBuffer {
+    // Composite `color` through the shape coverage, scaled by the selection
+    fn paint_shape(
+        &mut self,
+        mask: Option<Mask>,
+        color: [u8; 4],
+        options: &ShapeOption,
+    ) -> Option<FillResult> {
+        let width = self.width as usize;
+        let selection = options.selection();
+        if selection.is_some_and(|s| s.width != self.width || s.height != self.height) {
+            return None;
+        }
+        let Some(mask) = mask else {
+            return Some(FillResult::from_changes(0, 0, &[], false));
+        };
+
+        self.journal_rect(
+            mask.x as i32,
+            mask.y as i32,
+            mask.width as u32,
+            mask.height as u32,
+        );
+        let mut changes = vec![0u8; mask.data.len()];
+        for my in 0..mask.height {
+            for mx in 0..mask.width {
+                let m = my * mask.width + mx;
+                let i = (mask.y as usize + my) * width + mask.x as usize + mx;
+                let c = match selection {
+                    Some(selection) => {
+                        ((mask.data[m] as u32 * selection.data[i] as u32 + 127) / 255) as u8
+                    }
+                    None => mask.data[m],
+                };
+                if c == 0 {
+                    continue;
+                }
+                let pixel = &mut self.data[i * 4..i * 4 + 4];
+                let painted = blend_coverage(pixel, color, c, CoverageMode::Premultiplied);
+                if pixel != painted {
+                    pixel.copy_from_slice(&painted);
+                    changes[m] = c;
+                }
+            }
+        }
+        Some(FillResult::from_changes_at(
+            mask.x as usize,
+            mask.y as usize,
+            mask.width,
+            mask.height,
+            &changes,
+            self.fill_coverage,
+        ))
+    }
+}
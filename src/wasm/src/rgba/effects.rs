@@ -9,6 +9,7 @@ use crate::{
         posterize::{posterize, PosterizeOption},
     },
     rgba::RgbaBuffer,
+    selection::SelectionMask,
 };
 use wasm_bindgen::prelude::*;
 
@@ -16,45 +17,183 @@ use wasm_bindgen::prelude::*;
 impl RgbaBuffer {
     #[wasm_bindgen(js_name = brightnessAndContrast)]
     pub fn brightness_contrast(&mut self, brightness: f32, contrast: f32) {
-        brightness_contrast(
-            &mut self.data,
-            self.width,
-            self.height,
-            &BrightnessContrastOption::new(brightness, contrast),
-        );
+        let options = BrightnessContrastOption::new(brightness, contrast);
+        self.journal_rect(0, 0, self.width, self.height);
+        brightness_contrast(&mut self.data, self.width, self.height, &options);
     }
 
     #[wasm_bindgen(js_name = invert)]
     pub fn invert(&mut self) {
+        self.journal_rect(0, 0, self.width, self.height);
         invert(&mut self.data, self.width, self.height);
     }
 
     #[wasm_bindgen(js_name = grayscale)]
     pub fn grayscale(&mut self) {
+        self.journal_rect(0, 0, self.width, self.height);
         grayscale(&mut self.data, self.width, self.height);
     }
 
     #[wasm_bindgen(js_name = gaussianBlur)]
     pub fn gaussian_blur(&mut self, radius: f32, alpha_mode: AlphaBlurMode) {
         let options = GaussianBlurOption::new(radius, alpha_mode);
+        self.journal_rect(0, 0, self.width, self.height);
         gaussian_blur(&mut self.data, self.width, self.height, &options);
     }
 
     #[wasm_bindgen(js_name = posterize)]
     pub fn posterize(&mut self, levels: u32) {
         let options = PosterizeOption::new(levels);
+        self.journal_rect(0, 0, self.width, self.height);
         posterize(&mut self.data, self.width, self.height, &options);
     }
 
     #[wasm_bindgen(js_name = dustRemoval)]
     pub fn dust_removal(&mut self, max_size: u32, alpha_threshold: u8) {
         let options = DustRemovalOption::new(max_size, alpha_threshold);
-        dust_removal(&mut self.data, self.width, self.height, &options);
+        self.dust_removal_with_options(&options);
+    }
+
+    #[wasm_bindgen(js_name = dustRemovalWithOptions)]
+    pub fn dust_removal_with_options(&mut self, options: &DustRemovalOption) {
+        self.journal_rect(0, 0, self.width, self.height);
+        dust_removal(&mut self.data, self.width, self.height, options);
     }
 
     #[wasm_bindgen(js_name = dithering)]
     pub fn dithering(&mut self, mode: DitheringMode, levels: u32, strength: f32) {
         let options = DitheringOption::new(mode, levels, strength);
+        self.journal_rect(0, 0, self.width, self.height);
         dithering(&mut self.data, self.width, self.height, &options);
     }
+
+    // Selection-limited variants. Each effect runs on a copy of the selection bound box,
+    // grown by the distance the effect reads around a pixel, and the result is mixed back
+    // by selection coverage. They return false if the selection size differs.
+
+    #[wasm_bindgen(js_name = brightnessAndContrastInSelection)]
+    pub fn brightness_contrast_in_selection(
+        &mut self,
+        selection: &SelectionMask,
+        brightness: f32,
+        contrast: f32,
+    ) -> bool {
+        let options = BrightnessContrastOption::new(brightness, contrast);
+        self.in_selection(selection, Some(0), |pixels, width, height| {
+            brightness_contrast(pixels, width, height, &options)
+        })
+    }
+
+    #[wasm_bindgen(js_name = invertInSelection)]
+    pub fn invert_in_selection(&mut self, selection: &SelectionMask) -> bool {
+        self.in_selection(selection, Some(0), invert)
+    }
+
+    #[wasm_bindgen(js_name = grayscaleInSelection)]
+    pub fn grayscale_in_selection(&mut self, selection: &SelectionMask) -> bool {
+        self.in_selection(selection, Some(0), grayscale)
+    }
+
+    #[wasm_bindgen(js_name = gaussianBlurInSelection)]
+    pub fn gaussian_blur_in_selection(
+        &mut self,
+        selection: &SelectionMask,
+        radius: f32,
+        alpha_mode: AlphaBlurMode,
+    ) -> bool {
+        let options = GaussianBlurOption::new(radius, alpha_mode);
+        // 3x3 kernel
+        self.in_selection(selection, Some(1), |pixels, width, height| {
+            gaussian_blur(pixels, width, height, &options)
+        })
+    }
+
+    #[wasm_bindgen(js_name = posterizeInSelection)]
+    pub fn posterize_in_selection(&mut self, selection: &SelectionMask, levels: u32) -> bool {
+        let options = PosterizeOption::new(levels);
+        self.in_selection(selection, Some(0), |pixels, width, height| {
+            posterize(pixels, width, height, &options)
+        })
+    }
+
+    #[wasm_bindgen(js_name = dustRemovalInSelection)]
+    pub fn dust_removal_in_selection(
+        &mut self,
+        selection: &SelectionMask,
+        max_size: u32,
+        alpha_threshold: u8,
+    ) -> bool {
+        let options = DustRemovalOption::new(max_size, alpha_threshold);
+        // A component that reaches past this margin has more than `max_size` pixels
+        // inside it, so it is kept just like on the whole buffer
+        self.in_selection(selection, Some(max_size), |pixels, width, height| {
+            dust_removal(pixels, width, height, &options)
+        })
+    }
+
+    #[wasm_bindgen(js_name = ditheringInSelection)]
+    pub fn dithering_in_selection(
+        &mut self,
+        selection: &SelectionMask,
+        mode: DitheringMode,
+        levels: u32,
+        strength: f32,
+    ) -> bool {
+        let options = DitheringOption::new(mode, levels, strength);
+        // The pattern, random sequence and diffused error depend on the pixel position
+        self.in_selection(selection, None, |pixels, width, height| {
+            dithering(pixels, width, height, &options)
+        })
+    }
+}
+
+impl RgbaBuffer {
+    // Run `effect` on a copy of the selection bound box grown by `margin` pixels (None for
+    // the whole buffer) and mix the result in. The buffer itself keeps the original pixels
+    // until the mix, so no other copy is needed.
+    fn in_selection(
+        &mut self,
+        selection: &SelectionMask,
+        margin: Option<u32>,
+        effect: impl FnOnce(&mut [u8], u32, u32),
+    ) -> bool {
+        if selection.width != self.width || selection.height != self.height {
+            return false;
+        }
+        let bound_box = selection.bound_box();
+        let [x, y, w, h] = bound_box[..] else {
+            return true;
+        };
+        let (left, top, right, bottom) = match margin {
+            Some(m) => (
+                x.saturating_sub(m),
+                y.saturating_sub(m),
+                (x + w).saturating_add(m).min(self.width),
+                (y + h).saturating_add(m).min(self.height),
+            ),
+            None => (0, 0, self.width, self.height),
+        };
+        let (work_width, work_height) = (right - left, bottom - top);
+        let mut work = self.read_rect(left as i32, top as i32, work_width, work_height);
+        effect(&mut work, work_width, work_height);
+
+        // Only the selected tiles change
+        self.journal_coverage(&selection.data);
+        let width = self.width as usize;
+        for row in y as usize..(y + h) as usize {
+            for col in x as usize..(x + w) as usize {
+                let c = selection.data[row * width + col] as u32;
+                if c == 0 {
+                    continue;
+                }
+                let i = (row * width + col) * 4;
+                let wi = ((row - top as usize) * work_width as usize + col - left as usize) * 4;
+                let (pixel, effected) = (&mut self.data[i..i + 4], &work[wi..wi + 4]);
+                for (out, new) in pixel.iter_mut().zip(effected.iter()) {
+                    *out = ((*new as u32 * c + *out as u32 * (255 - c) + 127) / 255) as u8;
+                }
+            }
+        }
+        true
+    }
 }
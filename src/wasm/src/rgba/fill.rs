@@ -1,16 +1,33 @@
 use crate::{
     fill::{
-        area_fill::fill_mask_area,
-        flood_fill::{scanline_flood_fill, scanline_flood_fill_with_mask},
+        area_fill::paint_mask_area,
+        coverage::CoverageMode,
+        flood_fill::{
+            flood_fill_result, flood_fill_with_mask_result, options_fill_result,
+            reference_fill_result, FloodFillOption,
+        },
+        gradient::{gradient_fill_result, GradientFillOption},
+        pattern::{pattern_fill_result, pattern_flood_fill_result, PatternFillOption},
+        result::FillResult,
+        Connectivity,
     },
     rgba::RgbaBuffer,
+    selection::SelectionMask,
 };
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
 impl RgbaBuffer {
+    /// Make fills copy the coverage of the pixels they changed into `FillResult.coverage`.
+    /// Off by default, since the copy is as large as the changed area.
+    #[wasm_bindgen(js_name = setFillCoverage)]
+    pub fn set_fill_coverage(&mut self, enabled: bool) {
+        self.fill_coverage = enabled;
+    }
+
     #[wasm_bindgen(js_name = fillAllCodes)]
     pub fn fill_all_codes(&mut self, fill_code: u8) -> bool {
+        self.journal_rect(0, 0, self.width, self.height);
         self.data.fill(fill_code);
         true
     }
@@ -23,18 +40,15 @@ impl RgbaBuffer {
         fill_color_b: u8,
         fill_color_a: u8,
     ) -> bool {
-        let pixel_len = self.width * self.height;
-        for i in 0..pixel_len {
-            self.index_set(
-                i * 4,
-                fill_color_r,
-                fill_color_g,
-                fill_color_b,
-                fill_color_a,
-            );
+        self.journal_rect(0, 0, self.width, self.height);
+        let fill_color = [fill_color_r, fill_color_g, fill_color_b, fill_color_a];
+        for pixel in self.data.chunks_exact_mut(4) {
+            pixel.copy_from_slice(&fill_color);
         }
         true
     }
+
+    /// Fill every pixel whose mask flag is non-zero and report what changed.
     #[wasm_bindgen(js_name = fillMaskArea)]
     pub fn fill_mask_area(
         &mut self,
@@ -43,17 +57,36 @@ impl RgbaBuffer {
         fill_color_g: u8,
         fill_color_b: u8,
         fill_color_a: u8,
-    ) -> bool {
-        fill_mask_area(
-            &mut self.data,
+    ) -> FillResult {
+        self.fill_mask_area_with_coverage(
             mask,
             fill_color_r,
             fill_color_g,
             fill_color_b,
             fill_color_a,
+            CoverageMode::Binary,
         )
     }
 
+    /// `fillMaskArea` where mask values are applied according to `mode`.
+    #[wasm_bindgen(js_name = fillMaskAreaWithCoverage)]
+    pub fn fill_mask_area_with_coverage(
+        &mut self,
+        mask: &[u8],
+        fill_color_r: u8,
+        fill_color_g: u8,
+        fill_color_b: u8,
+        fill_color_a: u8,
+        mode: CoverageMode,
+    ) -> FillResult {
+        let fill_color = [fill_color_r, fill_color_g, fill_color_b, fill_color_a];
+        let (data, before_paint) = self.journaled_paint();
+        let changes = paint_mask_area(data, mask, fill_color, mode, before_paint);
+        self.fill_result(&changes)
+    }
+
+    /// Flood fill and report what changed, or undefined if the start point is out of bounds.
+    /// `connectivity` defaults to `Four`.
     #[wasm_bindgen(js_name = floodFill)]
     #[allow(clippy::too_many_arguments)]
     pub fn flood_fill(
@@ -65,21 +98,26 @@ impl RgbaBuffer {
         fill_color_b: u8,
         fill_color_a: u8,
         threshold: u8,
-    ) -> bool {
-        scanline_flood_fill(
-            &mut self.data,
-            self.width,
-            self.height,
+        connectivity: Option<Connectivity>,
+    ) -> Option<FillResult> {
+        let (width, height) = (self.width, self.height);
+        let (data, before_paint) = self.journaled_paint();
+        let changes = flood_fill_result(
+            data,
+            width,
+            height,
             start_x,
             start_y,
-            fill_color_r,
-            fill_color_g,
-            fill_color_b,
-            fill_color_a,
+            [fill_color_r, fill_color_g, fill_color_b, fill_color_a],
             threshold,
-        )
+            connectivity.unwrap_or(Connectivity::Four),
+            before_paint,
+        )?;
+        Some(self.fill_result(&changes))
     }
 
+    /// `floodFill` limited by a selection mask. Undefined if the start point is not allowed.
+    /// `connectivity` defaults to `Four`.
     #[wasm_bindgen(js_name = floodFillWithMask)]
     #[allow(clippy::too_many_arguments)]
     pub fn flood_fill_with_mask(
@@ -93,11 +131,70 @@ impl RgbaBuffer {
         threshold: u8,
         selection_mask: &[u8],
         limit_mode: &str,
-    ) -> bool {
-        scanline_flood_fill_with_mask(
-            &mut self.data,
-            self.width,
-            self.height,
+        connectivity: Option<Connectivity>,
+    ) -> Option<FillResult> {
+        let (width, height) = (self.width, self.height);
+        let (data, before_paint) = self.journaled_paint();
+        let changes = flood_fill_with_mask_result(
+            data,
+            width,
+            height,
+            start_x,
+            start_y,
+            [fill_color_r, fill_color_g, fill_color_b, fill_color_a],
+            threshold,
+            connectivity.unwrap_or(Connectivity::Four),
+            selection_mask,
+            limit_mode,
+            before_paint,
+        )?;
+        Some(self.fill_result(&changes))
+    }
+
+    /// Fill the selection, fading out where its coverage is partial (premultiplied).
+    /// The selection must match the buffer size.
+    #[wasm_bindgen(js_name = fillSelection)]
+    pub fn fill_selection(
+        &mut self,
+        selection: &SelectionMask,
+        fill_color_r: u8,
+        fill_color_g: u8,
+        fill_color_b: u8,
+        fill_color_a: u8,
+    ) -> Option<FillResult> {
+        if selection.width != self.width || selection.height != self.height {
+            return None;
+        }
+        Some(self.fill_mask_area_with_coverage(
+            &selection.data,
+            fill_color_r,
+            fill_color_g,
+            fill_color_b,
+            fill_color_a,
+            CoverageMode::Premultiplied,
+        ))
+    }
+
+    /// `floodFillWithMask` limited by a `SelectionMask` of the buffer size.
+    #[wasm_bindgen(js_name = floodFillWithSelection)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn flood_fill_with_selection(
+        &mut self,
+        start_x: u32,
+        start_y: u32,
+        fill_color_r: u8,
+        fill_color_g: u8,
+        fill_color_b: u8,
+        fill_color_a: u8,
+        threshold: u8,
+        selection: &SelectionMask,
+        limit_mode: &str,
+        connectivity: Option<Connectivity>,
+    ) -> Option<FillResult> {
+        if selection.width != self.width || selection.height != self.height {
+            return None;
+        }
+        self.flood_fill_with_mask(
             start_x,
             start_y,
             fill_color_r,
@@ -105,8 +202,328 @@ impl RgbaBuffer {
             fill_color_b,
             fill_color_a,
             threshold,
-            selection_mask,
+            &selection.data,
             limit_mode,
+            connectivity,
+        )
+    }
+
+    /// Flood fill with a configurable color metric and alpha handling.
+    #[wasm_bindgen(js_name = floodFillWithOptions)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn flood_fill_with_options(
+        &mut self,
+        start_x: u32,
+        start_y: u32,
+        fill_color_r: u8,
+        fill_color_g: u8,
+        fill_color_b: u8,
+        fill_color_a: u8,
+        options: &FloodFillOption,
+    ) -> Option<FillResult> {
+        let (width, height) = (self.width, self.height);
+        let (data, before_paint) = self.journaled_paint();
+        let changes = options_fill_result(
+            data,
+            width,
+            height,
+            start_x,
+            start_y,
+            [fill_color_r, fill_color_g, fill_color_b, fill_color_a],
+            &[],
+            "none",
+            options,
+            before_paint,
+        )?;
+        Some(self.fill_result(&changes))
+    }
+
+    /// `floodFillWithOptions` limited by a `SelectionMask` of the buffer size.
+    #[wasm_bindgen(js_name = floodFillWithSelectionAndOptions)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn flood_fill_with_selection_and_options(
+        &mut self,
+        start_x: u32,
+        start_y: u32,
+        fill_color_r: u8,
+        fill_color_g: u8,
+        fill_color_b: u8,
+        fill_color_a: u8,
+        selection: &SelectionMask,
+        limit_mode: &str,
+        options: &FloodFillOption,
+    ) -> Option<FillResult> {
+        if selection.width != self.width || selection.height != self.height {
+            return None;
+        }
+        let (width, height) = (self.width, self.height);
+        let (data, before_paint) = self.journaled_paint();
+        let changes = options_fill_result(
+            data,
+            width,
+            height,
+            start_x,
+            start_y,
+            [fill_color_r, fill_color_g, fill_color_b, fill_color_a],
+            &selection.data,
+            limit_mode,
+            options,
+            before_paint,
+        )?;
+        Some(self.fill_result(&changes))
+    }
+
+    /// Flood fill whose region is computed from `reference` (e.g. the line art layer or a
+    /// merged composite) while the fill color is written into this buffer.
+    /// Both buffers must have the same size.
+    #[wasm_bindgen(js_name = floodFillFromReference)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn flood_fill_from_reference(
+        &mut self,
+        reference: &RgbaBuffer,
+        start_x: u32,
+        start_y: u32,
+        fill_color_r: u8,
+        fill_color_g: u8,
+        fill_color_b: u8,
+        fill_color_a: u8,
+        options: &FloodFillOption,
+    ) -> Option<FillResult> {
+        let (width, height) = (self.width, self.height);
+        let (data, before_paint) = self.journaled_paint();
+        let changes = reference_fill_result(
+            data,
+            &reference.data,
+            width,
+            height,
+            start_x,
+            start_y,
+            [fill_color_r, fill_color_g, fill_color_b, fill_color_a],
+            &[],
+            "none",
+            options,
+            before_paint,
+        )?;
+        Some(self.fill_result(&changes))
+    }
+
+    /// `floodFillFromReference` limited by a `SelectionMask` of the buffer size.
+    #[wasm_bindgen(js_name = floodFillFromReferenceWithSelection)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn flood_fill_from_reference_with_selection(
+        &mut self,
+        reference: &RgbaBuffer,
+        start_x: u32,
+        start_y: u32,
+        fill_color_r: u8,
+        fill_color_g: u8,
+        fill_color_b: u8,
+        fill_color_a: u8,
+        selection: &SelectionMask,
+        limit_mode: &str,
+        options: &FloodFillOption,
+    ) -> Option<FillResult> {
+        if selection.width != self.width || selection.height != self.height {
+            return None;
+        }
+        let (width, height) = (self.width, self.height);
+        let (data, before_paint) = self.journaled_paint();
+        let changes = reference_fill_result(
+            data,
+            &reference.data,
+            width,
+            height,
+            start_x,
+            start_y,
+            [fill_color_r, fill_color_g, fill_color_b, fill_color_a],
+            &selection.data,
+            limit_mode,
+            options,
+            before_paint,
+        )?;
+        Some(self.fill_result(&changes))
+    }
+
+    /// Paint a gradient over the whole buffer.
+    /// `stop_colors` holds four RGBA bytes per entry of `stop_positions` (0-1).
+    /// Undefined if there are no stops.
+    #[wasm_bindgen(js_name = gradientFill)]
+    pub fn gradient_fill(
+        &mut self,
+        stop_positions: &[f32],
+        stop_colors: &[u8],
+        options: &GradientFillOption,
+    ) -> Option<FillResult> {
+        let (width, height) = (self.width, self.height);
+        let (data, before_paint) = self.journaled_paint();
+        let changes = gradient_fill_result(
+            data,
+            width,
+            height,
+            stop_positions,
+            stop_colors,
+            &[],
+            options,
+            before_paint,
+        )?;
+        Some(self.fill_result(&changes))
+    }
+
+    /// `gradientFill` limited to a `SelectionMask` of the buffer size, fading at partial
+    /// coverage.
+    #[wasm_bindgen(js_name = gradientFillWithSelection)]
+    pub fn gradient_fill_with_selection(
+        &mut self,
+        stop_positions: &[f32],
+        stop_colors: &[u8],
+        selection: &SelectionMask,
+        options: &GradientFillOption,
+    ) -> Option<FillResult> {
+        if selection.width != self.width || selection.height != self.height {
+            return None;
+        }
+        let (width, height) = (self.width, self.height);
+        let (data, before_paint) = self.journaled_paint();
+        let changes = gradient_fill_result(
+            data,
+            width,
+            height,
+            stop_positions,
+            stop_colors,
+            &selection.data,
+            options,
+            before_paint,
+        )?;
+        Some(self.fill_result(&changes))
+    }
+
+    /// Fill the whole buffer with `tile` repeated.
+    #[wasm_bindgen(js_name = patternFill)]
+    pub fn pattern_fill(
+        &mut self,
+        tile: &RgbaBuffer,
+        options: &PatternFillOption,
+    ) -> Option<FillResult> {
+        let (width, height) = (self.width, self.height);
+        let (data, before_paint) = self.journaled_paint();
+        let changes = pattern_fill_result(
+            data,
+            width,
+            height,
+            &tile.data,
+            tile.width,
+            tile.height,
+            &[],
+            options,
+            before_paint,
+        )?;
+        Some(self.fill_result(&changes))
+    }
+
+    /// `patternFill` limited to a `SelectionMask` of the buffer size, fading at partial
+    /// coverage.
+    #[wasm_bindgen(js_name = patternFillWithSelection)]
+    pub fn pattern_fill_with_selection(
+        &mut self,
+        tile: &RgbaBuffer,
+        selection: &SelectionMask,
+        options: &PatternFillOption,
+    ) -> Option<FillResult> {
+        if selection.width != self.width || selection.height != self.height {
+            return None;
+        }
+        let (width, height) = (self.width, self.height);
+        let (data, before_paint) = self.journaled_paint();
+        let changes = pattern_fill_result(
+            data,
+            width,
+            height,
+            &tile.data,
+            tile.width,
+            tile.height,
+            &selection.data,
+            options,
+            before_paint,
+        )?;
+        Some(self.fill_result(&changes))
+    }
+
+    /// Bucket fill with `tile` instead of a solid color. The region is found like
+    /// `floodFillWithOptions`.
+    #[wasm_bindgen(js_name = floodFillWithPattern)]
+    pub fn flood_fill_with_pattern(
+        &mut self,
+        start_x: u32,
+        start_y: u32,
+        tile: &RgbaBuffer,
+        fill_options: &FloodFillOption,
+        options: &PatternFillOption,
+    ) -> Option<FillResult> {
+        let (width, height) = (self.width, self.height);
+        let (data, before_paint) = self.journaled_paint();
+        let changes = pattern_flood_fill_result(
+            data,
+            width,
+            height,
+            start_x,
+            start_y,
+            &tile.data,
+            tile.width,
+            tile.height,
+            &[],
+            "none",
+            fill_options,
+            options,
+            before_paint,
+        )?;
+        Some(self.fill_result(&changes))
+    }
+
+    /// `floodFillWithPattern` limited by a `SelectionMask` of the buffer size.
+    #[wasm_bindgen(js_name = floodFillWithPatternAndSelection)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn flood_fill_with_pattern_and_selection(
+        &mut self,
+        start_x: u32,
+        start_y: u32,
+        tile: &RgbaBuffer,
+        selection: &SelectionMask,
+        limit_mode: &str,
+        fill_options: &FloodFillOption,
+        options: &PatternFillOption,
+    ) -> Option<FillResult> {
+        if selection.width != self.width || selection.height != self.height {
+            return None;
+        }
+        let (width, height) = (self.width, self.height);
+        let (data, before_paint) = self.journaled_paint();
+        let changes = pattern_flood_fill_result(
+            data,
+            width,
+            height,
+            start_x,
+            start_y,
+            &tile.data,
+            tile.width,
+            tile.height,
+            &selection.data,
+            limit_mode,
+            fill_options,
+            options,
+            before_paint,
+        )?;
+        Some(self.fill_result(&changes))
+    }
+}
+
+impl RgbaBuffer {
+    /// `FillResult` for a change map of the buffer size, with coverage if it was asked for.
+    pub(crate) fn fill_result(&self, changes: &[u8]) -> FillResult {
+        FillResult::from_changes(
+            self.width as usize,
+            self.height as usize,
+            changes,
+            self.fill_coverage,
         )
     }
 }
@@ -12,6 +12,7 @@ impl RgbaBuffer {
         if raw.len() != expected {
             return false;
         }
+        self.journal_discard();
         self.width = width;
         self.height = height;
         self.data.resize(expected, 0);
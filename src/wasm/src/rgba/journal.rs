@@ -0,0 +1,263 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    packing::tile::{encode_tile, join_tiles, tile_bounds, TileEntry},
+    rgba::RgbaBuffer,
+};
+use wasm_bindgen::prelude::*;
+
+/// Copy-on-write undo journal.
+///
+/// While a journal is active, every mutating method snapshots the tiles it is about to
+/// write the first time they are touched in the current transaction. Raw snapshots are
+/// encoded once they exceed the byte budget; if even the encoded snapshots do not fit,
+/// the transaction overflows and its writes are no longer tracked.
+pub(crate) struct Journal {
+    tile_size: u32,
+    budget_bytes: usize,
+    snapshots: BTreeMap<u32, Snapshot>,
+    used_bytes: usize,
+    overflowed: bool,
+}
+
+enum Snapshot {
+    // RGBA of the tile before its first write
+    Raw(Vec<u8>),
+    // `encode_tile` entry, made when the raw snapshots outgrew the budget
+    Encoded(Vec<u8>),
+}
+
+impl Snapshot {
+    fn len(&self) -> usize {
+        match self {
+            Snapshot::Raw(bytes) | Snapshot::Encoded(bytes) => bytes.len(),
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl RgbaBuffer {
+    /// Start journaling writes into tile snapshots.
+    /// Any transaction in progress is discarded.
+    #[wasm_bindgen(js_name = beginJournal)]
+    pub fn begin_journal(&mut self, tile_size: u32, budget_bytes: u32) -> bool {
+        if tile_size == 0 {
+            return false;
+        }
+        self.journal = Some(Journal {
+            tile_size,
+            budget_bytes: budget_bytes as usize,
+            snapshots: BTreeMap::new(),
+            used_bytes: 0,
+            overflowed: false,
+        });
+        true
+    }
+
+    /// Stop journaling and drop the transaction in progress.
+    #[wasm_bindgen(js_name = endJournal)]
+    pub fn end_journal(&mut self) {
+        self.journal = None;
+    }
+
+    #[wasm_bindgen(js_name = isJournaling)]
+    pub fn is_journaling(&self) -> bool {
+        self.journal.is_some()
+    }
+
+    /// Bytes currently held by tile snapshots, raw or encoded. Never above the budget.
+    #[wasm_bindgen(js_name = journalBytes)]
+    pub fn journal_bytes(&self) -> usize {
+        self.journal.as_ref().map_or(0, |j| j.used_bytes)
+    }
+
+    /// Whether the transaction in progress outgrew the budget. Its undo record is lost,
+    /// so `commitJournal` returns an empty array and the caller has to record the change
+    /// another way.
+    #[wasm_bindgen(js_name = journalOverflowed)]
+    pub fn journal_overflowed(&self) -> bool {
+        self.journal.as_ref().is_some_and(|j| j.overflowed)
+    }
+
+    /// Close the current transaction and return its undo record, then start a new one.
+    /// The record is a tile patch: apply it with `swapTiles`, which returns the redo record.
+    /// Returns an empty array if nothing changed or the transaction overflowed.
+    #[wasm_bindgen(js_name = commitJournal)]
+    pub fn commit_journal(&mut self) -> Vec<u8> {
+        let (width, height) = (self.width, self.height);
+        let Some(journal) = self.journal.as_mut() else {
+            return Vec::new();
+        };
+        let snapshots = std::mem::take(&mut journal.snapshots);
+        journal.used_bytes = 0;
+        if std::mem::take(&mut journal.overflowed) {
+            return Vec::new();
+        }
+
+        let tile_size = journal.tile_size;
+        let mut entries = Vec::with_capacity(snapshots.len());
+        for (index, snapshot) in snapshots {
+            match snapshot {
+                Snapshot::Raw(pixels) => {
+                    // Tiles written back to their old contents need no undo
+                    if pixels == tile_pixels(&self.data, width, height, tile_size, index) {
+                        continue;
+                    }
+                    let Some((_, _, w, h)) = tile_bounds(width, height, tile_size, index) else {
+                        continue;
+                    };
+                    entries.push(encode_tile(&TileEntry {
+                        index,
+                        width: w,
+                        height: h,
+                        pixels,
+                    }));
+                }
+                Snapshot::Encoded(entry) => entries.push(entry),
+            }
+        }
+        if entries.is_empty() {
+            return Vec::new();
+        }
+        join_tiles(tile_size, &entries)
+    }
+}
+
+impl RgbaBuffer {
+    /// Snapshot the tiles intersecting a rectangle that is about to be written.
+    pub(crate) fn journal_rect(&mut self, x: i32, y: i32, width: u32, height: u32) {
+        if let Some(journal) = self.journal.as_mut() {
+            journal.snapshot_rect(&self.data, self.width, self.height, x, y, width, height);
+        }
+    }
+
+    /// Snapshot the tiles holding a non-zero value of a width * height `coverage` map that
+    /// is about to be painted.
+    pub(crate) fn journal_coverage(&mut self, coverage: &[u8]) {
+        if let Some(journal) = self.journal.as_mut() {
+            journal.snapshot_coverage(&self.data, self.width, self.height, coverage);
+        }
+    }
+
+    /// The pixel data and a `before_paint` hook for the fill kernels that snapshots the
+    /// tiles they are about to paint.
+    pub(crate) fn journaled_paint(&mut self) -> (&mut [u8], impl FnOnce(&[u8], &[u8]) + '_) {
+        let (width, height) = (self.width, self.height);
+        let journal = &mut self.journal;
+        let before_paint = move |data: &[u8], coverage: &[u8]| {
+            if let Some(journal) = journal {
+                journal.snapshot_coverage(data, width, height, coverage);
+            }
+        };
+        (&mut self.data, before_paint)
+    }
+
+    /// Drop the transaction in progress, e.g. when the buffer size changes.
+    pub(crate) fn journal_discard(&mut self) {
+        if let Some(journal) = self.journal.as_mut() {
+            journal.snapshots.clear();
+            journal.used_bytes = 0;
+            journal.overflowed = false;
+        }
+    }
+}
+
+impl Journal {
+    #[allow(clippy::too_many_arguments)]
+    fn snapshot_rect(
+        &mut self,
+        data: &[u8],
+        buffer_width: u32,
+        buffer_height: u32,
+        x: i32,
+        y: i32,
+        width: u32,
+        height: u32,
+    ) {
+        let ts = self.tile_size as i64;
+        let left = (x as i64).max(0);
+        let top = (y as i64).max(0);
+        let right = (x as i64 + width as i64).min(buffer_width as i64);
+        let bottom = (y as i64 + height as i64).min(buffer_height as i64);
+        if left >= right || top >= bottom {
+            return;
+        }
+        let cols = buffer_width.div_ceil(self.tile_size);
+        for row in (top / ts)..=((bottom - 1) / ts) {
+            for col in (left / ts)..=((right - 1) / ts) {
+                let index = row as u32 * cols + col as u32;
+                self.snapshot(data, buffer_width, buffer_height, index);
+            }
+        }
+    }
+
+    fn snapshot_coverage(&mut self, data: &[u8], width: u32, height: u32, coverage: &[u8]) {
+        let (w, h, ts) = (width as usize, height as usize, self.tile_size as usize);
+        if self.overflowed || coverage.len() < w * h {
+            return;
+        }
+        let cols = w.div_ceil(ts);
+        for y in 0..h {
+            for col in 0..cols {
+                let index = ((y / ts) * cols + col) as u32;
+                if self.snapshots.contains_key(&index) {
+                    continue;
+                }
+                let row = &coverage[y * w + col * ts..y * w + (col * ts + ts).min(w)];
+                if row.iter().any(|c| *c != 0) {
+                    self.snapshot(data, width, height, index);
+                }
+            }
+        }
+    }
+
+    // Keep the current contents of a tile unless it already has a snapshot
+    fn snapshot(&mut self, data: &[u8], width: u32, height: u32, index: u32) {
+        if self.overflowed || self.snapshots.contains_key(&index) {
+            return;
+        }
+        let pixels = tile_pixels(data, width, height, self.tile_size, index);
+        self.used_bytes += pixels.len();
+        self.snapshots.insert(index, Snapshot::Raw(pixels));
+        if self.used_bytes > self.budget_bytes {
+            self.compact(width, height);
+        }
+    }
+
+    // Encode the raw snapshots; give up on the transaction if they still do not fit
+    fn compact(&mut self, width: u32, height: u32) {
+        for (&index, snapshot) in self.snapshots.iter_mut() {
+            let Snapshot::Raw(pixels) = snapshot else {
+                continue;
+            };
+            let Some((_, _, w, h)) = tile_bounds(width, height, self.tile_size, index) else {
+                continue;
+            };
+            *snapshot = Snapshot::Encoded(encode_tile(&TileEntry {
+                index,
+                width: w,
+                height: h,
+                pixels: std::mem::take(pixels),
+            }));
+        }
+        self.used_bytes = self.snapshots.values().map(Snapshot::len).sum();
+        if self.used_bytes > self.budget_bytes {
+            self.snapshots.clear();
+            self.used_bytes = 0;
+            self.overflowed = true;
+        }
+    }
+}
+
+fn tile_pixels(data: &[u8], width: u32, height: u32, tile_size: u32, index: u32) -> Vec<u8> {
+    let Some((x, y, w, h)) = tile_bounds(width, height, tile_size, index) else {
+        return Vec::new();
+    };
+    let row_bytes = w as usize * 4;
+    let mut pixels = Vec::with_capacity(row_bytes * h as usize);
+    for row in y..y + h {
+        let start = (row as usize * width as usize + x as usize) * 4;
+        pixels.extend_from_slice(&data[start..start + row_bytes]);
+    }
+    pixels
+}
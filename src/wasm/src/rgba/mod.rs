@@ -1,17 +1,23 @@
 use wasm_bindgen::prelude::*;
 
-mod base;
+pub(crate) mod base;
 mod blit_crop;
+mod draw;
 mod effects;
 mod export;
 mod fill;
 mod import;
+mod journal;
 mod rect;
 mod resize;
+mod tile;
+mod wand;
 
 #[wasm_bindgen]
 pub struct RgbaBuffer {
-    width: u32,
-    height: u32,
-    data: Vec<u8>,
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+    pub(crate) data: Vec<u8>,
+    pub(crate) journal: Option<journal::Journal>,
+    pub(crate) fill_coverage: bool,
 }
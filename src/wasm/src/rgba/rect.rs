@@ -76,6 +76,8 @@ impl RgbaBuffer {
             return false;
         }
 
+        self.journal_rect(rect_x, rect_y, rect_width, rect_height);
+
         let dst_w = self.width as i32;
         let dst_h = self.height as i32;
 
@@ -131,6 +133,7 @@ impl RgbaBuffer {
                 continue;
             }
 
+            self.journal_rect(x as i32, y as i32, 1, 1);
             let dst_index = (y * width + x) * 4;
             let color_index = i * 4;
             self.data[dst_index..dst_index + 4]
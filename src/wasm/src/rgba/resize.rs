@@ -41,6 +41,7 @@ impl RgbaBuffer {
             return;
         }
 
+        self.journal_discard();
         let pixel_stride = 4usize;
         let valid_dx_min = dest_origin_x - src_origin_x;
         let valid_dx_max = dest_origin_x - src_origin_x + old_w;
@@ -0,0 +1,56 @@
+use crate::{
+    packing::tile::{decode_tiles, encode_tiles, tile_bounds, TileEntry},
+    rgba::RgbaBuffer,
+};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+impl RgbaBuffer {
+    /// Capture the current contents of the given tiles (row-major linear indices) as a tile patch.
+    #[wasm_bindgen(js_name = captureTiles)]
+    pub fn capture_tiles(&self, tile_size: u32, tile_indices: &[u32]) -> Vec<u8> {
+        let tiles = self.read_tiles(tile_size, tile_indices);
+        encode_tiles(tile_size, &tiles)
+    }
+
+    /// Swap the tiles stored in a tile patch with the current contents.
+    /// Returns the tile patch to apply for the opposite direction (undo <-> redo),
+    /// or an empty array if the patch is invalid for this buffer.
+    #[wasm_bindgen(js_name = swapTiles)]
+    pub fn swap_tiles(&mut self, encoded: &[u8]) -> Vec<u8> {
+        let Some((tile_size, tiles)) = decode_tiles(encoded, self.width, self.height) else {
+            return Vec::new();
+        };
+        let indices: Vec<u32> = tiles.iter().map(|tile| tile.index).collect();
+        let previous = self.read_tiles(tile_size, &indices);
+
+        // undo/redo writes are not part of the journaled transaction
+        let journal = self.journal.take();
+        for tile in &tiles {
+            if let Some((x, y, w, h)) = tile_bounds(self.width, self.height, tile_size, tile.index)
+            {
+                self.write_rect(x as i32, y as i32, w, h, &tile.pixels);
+            }
+        }
+        self.journal = journal;
+
+        encode_tiles(tile_size, &previous)
+    }
+}
+
+impl RgbaBuffer {
+    fn read_tiles(&self, tile_size: u32, tile_indices: &[u32]) -> Vec<TileEntry> {
+        let mut tiles = Vec::with_capacity(tile_indices.len());
+        for &index in tile_indices {
+            if let Some((x, y, w, h)) = tile_bounds(self.width, self.height, tile_size, index) {
+                tiles.push(TileEntry {
+                    index,
+                    width: w,
+                    height: h,
+                    pixels: self.read_rect(x as i32, y as i32, w, h),
+                });
+            }
+        }
+        tiles
+    }
+}
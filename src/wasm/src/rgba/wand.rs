@@ -0,0 +1,117 @@
+use crate::{
+    fill::{
+        flood_fill::FloodFillOption,
+        magic_wand::{magic_wand, magic_wand_with_options},
+        Connectivity,
+    },
+    rgba::RgbaBuffer,
+    selection::{SelectionMask, SelectionOp},
+};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+impl RgbaBuffer {
+    /// Select pixels similar to the one at (x, y). The buffer is left untouched.
+    /// An out-of-bounds start point yields an empty selection. `connectivity` defaults to `Four`.
+    #[wasm_bindgen(js_name = magicWand)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn magic_wand(
+        &self,
+        x: u32,
+        y: u32,
+        tolerance: u8,
+        contiguous: bool,
+        antialias: bool,
+        connectivity: Option<Connectivity>,
+    ) -> SelectionMask {
+        let data = magic_wand(
+            &self.data,
+            self.width,
+            self.height,
+            x,
+            y,
+            tolerance,
+            contiguous,
+            antialias,
+            connectivity,
+        );
+        self.wand_selection(data)
+    }
+
+    /// `magicWand` with a configurable color metric; `options.threshold` is the tolerance.
+    #[wasm_bindgen(js_name = magicWandWithOptions)]
+    pub fn magic_wand_with_options(
+        &self,
+        x: u32,
+        y: u32,
+        contiguous: bool,
+        antialias: bool,
+        options: &FloodFillOption,
+    ) -> SelectionMask {
+        let data = magic_wand_with_options(
+            &self.data,
+            self.width,
+            self.height,
+            x,
+            y,
+            contiguous,
+            antialias,
+            options,
+        );
+        self.wand_selection(data)
+    }
+
+    /// Run the magic wand and combine its result into an existing selection of the buffer size.
+    /// `connectivity` defaults to `Four`.
+    #[wasm_bindgen(js_name = magicWandInto)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn magic_wand_into(
+        &self,
+        selection: &mut SelectionMask,
+        x: u32,
+        y: u32,
+        tolerance: u8,
+        contiguous: bool,
+        antialias: bool,
+        op: SelectionOp,
+        connectivity: Option<Connectivity>,
+    ) -> bool {
+        if selection.width != self.width || selection.height != self.height {
+            return false;
+        }
+        let picked = self.magic_wand(x, y, tolerance, contiguous, antialias, connectivity);
+        selection.apply(&picked, op)
+    }
+
+    #[wasm_bindgen(js_name = magicWandIntoWithOptions)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn magic_wand_into_with_options(
+        &self,
+        selection: &mut SelectionMask,
+        x: u32,
+        y: u32,
+        contiguous: bool,
+        antialias: bool,
+        options: &FloodFillOption,
+        op: SelectionOp,
+    ) -> bool {
+        if selection.width != self.width || selection.height != self.height {
+            return false;
+        }
+        let picked = self.magic_wand_with_options(x, y, contiguous, antialias, options);
+        selection.apply(&picked, op)
+    }
+}
+
+impl RgbaBuffer {
+    fn wand_selection(&self, data: Vec<u8>) -> SelectionMask {
+        if data.is_empty() {
+            return SelectionMask::new(self.width, self.height);
+        }
+        SelectionMask {
+            width: self.width,
+            height: self.height,
+            data,
+        }
+    }
+}
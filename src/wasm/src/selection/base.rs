@@ -0,0 +1,113 @@
+use crate::{rgba::base::mask_pixel_count, selection::SelectionMask};
+use js_sys::Uint8Array;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+impl SelectionMask {
+    /// Create an empty selection.
+    #[wasm_bindgen(constructor)]
+    pub fn new(width: u32, height: u32) -> SelectionMask {
+        SelectionMask {
+            width,
+            height,
+            data: vec![0u8; mask_pixel_count(width, height)],
+        }
+    }
+
+    /// Import coverage bytes (0-255) as they are.
+    #[wasm_bindgen(js_name = fromRaw)]
+    pub fn from_raw(width: u32, height: u32, buf: Vec<u8>) -> Result<SelectionMask, JsValue> {
+        if buf.len() != mask_pixel_count(width, height) {
+            return Err(JsValue::from_str(
+                "Buffer length does not match the specified width/height.",
+            ));
+        }
+        Ok(SelectionMask {
+            width,
+            height,
+            data: buf,
+        })
+    }
+
+    /// Import a flag mask where any non-zero value means fully selected.
+    #[wasm_bindgen(js_name = fromFlags)]
+    pub fn from_flags(width: u32, height: u32, buf: Vec<u8>) -> Result<SelectionMask, JsValue> {
+        let mut mask = SelectionMask::from_raw(width, height, buf)?;
+        for value in mask.data.iter_mut() {
+            if *value != 0 {
+                *value = 255;
+            }
+        }
+        Ok(mask)
+    }
+
+    #[wasm_bindgen(js_name = clone)]
+    pub fn slice(&self) -> SelectionMask {
+        SelectionMask {
+            width: self.width,
+            height: self.height,
+            data: self.data.clone(),
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Copy of the coverage bytes.
+    #[wasm_bindgen(js_name = toRaw)]
+    pub fn to_raw(&self) -> Vec<u8> {
+        self.data.clone()
+    }
+
+    /// Copy as a flag mask (1 = selected, 0 = not) for the slice-based APIs.
+    #[wasm_bindgen(js_name = toFlags)]
+    pub fn to_flags(&self) -> Vec<u8> {
+        self.data.iter().map(|value| (*value != 0) as u8).collect()
+    }
+
+    #[wasm_bindgen(js_name = data)]
+    pub fn data_view(&self) -> Uint8Array {
+        // Safety: view becomes invalid if wasm memory grows; callers must refresh after resize.
+        unsafe { Uint8Array::view(&self.data) }
+    }
+
+    #[wasm_bindgen(js_name = isInBounds)]
+    pub fn in_bounds(&self, x: u32, y: u32) -> bool {
+        x < self.width && y < self.height
+    }
+
+    /// Coverage at a pixel, 0 outside the mask.
+    pub fn get(&self, x: u32, y: u32) -> u8 {
+        if !self.in_bounds(x, y) {
+            return 0;
+        }
+        self.data[(y * self.width + x) as usize]
+    }
+
+    pub fn set(&mut self, x: u32, y: u32, coverage: u8) -> bool {
+        if !self.in_bounds(x, y) {
+            return false;
+        }
+        self.data[(y * self.width + x) as usize] = coverage;
+        true
+    }
+
+    /// True when no pixel has any coverage.
+    #[wasm_bindgen(js_name = isEmpty)]
+    pub fn is_empty(&self) -> bool {
+        self.data.iter().all(|value| *value == 0)
+    }
+
+    pub fn clear(&mut self) {
+        self.data.fill(0);
+    }
+
+    #[wasm_bindgen(js_name = selectAll)]
+    pub fn select_all(&mut self) {
+        self.data.fill(255);
+    }
+}
@@ -0,0 +1,51 @@
+use crate::selection::{SelectionMask, SelectionOp};
+use wasm_bindgen::prelude::*;
+
+// Boolean operations use fuzzy set semantics on coverage, so binary masks behave as sets.
+// Each returns false (and leaves the mask untouched) when the sizes differ.
+#[wasm_bindgen]
+impl SelectionMask {
+    pub fn union(&mut self, other: &SelectionMask) -> bool {
+        self.combine(other, |a, b| a.max(b))
+    }
+
+    pub fn subtract(&mut self, other: &SelectionMask) -> bool {
+        self.combine(other, |a, b| a.min(255 - b))
+    }
+
+    pub fn intersect(&mut self, other: &SelectionMask) -> bool {
+        self.combine(other, |a, b| a.min(b))
+    }
+
+    pub fn xor(&mut self, other: &SelectionMask) -> bool {
+        self.combine(other, |a, b| a.min(255 - b).max(b.min(255 - a)))
+    }
+
+    /// Combine `other` into this selection according to `op`.
+    pub fn apply(&mut self, other: &SelectionMask, op: SelectionOp) -> bool {
+        match op {
+            SelectionOp::Replace => self.combine(other, |_, b| b),
+            SelectionOp::Add => self.union(other),
+            SelectionOp::Subtract => self.subtract(other),
+            SelectionOp::Intersect => self.intersect(other),
+        }
+    }
+
+    pub fn invert(&mut self) {
+        for value in self.data.iter_mut() {
+            *value = 255 - *value;
+        }
+    }
+}
+
+impl SelectionMask {
+    fn combine(&mut self, other: &SelectionMask, op: impl Fn(u8, u8) -> u8) -> bool {
+        if self.width != other.width || self.height != other.height {
+            return false;
+        }
+        for (a, b) in self.data.iter_mut().zip(other.data.iter()) {
+            *a = op(*a, *b);
+        }
+        true
+    }
+}
@@ -0,0 +1,34 @@
+use crate::{
+    draw::{path::Path, path_mask, ShapeOption},
+    selection::{SelectionMask, SelectionOp},
+};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+impl SelectionMask {
+    /// Combine the fill and/or stroke of `path` into this selection according to `op`.
+    /// A selection set on `options` limits the path coverage first.
+    /// Returns false if that selection does not match this mask's size.
+    #[wasm_bindgen(js_name = drawPath)]
+    pub fn draw_path(&mut self, path: &Path, options: &ShapeOption, op: SelectionOp) -> bool {
+        let limit = options.selection();
+        if limit.is_some_and(|s| s.width != self.width || s.height != self.height) {
+            return false;
+        }
+        let mut shape = SelectionMask::new(self.width, self.height);
+        if let Some(mask) = path_mask(self.width, self.height, path, options) {
+            let width = self.width as usize;
+            for my in 0..mask.height {
+                let row = (mask.y as usize + my) * width + mask.x as usize;
+                for mx in 0..mask.width {
+                    let c = mask.data[my * mask.width + mx];
+                    shape.data[row + mx] = match limit {
+                        Some(limit) => ((c as u32 * limit.data[row + mx] as u32 + 127) / 255) as u8,
+                        None => c,
+                    };
+                }
+            }
+        }
+        self.apply(&shape, op)
+    }
+}
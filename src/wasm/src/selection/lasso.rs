@@ -0,0 +1,53 @@
+use crate::{
+    draw::{polygon_mask, FillRule, ShapeOption},
+    rgba::base::mask_pixel_count,
+    selection::SelectionMask,
+};
+use wasm_bindgen::prelude::*;
+
+/// Rasterize a lasso outline into a width * height coverage mask.
+///
+/// `points` is [x0, y0, x1, y1, ...] in pixel coordinates and is closed back to the
+/// first point; parts outside the bounds are clipped. With `antialias` the edge gets
+/// partial coverage, otherwise a pixel is selected when its center is inside.
+/// The result can be passed as-is to `sliceWithMask`, `cropWithMask` and
+/// `floodFillWithMask`.
+#[wasm_bindgen]
+pub fn lasso_mask(
+    width: u32,
+    height: u32,
+    points: &[f32],
+    rule: FillRule,
+    antialias: bool,
+) -> Vec<u8> {
+    let mut out = vec![0u8; mask_pixel_count(width, height)];
+    let mut options = ShapeOption::new(true, 0.0, antialias);
+    options.fill_rule = rule;
+    if let Some(mask) = polygon_mask(width, height, points, &options) {
+        for my in 0..mask.height {
+            let row = (mask.y as usize + my) * width as usize + mask.x as usize;
+            out[row..row + mask.width]
+                .copy_from_slice(&mask.data[my * mask.width..(my + 1) * mask.width]);
+        }
+    }
+    out
+}
+
+#[wasm_bindgen]
+impl SelectionMask {
+    /// Selection from a free-hand or polygon lasso, see `lasso_mask`.
+    #[wasm_bindgen(js_name = fromLasso)]
+    pub fn from_lasso(
+        width: u32,
+        height: u32,
+        points: &[f32],
+        rule: FillRule,
+        antialias: bool,
+    ) -> SelectionMask {
+        SelectionMask {
+            width,
+            height,
+            data: lasso_mask(width, height, points, rule, antialias),
+        }
+    }
+}
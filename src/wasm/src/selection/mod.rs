@@ -0,0 +1,30 @@
+use wasm_bindgen::prelude::*;
+
+mod base;
+mod boolean;
+mod draw;
+pub mod lasso;
+pub mod morphology;
+mod transform;
+
+/// Selection with 8-bit coverage per pixel (0 = unselected, 255 = fully selected).
+#[wasm_bindgen]
+pub struct SelectionMask {
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+    pub(crate) data: Vec<u8>,
+}
+
+/// How a new selection is combined with an existing one.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SelectionOp {
+    /// Discard the existing selection
+    Replace = 0,
+    /// Union
+    Add = 1,
+    /// Remove the new selection from the existing one
+    Subtract = 2,
+    /// Keep only the overlap
+    Intersect = 3,
+}
@@ -0,0 +1,375 @@
+use std::collections::VecDeque;
+
+use crate::{rgba::base::mask_is_valid, selection::SelectionMask};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MaskShape {
+    /// (2r+1) x (2r+1) square, works directly on soft coverage
+    Square = 0,
+    /// Euclidean disc of radius r, measured from pixels with coverage >= 128
+    Round = 1,
+}
+
+// Coverage at or above this counts as selected for the distance-based shapes
+const SELECTED_THRESHOLD: u8 = 128;
+
+/// Expand the selection by `radius` pixels.
+#[wasm_bindgen]
+pub fn grow_mask(mask: &mut [u8], width: u32, height: u32, radius: u32, shape: MaskShape) {
+    if radius == 0 || !mask_is_valid(width, height, mask) {
+        return;
+    }
+    let (w, h) = (width as usize, height as usize);
+    match shape {
+        MaskShape::Square => extremum_filter(&mut mask[..w * h], w, h, radius as usize, true),
+        MaskShape::Round => {
+            let dist = squared_distance(&mask[..w * h], w, h, |v| v >= SELECTED_THRESHOLD);
+            let limit = (radius as f32) * (radius as f32);
+            for (value, d) in mask.iter_mut().zip(dist.iter()) {
+                if *d <= limit {
+                    *value = 255;
+                }
+            }
+        }
+    }
+}
+
+/// Contract the selection by `radius` pixels. The canvas edge does not erode the selection.
+#[wasm_bindgen]
+pub fn shrink_mask(mask: &mut [u8], width: u32, height: u32, radius: u32, shape: MaskShape) {
+    if radius == 0 || !mask_is_valid(width, height, mask) {
+        return;
+    }
+    let (w, h) = (width as usize, height as usize);
+    match shape {
+        MaskShape::Square => extremum_filter(&mut mask[..w * h], w, h, radius as usize, false),
+        MaskShape::Round => {
+            let dist = squared_distance(&mask[..w * h], w, h, |v| v < SELECTED_THRESHOLD);
+            let limit = (radius as f32) * (radius as f32);
+            for (value, d) in mask.iter_mut().zip(dist.iter()) {
+                if *d <= limit {
+                    *value = 0;
+                }
+            }
+        }
+    }
+}
+
+/// Replace the selection with a ring of `size` pixels centered on its edge.
+#[wasm_bindgen]
+pub fn border_mask(mask: &mut [u8], width: u32, height: u32, size: u32, shape: MaskShape) {
+    if size == 0 || !mask_is_valid(width, height, mask) {
+        return;
+    }
+    let len = width as usize * height as usize;
+    let mut outer = mask[..len].to_vec();
+    grow_mask(&mut outer, width, height, size - size / 2, shape);
+    let inner = &mut mask[..len];
+    shrink_mask(inner, width, height, size / 2, shape);
+    for (value, o) in inner.iter_mut().zip(outer.iter()) {
+        *value = (*o).min(255 - *value);
+    }
+}
+
+/// Round off jagged edges: each pixel is selected when most of its (2r+1)^2 neighborhood is.
+#[wasm_bindgen]
+pub fn smooth_mask(mask: &mut [u8], width: u32, height: u32, radius: u32) {
+    if radius == 0 || !mask_is_valid(width, height, mask) {
+        return;
+    }
+    let (w, h) = (width as usize, height as usize);
+    let r = radius as usize;
+
+    // Summed-area table of coverage
+    let mut sums = vec![0u64; (w + 1) * (h + 1)];
+    for y in 0..h {
+        let mut row = 0u64;
+        for x in 0..w {
+            row += mask[y * w + x] as u64;
+            sums[(y + 1) * (w + 1) + x + 1] = sums[y * (w + 1) + x + 1] + row;
+        }
+    }
+
+    for y in 0..h {
+        let (y0, y1) = (y.saturating_sub(r), (y + r + 1).min(h));
+        for x in 0..w {
+            let (x0, x1) = (x.saturating_sub(r), (x + r + 1).min(w));
+            let total = sums[y1 * (w + 1) + x1] + sums[y0 * (w + 1) + x0]
+                - sums[y0 * (w + 1) + x1]
+                - sums[y1 * (w + 1) + x0];
+            let area = ((x1 - x0) * (y1 - y0)) as u64;
+            mask[y * w + x] = if total * 2 >= area * 255 { 255 } else { 0 };
+        }
+    }
+}
+
+/// Soften the selection edge with a Gaussian falloff. `radius` spans two standard deviations.
+#[wasm_bindgen]
+pub fn feather_mask(mask: &mut [u8], width: u32, height: u32, radius: f32) {
+    if radius <= 0.0 || !mask_is_valid(width, height, mask) {
+        return;
+    }
+    let (w, h) = (width as usize, height as usize);
+    let sigma = radius / 2.0;
+    let reach = (sigma * 3.0).ceil() as i64;
+    let kernel: Vec<f32> = (-reach..=reach)
+        .map(|i| (-((i * i) as f32) / (2.0 * sigma * sigma)).exp())
+        .collect();
+
+    let mut temp = vec![0f32; w * h];
+    for y in 0..h {
+        for x in 0..w {
+            temp[y * w + x] = convolve(&kernel, reach, x, w, |sx| mask[y * w + sx] as f32);
+        }
+    }
+    for y in 0..h {
+        for x in 0..w {
+            let value = convolve(&kernel, reach, y, h, |sy| temp[sy * w + x]);
+            mask[y * w + x] = value.round().clamp(0.0, 255.0) as u8;
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl SelectionMask {
+    pub fn grow(&mut self, radius: u32, shape: MaskShape) {
+        grow_mask(&mut self.data, self.width, self.height, radius, shape);
+    }
+
+    pub fn shrink(&mut self, radius: u32, shape: MaskShape) {
+        shrink_mask(&mut self.data, self.width, self.height, radius, shape);
+    }
+
+    pub fn border(&mut self, size: u32, shape: MaskShape) {
+        border_mask(&mut self.data, self.width, self.height, size, shape);
+    }
+
+    pub fn smooth(&mut self, radius: u32) {
+        smooth_mask(&mut self.data, self.width, self.height, radius);
+    }
+
+    pub fn feather(&mut self, radius: f32) {
+        feather_mask(&mut self.data, self.width, self.height, radius);
+    }
+}
+
+// Separable running max (dilate) or min (erode) over a (2r+1) window, clipped at the edges
+fn extremum_filter(mask: &mut [u8], w: usize, h: usize, r: usize, take_max: bool) {
+    let mut line = Vec::with_capacity(w.max(h));
+    let mut out = Vec::with_capacity(w.max(h));
+    for y in 0..h {
+        line.clear();
+        line.extend_from_slice(&mask[y * w..(y + 1) * w]);
+        extremum_1d(&line, &mut out, r, take_max);
+        mask[y * w..(y + 1) * w].copy_from_slice(&out);
+    }
+    for x in 0..w {
+        line.clear();
+        line.extend((0..h).map(|y| mask[y * w + x]));
+        extremum_1d(&line, &mut out, r, take_max);
+        for (y, value) in out.iter().enumerate() {
+            mask[y * w + x] = *value;
+        }
+    }
+}
+
+// Monotonic deque sliding window
+fn extremum_1d(line: &[u8], out: &mut Vec<u8>, r: usize, take_max: bool) {
+    let beats = |a: u8, b: u8| if take_max { a >= b } else { a <= b };
+    let n = line.len();
+    let mut window: VecDeque<usize> = VecDeque::new();
+    out.clear();
+    let mut next = 0;
+    for i in 0..n {
+        while next < n && next <= i + r {
+            while window.back().is_some_and(|&j| beats(line[next], line[j])) {
+                window.pop_back();
+            }
+            window.push_back(next);
+            next += 1;
+        }
+        while window.front().is_some_and(|&j| j + r < i) {
+            window.pop_front();
+        }
+        out.push(line[window[0]]);
+    }
+}
+
+// Squared Euclidean distance from each pixel to the nearest pixel matching `is_seed`
+// (Felzenszwalb & Huttenlocher). Pixels with no seed get f32::INFINITY.
+pub(crate) fn squared_distance(
+    mask: &[u8],
+    w: usize,
+    h: usize,
+    is_seed: impl Fn(u8) -> bool,
+) -> Vec<f32> {
+    let mut grid: Vec<f32> = mask
+        .iter()
+        .map(|v| if is_seed(*v) { 0.0 } else { f32::INFINITY })
+        .collect();
+    let mut f = Vec::with_capacity(w.max(h));
+    let mut d = Vec::with_capacity(w.max(h));
+    for x in 0..w {
+        f.clear();
+        f.extend((0..h).map(|y| grid[y * w + x]));
+        distance_1d(&f, &mut d);
+        for (y, value) in d.iter().enumerate() {
+            grid[y * w + x] = *value;
+        }
+    }
+    for y in 0..h {
+        f.clear();
+        f.extend_from_slice(&grid[y * w..(y + 1) * w]);
+        distance_1d(&f, &mut d);
+        grid[y * w..(y + 1) * w].copy_from_slice(&d);
+    }
+    grid
+}
+
+fn distance_1d(f: &[f32], d: &mut Vec<f32>) {
+    let n = f.len();
+    d.clear();
+    // Lower envelope of the parabolas rooted at finite samples
+    let mut v: Vec<usize> = Vec::with_capacity(n);
+    let mut z: Vec<f32> = Vec::with_capacity(n + 1);
+    for q in 0..n {
+        if !f[q].is_finite() {
+            continue;
+        }
+        loop {
+            let Some(&p) = v.last() else {
+                v.push(q);
+                z.push(f32::NEG_INFINITY);
+                break;
+            };
+            let s = ((f[q] + (q * q) as f32) - (f[p] + (p * p) as f32)) / (2.0 * (q - p) as f32);
+            if s <= *z.last().unwrap_or(&f32::NEG_INFINITY) {
+                v.pop();
+                z.pop();
+                continue;
+            }
+            v.push(q);
+            z.push(s);
+            break;
+        }
+    }
+    if v.is_empty() {
+        d.resize(n, f32::INFINITY);
+        return;
+    }
+    let mut k = 0;
+    for q in 0..n {
+        while k + 1 < v.len() && z[k + 1] < q as f32 {
+            k += 1;
+        }
+        let dq = q as f32 - v[k] as f32;
+        d.push(dq * dq + f[v[k]]);
+    }
+}
+
+fn convolve(
+    kernel: &[f32],
+    reach: i64,
+    at: usize,
+    len: usize,
+    sample: impl Fn(usize) -> f32,
+) -> f32 {
+    let mut sum = 0.0;
+    let mut weight = 0.0;
+    for (k, kw) in kernel.iter().enumerate() {
+        let s = at as i64 + k as i64 - reach;
+        if s < 0 || s >= len as i64 {
+            continue;
+        }
+        sum += sample(s as usize) * kw;
+        weight += kw;
+    }
+    if weight > 0.0 {
+        sum / weight
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dot(size: u32) -> Vec<u8> {
+        let mut mask = vec![0u8; (size * size) as usize];
+        let center = size / 2;
+        mask[(center * size + center) as usize] = 255;
+        mask
+    }
+
+    fn selected(mask: &[u8]) -> usize {
+        mask.iter().filter(|v| **v >= SELECTED_THRESHOLD).count()
+    }
+
+    #[test]
+    fn round_grow_cuts_the_square_corners() {
+        let mut square = dot(11);
+        grow_mask(&mut square, 11, 11, 3, MaskShape::Square);
+        assert_eq!(selected(&square), 7 * 7);
+
+        let mut round = dot(11);
+        grow_mask(&mut round, 11, 11, 3, MaskShape::Round);
+        // Offsets with dx^2 + dy^2 <= 9
+        assert_eq!(selected(&round), 29);
+        assert_eq!(round[5 * 11 + 8], 255);
+        assert_eq!(round[8 * 11 + 5], 255);
+        assert_eq!(round[7 * 11 + 7], 255);
+        assert_eq!(round[8 * 11 + 8], 0);
+        assert_eq!(square[8 * 11 + 8], 255);
+    }
+
+    #[test]
+    fn shrink_keeps_pixels_on_the_canvas_edge() {
+        for shape in [MaskShape::Square, MaskShape::Round] {
+            let mut full = vec![255u8; 64];
+            shrink_mask(&mut full, 8, 8, 2, shape);
+            assert!(full.iter().all(|v| *v == 255), "{shape:?}");
+
+            // A 4x4 block in the corner only loses its inner side
+            let mut corner = vec![0u8; 64];
+            for y in 0..4 {
+                corner[y * 8..y * 8 + 4].fill(255);
+            }
+            shrink_mask(&mut corner, 8, 8, 1, shape);
+            for y in 0..8 {
+                for x in 0..8 {
+                    let expected = if x < 3 && y < 3 { 255 } else { 0 };
+                    assert_eq!(corner[y * 8 + x], expected, "{shape:?} ({x}, {y})");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn border_is_size_pixels_wide() {
+        for shape in [MaskShape::Square, MaskShape::Round] {
+            for size in 1..=5u32 {
+                // Left half selected, so the edge runs between x = 5 and x = 6
+                let mut mask = vec![0u8; 12 * 4];
+                for y in 0..4 {
+                    mask[y * 12..y * 12 + 6].fill(255);
+                }
+                border_mask(&mut mask, 12, 4, size, shape);
+                let start = 6 - size / 2;
+                let end = 6 + (size - size / 2);
+                for y in 0..4 {
+                    for x in 0..12u32 {
+                        let expected = if (start..end).contains(&x) { 255 } else { 0 };
+                        assert_eq!(
+                            mask[y * 12 + x as usize],
+                            expected,
+                            "{shape:?} size {size} ({x}, {y})"
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
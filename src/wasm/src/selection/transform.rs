@@ -0,0 +1,55 @@
+use crate::selection::SelectionMask;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+impl SelectionMask {
+    /// Bounding box of the covered pixels as [x, y, width, height].
+    /// Returns an empty array if nothing is selected.
+    #[wasm_bindgen(js_name = boundBox)]
+    pub fn bound_box(&self) -> Vec<u32> {
+        let width = self.width as usize;
+        let (mut min_x, mut min_y, mut max_x, mut max_y) = (usize::MAX, usize::MAX, 0, 0);
+        for (y, row) in self.data.chunks_exact(width.max(1)).enumerate() {
+            let Some(first) = row.iter().position(|value| *value != 0) else {
+                continue;
+            };
+            let last = row.iter().rposition(|value| *value != 0).unwrap_or(first);
+            min_x = min_x.min(first);
+            max_x = max_x.max(last);
+            min_y = min_y.min(y);
+            max_y = y;
+        }
+        if min_x == usize::MAX {
+            return Vec::new();
+        }
+        vec![
+            min_x as u32,
+            min_y as u32,
+            (max_x - min_x + 1) as u32,
+            (max_y - min_y + 1) as u32,
+        ]
+    }
+
+    /// Move the selection by whole pixels. Coverage shifted past the edges is dropped.
+    pub fn translate(&mut self, dx: i32, dy: i32) {
+        let width = self.width as i64;
+        let height = self.height as i64;
+        let mut moved = vec![0u8; self.data.len()];
+        for y in 0..height {
+            let sy = y - dy as i64;
+            if sy < 0 || sy >= height {
+                continue;
+            }
+            let x0 = (dx as i64).clamp(0, width);
+            let x1 = (width + dx as i64).clamp(0, width);
+            if x0 >= x1 {
+                continue;
+            }
+            let dst = (y * width + x0) as usize;
+            let src = (sy * width + x0 - dx as i64) as usize;
+            let len = (x1 - x0) as usize;
+            moved[dst..dst + len].copy_from_slice(&self.data[src..src + len]);
+        }
+        self.data = moved;
+    }
+}
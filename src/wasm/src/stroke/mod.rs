@@ -0,0 +1,600 @@
+use std::collections::HashMap;
+
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    fill::coverage::{blend_coverage, CoverageMode},
+    rgba::RgbaBuffer,
+};
+
+pub mod stamp;
+
+use stamp::BrushStamp;
+
+#[wasm_bindgen]
+#[derive(Clone, Copy)]
+pub struct StrokeOption {
+    /// Distance between dabs as a fraction of the dab size (0 = a dab on every pixel)
+    pub spacing: f32,
+    /// Pressure scales the dab size
+    pub pressure_size: bool,
+    /// Pressure scales the dab opacity
+    pub pressure_opacity: bool,
+    /// Drop the corner pixel of every L-shaped step so 1px lines stay one pixel thin.
+    /// Only affects 1x1 dabs.
+    pub pixel_perfect: bool,
+}
+
+#[wasm_bindgen]
+impl StrokeOption {
+    /// `pixel_perfect` starts disabled and is set as a property.
+    #[wasm_bindgen(constructor)]
+    pub fn new(spacing: f32, pressure_size: bool, pressure_opacity: bool) -> StrokeOption {
+        StrokeOption {
+            spacing: spacing.max(0.0),
+            pressure_size,
+            pressure_opacity,
+            pixel_perfect: false,
+        }
+    }
+}
+
+/// One brush stroke drawn into an `RgbaBuffer` point by point.
+///
+/// Dabs are placed along the pixel line between consecutive points with pressure, size
+/// and opacity interpolated. Within a stroke each pixel keeps the highest coverage it
+/// received, so overlapping dabs do not build up opacity. The stroke remembers the color
+/// every touched pixel had before it started, which is the undo diff of the stroke.
+#[wasm_bindgen]
+pub struct Stroke {
+    stamp: BrushStamp,
+    color: [u8; 4],
+    options: StrokeOption,
+    last: Option<StrokePoint>,
+    // distance walked since the last dab
+    travelled: f32,
+    // stamp resized for the most recent dab scale
+    scaled: Option<(f32, BrushStamp)>,
+    // last dabs that may still turn out to be an L corner (pixel-perfect mode)
+    recent: Vec<RecentDab>,
+    diff: StrokeDiff,
+}
+
+#[derive(Clone, Copy)]
+struct StrokePoint {
+    x: i64,
+    y: i64,
+    pressure: f32,
+    size: f32,
+    opacity: f32,
+}
+
+#[derive(Clone, Copy)]
+struct RecentDab {
+    x: i64,
+    y: i64,
+    change: PixelChange,
+}
+
+// What a dab did to the stroke coverage of a pixel
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum PixelChange {
+    Unchanged,
+    // first touched by the stroke
+    Created,
+    // already touched, with the coverage it had before
+    Raised(u8),
+}
+
+#[wasm_bindgen]
+impl Stroke {
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        stamp: &BrushStamp,
+        color_r: u8,
+        color_g: u8,
+        color_b: u8,
+        color_a: u8,
+        options: &StrokeOption,
+    ) -> Stroke {
+        Stroke {
+            stamp: stamp.clone(),
+            color: [color_r, color_g, color_b, color_a],
+            options: *options,
+            last: None,
+            travelled: 0.0,
+            scaled: None,
+            recent: Vec::new(),
+            diff: StrokeDiff::default(),
+        }
+    }
+
+    /// Draw up to the next input point.
+    ///
+    /// `size` scales the stamp (1 = as given) and `opacity` (0-1) scales its coverage;
+    /// `pressure` (0-1) scales either according to the options.
+    /// Returns the bounds written by this call as [x, y, width, height], or an empty
+    /// array if nothing changed.
+    #[wasm_bindgen(js_name = addPoint)]
+    pub fn add_point(
+        &mut self,
+        buffer: &mut RgbaBuffer,
+        x: f32,
+        y: f32,
+        pressure: f32,
+        size: f32,
+        opacity: f32,
+    ) -> Vec<u32> {
+        let point = StrokePoint {
+            x: x.floor() as i64,
+            y: y.floor() as i64,
+            pressure: pressure.clamp(0.0, 1.0),
+            size,
+            opacity: opacity.clamp(0.0, 1.0),
+        };
+        let mut dirty = None;
+        match self.last {
+            None => self.place_dab(buffer, point, &mut dirty),
+            Some(from) => {
+                let line = line_pixels(from.x, from.y, point.x, point.y);
+                let steps = (line.len() - 1) as f32;
+                for (k, pair) in line.windows(2).enumerate() {
+                    let ((px, py), (x, y)) = (pair[0], pair[1]);
+                    self.travelled += if px != x && py != y {
+                        std::f32::consts::SQRT_2
+                    } else {
+                        1.0
+                    };
+                    let t = (k + 1) as f32 / steps;
+                    let p = StrokePoint {
+                        x,
+                        y,
+                        pressure: lerp(from.pressure, point.pressure, t),
+                        size: lerp(from.size, point.size, t),
+                        opacity: lerp(from.opacity, point.opacity, t),
+                    };
+                    if self.travelled + 1e-4 >= self.options.spacing * self.diameter(&p) {
+                        self.place_dab(buffer, p, &mut dirty);
+                    }
+                }
+            }
+        }
+        self.last = Some(point);
+        bounds_to_rect(dirty)
+    }
+
+    /// Number of pixels the stroke has touched.
+    #[wasm_bindgen(js_name = changedPixels)]
+    pub fn changed_pixels(&self) -> u32 {
+        self.diff.pixels.len() as u32
+    }
+
+    /// Flattened [x, y, color] triples of every touched pixel with the color it had before
+    /// the stroke, packed as (a << 24) | (r << 16) | (g << 8) | b like `rgbaToPackedU32`.
+    pub fn pixels(&self) -> Vec<u32> {
+        let mut out = Vec::with_capacity(self.diff.pixels.len() * 3);
+        for pixel in &self.diff.pixels {
+            let [r, g, b, a] = pixel.before;
+            out.push(pixel.x);
+            out.push(pixel.y);
+            out.push(((a as u32) << 24) | ((r as u32) << 16) | ((g as u32) << 8) | b as u32);
+        }
+        out
+    }
+
+    /// Bounding box of the touched pixels as [x, y, width, height] (all zero if none).
+    #[wasm_bindgen(js_name = boundBox)]
+    pub fn bound_box(&self) -> Vec<u32> {
+        let mut bounds = None;
+        for pixel in &self.diff.pixels {
+            extend_bounds(&mut bounds, pixel.x, pixel.y);
+        }
+        match bounds {
+            Some(_) => bounds_to_rect(bounds),
+            None => vec![0; 4],
+        }
+    }
+}
+
+impl Stroke {
+    fn scale(&self, point: &StrokePoint) -> f32 {
+        if self.options.pressure_size {
+            point.size * point.pressure
+        } else {
+            point.size
+        }
+    }
+
+    fn diameter(&self, point: &StrokePoint) -> f32 {
+        self.stamp.width.max(self.stamp.height) as f32 * self.scale(point)
+    }
+
+    fn place_dab(
+        &mut self,
+        buffer: &mut RgbaBuffer,
+        point: StrokePoint,
+        dirty: &mut Option<[u32; 4]>,
+    ) {
+        let change = self.dab(buffer, point, dirty);
+        let single_pixel = self
+            .scaled
+            .as_ref()
+            .is_some_and(|(_, stamp)| stamp.width == 1 && stamp.height == 1);
+        if !self.options.pixel_perfect || !single_pixel {
+            self.recent.clear();
+            return;
+        }
+
+        self.recent.push(RecentDab {
+            x: point.x,
+            y: point.y,
+            change,
+        });
+        if self.recent.len() < 3 {
+            return;
+        }
+        let (a, b, c) = (self.recent[0], self.recent[1], self.recent[2]);
+        if !is_corner(a, b, c) {
+            self.recent.remove(0);
+            return;
+        }
+        // Undo the corner dab: drop a pixel it added, or lower one it raised back to the
+        // coverage the stroke had given it before
+        if let Some((_, stamp)) = &self.scaled {
+            let (x, y) = (b.x + stamp.offset_x as i64, b.y + stamp.offset_y as i64);
+            let undone = match b.change {
+                PixelChange::Unchanged => false,
+                PixelChange::Created => self.diff.retract(buffer, x as u32, y as u32),
+                PixelChange::Raised(coverage) => self
+                    .diff
+                    .lower(buffer, x as u32, y as u32, self.color, coverage),
+            };
+            if undone {
+                extend_bounds(dirty, x as u32, y as u32);
+            }
+        }
+        self.recent.remove(1);
+    }
+
+    // Returns the change to the last pixel the dab covered (the only one for 1x1 dabs)
+    fn dab(
+        &mut self,
+        buffer: &mut RgbaBuffer,
+        point: StrokePoint,
+        dirty: &mut Option<[u32; 4]>,
+    ) -> PixelChange {
+        self.travelled = 0.0;
+        let scale = self.scale(&point);
+        let alpha = if self.options.pressure_opacity {
+            point.opacity * point.pressure
+        } else {
+            point.opacity
+        };
+        if scale <= 0.0 || alpha <= 0.0 {
+            return PixelChange::Unchanged;
+        }
+        if self.scaled.as_ref().is_none_or(|(s, _)| *s != scale) {
+            self.scaled = Some((scale, self.stamp.scaled(scale)));
+        }
+        let Some((_, stamp)) = &self.scaled else {
+            return PixelChange::Unchanged;
+        };
+
+        let left = point.x + stamp.offset_x as i64;
+        let top = point.y + stamp.offset_y as i64;
+        let x0 = left.max(0);
+        let y0 = top.max(0);
+        let x1 = (left + stamp.width as i64).min(buffer.width as i64);
+        let y1 = (top + stamp.height as i64).min(buffer.height as i64);
+        if x0 >= x1 || y0 >= y1 {
+            return PixelChange::Unchanged;
+        }
+        let mut change = PixelChange::Unchanged;
+        buffer.journal_rect(x0 as i32, y0 as i32, (x1 - x0) as u32, (y1 - y0) as u32);
+        for y in y0..y1 {
+            let row = (y - top) * stamp.width as i64;
+            for x in x0..x1 {
+                let value = stamp.data[(row + x - left) as usize];
+                let coverage = (value as f32 * alpha).round() as u8;
+                if coverage == 0 {
+                    continue;
+                }
+                change = self
+                    .diff
+                    .apply(buffer, x as u32, y as u32, self.color, coverage);
+                if change != PixelChange::Unchanged {
+                    extend_bounds(dirty, x as u32, y as u32);
+                }
+            }
+        }
+        change
+    }
+}
+
+// Pixels touched by the stroke and the color each had before it
+#[derive(Default)]
+struct StrokeDiff {
+    pixels: Vec<StrokePixel>,
+    index: HashMap<(u32, u32), usize>,
+}
+
+struct StrokePixel {
+    x: u32,
+    y: u32,
+    before: [u8; 4],
+    coverage: u8,
+}
+
+impl StrokeDiff {
+    // Raise the pixel's stroke coverage and repaint it from its original color.
+    // Returns `Unchanged` if it already had at least this coverage.
+    fn apply(
+        &mut self,
+        buffer: &mut RgbaBuffer,
+        x: u32,
+        y: u32,
+        color: [u8; 4],
+        coverage: u8,
+    ) -> PixelChange {
+        let i = (y as usize * buffer.width as usize + x as usize) * 4;
+        let (before, change) = match self.index.get(&(x, y)) {
+            Some(&k) => {
+                let pixel = &mut self.pixels[k];
+                if coverage <= pixel.coverage {
+                    return PixelChange::Unchanged;
+                }
+                let previous = pixel.coverage;
+                pixel.coverage = coverage;
+                (pixel.before, PixelChange::Raised(previous))
+            }
+            None => {
+                let before = [
+                    buffer.data[i],
+                    buffer.data[i + 1],
+                    buffer.data[i + 2],
+                    buffer.data[i + 3],
+                ];
+                self.index.insert((x, y), self.pixels.len());
+                self.pixels.push(StrokePixel {
+                    x,
+                    y,
+                    before,
+                    coverage,
+                });
+                (before, PixelChange::Created)
+            }
+        };
+        let painted = blend_coverage(&before, color, coverage, CoverageMode::Premultiplied);
+        buffer.data[i..i + 4].copy_from_slice(&painted);
+        change
+    }
+
+    // Set a touched pixel back to a lower stroke coverage and repaint it
+    fn lower(
+        &mut self,
+        buffer: &mut RgbaBuffer,
+        x: u32,
+        y: u32,
+        color: [u8; 4],
+        coverage: u8,
+    ) -> bool {
+        let Some(&k) = self.index.get(&(x, y)) else {
+            return false;
+        };
+        let pixel = &mut self.pixels[k];
+        pixel.coverage = coverage;
+        let painted = blend_coverage(&pixel.before, color, coverage, CoverageMode::Premultiplied);
+        let i = (y as usize * buffer.width as usize + x as usize) * 4;
+        buffer.data[i..i + 4].copy_from_slice(&painted);
+        true
+    }
+
+    // Restore a pixel to its color before the stroke and forget it
+    fn retract(&mut self, buffer: &mut RgbaBuffer, x: u32, y: u32) -> bool {
+        let Some(k) = self.index.remove(&(x, y)) else {
+            return false;
+        };
+        let pixel = self.pixels.swap_remove(k);
+        if let Some(moved) = self.pixels.get(k) {
+            self.index.insert((moved.x, moved.y), k);
+        }
+        let i = (y as usize * buffer.width as usize + x as usize) * 4;
+        buffer.data[i..i + 4].copy_from_slice(&pixel.before);
+        true
+    }
+}
+
+// `b` is the corner of an L-shaped step from `a` to `c`
+fn is_corner(a: RecentDab, b: RecentDab, c: RecentDab) -> bool {
+    let unit_step = |p: RecentDab, q: RecentDab| (p.x - q.x).abs() + (p.y - q.y).abs() == 1;
+    unit_step(a, b) && unit_step(b, c) && (a.x - c.x).abs() == 1 && (a.y - c.y).abs() == 1
+}
+
+/// Bresenham line from (x0, y0) to (x1, y1), both ends included.
+pub(crate) fn line_pixels(x0: i64, y0: i64, x1: i64, y1: i64) -> Vec<(i64, i64)> {
+    let (dx, dy) = ((x1 - x0).abs(), (y1 - y0).abs());
+    let (sx, sy) = ((x1 - x0).signum(), (y1 - y0).signum());
+    let mut err = dx - dy;
+    let (mut x, mut y) = (x0, y0);
+    let mut out = Vec::with_capacity((dx.max(dy) + 1) as usize);
+    loop {
+        out.push((x, y));
+        if x == x1 && y == y1 {
+            break;
+        }
+        let e2 = err * 2;
+        if e2 > -dy {
+            err -= dy;
+            x += sx;
+        }
+        if e2 < dx {
+            err += dx;
+            y += sy;
+        }
+    }
+    out
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+// Bounds as [min_x, min_y, max_x, max_y]
+fn extend_bounds(bounds: &mut Option<[u32; 4]>, x: u32, y: u32) {
+    *bounds = Some(match *bounds {
+        None => [x, y, x, y],
+        Some([x0, y0, x1, y1]) => [x0.min(x), y0.min(y), x1.max(x), y1.max(y)],
+    });
+}
+
+fn bounds_to_rect(bounds: Option<[u32; 4]>) -> Vec<u32> {
+    match bounds {
+        Some([x0, y0, x1, y1]) => vec![x0, y0, x1 - x0 + 1, y1 - y0 + 1],
+        None => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: [u8; 4] = [255, 0, 0, 255];
+
+    fn red_stroke(stamp: &BrushStamp, options: &StrokeOption) -> Stroke {
+        Stroke::new(stamp, RED[0], RED[1], RED[2], RED[3], options)
+    }
+
+    fn pixel(buffer: &RgbaBuffer, x: u32, y: u32) -> [u8; 4] {
+        let i = ((y * buffer.width + x) * 4) as usize;
+        [
+            buffer.data[i],
+            buffer.data[i + 1],
+            buffer.data[i + 2],
+            buffer.data[i + 3],
+        ]
+    }
+
+    fn pixel_perfect() -> (BrushStamp, StrokeOption) {
+        let stamp = BrushStamp::new(vec![255], 1, 1, 0, 0).unwrap();
+        let mut options = StrokeOption::new(0.0, false, false);
+        options.pixel_perfect = true;
+        (stamp, options)
+    }
+
+    #[test]
+    fn pixel_perfect_removes_the_l_corner() {
+        let (stamp, options) = pixel_perfect();
+        let mut buffer = RgbaBuffer::new(4, 4);
+        let mut stroke = red_stroke(&stamp, &options);
+        stroke.add_point(&mut buffer, 0.0, 0.0, 1.0, 1.0, 1.0);
+        stroke.add_point(&mut buffer, 1.0, 0.0, 1.0, 1.0, 1.0);
+        let dirty = stroke.add_point(&mut buffer, 1.0, 1.0, 1.0, 1.0, 1.0);
+
+        assert_eq!(pixel(&buffer, 0, 0), RED);
+        assert_eq!(pixel(&buffer, 1, 0), [0, 0, 0, 0]);
+        assert_eq!(pixel(&buffer, 1, 1), RED);
+        // The retracted pixel is reported as written and leaves the diff
+        assert_eq!(dirty, [1, 0, 1, 2]);
+        assert_eq!(stroke.changed_pixels(), 2);
+        let pixels = stroke.pixels();
+        assert!(pixels.chunks_exact(3).all(|p| (p[0], p[1]) != (1, 0)));
+
+        // Without pixel-perfect mode the corner stays
+        let mut buffer = RgbaBuffer::new(4, 4);
+        let mut stroke = red_stroke(&stamp, &StrokeOption::new(0.0, false, false));
+        for (x, y) in [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0)] {
+            stroke.add_point(&mut buffer, x, y, 1.0, 1.0, 1.0);
+        }
+        assert_eq!(pixel(&buffer, 1, 0), RED);
+        assert_eq!(stroke.changed_pixels(), 3);
+    }
+
+    #[test]
+    fn pixel_perfect_lowers_a_raised_corner_back() {
+        let (stamp, options) = pixel_perfect();
+        let mut buffer = RgbaBuffer::new(4, 4);
+        let mut stroke = red_stroke(&stamp, &options);
+        // (1, 0) is first painted at half opacity, then raised by the corner dab
+        stroke.add_point(&mut buffer, 1.0, 0.0, 1.0, 1.0, 0.5);
+        stroke.add_point(&mut buffer, 0.0, 0.0, 1.0, 1.0, 1.0);
+        stroke.add_point(&mut buffer, 1.0, 0.0, 1.0, 1.0, 1.0);
+        assert_eq!(pixel(&buffer, 1, 0), RED);
+        let dirty = stroke.add_point(&mut buffer, 1.0, 1.0, 1.0, 1.0, 1.0);
+
+        let half = blend_coverage(&[0, 0, 0, 0], RED, 128, CoverageMode::Premultiplied);
+        assert_eq!(pixel(&buffer, 1, 0), half);
+        assert_eq!(pixel(&buffer, 0, 0), RED);
+        assert_eq!(pixel(&buffer, 1, 1), RED);
+        assert_eq!(dirty, [1, 0, 1, 2]);
+        // The pixel was part of the stroke before the corner, so it stays in the diff
+        assert_eq!(stroke.changed_pixels(), 3);
+    }
+
+    #[test]
+    fn spacing_places_dabs_along_the_line() {
+        let stamp = BrushStamp::new(vec![255], 1, 1, 0, 0).unwrap();
+        for (spacing, expected) in [
+            (0.0, vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9]),
+            (3.0, vec![0, 3, 6, 9]),
+        ] {
+            let mut buffer = RgbaBuffer::new(10, 1);
+            let mut stroke = red_stroke(&stamp, &StrokeOption::new(spacing, false, false));
+            stroke.add_point(&mut buffer, 0.0, 0.0, 1.0, 1.0, 1.0);
+            stroke.add_point(&mut buffer, 9.0, 0.0, 1.0, 1.0, 1.0);
+            let painted: Vec<u32> = (0..10).filter(|x| pixel(&buffer, *x, 0) == RED).collect();
+            assert_eq!(painted, expected, "spacing {spacing}");
+            assert_eq!(stroke.changed_pixels() as usize, expected.len());
+        }
+
+        // Spacing is a fraction of the dab size, and diagonal steps count as sqrt(2)
+        let stamp = BrushStamp::new(vec![255; 4], 2, 2, 0, 0).unwrap();
+        let mut buffer = RgbaBuffer::new(16, 16);
+        let mut stroke = red_stroke(&stamp, &StrokeOption::new(2.0, false, false));
+        stroke.add_point(&mut buffer, 0.0, 0.0, 1.0, 1.0, 1.0);
+        stroke.add_point(&mut buffer, 8.0, 8.0, 1.0, 1.0, 1.0);
+        // A dab every third diagonal step, the first to travel 2 * 2 (3 * sqrt(2))
+        let painted: Vec<u32> = (0..16).filter(|i| pixel(&buffer, *i, *i) == RED).collect();
+        assert_eq!(painted, [0, 1, 3, 4, 6, 7]);
+    }
+
+    #[test]
+    fn pressure_scales_size_and_opacity() {
+        let stamp = BrushStamp::new(vec![255; 64], 8, 8, -4, -4).unwrap();
+        for (pressure_size, pressure, expected) in
+            [(false, 0.5, 64), (true, 1.0, 64), (true, 0.5, 16)]
+        {
+            let mut buffer = RgbaBuffer::new(32, 32);
+            let mut stroke = red_stroke(&stamp, &StrokeOption::new(0.0, pressure_size, false));
+            stroke.add_point(&mut buffer, 16.0, 16.0, pressure, 1.0, 1.0);
+            assert_eq!(
+                stroke.changed_pixels(),
+                expected,
+                "{pressure_size} {pressure}"
+            );
+            assert_eq!(pixel(&buffer, 16, 16), RED);
+        }
+
+        for (pressure_opacity, pressure, alpha) in
+            [(false, 0.5, 255), (true, 1.0, 255), (true, 0.5, 128)]
+        {
+            let mut buffer = RgbaBuffer::new(32, 32);
+            let mut stroke = red_stroke(&stamp, &StrokeOption::new(0.0, false, pressure_opacity));
+            stroke.add_point(&mut buffer, 16.0, 16.0, pressure, 1.0, 1.0);
+            assert_eq!(stroke.changed_pixels(), 64);
+            assert_eq!(
+                pixel(&buffer, 16, 16),
+                [255, 0, 0, alpha],
+                "{pressure_opacity} {pressure}"
+            );
+        }
+
+        // Pressure is interpolated between points
+        let mut buffer = RgbaBuffer::new(32, 1);
+        let stamp = BrushStamp::new(vec![255], 1, 1, 0, 0).unwrap();
+        let mut stroke = red_stroke(&stamp, &StrokeOption::new(0.0, false, true));
+        stroke.add_point(&mut buffer, 0.0, 0.0, 0.0, 1.0, 1.0);
+        stroke.add_point(&mut buffer, 10.0, 0.0, 1.0, 1.0, 1.0);
+        let alphas: Vec<u8> = (0..=10).map(|x| pixel(&buffer, x, 0)[3]).collect();
+        assert_eq!(alphas, [0, 26, 51, 77, 102, 128, 153, 179, 204, 230, 255]);
+    }
+}
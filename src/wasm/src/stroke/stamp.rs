@@ -0,0 +1,94 @@
+use wasm_bindgen::prelude::*;
+
+/// Brush tip: a coverage mask placed relative to the dab center, like `ShapeMask` on
+/// the TS side (top-left corner at center + offset).
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct BrushStamp {
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+    pub(crate) offset_x: i32,
+    pub(crate) offset_y: i32,
+    pub(crate) data: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl BrushStamp {
+    /// Create a stamp from coverage bytes (0-255).
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        mask: Vec<u8>,
+        width: u32,
+        height: u32,
+        offset_x: i32,
+        offset_y: i32,
+    ) -> Result<BrushStamp, JsValue> {
+        if width == 0 || height == 0 || mask.len() != width as usize * height as usize {
+            return Err(JsValue::from_str(
+                "Mask length does not match the specified width/height.",
+            ));
+        }
+        Ok(BrushStamp {
+            width,
+            height,
+            offset_x,
+            offset_y,
+            data: mask,
+        })
+    }
+
+    /// Create a stamp from a `ShapeMask`-style flag mask where any non-zero value is fully covered.
+    #[wasm_bindgen(js_name = fromFlags)]
+    pub fn from_flags(
+        mask: Vec<u8>,
+        width: u32,
+        height: u32,
+        offset_x: i32,
+        offset_y: i32,
+    ) -> Result<BrushStamp, JsValue> {
+        let mut stamp = BrushStamp::new(mask, width, height, offset_x, offset_y)?;
+        for value in stamp.data.iter_mut() {
+            if *value != 0 {
+                *value = 255;
+            }
+        }
+        Ok(stamp)
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+}
+
+impl BrushStamp {
+    /// Nearest-neighbor resize by `scale`, offsets included. At least 1x1.
+    pub(crate) fn scaled(&self, scale: f32) -> BrushStamp {
+        if (scale - 1.0).abs() < 1e-3 {
+            return self.clone();
+        }
+        let width = ((self.width as f32 * scale).round() as u32).max(1);
+        let height = ((self.height as f32 * scale).round() as u32).max(1);
+        let (sx, sy) = (
+            self.width as f32 / width as f32,
+            self.height as f32 / height as f32,
+        );
+        let mut data = Vec::with_capacity(width as usize * height as usize);
+        for y in 0..height {
+            let src_y = (((y as f32 + 0.5) * sy) as u32).min(self.height - 1);
+            for x in 0..width {
+                let src_x = (((x as f32 + 0.5) * sx) as u32).min(self.width - 1);
+                data.push(self.data[(src_y * self.width + src_x) as usize]);
+            }
+        }
+        BrushStamp {
+            width,
+            height,
+            offset_x: (self.offset_x as f32 * width as f32 / self.width as f32).round() as i32,
+            offset_y: (self.offset_y as f32 * height as f32 / self.height as f32).round() as i32,
+            data,
+        }
+    }
+}
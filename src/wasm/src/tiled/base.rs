@@ -0,0 +1,213 @@
+use crate::{
+    rgba::RgbaBuffer,
+    tiled::{Tile, TiledRgbaBuffer},
+};
+use js_sys::Array;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+impl TiledRgbaBuffer {
+    #[wasm_bindgen(constructor)]
+    pub fn new(width: u32, height: u32, tile_size: u32) -> Result<TiledRgbaBuffer, JsValue> {
+        if tile_size == 0 {
+            return Err(JsValue::from_str("Tile size must be positive."));
+        }
+        Ok(Self::transparent(width, height, tile_size))
+    }
+
+    /// Build sparse storage from a dense buffer.
+    #[wasm_bindgen(js_name = fromBuffer)]
+    pub fn from_buffer(buffer: &RgbaBuffer, tile_size: u32) -> Result<TiledRgbaBuffer, JsValue> {
+        let mut tiled = TiledRgbaBuffer::new(buffer.width, buffer.height, tile_size)?;
+        tiled.store_dense(&buffer.data);
+        Ok(tiled)
+    }
+
+    /// Materialize the whole canvas as a dense buffer (width * height * 4 bytes).
+    #[wasm_bindgen(js_name = toBuffer)]
+    pub fn to_buffer(&self) -> RgbaBuffer {
+        RgbaBuffer {
+            width: self.width,
+            height: self.height,
+            data: self.read_rect(0, 0, self.width, self.height),
+            journal: None,
+            fill_coverage: false,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+    #[wasm_bindgen(js_name = tileSize)]
+    pub fn tile_size(&self) -> u32 {
+        self.tile_size
+    }
+
+    /// Number of tiles that hold their own pixel storage.
+    #[wasm_bindgen(js_name = allocatedTiles)]
+    pub fn allocated_tiles(&self) -> usize {
+        self.tiles
+            .iter()
+            .filter(|tile| matches!(tile, Tile::Dense(_)))
+            .count()
+    }
+
+    /// Bytes used by dense tile storage.
+    #[wasm_bindgen(js_name = allocatedBytes)]
+    pub fn allocated_bytes(&self) -> usize {
+        self.tiles
+            .iter()
+            .map(|tile| match tile {
+                Tile::Dense(pixels) => pixels.len(),
+                Tile::Uniform(_) => 0,
+            })
+            .sum()
+    }
+
+    /// Collapse dense tiles whose pixels all share one color.
+    pub fn compact(&mut self) {
+        for tile in self.tiles.iter_mut() {
+            if let Tile::Dense(pixels) = tile {
+                if let Some(color) = uniform_color(pixels) {
+                    *tile = Tile::Uniform(color);
+                }
+            }
+        }
+    }
+
+    #[wasm_bindgen(js_name = isInBounds)]
+    pub fn in_bounds(&self, x: u32, y: u32) -> bool {
+        x < self.width && y < self.height
+    }
+
+    #[wasm_bindgen(js_name = get)]
+    pub fn get(&self, x: u32, y: u32) -> Array {
+        let [r, g, b, a] = if self.in_bounds(x, y) {
+            self.pixel(x, y)
+        } else {
+            [0, 0, 0, 0]
+        };
+        let arr = Array::new_with_length(4);
+        arr.set(0, JsValue::from(r));
+        arr.set(1, JsValue::from(g));
+        arr.set(2, JsValue::from(b));
+        arr.set(3, JsValue::from(a));
+        arr
+    }
+
+    #[wasm_bindgen(js_name = set)]
+    pub fn set(&mut self, x: u32, y: u32, r: u8, g: u8, b: u8, a: u8) -> bool {
+        if !self.in_bounds(x, y) {
+            return false;
+        }
+        let color = [r, g, b, a];
+        if self.pixel(x, y) == color {
+            return false;
+        }
+        self.set_pixel(x, y, color);
+        true
+    }
+}
+
+impl TiledRgbaBuffer {
+    pub(crate) fn transparent(width: u32, height: u32, tile_size: u32) -> TiledRgbaBuffer {
+        let cols = width.div_ceil(tile_size);
+        let rows = height.div_ceil(tile_size);
+        let mut tiles = Vec::with_capacity((cols * rows) as usize);
+        tiles.resize_with((cols * rows) as usize, || Tile::Uniform([0, 0, 0, 0]));
+        TiledRgbaBuffer {
+            width,
+            height,
+            tile_size,
+            cols,
+            tiles,
+            fill_coverage: false,
+        }
+    }
+
+    /// Bounds (x, y, width, height) of a tile, clipped to the canvas.
+    pub(crate) fn tile_rect(&self, index: usize) -> (u32, u32, u32, u32) {
+        let x = (index as u32 % self.cols) * self.tile_size;
+        let y = (index as u32 / self.cols) * self.tile_size;
+        (
+            x,
+            y,
+            self.tile_size.min(self.width - x),
+            self.tile_size.min(self.height - y),
+        )
+    }
+
+    pub(crate) fn tile_index(&self, x: u32, y: u32) -> usize {
+        ((y / self.tile_size) * self.cols + x / self.tile_size) as usize
+    }
+
+    pub(crate) fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let index = self.tile_index(x, y);
+        match &self.tiles[index] {
+            Tile::Uniform(color) => *color,
+            Tile::Dense(pixels) => {
+                let (tx, ty, tw, _) = self.tile_rect(index);
+                let i = (((y - ty) * tw + (x - tx)) * 4) as usize;
+                [pixels[i], pixels[i + 1], pixels[i + 2], pixels[i + 3]]
+            }
+        }
+    }
+
+    pub(crate) fn set_pixel(&mut self, x: u32, y: u32, color: [u8; 4]) {
+        let index = self.tile_index(x, y);
+        let (tx, ty, tw, _) = self.tile_rect(index);
+        let pixels = self.dense_tile(index);
+        let i = (((y - ty) * tw + (x - tx)) * 4) as usize;
+        pixels[i..i + 4].copy_from_slice(&color);
+    }
+
+    /// Pixels of a tile, allocating dense storage for uniform tiles.
+    pub(crate) fn dense_tile(&mut self, index: usize) -> &mut Vec<u8> {
+        let (_, _, tw, th) = self.tile_rect(index);
+        let tile = &mut self.tiles[index];
+        if let Tile::Uniform(color) = tile {
+            *tile = Tile::Dense(color.repeat((tw * th) as usize));
+        }
+        match tile {
+            Tile::Dense(pixels) => pixels,
+            Tile::Uniform(_) => unreachable!(),
+        }
+    }
+
+    /// Replace every tile from a dense canvas-sized buffer, keeping unchanged tiles as they are.
+    pub(crate) fn store_dense(&mut self, data: &[u8]) {
+        let width = self.width as usize;
+        for index in 0..self.tiles.len() {
+            let (tx, ty, tw, th) = self.tile_rect(index);
+            let row_bytes = tw as usize * 4;
+            let mut pixels = Vec::with_capacity(row_bytes * th as usize);
+            for row in ty..ty + th {
+                let start = (row as usize * width + tx as usize) * 4;
+                pixels.extend_from_slice(&data[start..start + row_bytes]);
+            }
+            let unchanged = match &self.tiles[index] {
+                Tile::Uniform(color) => pixels.chunks_exact(4).all(|px| px == color),
+                Tile::Dense(current) => *current == pixels,
+            };
+            if unchanged {
+                continue;
+            }
+            self.tiles[index] = match uniform_color(&pixels) {
+                Some(color) => Tile::Uniform(color),
+                None => Tile::Dense(pixels),
+            };
+        }
+    }
+}
+
+pub(crate) fn uniform_color(pixels: &[u8]) -> Option<[u8; 4]> {
+    let first = pixels.get(0..4)?;
+    if pixels.chunks_exact(4).all(|px| px == first) {
+        Some([first[0], first[1], first[2], first[3]])
+    } else {
+        None
+    }
+}
@@ -0,0 +1,142 @@
+use crate::{
+    patch::{patch_buffer_rgba_instant, transformed_bounds, AntialiasMode, PatchBufferRgbaOption},
+    rgba::{base::pixel_byte_len, RgbaBuffer},
+    tiled::TiledRgbaBuffer,
+};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+impl TiledRgbaBuffer {
+    #[wasm_bindgen(js_name = blitFromRaw)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn blit_from_raw(
+        &mut self,
+        source: &[u8],
+        source_width: u32,
+        source_height: u32,
+        offset_x: f32,
+        offset_y: f32,
+        scale_x: f32,
+        scale_y: f32,
+        rotate_deg: f32,
+        antialias_mode: AntialiasMode,
+        flip_x: bool,
+        flip_y: bool,
+    ) {
+        let options = PatchBufferRgbaOption::new(antialias_mode, flip_x, flip_y);
+        self.blit_from_raw_with_options(
+            source,
+            source_width,
+            source_height,
+            offset_x,
+            offset_y,
+            scale_x,
+            scale_y,
+            rotate_deg,
+            &options,
+        );
+    }
+
+    /// Blit only touches the tiles under the transformed source bounds.
+    #[wasm_bindgen(js_name = blitFromRawWithOptions)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn blit_from_raw_with_options(
+        &mut self,
+        source: &[u8],
+        source_width: u32,
+        source_height: u32,
+        offset_x: f32,
+        offset_y: f32,
+        scale_x: f32,
+        scale_y: f32,
+        rotate_deg: f32,
+        options: &PatchBufferRgbaOption,
+    ) {
+        if pixel_byte_len(source_width, source_height) != source.len() {
+            return;
+        }
+        let (x, y, w, h) = transformed_bounds(
+            source_width as f32 * scale_x,
+            source_height as f32 * scale_y,
+            offset_x,
+            offset_y,
+            rotate_deg,
+        );
+        let Some((left, top, right, bottom)) = self.clip_rect(x, y, w, h) else {
+            return;
+        };
+
+        // Blend into a window of the canvas, then store it back
+        let (win_w, win_h) = (right - left, bottom - top);
+        let mut window = self.read_rect(left as i32, top as i32, win_w, win_h);
+        patch_buffer_rgba_instant(
+            &mut window,
+            win_w,
+            win_h,
+            source,
+            source_width,
+            source_height,
+            offset_x - left as f32,
+            offset_y - top as f32,
+            scale_x,
+            scale_y,
+            rotate_deg,
+            options,
+        );
+        self.write_rect(left as i32, top as i32, win_w, win_h, &window);
+    }
+
+    #[wasm_bindgen(js_name = blitFromBuffer)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn blit_from_buffer(
+        &mut self,
+        source: &RgbaBuffer,
+        offset_x: f32,
+        offset_y: f32,
+        scale_x: f32,
+        scale_y: f32,
+        rotate_deg: f32,
+        antialias_mode: AntialiasMode,
+        flip_x: bool,
+        flip_y: bool,
+    ) {
+        self.blit_from_raw(
+            &source.data,
+            source.width,
+            source.height,
+            offset_x,
+            offset_y,
+            scale_x,
+            scale_y,
+            rotate_deg,
+            antialias_mode,
+            flip_x,
+            flip_y,
+        );
+    }
+
+    #[wasm_bindgen(js_name = blitFromBufferWithOptions)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn blit_from_buffer_with_options(
+        &mut self,
+        source: &RgbaBuffer,
+        offset_x: f32,
+        offset_y: f32,
+        scale_x: f32,
+        scale_y: f32,
+        rotate_deg: f32,
+        options: &PatchBufferRgbaOption,
+    ) {
+        self.blit_from_raw_with_options(
+            &source.data,
+            source.width,
+            source.height,
+            offset_x,
+            offset_y,
+            scale_x,
+            scale_y,
+            rotate_deg,
+            options,
+        );
+    }
+}
@@ -0,0 +1,39 @@
+use std::io::Write;
+
+use crate::tiled::TiledRgbaBuffer;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+impl TiledRgbaBuffer {
+    /// Encode the canvas as PNG one tile row at a time, without materializing the whole image.
+    /// Returns an empty array on failure.
+    #[wasm_bindgen(js_name = exportPng)]
+    pub fn export_png(&self) -> Vec<u8> {
+        let mut output = Vec::new();
+        if self.write_png(&mut output).is_err() {
+            return Vec::new();
+        }
+        output
+    }
+}
+
+impl TiledRgbaBuffer {
+    fn write_png(&self, output: &mut Vec<u8>) -> Result<(), png::EncodingError> {
+        let mut encoder = png::Encoder::new(output, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        let mut stream = writer.stream_writer()?;
+
+        let mut band_y = 0;
+        while band_y < self.height {
+            let band_h = self.tile_size.min(self.height - band_y);
+            let band = self.read_rect(0, band_y as i32, self.width, band_h);
+            stream.write_all(&band)?;
+            band_y += band_h;
+        }
+
+        stream.finish()?;
+        writer.finish()
+    }
+}
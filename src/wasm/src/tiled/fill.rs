@@ -0,0 +1,370 @@
+use crate::{
+    fill::{
+        colors_match,
+        coverage::{blend_coverage, region_coverage, CoverageMode},
+        flood_fill::{scanline_walk_with, WalkState},
+        result::FillResult,
+        Connectivity,
+    },
+    tiled::{Tile, TiledRgbaBuffer},
+};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+impl TiledRgbaBuffer {
+    /// Make fills copy the coverage of the pixels they changed into `FillResult.coverage`
+    /// (see `RgbaBuffer.setFillCoverage`).
+    #[wasm_bindgen(js_name = setFillCoverage)]
+    pub fn set_fill_coverage(&mut self, enabled: bool) {
+        self.fill_coverage = enabled;
+    }
+
+    /// Fill the whole canvas. Every tile collapses to a single color.
+    #[wasm_bindgen(js_name = fillAllPixels)]
+    pub fn fill_all_pixels(
+        &mut self,
+        fill_color_r: u8,
+        fill_color_g: u8,
+        fill_color_b: u8,
+        fill_color_a: u8,
+    ) -> bool {
+        let fill_color = [fill_color_r, fill_color_g, fill_color_b, fill_color_a];
+        for tile in self.tiles.iter_mut() {
+            *tile = Tile::Uniform(fill_color);
+        }
+        true
+    }
+
+    /// Fill pixels whose canvas-sized mask value is non-zero and report what changed.
+    /// Fully covered tiles become uniform and untouched tiles stay unallocated.
+    #[wasm_bindgen(js_name = fillMaskArea)]
+    pub fn fill_mask_area(
+        &mut self,
+        mask: &[u8],
+        fill_color_r: u8,
+        fill_color_g: u8,
+        fill_color_b: u8,
+        fill_color_a: u8,
+    ) -> FillResult {
+        self.fill_mask_area_with_coverage(
+            mask,
+            fill_color_r,
+            fill_color_g,
+            fill_color_b,
+            fill_color_a,
+            CoverageMode::Binary,
+        )
+    }
+
+    /// `fillMaskArea` where mask values are applied according to `mode`
+    /// (see `RgbaBuffer.fillMaskAreaWithCoverage`).
+    #[wasm_bindgen(js_name = fillMaskAreaWithCoverage)]
+    pub fn fill_mask_area_with_coverage(
+        &mut self,
+        mask: &[u8],
+        fill_color_r: u8,
+        fill_color_g: u8,
+        fill_color_b: u8,
+        fill_color_a: u8,
+        mode: CoverageMode,
+    ) -> FillResult {
+        let fill_color = [fill_color_r, fill_color_g, fill_color_b, fill_color_a];
+        let width = self.width as usize;
+        self.paint_tiles(fill_color, mode, |rect| {
+            tile_coverage(rect, |x, y| {
+                mask.get(y as usize * width + x as usize)
+                    .map_or(0, |m| mode.coverage(*m))
+            })
+        })
+    }
+
+    /// Flood fill from a seed point and report what changed, or undefined if the start
+    /// point is out of bounds (see `RgbaBuffer.floodFill`). `connectivity` defaults to `Four`.
+    ///
+    /// The fill reads pixels tile by tile and keeps its walk state only for the tiles it
+    /// reaches, so it never materializes the canvas. Only changed tiles are written back.
+    #[wasm_bindgen(js_name = floodFill)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn flood_fill(
+        &mut self,
+        start_x: u32,
+        start_y: u32,
+        fill_color_r: u8,
+        fill_color_g: u8,
+        fill_color_b: u8,
+        fill_color_a: u8,
+        threshold: u8,
+        connectivity: Option<Connectivity>,
+    ) -> Option<FillResult> {
+        self.flood_fill_limited(
+            start_x,
+            start_y,
+            [fill_color_r, fill_color_g, fill_color_b, fill_color_a],
+            threshold,
+            connectivity.unwrap_or(Connectivity::Four),
+            |_, _| true,
+        )
+    }
+
+    /// `floodFill` limited by a selection mask (see `RgbaBuffer.floodFillWithMask`).
+    #[wasm_bindgen(js_name = floodFillWithMask)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn flood_fill_with_mask(
+        &mut self,
+        start_x: u32,
+        start_y: u32,
+        fill_color_r: u8,
+        fill_color_g: u8,
+        fill_color_b: u8,
+        fill_color_a: u8,
+        threshold: u8,
+        selection_mask: &[u8],
+        limit_mode: &str,
+        connectivity: Option<Connectivity>,
+    ) -> Option<FillResult> {
+        let width = self.width as usize;
+        let is_allowed = |x: u32, y: u32| {
+            let Some(value) = selection_mask.get(y as usize * width + x as usize) else {
+                return false;
+            };
+            match limit_mode {
+                "inside" => *value != 0,
+                "outside" => *value == 0,
+                _ => true,
+            }
+        };
+        self.flood_fill_limited(
+            start_x,
+            start_y,
+            [fill_color_r, fill_color_g, fill_color_b, fill_color_a],
+            threshold,
+            connectivity.unwrap_or(Connectivity::Four),
+            is_allowed,
+        )
+    }
+}
+
+impl TiledRgbaBuffer {
+    fn flood_fill_limited(
+        &mut self,
+        start_x: u32,
+        start_y: u32,
+        fill_color: [u8; 4],
+        threshold: u8,
+        connectivity: Connectivity,
+        is_allowed: impl Fn(u32, u32) -> bool,
+    ) -> Option<FillResult> {
+        if !self.in_bounds(start_x, start_y) || !is_allowed(start_x, start_y) {
+            return None;
+        }
+        // Like the dense fill, the largest threshold fills every allowed pixel
+        if threshold == 255 {
+            return Some(self.paint_tiles(fill_color, CoverageMode::Binary, |rect| {
+                tile_coverage(rect, |x, y| if is_allowed(x, y) { 255 } else { 0 })
+            }));
+        }
+
+        let target = self.pixel(start_x, start_y);
+        let mut walk = TileWalk {
+            buffer: self,
+            states: (0..self.tiles.len()).map(|_| None).collect(),
+        };
+        scanline_walk_with(
+            &mut walk,
+            self.width as usize,
+            self.height as usize,
+            start_x as usize,
+            start_y as usize,
+            connectivity,
+            |x, y| {
+                let (x, y) = (x as u32, y as u32);
+                is_allowed(x, y) && colors_match(&self.pixel(x, y), &target, threshold)
+            },
+        );
+        let mut regions: Vec<Option<Vec<bool>>> = walk
+            .states
+            .into_iter()
+            .map(|states| {
+                let states = states?;
+                states
+                    .contains(&WALK_REGION)
+                    .then(|| states.iter().map(|s| *s == WALK_REGION).collect())
+            })
+            .collect();
+
+        let cols = self.cols;
+        let tile_size = self.tile_size;
+        Some(
+            self.paint_tiles(fill_color, CoverageMode::Binary, |(x, y, _, _)| {
+                regions[((y / tile_size) * cols + x / tile_size) as usize]
+                    .take()
+                    .map(|region| region_coverage(&region))
+            }),
+        )
+    }
+
+    /// Paint `fill_color` with the coverage `tile_coverage` returns for each tile, given its
+    /// bounds, as row-major 0-255 values (None when nothing is covered). Partial coverage
+    /// is blended according to `mode` like the dense fills. Fully covered tiles become
+    /// uniform and tiles that would not change are left alone.
+    fn paint_tiles(
+        &mut self,
+        fill_color: [u8; 4],
+        mode: CoverageMode,
+        mut tile_coverage: impl FnMut((u32, u32, u32, u32)) -> Option<Vec<u8>>,
+    ) -> FillResult {
+        let mut changes = Vec::new();
+        for index in 0..self.tiles.len() {
+            let (tx, ty, tw, th) = self.tile_rect(index);
+            let Some(coverage) = tile_coverage((tx, ty, tw, th)) else {
+                continue;
+            };
+            let changed = |c: &u8, pixel: &[u8]| {
+                if *c != 0 && blend_coverage(pixel, fill_color, *c, mode)[..] != *pixel {
+                    *c
+                } else {
+                    0
+                }
+            };
+            let change: Vec<u8> = match &self.tiles[index] {
+                Tile::Uniform(color) => coverage.iter().map(|c| changed(c, color)).collect(),
+                Tile::Dense(pixels) => coverage
+                    .iter()
+                    .zip(pixels.chunks_exact(4))
+                    .map(|(c, px)| changed(c, px))
+                    .collect(),
+            };
+            if change.iter().all(|c| *c == 0) {
+                continue;
+            }
+
+            if coverage.iter().all(|c| *c == 255) {
+                self.tiles[index] = Tile::Uniform(fill_color);
+            } else {
+                let pixels = self.dense_tile(index);
+                for (pixel, c) in pixels.chunks_exact_mut(4).zip(change.iter()) {
+                    if *c != 0 {
+                        let painted = blend_coverage(pixel, fill_color, *c, mode);
+                        pixel.copy_from_slice(&painted);
+                    }
+                }
+            }
+            changes.push(([tx, ty, tw, th].map(|v| v as usize), change));
+        }
+        FillResult::from_rect_changes(&changes, self.fill_coverage)
+    }
+}
+
+// Row-major coverage of a tile, or None if no pixel is covered
+fn tile_coverage(
+    (tx, ty, tw, th): (u32, u32, u32, u32),
+    value: impl Fn(u32, u32) -> u8,
+) -> Option<Vec<u8>> {
+    let coverage: Vec<u8> = (ty..ty + th)
+        .flat_map(|y| (tx..tx + tw).map(move |x| (x, y)))
+        .map(|(x, y)| value(x, y))
+        .collect();
+    coverage.iter().any(|c| *c != 0).then_some(coverage)
+}
+
+// Walk states of a pixel, kept per tile and allocated when the walk reaches the tile
+const WALK_VISITED: u8 = 1;
+const WALK_REGION: u8 = 2;
+
+struct TileWalk<'a> {
+    buffer: &'a TiledRgbaBuffer,
+    states: Vec<Option<Vec<u8>>>,
+}
+
+impl TileWalk<'_> {
+    fn state(&self, x: usize, y: usize) -> u8 {
+        let (index, offset) = self.locate(x, y);
+        self.states[index]
+            .as_ref()
+            .map_or(0, |states| states[offset])
+    }
+
+    fn set_state(&mut self, x: usize, y: usize, state: u8) {
+        let (index, offset) = self.locate(x, y);
+        let (_, _, tw, th) = self.buffer.tile_rect(index);
+        self.states[index].get_or_insert_with(|| vec![0; (tw * th) as usize])[offset] = state;
+    }
+
+    fn locate(&self, x: usize, y: usize) -> (usize, usize) {
+        let (x, y) = (x as u32, y as u32);
+        let index = self.buffer.tile_index(x, y);
+        let (tx, ty, tw, _) = self.buffer.tile_rect(index);
+        (index, ((y - ty) * tw + (x - tx)) as usize)
+    }
+}
+
+impl WalkState for TileWalk<'_> {
+    fn is_visited(&self, x: usize, y: usize) -> bool {
+        self.state(x, y) != 0
+    }
+    fn visit(&mut self, x: usize, y: usize) {
+        if self.state(x, y) == 0 {
+            self.set_state(x, y, WALK_VISITED);
+        }
+    }
+    fn add_span(&mut self, y: usize, left: usize, right: usize) {
+        for x in left..=right {
+            self.set_state(x, y, WALK_REGION);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rgba::RgbaBuffer;
+
+    const WIDTH: u32 = 50;
+    const HEIGHT: u32 = 37;
+    const TILE_SIZE: u32 = 16;
+
+    // Black canvas with a red stripe, under a horizontal coverage ramp with an empty column
+    fn canvas_and_mask() -> (RgbaBuffer, Vec<u8>) {
+        let mut buffer = RgbaBuffer::new(WIDTH, HEIGHT);
+        for (i, pixel) in buffer.data.chunks_exact_mut(4).enumerate() {
+            let color = if (i as u32 / WIDTH).is_multiple_of(5) {
+                [255, 0, 0, 255]
+            } else {
+                [0, 0, 0, 255]
+            };
+            pixel.copy_from_slice(&color);
+        }
+        let mask = (0..WIDTH * HEIGHT)
+            .map(|i| match i % WIDTH {
+                20 => 0,
+                x => (x * 255 / (WIDTH - 1)) as u8,
+            })
+            .collect();
+        (buffer, mask)
+    }
+
+    #[test]
+    fn mask_area_fill_matches_the_dense_fill_in_every_mode() {
+        for mode in [
+            CoverageMode::Binary,
+            CoverageMode::Straight,
+            CoverageMode::Premultiplied,
+        ] {
+            let (mut dense, mask) = canvas_and_mask();
+            let mut tiled = TiledRgbaBuffer::from_buffer(&dense, TILE_SIZE).unwrap();
+            dense.set_fill_coverage(true);
+            tiled.set_fill_coverage(true);
+
+            let expected = dense.fill_mask_area_with_coverage(&mask, 0, 128, 255, 200, mode);
+            let actual = tiled.fill_mask_area_with_coverage(&mask, 0, 128, 255, 200, mode);
+            assert_eq!(
+                actual.changed_pixels(),
+                expected.changed_pixels(),
+                "{mode:?}"
+            );
+            assert_eq!(actual.bound_box(), expected.bound_box(), "{mode:?}");
+            assert_eq!(actual.coverage(), expected.coverage(), "{mode:?}");
+            assert!(tiled.to_buffer().data == dense.data, "{mode:?}");
+        }
+    }
+}
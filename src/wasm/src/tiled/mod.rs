@@ -0,0 +1,28 @@
+use wasm_bindgen::prelude::*;
+
+mod base;
+mod blit;
+mod export;
+mod fill;
+mod rect;
+
+pub(crate) enum Tile {
+    /// Every pixel of the tile has the same color (covers untouched, fully transparent tiles)
+    Uniform([u8; 4]),
+    /// Row-major RGBA pixels of the (edge-clipped) tile
+    Dense(Vec<u8>),
+}
+
+/// Sparse RGBA storage for very large canvases.
+///
+/// The canvas is split into square tiles. Uniform tiles keep a single color, and only
+/// tiles that actually hold differing pixels allocate memory.
+#[wasm_bindgen]
+pub struct TiledRgbaBuffer {
+    width: u32,
+    height: u32,
+    tile_size: u32,
+    cols: u32,
+    tiles: Vec<Tile>,
+    fill_coverage: bool,
+}
@@ -0,0 +1,188 @@
+use crate::{
+    rgba::base::{pixel_byte_len, positive_area},
+    tiled::{base::uniform_color, Tile, TiledRgbaBuffer},
+};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+impl TiledRgbaBuffer {
+    #[wasm_bindgen(js_name = readRect)]
+    pub fn read_rect(
+        &self,
+        rect_x: i32,
+        rect_y: i32,
+        rect_width: u32,
+        rect_height: u32,
+    ) -> Vec<u8> {
+        if !positive_area(rect_width as i32, rect_height as i32) {
+            return Vec::new();
+        }
+
+        let mut result = vec![0u8; pixel_byte_len(rect_width, rect_height)];
+        let Some((left, top, right, bottom)) =
+            self.clip_rect(rect_x, rect_y, rect_width, rect_height)
+        else {
+            return result;
+        };
+
+        let dst_stride = rect_width as usize * 4;
+        for index in self.tiles_in(left, top, right, bottom) {
+            let (tx, ty, tw, th) = self.tile_rect(index);
+            let x0 = left.max(tx);
+            let x1 = right.min(tx + tw);
+            let y0 = top.max(ty);
+            let y1 = bottom.min(ty + th);
+            let span = (x1 - x0) as usize * 4;
+            for y in y0..y1 {
+                let dst = (y as i64 - rect_y as i64) as usize * dst_stride
+                    + (x0 as i64 - rect_x as i64) as usize * 4;
+                let dst_row = &mut result[dst..dst + span];
+                match &self.tiles[index] {
+                    Tile::Uniform(color) => {
+                        for pixel in dst_row.chunks_exact_mut(4) {
+                            pixel.copy_from_slice(color);
+                        }
+                    }
+                    Tile::Dense(pixels) => {
+                        let src = (((y - ty) * tw + (x0 - tx)) * 4) as usize;
+                        dst_row.copy_from_slice(&pixels[src..src + span]);
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Write a rectangle of pixels. Tiles that end up uniform are stored as a single color.
+    #[wasm_bindgen(js_name = writeRect)]
+    pub fn write_rect(
+        &mut self,
+        rect_x: i32,
+        rect_y: i32,
+        rect_width: u32,
+        rect_height: u32,
+        data: &[u8],
+    ) -> bool {
+        if !positive_area(rect_width as i32, rect_height as i32) {
+            return false;
+        }
+        if data.len() != pixel_byte_len(rect_width, rect_height) {
+            return false;
+        }
+        let Some((left, top, right, bottom)) =
+            self.clip_rect(rect_x, rect_y, rect_width, rect_height)
+        else {
+            return true;
+        };
+
+        let src_stride = rect_width as usize * 4;
+        for index in self.tiles_in(left, top, right, bottom) {
+            let (tx, ty, tw, th) = self.tile_rect(index);
+            let x0 = left.max(tx);
+            let x1 = right.min(tx + tw);
+            let y0 = top.max(ty);
+            let y1 = bottom.min(ty + th);
+            let span = (x1 - x0) as usize * 4;
+            let src_at = |y: u32| {
+                (y as i64 - rect_y as i64) as usize * src_stride
+                    + (x0 as i64 - rect_x as i64) as usize * 4
+            };
+
+            // A write covering the whole tile replaces it outright
+            if x0 == tx && y0 == ty && x1 == tx + tw && y1 == ty + th {
+                let mut pixels = Vec::with_capacity(span * th as usize);
+                for y in y0..y1 {
+                    let src = src_at(y);
+                    pixels.extend_from_slice(&data[src..src + span]);
+                }
+                self.tiles[index] = match uniform_color(&pixels) {
+                    Some(color) => Tile::Uniform(color),
+                    None => Tile::Dense(pixels),
+                };
+                continue;
+            }
+
+            // Skip partial writes that leave a uniform tile as it is
+            if let Tile::Uniform(color) = &self.tiles[index] {
+                let unchanged = (y0..y1).all(|y| {
+                    let src = src_at(y);
+                    data[src..src + span].chunks_exact(4).all(|px| px == color)
+                });
+                if unchanged {
+                    continue;
+                }
+            }
+
+            let pixels = self.dense_tile(index);
+            for y in y0..y1 {
+                let src = src_at(y);
+                let dst = (((y - ty) * tw + (x0 - tx)) * 4) as usize;
+                pixels[dst..dst + span].copy_from_slice(&data[src..src + span]);
+            }
+        }
+
+        true
+    }
+
+    #[wasm_bindgen(js_name = writePixels)]
+    pub fn write_pixels(&mut self, coords: &[u32], colors: &[u8]) -> bool {
+        if !coords.len().is_multiple_of(2) || !colors.len().is_multiple_of(4) {
+            return false;
+        }
+        if colors.len() / 4 != coords.len() / 2 {
+            return false;
+        }
+
+        for (coord, color) in coords.chunks_exact(2).zip(colors.chunks_exact(4)) {
+            let (x, y) = (coord[0], coord[1]);
+            if !self.in_bounds(x, y) {
+                continue;
+            }
+            let color = [color[0], color[1], color[2], color[3]];
+            if self.pixel(x, y) != color {
+                self.set_pixel(x, y, color);
+            }
+        }
+
+        true
+    }
+}
+
+impl TiledRgbaBuffer {
+    /// Clip a rectangle to the canvas as (left, top, right, bottom), or None if it misses.
+    pub(crate) fn clip_rect(
+        &self,
+        x: i32,
+        y: i32,
+        width: u32,
+        height: u32,
+    ) -> Option<(u32, u32, u32, u32)> {
+        let left = (x as i64).max(0);
+        let top = (y as i64).max(0);
+        let right = (x as i64 + width as i64).min(self.width as i64);
+        let bottom = (y as i64 + height as i64).min(self.height as i64);
+        if left >= right || top >= bottom {
+            return None;
+        }
+        Some((left as u32, top as u32, right as u32, bottom as u32))
+    }
+
+    /// Linear indices of the tiles intersecting a clipped rectangle.
+    pub(crate) fn tiles_in(
+        &self,
+        left: u32,
+        top: u32,
+        right: u32,
+        bottom: u32,
+    ) -> impl Iterator<Item = usize> {
+        let ts = self.tile_size;
+        let cols = self.cols;
+        let col_range = (left / ts)..=((right - 1) / ts);
+        ((top / ts)..=((bottom - 1) / ts)).flat_map(move |row| {
+            col_range
+                .clone()
+                .map(move |col| (row * cols + col) as usize)
+        })
+    }
+}
@@ -0,0 +1,71 @@
+import { describe, expect, it } from 'vitest';
+import { RgbaBuffer } from '../../../src/wasm/pkg/anvil_wasm';
+import { BLUE, RED, TRANSPARENT, coordinateColoredBuffer } from '../../support/colors';
+
+const TILE_SIZE = 16;
+const WIDTH = 64;
+const HEIGHT = 48;
+const TILE_BYTES = TILE_SIZE * TILE_SIZE * 4;
+
+describe('undo journal', () => {
+  it('round-trips a transaction through commitJournal and swapTiles', () => {
+    const buffer = coordinateColoredBuffer(WIDTH, HEIGHT);
+    const before = buffer.data();
+    buffer.beginJournal(TILE_SIZE, 1 << 20);
+
+    buffer.writeRect(3, 3, 2, 2, new Uint8Array(16).fill(255));
+    buffer.floodFill(60, 40, ...RED, 0);
+    const after = buffer.data();
+    const undo = buffer.commitJournal();
+    expect(undo.length).toBeGreaterThan(0);
+    expect(buffer.journalBytes()).toBe(0);
+
+    const redo = buffer.swapTiles(undo);
+    expect(buffer.data()).toEqual(before);
+    buffer.swapTiles(redo);
+    expect(buffer.data()).toEqual(after);
+  });
+
+  it('snapshots only the tiles a fill paints', () => {
+    const buffer = new RgbaBuffer(WIDTH, HEIGHT);
+    buffer.fillMaskArea(new Uint8Array(WIDTH * HEIGHT).fill(1), ...BLUE);
+    buffer.beginJournal(TILE_SIZE, 1 << 20);
+
+    const mask = new Uint8Array(WIDTH * HEIGHT);
+    mask[5 * WIDTH + 5] = 1;
+    mask[40 * WIDTH + 60] = 1;
+    buffer.fillMaskArea(mask, ...RED);
+    expect(buffer.journalBytes()).toBe(2 * TILE_BYTES);
+  });
+
+  it('returns an empty record when nothing changed', () => {
+    const buffer = coordinateColoredBuffer(WIDTH, HEIGHT);
+    buffer.beginJournal(TILE_SIZE, 1 << 20);
+    buffer.writeRect(0, 0, 2, 2, buffer.readRect(0, 0, 2, 2));
+    expect(buffer.commitJournal()).toHaveLength(0);
+  });
+
+  it('encodes snapshots to stay within the budget', () => {
+    const buffer = new RgbaBuffer(WIDTH, HEIGHT);
+    const budget = (WIDTH * HEIGHT * 4) / 2;
+    buffer.beginJournal(TILE_SIZE, budget);
+
+    buffer.fillAllPixels(...BLUE);
+    expect(buffer.journalOverflowed()).toBe(false);
+    expect(buffer.journalBytes()).toBeLessThanOrEqual(budget);
+
+    buffer.swapTiles(buffer.commitJournal());
+    expect(buffer.get(10, 10)).toEqual(TRANSPARENT);
+  });
+
+  it('overflows when even the encoded snapshots exceed the budget', () => {
+    const buffer = coordinateColoredBuffer(WIDTH, HEIGHT);
+    buffer.beginJournal(TILE_SIZE, 16);
+
+    buffer.fillAllPixels(...RED);
+    expect(buffer.journalOverflowed()).toBe(true);
+    expect(buffer.journalBytes()).toBe(0);
+    expect(buffer.commitJournal()).toHaveLength(0);
+    expect(buffer.journalOverflowed()).toBe(false);
+  });
+});
@@ -0,0 +1,54 @@
+import { describe, expect, it } from 'vitest';
+import { Anvil } from '../../../src/Anvil';
+import { DiffsController } from '../../../src/buffer/DiffsController';
+import { BLUE, RED, TRANSPARENT } from '../../support/colors';
+
+const TILE_SIZE = 32;
+const WIDTH = 96; // 3 tiles wide
+const HEIGHT = 64; // 2 tiles high
+
+describe('tile patches', () => {
+  it('undoes and redoes scattered edits through applyPatch', () => {
+    const anvil = new Anvil(WIDTH, HEIGHT, TILE_SIZE);
+    anvil.setPixel(1, 1, BLUE, true);
+
+    // tile 0 holds (1, 1), tile 5 holds (70, 40)
+    anvil.addCurrentTilesDiff([0, 5]);
+    anvil.setPixel(1, 1, RED, true);
+    anvil.setPixel(70, 40, RED, true);
+    const patch = anvil.flushDiffs();
+    expect(patch?.tiles).toBeDefined();
+    expect(patch?.whole).toBeUndefined();
+
+    anvil.applyPatch(patch, 'undo');
+    expect(anvil.getPixel(1, 1)).toEqual(BLUE);
+    expect(anvil.getPixel(70, 40)).toEqual(TRANSPARENT);
+
+    anvil.applyPatch(patch, 'redo');
+    expect(anvil.getPixel(1, 1)).toEqual(RED);
+    expect(anvil.getPixel(70, 40)).toEqual(RED);
+  });
+
+  it('leaves tiles outside the patch untouched', () => {
+    const anvil = new Anvil(WIDTH, HEIGHT, TILE_SIZE);
+    anvil.addCurrentTilesDiff([0]);
+    anvil.setPixel(1, 1, RED, true);
+    anvil.setPixel(40, 1, RED, true);
+    const patch = anvil.flushDiffs();
+
+    anvil.applyPatch(patch, 'undo');
+    expect(anvil.getPixel(1, 1)).toEqual(TRANSPARENT);
+    expect(anvil.getPixel(40, 1)).toEqual(RED);
+  });
+
+  it('drops the tile patch when a whole patch replaces it', () => {
+    const diffs = new DiffsController();
+    diffs.addTilesPacked({ swapBufferTiles: new Uint8Array(8) });
+    expect(diffs.hasPendingChanges()).toBe(true);
+
+    diffs.addWholePacked({ swapBufferWebp: new Uint8Array(), width: WIDTH, height: HEIGHT });
+    const preview = diffs.previewPatch();
+    expect(preview?.whole).toBeDefined();
+    expect(preview?.tiles).toBeUndefined();
+  });
+});
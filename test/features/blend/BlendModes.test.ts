@@ -0,0 +1,55 @@
+import { describe, expect, it } from 'vitest';
+import type { RGBA } from '../../../src/models/RGBA';
+import { AntialiasMode, BlendMode, PatchBufferRgbaOption, RgbaBuffer } from '../../../src/wasm/pkg/anvil_wasm';
+
+// Backdrop (0.8, 0.4, 0.2) and source (0.2, 0.6, 1.0), both opaque
+const BACKDROP: RGBA = [204, 102, 51, 255];
+const SOURCE: RGBA = [51, 153, 255, 255];
+
+// Expected results from the W3C Compositing and Blending Level 1 formulas, rounded to 8 bits
+const W3C_REFERENCE: [BlendMode, RGBA][] = [
+  [BlendMode.Normal, [51, 153, 255, 255]],
+  [BlendMode.Multiply, [41, 61, 51, 255]],
+  [BlendMode.Screen, [214, 194, 255, 255]],
+  [BlendMode.Overlay, [173, 122, 102, 255]],
+  [BlendMode.Darken, [51, 102, 51, 255]],
+  [BlendMode.Lighten, [204, 153, 255, 255]],
+  [BlendMode.ColorDodge, [255, 255, 255, 255]],
+  [BlendMode.ColorBurn, [0, 0, 51, 255]],
+  [BlendMode.HardLight, [82, 133, 255, 255]],
+  [BlendMode.Difference, [153, 51, 204, 255]],
+  [BlendMode.Exclusion, [173, 133, 204, 255]],
+];
+
+const blitPixel = (backdrop: RGBA, source: RGBA, options: PatchBufferRgbaOption): RGBA => {
+  const target = new RgbaBuffer(1, 1);
+  target.set(0, 0, ...backdrop);
+  target.blitFromRawWithOptions(new Uint8Array(source), 1, 1, 0, 0, 1, 1, 0, options);
+  const pixel = target.get(0, 0);
+  return [pixel[0], pixel[1], pixel[2], pixel[3]];
+};
+
+describe('blend modes', () => {
+  it.each(W3C_REFERENCE)('mode %i matches the W3C reference', (mode, expected) => {
+    const options = new PatchBufferRgbaOption(AntialiasMode.Nearest, false, false);
+    options.blend_mode = mode;
+    expect(blitPixel(BACKDROP, SOURCE, options)).toEqual(expected);
+  });
+
+  it('keeps the 3-argument option constructor at Normal and full opacity', () => {
+    const options = new PatchBufferRgbaOption(AntialiasMode.Nearest, false, false);
+    expect(options.blend_mode).toBe(BlendMode.Normal);
+    expect(options.opacity).toBe(1);
+  });
+
+  it('scales the source alpha by opacity', () => {
+    const options = new PatchBufferRgbaOption(AntialiasMode.Nearest, false, false);
+    options.opacity = 0.5;
+    expect(blitPixel([0, 0, 0, 255], [255, 255, 255, 255], options)).toEqual([128, 128, 128, 255]);
+  });
+
+  it('keeps Normal blits over transparent pixels identical to earlier releases', () => {
+    const options = new PatchBufferRgbaOption(AntialiasMode.Nearest, false, false);
+    expect(blitPixel([0, 0, 0, 0], [255, 0, 0, 128], options)).toEqual([128, 0, 0, 128]);
+  });
+});
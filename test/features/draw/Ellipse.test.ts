@@ -0,0 +1,62 @@
+import { describe, expect, it } from 'vitest';
+import { RgbaBuffer, ShapeOption } from '../../../src/wasm/pkg/anvil_wasm';
+import { RED } from '../../support/colors';
+
+const CANVAS = 24;
+const LEFT = 2;
+const TOP = 3;
+
+const SIZES: [number, number][] = [
+  [7, 5],
+  [8, 6],
+  [9, 9],
+  [10, 10],
+  [11, 8],
+  [1, 4],
+  [2, 5],
+  [4, 10],
+];
+
+const STYLES: [string, ShapeOption][] = [
+  ['filled', new ShapeOption(true, 0, false)],
+  ['1px outline', new ShapeOption(false, 1, false)],
+  ['2px outline', new ShapeOption(false, 2, false)],
+];
+
+// Painted pixels of an aliased ellipse drawn in the box (LEFT, TOP, width, height)
+const drawnEllipse = (width: number, height: number, options: ShapeOption) => {
+  const buffer = new RgbaBuffer(CANVAS, CANVAS);
+  buffer.drawEllipse(LEFT, TOP, width, height, ...RED, options);
+  const data = buffer.data();
+  return (x: number, y: number) => data[(y * CANVAS + x) * 4 + 3] !== 0;
+};
+
+describe('aliased ellipses', () => {
+  describe.each(STYLES)('%s', (_, options) => {
+    it.each(SIZES)('%ix%i is mirror-symmetric and fills its box', (width, height) => {
+      const painted = drawnEllipse(width, height, options);
+      const right = LEFT + width - 1;
+      const bottom = TOP + height - 1;
+
+      for (let y = 0; y < CANVAS; y++) {
+        for (let x = 0; x < CANVAS; x++) {
+          const inBox = x >= LEFT && x <= right && y >= TOP && y <= bottom;
+          if (!inBox) {
+            expect(painted(x, y)).toBe(false);
+            continue;
+          }
+          expect(painted(x, y)).toBe(painted(LEFT + right - x, y));
+          expect(painted(x, y)).toBe(painted(x, TOP + bottom - y));
+          if (width === height) expect(painted(x, y)).toBe(painted(LEFT + y - TOP, TOP + x - LEFT));
+        }
+      }
+
+      const rows = Array.from({ length: height }, (_, i) => TOP + i);
+      const columns = Array.from({ length: width }, (_, i) => LEFT + i);
+      expect(rows.some((y) => painted(LEFT, y))).toBe(true);
+      expect(rows.some((y) => painted(right, y))).toBe(true);
+      expect(columns.some((x) => painted(x, TOP))).toBe(true);
+      expect(columns.some((x) => painted(x, bottom))).toBe(true);
+    });
+  });
+});
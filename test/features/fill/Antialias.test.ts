@@ -0,0 +1,44 @@
+import { describe, expect, it } from 'vitest';
+import { ColorMetric, Connectivity, FloodFillOption, RgbaBuffer } from '../../../src/wasm/pkg/anvil_wasm';
+import { RED } from '../../support/colors';
+
+const WIDTH = 10;
+const HEIGHT = 4;
+const EDGE_X = 3; // last white column of the region
+const GRAY_X = 4; // antialiased pixel of the line
+const LINE_X = 5; // solid line
+
+// White canvas with a black vertical line whose left side is antialiased with gray
+const lineArtBuffer = () => {
+  const buffer = new RgbaBuffer(WIDTH, HEIGHT);
+  for (let y = 0; y < HEIGHT; y++) {
+    for (let x = 0; x < WIDTH; x++) {
+      const v = x === GRAY_X ? 128 : x === LINE_X ? 0 : 255;
+      buffer.set(x, y, v, v, v, 255);
+    }
+  }
+  return buffer;
+};
+
+describe('antialiased fill edges', () => {
+  it('keeps the region fully covered and fades only outside it', () => {
+    const mask = lineArtBuffer().magicWand(0, 0, 0, true, true, Connectivity.Four);
+
+    expect(mask.get(0, 1)).toBe(255);
+    expect(mask.get(EDGE_X, 1)).toBe(255);
+    expect(mask.get(GRAY_X, 1)).toBe(128);
+    expect(mask.get(LINE_X, 1)).toBe(0);
+  });
+
+  it('paints the interior edge pixel with the full fill color', () => {
+    const options = new FloodFillOption(0, ColorMetric.ChannelMax, false);
+    options.antialias = true;
+    const buffer = lineArtBuffer();
+    const result = buffer.floodFillWithOptions(0, 0, ...RED, options);
+
+    expect(buffer.get(EDGE_X, 1)).toEqual(RED);
+    expect(buffer.get(GRAY_X, 1)).toEqual([192, 64, 64, 255]);
+    expect(buffer.get(LINE_X, 1)).toEqual([0, 0, 0, 255]);
+    expect(Array.from(result?.boundBox() ?? [])).toEqual([0, 0, GRAY_X + 1, HEIGHT]);
+  });
+});
@@ -0,0 +1,55 @@
+import { describe, expect, it } from 'vitest';
+import { Connectivity, RgbaBuffer, TiledRgbaBuffer } from '../../../src/wasm/pkg/anvil_wasm';
+import { BLUE, RED, TRANSPARENT } from '../../support/colors';
+
+const SIZE = 8;
+
+// Transparent canvas split by a one-pixel red diagonal from (0, 0) to (7, 7)
+const diagonalBuffer = () => {
+  const buffer = new RgbaBuffer(SIZE, SIZE);
+  for (let i = 0; i < SIZE; i++) buffer.set(i, i, ...RED);
+  return buffer;
+};
+
+const UPPER_TRIANGLE = (SIZE * (SIZE - 1)) / 2;
+
+describe('fill connectivity', () => {
+  it('follows a diagonal line only with eight neighbors', () => {
+    const four = diagonalBuffer();
+    expect(four.floodFill(0, 0, ...BLUE, 0, Connectivity.Four)?.changedPixels()).toBe(1);
+    expect(four.get(1, 1)).toEqual(RED);
+
+    const eight = diagonalBuffer();
+    expect(eight.floodFill(0, 0, ...BLUE, 0, Connectivity.Eight)?.changedPixels()).toBe(SIZE);
+    expect(eight.get(SIZE - 1, SIZE - 1)).toEqual(BLUE);
+  });
+
+  it('crosses a diagonal line only with eight neighbors', () => {
+    const four = diagonalBuffer();
+    expect(four.floodFill(1, 0, ...BLUE, 0, Connectivity.Four)?.changedPixels()).toBe(UPPER_TRIANGLE);
+    expect(four.get(0, 1)).toEqual(TRANSPARENT);
+
+    const eight = diagonalBuffer();
+    expect(eight.floodFill(1, 0, ...BLUE, 0, Connectivity.Eight)?.changedPixels()).toBe(UPPER_TRIANGLE * 2);
+    expect(eight.get(0, 1)).toEqual(BLUE);
+  });
+
+  it('defaults to four neighbors', () => {
+    const buffer = diagonalBuffer();
+    expect(buffer.floodFill(1, 0, ...BLUE, 0)?.changedPixels()).toBe(UPPER_TRIANGLE);
+  });
+
+  it('applies to masked, tiled and magic wand fills', () => {
+    const mask = new Uint8Array(SIZE * SIZE).fill(1);
+    expect(diagonalBuffer().floodFillWithMask(1, 0, ...BLUE, 0, mask, 'inside', Connectivity.Eight)?.changedPixels()).toBe(UPPER_TRIANGLE * 2);
+
+    const tiled = TiledRgbaBuffer.fromBuffer(diagonalBuffer(), 3);
+    expect(tiled.floodFill(0, 0, ...BLUE, 0, Connectivity.Four)?.changedPixels()).toBe(1);
+    expect(tiled.floodFill(1, 1, ...BLUE, 0, Connectivity.Eight)?.changedPixels()).toBe(SIZE - 1);
+
+    const buffer = diagonalBuffer();
+    const count = (data: Uint8Array) => data.filter((v) => v !== 0).length;
+    expect(count(buffer.magicWand(0, 0, 0, true, false, Connectivity.Four).data())).toBe(1);
+    expect(count(buffer.magicWand(0, 0, 0, true, false, Connectivity.Eight).data())).toBe(SIZE);
+  });
+});
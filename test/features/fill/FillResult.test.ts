@@ -0,0 +1,61 @@
+import { describe, expect, it } from 'vitest';
+import { RgbaBuffer } from '../../../src/wasm/pkg/anvil_wasm';
+import { BLUE, GREEN, RED } from '../../support/colors';
+
+const WIDTH = 10;
+const HEIGHT = 8;
+
+// Transparent canvas with a 3x2 red block at (2, 3)
+const blockBuffer = () => {
+  const buffer = new RgbaBuffer(WIDTH, HEIGHT);
+  for (let y = 3; y < 5; y++) {
+    for (let x = 2; x < 5; x++) buffer.set(x, y, ...RED);
+  }
+  return buffer;
+};
+
+describe('FillResult', () => {
+  it('reports the changed pixels and their bounds', () => {
+    const buffer = blockBuffer();
+    const result = buffer.floodFill(3, 4, ...BLUE, 0);
+
+    expect(result?.changedPixels()).toBe(6);
+    expect(Array.from(result?.boundBox() ?? [])).toEqual([2, 3, 3, 2]);
+  });
+
+  it('leaves the coverage empty unless asked for', () => {
+    const buffer = blockBuffer();
+    expect(buffer.floodFill(3, 4, ...BLUE, 0)?.coverage()).toHaveLength(0);
+
+    buffer.setFillCoverage(true);
+    expect(Array.from(buffer.floodFill(3, 4, ...GREEN, 0)?.coverage() ?? [])).toEqual(new Array(6).fill(255));
+  });
+
+  it('is empty but defined when nothing changed', () => {
+    const buffer = blockBuffer();
+    buffer.floodFill(3, 4, ...BLUE, 0);
+    const result = buffer.floodFill(3, 4, ...BLUE, 0);
+
+    expect(result).toBeDefined();
+    expect(result?.changedPixels()).toBe(0);
+    expect(Array.from(result?.boundBox() ?? [])).toEqual([0, 0, 0, 0]);
+  });
+
+  it('skips mask pixels that already have the fill color', () => {
+    const buffer = blockBuffer();
+    buffer.setFillCoverage(true);
+    buffer.floodFill(3, 4, ...GREEN, 0);
+    const mask = new Uint8Array(WIDTH * HEIGHT);
+    // (2, 1), (3, 1), (4, 1), (5, 2) and the already green (4, 3)
+    for (const i of [12, 13, 14, 25, 34]) mask[i] = 1;
+    const result = buffer.fillMaskArea(mask, ...GREEN);
+
+    expect(result.changedPixels()).toBe(4);
+    expect(Array.from(result.boundBox())).toEqual([2, 1, 4, 2]);
+    expect(Array.from(result.coverage())).toEqual([255, 255, 255, 0, 0, 0, 0, 255]);
+  });
+
+  it('returns undefined for a start point outside the canvas', () => {
+    expect(blockBuffer().floodFill(WIDTH, 0, ...BLUE, 0)).toBeUndefined();
+  });
+});
@@ -0,0 +1,104 @@
+import { describe, expect, it } from 'vitest';
+import { HistoryReader, PatchRecord, RgbaBuffer, history_log_header, raw_to_webp } from '../../../src/wasm/pkg/anvil_wasm';
+import { RED } from '../../support/colors';
+
+const WIDTH = 64;
+const HEIGHT = 32;
+const TILE_SIZE = 32;
+
+const webp = (width: number, height: number) => raw_to_webp(new Uint8Array(width * height * 4).fill(128), width, height);
+
+const samplePatch = () => {
+  const buffer = new RgbaBuffer(WIDTH, HEIGHT);
+  buffer.set(3, 4, ...RED);
+  buffer.set(40, 20, 0, 128, 255, 64);
+
+  const record = new PatchRecord();
+  record.setLayerId('layer-1');
+  record.setTool('pen');
+  record.setTimestamp(1700000000000);
+  record.setPixelCount(2);
+  expect(record.setPixels(new Uint32Array([3, 4, 0xff0000ff, 40, 20, 0x0080ff40]))).toBe(true);
+  record.setPartial(-2, 5, 10, 12, webp(10, 12));
+  expect(record.setTiles(WIDTH, HEIGHT, buffer.captureTiles(TILE_SIZE, new Uint32Array([0, 1])))).toBe(true);
+  return record;
+};
+
+const concat = (...parts: Uint8Array[]) => {
+  const out = new Uint8Array(parts.reduce((sum, part) => sum + part.length, 0));
+  let offset = 0;
+  for (const part of parts) {
+    out.set(part, offset);
+    offset += part.length;
+  }
+  return out;
+};
+
+describe('patch history records', () => {
+  it('round-trips every section through encode and decode', () => {
+    const record = samplePatch();
+    const decoded = PatchRecord.decode(record.encode());
+
+    expect(decoded.layerId()).toBe('layer-1');
+    expect(decoded.tool()).toBe('pen');
+    expect(decoded.timestamp()).toBe(1700000000000);
+    expect(decoded.pixelCount()).toBe(2);
+    expect(Array.from(decoded.pixels())).toEqual(Array.from(record.pixels()));
+    expect(Array.from(decoded.partialBoundBox())).toEqual([-2, 5, 10, 12]);
+    expect(decoded.partialWebp()).toEqual(record.partialWebp());
+    expect(decoded.hasWhole()).toBe(false);
+    expect(Array.from(decoded.tilesSize())).toEqual([WIDTH, HEIGHT]);
+    expect(decoded.tiles()).toEqual(record.tiles());
+  });
+
+  it('reads records back in order from a log', () => {
+    const first = samplePatch();
+    const second = new PatchRecord();
+    second.setTool('eraser');
+    const log = concat(history_log_header(), first.encode(), second.encode());
+
+    const reader = new HistoryReader(log);
+    expect(reader.next()?.tool()).toBe('pen');
+    expect(reader.next()?.tool()).toBe('eraser');
+    expect(reader.next()).toBeUndefined();
+    expect(reader.offset()).toBe(log.length);
+  });
+
+  it('rejects a record whose checksum does not match', () => {
+    const encoded = samplePatch().encode();
+    // the first body byte is the metadata section tag
+    encoded[4] ^= 0xff;
+    expect(() => PatchRecord.decode(encoded)).toThrow();
+
+    const header = history_log_header();
+    const valid = new PatchRecord().encode();
+    const reader = new HistoryReader(concat(header, valid, encoded));
+    expect(reader.next()).toBeDefined();
+    expect(() => reader.next()).toThrow();
+    // still points at the corrupt record so the log can be truncated there
+    expect(reader.offset()).toBe(header.length + valid.length);
+  });
+
+  it('rejects webp sections that do not match the stated size', () => {
+    const partial = new PatchRecord();
+    partial.setPartial(0, 0, 10, 12, webp(12, 10));
+    expect(() => PatchRecord.decode(partial.encode())).toThrow();
+
+    const whole = new PatchRecord();
+    whole.setWhole(WIDTH, HEIGHT, webp(WIDTH, HEIGHT - 1));
+    expect(() => PatchRecord.decode(whole.encode())).toThrow();
+
+    whole.setWhole(WIDTH, HEIGHT, webp(WIDTH, HEIGHT));
+    expect(PatchRecord.decode(whole.encode()).hasWhole()).toBe(true);
+  });
+
+  it('rejects tile patches that do not fit the stated buffer size', () => {
+    const buffer = new RgbaBuffer(WIDTH, HEIGHT);
+    const tiles = buffer.captureTiles(TILE_SIZE, new Uint32Array([1]));
+
+    const record = new PatchRecord();
+    expect(record.setTiles(TILE_SIZE, TILE_SIZE, tiles)).toBe(false);
+    expect(record.setTiles(WIDTH, HEIGHT, tiles.subarray(0, tiles.length - 1))).toBe(false);
+    expect(record.hasTiles()).toBe(false);
+  });
+});
@@ -0,0 +1,70 @@
+import { describe, expect, it } from 'vitest';
+import { Anvil } from '../../../src/Anvil';
+import { packBufferDiff } from '../../../src/ops/Packing';
+import { DiffKind, RgbaBuffer, diff } from '../../../src/wasm/pkg/anvil_wasm';
+
+const WIDTH = 128;
+const HEIGHT = 128;
+const TILE_SIZE = 32;
+const TILE_PIXELS = TILE_SIZE * TILE_SIZE;
+
+// Opaque pseudo-random pixels, so WebP cannot shrink large regions to almost nothing
+const noiseBuffer = (width: number, height: number) => {
+  const data = new Uint8Array(width * height * 4);
+  let seed = 1;
+  for (let i = 0; i < data.length; i++) {
+    seed = (Math.imul(seed, 1664525) + 1013904223) >>> 0;
+    data[i] = i % 4 === 3 ? 255 : seed >>> 24;
+  }
+  return RgbaBuffer.fromRaw(width, height, data);
+};
+
+// Diff `before` against `after`, then undo it on an Anvil holding `after`
+const undoThroughAnvil = (before: RgbaBuffer, after: RgbaBuffer, expectedKind: DiffKind) => {
+  const result = diff(before, after, TILE_SIZE);
+  expect(result.kind()).toBe(expectedKind);
+
+  const anvil = new Anvil(WIDTH, HEIGHT, TILE_SIZE);
+  anvil.replaceBuffer(after.data());
+  const patch = packBufferDiff(result, WIDTH, HEIGHT);
+  expect(patch).toBeDefined();
+  anvil.applyPatch(patch!, 'undo');
+  expect(new Uint8Array(anvil.getBufferCopy().buffer)).toEqual(before.data());
+};
+
+describe('diff', () => {
+  it('reports unchanged buffers and packs nothing', () => {
+    const result = diff(noiseBuffer(WIDTH, HEIGHT), noiseBuffer(WIDTH, HEIGHT), TILE_SIZE);
+    expect(result.kind()).toBe(DiffKind.Unchanged);
+    expect(packBufferDiff(result, WIDTH, HEIGHT)).toBeUndefined();
+  });
+
+  it('restores a single pixel through a pixel patch', () => {
+    const before = noiseBuffer(WIDTH, HEIGHT);
+    const after = noiseBuffer(WIDTH, HEIGHT);
+    after.set(3, 3, 0, 0, 0, 0);
+    undoThroughAnvil(before, after, DiffKind.Pixel);
+  });
+
+  it('restores two distant tiles through a tile patch', () => {
+    const before = noiseBuffer(WIDTH, HEIGHT);
+    const after = noiseBuffer(WIDTH, HEIGHT);
+    after.writeRect(0, 0, TILE_SIZE, TILE_SIZE, new Uint8Array(TILE_PIXELS * 4).fill(200));
+    after.writeRect(WIDTH - TILE_SIZE, HEIGHT - TILE_SIZE, TILE_SIZE, TILE_SIZE, new Uint8Array(TILE_PIXELS * 4).fill(200));
+    undoThroughAnvil(before, after, DiffKind.Tile);
+  });
+
+  it('restores a block inside the buffer through a partial patch', () => {
+    const before = noiseBuffer(WIDTH, HEIGHT);
+    const after = noiseBuffer(WIDTH, HEIGHT);
+    after.writeRect(40, 40, 40, 40, new Uint8Array(40 * 40 * 4).fill(200));
+    undoThroughAnvil(before, after, DiffKind.Partial);
+  });
+
+  it('restores a full repaint through a whole patch', () => {
+    const before = noiseBuffer(WIDTH, HEIGHT);
+    const after = noiseBuffer(WIDTH, HEIGHT);
+    after.fillAllPixels(1, 2, 3, 255);
+    undoThroughAnvil(before, after, DiffKind.Whole);
+  });
+});
@@ -0,0 +1,48 @@
+import { describe, expect, it } from 'vitest';
+import { FillRule, SelectionMask } from '../../../src/wasm/pkg/anvil_wasm';
+
+const SIZE = 20;
+
+// Outline of the box (2, 2)-(18, 18) without its top-right corner that crosses itself at
+// (14, 6) and winds around the square (6, 6)-(14, 14) a second time
+const CROSSING_LOOPS = new Float32Array([2, 2, 14, 2, 14, 14, 6, 14, 6, 6, 18, 6, 18, 18, 2, 18]);
+
+const OUTLINED = 16 * 16 - 4 * 4;
+const OVERLAP = 8 * 8;
+
+const selectedCount = (mask: SelectionMask) => mask.data().filter((v) => v !== 0).length;
+
+describe('SelectionMask.fromLasso', () => {
+  it('selects the doubly wound overlap with the non-zero rule', () => {
+    const mask = SelectionMask.fromLasso(SIZE, SIZE, CROSSING_LOOPS, FillRule.NonZero, false);
+
+    expect(selectedCount(mask)).toBe(OUTLINED);
+    expect(mask.get(10, 10)).toBe(255);
+    expect(mask.get(3, 3)).toBe(255);
+    expect(mask.get(17, 17)).toBe(255);
+    expect(mask.get(16, 3)).toBe(0);
+  });
+
+  it('leaves the doubly wound overlap out with the even-odd rule', () => {
+    const mask = SelectionMask.fromLasso(SIZE, SIZE, CROSSING_LOOPS, FillRule.EvenOdd, false);
+
+    expect(selectedCount(mask)).toBe(OUTLINED - OVERLAP);
+    expect(mask.get(10, 10)).toBe(0);
+    expect(mask.get(6, 6)).toBe(0);
+    expect(mask.get(13, 13)).toBe(0);
+    expect(mask.get(3, 3)).toBe(255);
+    expect(mask.get(17, 17)).toBe(255);
+  });
+
+  it('agrees on pixels wound once', () => {
+    const nonZero = SelectionMask.fromLasso(SIZE, SIZE, CROSSING_LOOPS, FillRule.NonZero, false).data();
+    const evenOdd = SelectionMask.fromLasso(SIZE, SIZE, CROSSING_LOOPS, FillRule.EvenOdd, false).data();
+
+    for (let y = 0; y < SIZE; y++) {
+      for (let x = 0; x < SIZE; x++) {
+        const overlap = x >= 6 && x < 14 && y >= 6 && y < 14;
+        if (!overlap) expect(evenOdd[y * SIZE + x]).toBe(nonZero[y * SIZE + x]);
+      }
+    }
+  });
+});
@@ -0,0 +1,98 @@
+import { describe, expect, it } from 'vitest';
+import { CoverageMode, FillResult, RgbaBuffer, TiledRgbaBuffer } from '../../../src/wasm/pkg/anvil_wasm';
+import { BLUE, GREEN, RED } from '../../support/colors';
+
+const WIDTH = 50;
+const HEIGHT = 37;
+// does not divide the canvas, so edge tiles are clipped
+const TILE_SIZE = 16;
+
+// Opaque black canvas with pseudo-random red walls
+const wallBuffer = () => {
+  const buffer = new RgbaBuffer(WIDTH, HEIGHT);
+  let seed = 7;
+  for (let y = 0; y < HEIGHT; y++) {
+    for (let x = 0; x < WIDTH; x++) {
+      seed = (Math.imul(seed, 1664525) + 1013904223) >>> 0;
+      if (seed >>> 24 < 60) buffer.set(x, y, ...RED);
+      else buffer.set(x, y, 0, 0, 0, 255);
+    }
+  }
+  return buffer;
+};
+
+const LEFT_HALF = (() => {
+  const mask = new Uint8Array(WIDTH * HEIGHT);
+  for (let i = 0; i < mask.length; i++) mask[i] = i % WIDTH < 30 ? 1 : 0;
+  return mask;
+})();
+
+const expectSameResult = (tiled: FillResult | undefined, dense: FillResult | undefined) => {
+  expect(tiled === undefined).toBe(dense === undefined);
+  if (!tiled || !dense) return;
+  expect(tiled.changedPixels()).toBe(dense.changedPixels());
+  expect(Array.from(tiled.boundBox())).toEqual(Array.from(dense.boundBox()));
+  expect(tiled.coverage()).toEqual(dense.coverage());
+};
+
+describe('TiledRgbaBuffer fills', () => {
+  it.each([
+    [3, 3, 0],
+    [25, 10, 100],
+    [49, 36, 255],
+  ])('matches the dense flood fill from (%i, %i) with threshold %i', (x, y, threshold) => {
+    const dense = wallBuffer();
+    const tiled = TiledRgbaBuffer.fromBuffer(dense, TILE_SIZE);
+    dense.setFillCoverage(true);
+    tiled.setFillCoverage(true);
+
+    expectSameResult(tiled.floodFill(x, y, ...BLUE, threshold), dense.floodFill(x, y, ...BLUE, threshold));
+    expect(tiled.toBuffer().data()).toEqual(dense.data());
+
+    for (const mode of ['inside', 'outside']) {
+      expectSameResult(
+        tiled.floodFillWithMask(x, y, ...GREEN, threshold, LEFT_HALF, mode),
+        dense.floodFillWithMask(x, y, ...GREEN, threshold, LEFT_HALF, mode),
+      );
+      expect(tiled.toBuffer().data()).toEqual(dense.data());
+    }
+  });
+
+  it('matches the dense mask area fill', () => {
+    const dense = wallBuffer();
+    const tiled = TiledRgbaBuffer.fromBuffer(dense, TILE_SIZE);
+    dense.setFillCoverage(true);
+    tiled.setFillCoverage(true);
+
+    expectSameResult(tiled.fillMaskArea(LEFT_HALF, ...GREEN), dense.fillMaskArea(LEFT_HALF, ...GREEN));
+    expect(tiled.toBuffer().data()).toEqual(dense.data());
+  });
+
+  it.each([CoverageMode.Binary, CoverageMode.Straight, CoverageMode.Premultiplied])('matches the dense soft mask area fill in mode %i', (mode) => {
+    const ramp = new Uint8Array(WIDTH * HEIGHT);
+    for (let i = 0; i < ramp.length; i++) ramp[i] = Math.round(((i % WIDTH) * 255) / (WIDTH - 1));
+    const dense = wallBuffer();
+    const tiled = TiledRgbaBuffer.fromBuffer(dense, TILE_SIZE);
+    dense.setFillCoverage(true);
+    tiled.setFillCoverage(true);
+
+    expectSameResult(
+      tiled.fillMaskAreaWithCoverage(ramp, 0, 128, 255, 200, mode),
+      dense.fillMaskAreaWithCoverage(ramp, 0, 128, 255, 200, mode),
+    );
+    expect(tiled.toBuffer().data()).toEqual(dense.data());
+  });
+
+  it('returns undefined for a start point outside the canvas', () => {
+    const tiled = new TiledRgbaBuffer(WIDTH, HEIGHT, TILE_SIZE);
+    expect(tiled.floodFill(WIDTH, 0, ...RED, 0)).toBeUndefined();
+  });
+
+  it('keeps fully filled tiles uniform', () => {
+    const tiled = new TiledRgbaBuffer(WIDTH, HEIGHT, TILE_SIZE);
+    const result = tiled.floodFill(0, 0, ...RED, 0);
+
+    expect(result?.changedPixels()).toBe(WIDTH * HEIGHT);
+    expect(tiled.allocatedTiles()).toBe(0);
+  });
+});